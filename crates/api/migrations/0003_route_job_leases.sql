@@ -0,0 +1,23 @@
+-- Worker leases, retry backoff and results for route computation jobs
+-- Lets background workers claim jobs safely and reclaim jobs from crashed workers
+
+ALTER TABLE route_computation_jobs
+  ADD COLUMN IF NOT EXISTS locked_by TEXT,
+  ADD COLUMN IF NOT EXISTS lease_expires_at TIMESTAMPTZ,
+  ADD COLUMN IF NOT EXISTS available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  ADD COLUMN IF NOT EXISTS result JSONB;
+
+-- Claim path: pending jobs whose backoff has elapsed
+CREATE INDEX IF NOT EXISTS idx_route_jobs_available
+  ON route_computation_jobs(available_at ASC)
+  WHERE status = 'pending';
+
+-- Reclaim path: processing jobs whose lease has expired
+CREATE INDEX IF NOT EXISTS idx_route_jobs_lease
+  ON route_computation_jobs(lease_expires_at ASC)
+  WHERE status = 'processing';
+
+COMMENT ON COLUMN route_computation_jobs.locked_by IS 'Worker id holding the current lease';
+COMMENT ON COLUMN route_computation_jobs.lease_expires_at IS 'Processing jobs past this time are reclaimed by other workers';
+COMMENT ON COLUMN route_computation_jobs.available_at IS 'Earliest time a pending job may be claimed (retry backoff)';
+COMMENT ON COLUMN route_computation_jobs.result IS 'Serialized RouteComputationResult for completed jobs';
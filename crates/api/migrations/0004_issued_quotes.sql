@@ -0,0 +1,20 @@
+-- Issued quotes for GET /api/v1/quotes/{id}
+-- Each quote computed by /api/v1/quote is kept, with its signed payload,
+-- until it expires; expired rows are pruned by the API.
+
+CREATE TABLE IF NOT EXISTS issued_quotes (
+    quote_id        UUID        PRIMARY KEY,
+    -- QuoteResponse JSON as returned to the client, including its signature
+    quote           JSONB       NOT NULL,
+    -- XDR of the router contract's SignedQuote; the signature covers these bytes
+    signed_payload  BYTEA       NOT NULL,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Efficient pruning of expired quotes
+CREATE INDEX IF NOT EXISTS idx_issued_quotes_expires_at
+    ON issued_quotes(expires_at);
+
+COMMENT ON TABLE issued_quotes IS
+    'Firm quotes issued by the API, retrievable by quote_id until expires_at.';
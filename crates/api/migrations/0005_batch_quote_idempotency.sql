@@ -0,0 +1,20 @@
+-- Stored results of POST /api/v1/batch/quote requests sent with an
+-- Idempotency-Key header, so a retried batch returns the same quotes.
+-- Expired rows are pruned by the API.
+
+CREATE TABLE IF NOT EXISTS batch_quote_idempotency (
+    idempotency_key TEXT        PRIMARY KEY,
+    -- SHA-256 of the request body; a key reused with another body is rejected
+    request_hash    BYTEA       NOT NULL,
+    -- BatchQuoteResponse JSON as first returned
+    response        JSONB       NOT NULL,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Efficient pruning of expired results
+CREATE INDEX IF NOT EXISTS idx_batch_quote_idempotency_expires_at
+    ON batch_quote_idempotency(expires_at);
+
+COMMENT ON TABLE batch_quote_idempotency IS
+    'Batch quote results keyed by Idempotency-Key, replayed until expires_at.';
@@ -0,0 +1,324 @@
+//! Account-aware routing
+//!
+//! Loads an account's trustlines and balances from Horizon so `/quote` and
+//! `/routes` can avoid routes the account cannot execute: assets it has no
+//! authorized trustline for, and amounts it cannot spend.
+
+use dashmap::DashMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use stellarroute_indexer::error::IndexerError;
+use stellarroute_indexer::horizon::HorizonClient;
+use stellarroute_indexer::models::horizon::HorizonAccount;
+use stellarroute_routing::Amount;
+
+use crate::error::{ApiError, Result};
+
+/// How long a loaded account is reused before Horizon is asked again
+pub const DEFAULT_ACCOUNT_TTL: Duration = Duration::from_secs(30);
+
+/// Accounts cached before expired entries are swept
+const MAX_CACHED_ACCOUNTS: usize = 10_000;
+
+/// Base reserve of the network, in stroops (0.5 XLM)
+const BASE_RESERVE_STROOPS: i128 = 5_000_000;
+
+/// Assets an account can hold and how much of each it can spend
+#[derive(Debug, Clone)]
+pub struct AccountHoldings {
+    pub account_id: String,
+    /// Spendable balance per canonical asset id (`native` or `CODE:ISSUER`),
+    /// for XLM and every authorized trustline
+    spendable: BTreeMap<String, Amount>,
+}
+
+impl AccountHoldings {
+    /// Build holdings from a Horizon account.
+    ///
+    /// Spendable amounts exclude selling liabilities and, for XLM, the
+    /// account's minimum balance. Unauthorized trustlines and liquidity pool
+    /// shares are left out.
+    pub fn from_horizon(account: &HorizonAccount) -> Self {
+        let reserve_entries =
+            2 + i128::from(account.subentry_count) + i128::from(account.num_sponsoring)
+                - i128::from(account.num_sponsored);
+        let minimum_balance = Amount::from_stroops(reserve_entries.max(0) * BASE_RESERVE_STROOPS);
+
+        let mut spendable = BTreeMap::new();
+        for line in &account.balances {
+            let Some(asset) = line.canonical_asset() else {
+                continue;
+            };
+            if !line.is_authorized.unwrap_or(true) {
+                continue;
+            }
+
+            let parse = |s: Option<&str>| s.and_then(|s| s.parse::<Amount>().ok());
+            let balance = parse(Some(&line.balance)).unwrap_or(Amount::ZERO);
+            let mut locked = parse(line.selling_liabilities.as_deref()).unwrap_or(Amount::ZERO);
+            if asset == "native" {
+                locked = locked.checked_add(minimum_balance).unwrap_or(balance);
+            }
+            let available = balance
+                .checked_sub(locked)
+                .filter(|a| *a > Amount::ZERO)
+                .unwrap_or(Amount::ZERO);
+
+            spendable.insert(asset, available);
+        }
+
+        Self {
+            account_id: account.account_id.clone(),
+            spendable,
+        }
+    }
+
+    /// Whether the account can receive `asset` (a canonical asset id)
+    pub fn can_hold(&self, asset: &str) -> bool {
+        asset == "native" || self.spendable.contains_key(asset)
+    }
+
+    /// Spendable balance of `asset`; zero for assets the account cannot hold
+    pub fn spendable(&self, asset: &str) -> Amount {
+        self.spendable.get(asset).copied().unwrap_or(Amount::ZERO)
+    }
+
+    /// Canonical ids of every asset the account can hold
+    pub fn holdable_assets(&self) -> Vec<String> {
+        let mut assets: Vec<String> = self.spendable.keys().cloned().collect();
+        if !self.spendable.contains_key("native") {
+            assets.push("native".to_string());
+        }
+        assets
+    }
+
+    /// Assets a route for this account may pass through: everything it can
+    /// hold plus the asset it sends
+    pub fn route_assets(&self, source: &str) -> Vec<String> {
+        let mut assets = self.holdable_assets();
+        if !assets.iter().any(|a| a == source) {
+            assets.push(source.to_string());
+        }
+        assets
+    }
+
+    /// Check the account can receive `destination` and spend `amount` of `source`
+    pub fn ensure_can_trade(&self, source: &str, destination: &str, amount: Amount) -> Result<()> {
+        if !self.can_hold(destination) {
+            return Err(ApiError::AccountConstraint(format!(
+                "Account {} has no authorized trustline for {}",
+                self.account_id, destination
+            )));
+        }
+        self.ensure_can_spend(source, amount)
+    }
+
+    /// Check the account can spend `amount` of `asset`
+    pub fn ensure_can_spend(&self, asset: &str, amount: Amount) -> Result<()> {
+        let available = self.spendable(asset);
+        if available < amount {
+            return Err(ApiError::AccountConstraint(format!(
+                "Account {} can spend {} {} but {} is required",
+                self.account_id, available, asset, amount
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Check `account` is a Stellar account id (`G...`)
+pub fn validate_account_id(account: &str) -> std::result::Result<(), String> {
+    stellar_strkey::ed25519::PublicKey::from_string(account)
+        .map(|_| ())
+        .map_err(|_| format!("account must be a Stellar account id (G...): {}", account))
+}
+
+/// Horizon-backed account loader with a per-account TTL
+pub struct AccountCache {
+    horizon: HorizonClient,
+    ttl: Duration,
+    entries: DashMap<String, (Instant, Arc<AccountHoldings>)>,
+}
+
+impl AccountCache {
+    pub fn new(horizon: HorizonClient, ttl: Duration) -> Self {
+        Self {
+            horizon,
+            ttl,
+            entries: DashMap::new(),
+        }
+    }
+
+    /// Load from `STELLAR_HORIZON_URL` (default `https://horizon.stellar.org`),
+    /// caching each account for `ACCOUNT_CACHE_TTL_SECS` (default 30)
+    pub fn from_env() -> Self {
+        let url = std::env::var("STELLAR_HORIZON_URL")
+            .unwrap_or_else(|_| "https://horizon.stellar.org".to_string());
+        let ttl = std::env::var("ACCOUNT_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_ACCOUNT_TTL);
+        Self::new(HorizonClient::new(url), ttl)
+    }
+
+    /// Holdings of `account_id`, from cache while fresh
+    pub async fn holdings(&self, account_id: &str) -> Result<Arc<AccountHoldings>> {
+        if let Some(entry) = self.entries.get(account_id) {
+            let (loaded_at, holdings) = entry.value();
+            if loaded_at.elapsed() < self.ttl {
+                return Ok(holdings.clone());
+            }
+        }
+
+        debug!("Loading account {} from Horizon", account_id);
+        let account = self
+            .horizon
+            .get_account(account_id)
+            .await
+            .map_err(|e| match e {
+                IndexerError::StellarApi { status: 404, .. } => {
+                    ApiError::NotFound(format!("Account not found: {}", account_id))
+                }
+                other => other.into(),
+            })?;
+
+        let holdings = Arc::new(AccountHoldings::from_horizon(&account));
+        self.insert(holdings.clone());
+        Ok(holdings)
+    }
+
+    /// Cache `holdings` as freshly loaded
+    pub fn insert(&self, holdings: Arc<AccountHoldings>) {
+        if self.entries.len() >= MAX_CACHED_ACCOUNTS {
+            let ttl = self.ttl;
+            self.entries
+                .retain(|_, (loaded_at, _)| loaded_at.elapsed() < ttl);
+        }
+        self.entries
+            .insert(holdings.account_id.clone(), (Instant::now(), holdings));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellarroute_indexer::models::horizon::HorizonBalance;
+
+    const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const EURC: &str = "EURC:GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP";
+    const ACCOUNT: &str = "GDVEU3DD4KOFECV66VIHWEZOYX4ZKR3WV27L464SIIPOU2IUI3JCZA57";
+
+    fn balance(asset: &str, amount: &str, authorized: bool) -> HorizonBalance {
+        let (asset_type, code, issuer) = match asset.split_once(':') {
+            Some((code, issuer)) => (
+                "credit_alphanum4",
+                Some(code.to_string()),
+                Some(issuer.to_string()),
+            ),
+            None => ("native", None, None),
+        };
+        HorizonBalance {
+            balance: amount.to_string(),
+            asset_type: asset_type.to_string(),
+            asset_code: code,
+            asset_issuer: issuer,
+            liquidity_pool_id: None,
+            limit: None,
+            buying_liabilities: None,
+            selling_liabilities: Some("1.0000000".to_string()),
+            is_authorized: (asset_type != "native").then_some(authorized),
+        }
+    }
+
+    fn account() -> HorizonAccount {
+        HorizonAccount {
+            account_id: ACCOUNT.to_string(),
+            sequence: "1".to_string(),
+            subentry_count: 2,
+            num_sponsoring: 0,
+            num_sponsored: 0,
+            balances: vec![
+                balance(USDC, "25.0000000", true),
+                balance(EURC, "10.0000000", false),
+                balance("native", "100.0000000", true),
+            ],
+        }
+    }
+
+    #[test]
+    fn only_authorized_trustlines_are_holdable() {
+        let holdings = AccountHoldings::from_horizon(&account());
+        assert!(holdings.can_hold("native"));
+        assert!(holdings.can_hold(USDC));
+        assert!(!holdings.can_hold(EURC));
+        assert_eq!(
+            holdings.holdable_assets(),
+            vec![
+                "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+                "native"
+            ]
+        );
+    }
+
+    #[test]
+    fn spendable_excludes_liabilities_and_reserve() {
+        let holdings = AccountHoldings::from_horizon(&account());
+        assert_eq!(holdings.spendable(USDC), "24".parse().unwrap());
+        // 100 - 1 selling liabilities - (2 + 2 subentries) * 0.5 reserve
+        assert_eq!(holdings.spendable("native"), "97".parse().unwrap());
+        assert_eq!(holdings.spendable(EURC), Amount::ZERO);
+    }
+
+    #[test]
+    fn route_assets_include_the_source() {
+        let holdings = AccountHoldings::from_horizon(&account());
+        let assets = holdings.route_assets(EURC);
+        assert!(assets.iter().any(|a| a == EURC));
+        assert!(assets.iter().any(|a| a == USDC));
+    }
+
+    #[test]
+    fn ensure_can_trade_explains_missing_trustline_and_balance() {
+        let holdings = AccountHoldings::from_horizon(&account());
+        assert!(holdings
+            .ensure_can_trade("native", USDC, "10".parse().unwrap())
+            .is_ok());
+
+        let err = holdings
+            .ensure_can_trade("native", EURC, "10".parse().unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("no authorized trustline for EURC"));
+
+        let err = holdings
+            .ensure_can_trade("native", USDC, "500".parse().unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("can spend 97.0000000 native"));
+    }
+
+    #[test]
+    fn validate_account_id_accepts_only_g_addresses() {
+        assert!(validate_account_id(ACCOUNT).is_ok());
+        assert!(validate_account_id("GABC").is_err());
+        assert!(
+            validate_account_id("SADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP54X")
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn cached_holdings_are_served_without_horizon() {
+        // Unroutable Horizon: any request would fail
+        let cache = AccountCache::new(
+            HorizonClient::new("http://127.0.0.1:9"),
+            DEFAULT_ACCOUNT_TTL,
+        );
+        cache.insert(Arc::new(AccountHoldings::from_horizon(&account())));
+
+        let holdings = cache.holdings(ACCOUNT).await.unwrap();
+        assert!(holdings.can_hold(USDC));
+    }
+}
@@ -1,8 +1,11 @@
 //! StellarRoute API Server Binary
 
 use sqlx::postgres::PgPoolOptions;
+use std::sync::Arc;
 use std::time::Duration;
-use stellarroute_api::{state::DatabasePools, telemetry, Server, ServerConfig};
+use stellarroute_api::{
+    quotes::QuoteSigner, state::DatabasePools, telemetry, Server, ServerConfig,
+};
 use tracing::{error, info};
 
 fn parse_bool_env(name: &str) -> bool {
@@ -113,6 +116,15 @@ async fn main() {
         std::process::exit(1);
     }
 
+    // Refuse to issue quotes no allowlist can verify
+    let quote_signer = match QuoteSigner::from_env() {
+        Ok(signer) => Arc::new(signer),
+        Err(e) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
     // Get database URL from environment
     let database_url = std::env::var("DATABASE_URL")
         .unwrap_or_else(|_| "postgres://localhost/stellarroute".to_string());
@@ -227,6 +239,15 @@ async fn main() {
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(2),
+        quote_stale_grace_ms: std::env::var("QUOTE_STALE_GRACE_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1000),
+        enable_route_workers: std::env::var("ROUTE_WORKERS_ENABLED")
+            .map(|_| parse_bool_env("ROUTE_WORKERS_ENABLED"))
+            .unwrap_or(true),
+        multi_region: parse_bool_env("MULTI_REGION_ENABLED"),
+        quote_signer: Some(quote_signer),
     };
 
     // Create and start server
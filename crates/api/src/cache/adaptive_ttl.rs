@@ -57,7 +57,9 @@ pub struct TtlDecision {
     pub reason: TtlReason,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Why a quote or orderbook got its TTL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum TtlReason {
     HighVolatility,
     LowVolatility,
@@ -80,10 +82,18 @@ impl std::fmt::Display for TtlReason {
     }
 }
 
+/// Prices kept per pair by [`AdaptiveTtlEngine::observe`]
+pub const OBSERVATION_WINDOW: usize = 20;
+
+/// Prices needed before volatility is trusted: two give a single return,
+/// whose deviation is always zero
+const MIN_OBSERVATIONS: usize = 3;
+
 pub struct AdaptiveTtlEngine {
     config: AdaptiveTtlConfig,
     metrics: Arc<RwLock<HashMap<String, MarketMetrics>>>,
     decisions: Arc<RwLock<Vec<TtlDecisionRecord>>>,
+    observations: Arc<RwLock<VolatilityCalculator>>,
 }
 
 #[derive(Debug, Clone)]
@@ -106,7 +116,45 @@ impl AdaptiveTtlEngine {
             config,
             metrics: Arc::new(RwLock::new(HashMap::new())),
             decisions: Arc::new(RwLock::new(Vec::new())),
+            observations: Arc::new(RwLock::new(VolatilityCalculator::new(OBSERVATION_WINDOW))),
+        }
+    }
+
+    pub fn config(&self) -> &AdaptiveTtlConfig {
+        &self.config
+    }
+
+    /// Record a freshly computed price and the liquidity depth behind it.
+    ///
+    /// Metrics are published once the pair has enough prices to estimate
+    /// volatility from; until then it keeps the base TTL (`NoMetrics`).
+    pub async fn observe(&self, pair: &str, price: f64, depth: f64) {
+        if !(price.is_finite() && price > 0.0) {
+            return;
         }
+
+        let volatility = {
+            let mut observations = self.observations.write().await;
+            observations.add_price(pair, price);
+            let observed = observations.prices.get(pair).map_or(0, Vec::len);
+            (observed >= MIN_OBSERVATIONS)
+                .then(|| observations.calculate_volatility(pair))
+                .flatten()
+        };
+        let Some(volatility) = volatility else {
+            return;
+        };
+
+        self.update_metrics(
+            pair,
+            MarketMetrics {
+                volatility,
+                depth,
+                last_price: price,
+                ..Default::default()
+            },
+        )
+        .await;
     }
 
     pub async fn update_metrics(&self, pair: &str, metrics: MarketMetrics) {
@@ -501,6 +549,35 @@ mod tests {
             .contains_key(&TtlReason::LowVolatility));
     }
 
+    #[tokio::test]
+    async fn test_observe_publishes_metrics_once_volatility_is_known() {
+        let engine = AdaptiveTtlEngine::new(test_config());
+
+        engine.observe("XLM/USDC", 0.10, 500_000.0).await;
+        engine.observe("XLM/USDC", 0.12, 500_000.0).await;
+        assert_eq!(
+            engine.compute_ttl("XLM/USDC").await.reason,
+            TtlReason::NoMetrics
+        );
+
+        engine.observe("XLM/USDC", 0.10, 500_000.0).await;
+        let metrics = engine.get_metrics("XLM/USDC").await.unwrap();
+        assert!(metrics.volatility > 0.0);
+        assert_eq!(metrics.last_price, 0.10);
+        assert_eq!(
+            engine.compute_ttl("XLM/USDC").await.reason,
+            TtlReason::HighVolatility
+        );
+    }
+
+    #[test]
+    fn test_ttl_reason_serializes_as_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&TtlReason::HighVolatility).unwrap(),
+            "\"high_volatility\""
+        );
+    }
+
     #[test]
     fn test_volatility_calculator() {
         let mut calc = VolatilityCalculator::new(10);
@@ -2,15 +2,42 @@
 //!
 //! This module provides targeted cache invalidation when SDEX or AMM liquidity
 //! updates arrive, preventing stale outputs while maintaining cache efficiency.
+//! Every replica clears its own in-process L1; Redis is cleared once per
+//! change, by the replica that claims it first.
+//!
+//! Events arrive as JSON payloads on the Postgres `liquidity_update` channel,
+//! sent by the indexer's `notify_liquidity_update` trigger on
+//! `normalized_liquidity` (see `0012_liquidity_update_notify.sql`).
 
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgListener, PgPool};
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
 use super::CacheManager;
 
+/// Postgres channel liquidity changes are published on
+pub const LIQUIDITY_UPDATE_CHANNEL: &str = "liquidity_update";
+
+/// How long the listener keeps collecting notifications before invalidating,
+/// so a ledger that touches many offers of a pair clears its keys once
+const BATCH_WINDOW: Duration = Duration::from_millis(50);
+
+/// How long a replica's claim on clearing Redis for a pair change is kept,
+/// so the replicas receiving the same notification skip Redis
+const INVALIDATION_CLAIM_TTL: Duration = Duration::from_secs(60);
+
+/// Delay between attempts to re-establish a lost listener connection
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
 /// Liquidity update event types
-#[derive(Debug, Clone)]
+///
+/// Assets are canonical: `native` or `CODE:ISSUER`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum LiquidityUpdateEvent {
     /// SDEX orderbook update for a specific pair
     SdexUpdate {
@@ -33,16 +60,42 @@ pub enum LiquidityUpdateEvent {
     },
 }
 
+impl LiquidityUpdateEvent {
+    /// Parse a `liquidity_update` notification payload
+    pub fn from_payload(payload: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(payload)
+    }
+
+    /// The pair whose cached data this event makes stale
+    pub fn pair(&self) -> (&str, &str) {
+        match self {
+            Self::SdexUpdate {
+                base_asset,
+                counter_asset,
+                ..
+            }
+            | Self::RevisionUpdate {
+                base_asset,
+                counter_asset,
+                ..
+            } => (base_asset, counter_asset),
+            Self::AmmUpdate {
+                asset_a, asset_b, ..
+            } => (asset_a, asset_b),
+        }
+    }
+}
+
 /// Cache invalidation manager for liquidity-aware cache control
 pub struct CacheInvalidationManager {
-    cache: Arc<Mutex<CacheManager>>,
+    cache: CacheManager,
     /// Track pending invalidations to batch them
     pending_invalidations: Arc<Mutex<Vec<LiquidityUpdateEvent>>>,
 }
 
 impl CacheInvalidationManager {
     /// Create a new cache invalidation manager
-    pub fn new(cache: Arc<Mutex<CacheManager>>) -> Self {
+    pub fn new(cache: CacheManager) -> Self {
         Self {
             cache,
             pending_invalidations: Arc::new(Mutex::new(Vec::new())),
@@ -71,7 +124,8 @@ impl CacheInvalidationManager {
                     ledger = ledger_sequence,
                     "Processing SDEX liquidity update"
                 );
-                self.invalidate_pair_caches(base_asset, counter_asset).await
+                self.invalidate_pair_caches(base_asset, counter_asset, *ledger_sequence)
+                    .await
             }
             LiquidityUpdateEvent::AmmUpdate {
                 pool_address,
@@ -86,7 +140,8 @@ impl CacheInvalidationManager {
                     ledger = ledger_sequence,
                     "Processing AMM liquidity update"
                 );
-                self.invalidate_pair_caches(asset_a, asset_b).await
+                self.invalidate_pair_caches(asset_a, asset_b, *ledger_sequence)
+                    .await
             }
             LiquidityUpdateEvent::RevisionUpdate {
                 base_asset,
@@ -105,38 +160,68 @@ impl CacheInvalidationManager {
         }
     }
 
-    /// Invalidate all cached quotes for a specific trading pair
+    /// Invalidate all cached data derived from a trading pair
     ///
-    /// Uses pair-aware pattern matching to only invalidate relevant caches,
-    /// ensuring amount-safe invalidation (all amounts for the pair are cleared).
+    /// Clears quotes (every amount), orderbooks and route job results for the
+    /// pair in both directions, leaving other pairs untouched. Every replica
+    /// receives the notification, so each clears its L1 but only the one that
+    /// claims the change at `version` clears Redis.
     async fn invalidate_pair_caches(
         &self,
         base_asset: &str,
         counter_asset: &str,
+        version: u64,
     ) -> Result<u64, String> {
-        let pattern = super::keys::quote_pair_pattern(base_asset, counter_asset);
-
-        let mut cache = self.cache.lock().await;
-        match cache.delete_by_pattern(&pattern).await {
-            Ok(count) => {
-                info!(
+        let patterns = super::keys::pair_invalidation_patterns(base_asset, counter_asset);
+        let claim = super::keys::pair_invalidation_claim(base_asset, counter_asset, version);
+        match self.cache.claim(&claim, INVALIDATION_CLAIM_TTL).await {
+            Ok(true) => {}
+            Ok(false) => {
+                let deleted = patterns
+                    .iter()
+                    .map(|pattern| self.cache.delete_local_by_pattern(pattern))
+                    .sum();
+                debug!(
                     base = %base_asset,
                     counter = %counter_asset,
-                    deleted = count,
-                    "Invalidated quote caches for pair"
+                    deleted = deleted,
+                    "Invalidated local caches for pair; Redis cleared by another replica"
                 );
-                Ok(count)
+                return Ok(deleted);
             }
             Err(e) => {
                 warn!(
                     base = %base_asset,
                     counter = %counter_asset,
                     error = %e,
-                    "Failed to invalidate quote caches"
+                    "Failed to claim pair invalidation; clearing both tiers"
                 );
-                Err(format!("Cache invalidation failed: {}", e))
             }
         }
+
+        let mut deleted = 0;
+        for pattern in patterns {
+            match self.cache.delete_by_pattern(&pattern).await {
+                Ok(count) => deleted += count,
+                Err(e) => {
+                    warn!(
+                        base = %base_asset,
+                        counter = %counter_asset,
+                        error = %e,
+                        "Failed to invalidate pair caches"
+                    );
+                    return Err(format!("Cache invalidation failed: {}", e));
+                }
+            }
+        }
+
+        info!(
+            base = %base_asset,
+            counter = %counter_asset,
+            deleted = deleted,
+            "Invalidated caches for pair"
+        );
+        Ok(deleted)
     }
 
     /// Update liquidity revision and invalidate stale caches
@@ -152,10 +237,9 @@ impl CacheInvalidationManager {
     ) -> Result<u64, String> {
         let revision_key = super::keys::liquidity_revision(base_asset, counter_asset);
 
-        let mut cache = self.cache.lock().await;
-
         // Store the new revision (with a reasonable TTL)
-        if let Err(e) = cache
+        if let Err(e) = self
+            .cache
             .set(
                 &revision_key,
                 &revision,
@@ -171,38 +255,37 @@ impl CacheInvalidationManager {
             );
         }
 
-        // Invalidate all quotes for this pair
-        let pattern = super::keys::quote_pair_pattern(base_asset, counter_asset);
-        match cache.delete_by_pattern(&pattern).await {
-            Ok(count) => {
-                debug!(
-                    base = %base_asset,
-                    counter = %counter_asset,
-                    revision = revision,
-                    deleted = count,
-                    "Updated revision and invalidated caches"
-                );
-                Ok(count)
-            }
-            Err(e) => {
-                warn!(
-                    base = %base_asset,
-                    counter = %counter_asset,
-                    error = %e,
-                    "Failed to invalidate after revision update"
-                );
-                Err(format!("Cache invalidation failed: {}", e))
-            }
-        }
+        // Invalidate everything derived from this pair
+        let deleted = self
+            .invalidate_pair_caches(base_asset, counter_asset, revision)
+            .await?;
+        debug!(
+            base = %base_asset,
+            counter = %counter_asset,
+            revision = revision,
+            deleted = deleted,
+            "Updated revision and invalidated caches"
+        );
+        Ok(deleted)
     }
 
     /// Batch process multiple liquidity updates
     ///
-    /// Collects updates and processes them together to reduce lock contention
+    /// Collects updates and processes them together to reduce lock contention.
+    /// SDEX and AMM updates for a pair already invalidated in this batch are
+    /// skipped.
     pub async fn batch_invalidate(&self, events: Vec<LiquidityUpdateEvent>) -> Result<u64, String> {
         let mut total_invalidated = 0u64;
+        let mut invalidated_pairs = HashSet::new();
 
         for event in events {
+            if !matches!(event, LiquidityUpdateEvent::RevisionUpdate { .. }) {
+                let (a, b) = event.pair();
+                let pair = if a <= b { (a, b) } else { (b, a) };
+                if !invalidated_pairs.insert((pair.0.to_string(), pair.1.to_string())) {
+                    continue;
+                }
+            }
             match self.handle_liquidity_update(event).await {
                 Ok(count) => total_invalidated += count,
                 Err(e) => warn!("Batch invalidation error: {}", e),
@@ -236,11 +319,110 @@ impl CacheInvalidationManager {
 
         self.batch_invalidate(events).await
     }
+
+    /// Invalidate every cached quote, orderbook and route job result, for when
+    /// updates may have been missed
+    pub async fn invalidate_all(&self) -> Result<u64, String> {
+        let mut deleted = 0;
+        for pattern in ["*quote:*", "orderbook:*", "v1:route_job:*"] {
+            deleted += self
+                .cache
+                .delete_by_pattern(pattern)
+                .await
+                .map_err(|e| format!("Cache invalidation failed: {}", e))?;
+        }
+        info!(
+            deleted = deleted,
+            "Invalidated all liquidity-derived caches"
+        );
+        Ok(deleted)
+    }
+
+    /// Spawn a task that listens on [`LIQUIDITY_UPDATE_CHANNEL`] and
+    /// invalidates the pairs named by each notification
+    ///
+    /// Notifications are batched over a short window. Payloads that do not
+    /// parse, and reconnections (which may have missed notifications),
+    /// invalidate all liquidity-derived caches instead.
+    pub fn spawn_listener(self: Arc<Self>, db: PgPool) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut listener = self.connect_listener(&db).await;
+            info!(
+                "Cache invalidation subscribed to '{}' notifications",
+                LIQUIDITY_UPDATE_CHANNEL
+            );
+
+            loop {
+                let notification = match listener.recv().await {
+                    Ok(notification) => notification,
+                    Err(e) => {
+                        error!("Liquidity update listener lost: {}. Reconnecting...", e);
+                        listener = self.connect_listener(&db).await;
+                        if let Err(e) = self.invalidate_all().await {
+                            warn!("Failed to invalidate caches after reconnect: {}", e);
+                        }
+                        continue;
+                    }
+                };
+                let mut invalidate_all = !self.queue_payload(notification.payload()).await;
+
+                // Collect whatever else arrives within the batch window
+                let deadline = tokio::time::Instant::now() + BATCH_WINDOW;
+                while let Ok(Ok(notification)) =
+                    tokio::time::timeout_at(deadline, listener.recv()).await
+                {
+                    invalidate_all |= !self.queue_payload(notification.payload()).await;
+                }
+
+                let result = if invalidate_all {
+                    self.pending_invalidations.lock().await.clear();
+                    self.invalidate_all().await
+                } else {
+                    self.flush_pending().await
+                };
+                if let Err(e) = result {
+                    warn!("Failed to apply liquidity invalidations: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Queue the event in a notification payload; false if it does not parse
+    async fn queue_payload(&self, payload: &str) -> bool {
+        match LiquidityUpdateEvent::from_payload(payload) {
+            Ok(event) => {
+                self.queue_invalidation(event).await;
+                true
+            }
+            Err(e) => {
+                warn!(
+                    payload = %payload,
+                    error = %e,
+                    "Unrecognized liquidity update payload"
+                );
+                false
+            }
+        }
+    }
+
+    /// Connect and subscribe, retrying until it succeeds
+    async fn connect_listener(&self, db: &PgPool) -> PgListener {
+        loop {
+            match PgListener::connect_with(db).await {
+                Ok(mut listener) => match listener.listen(LIQUIDITY_UPDATE_CHANNEL).await {
+                    Ok(()) => return listener,
+                    Err(e) => error!("Failed to listen on '{}': {}", LIQUIDITY_UPDATE_CHANNEL, e),
+                },
+                Err(e) => error!("Failed to connect liquidity update listener: {}", e),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
 }
 
 /// Helper to check if a cached quote is stale relative to current revision
 pub async fn is_quote_stale(
-    cache: &mut CacheManager,
+    cache: &CacheManager,
     base_asset: &str,
     counter_asset: &str,
     quote_timestamp: u64,
@@ -289,4 +471,30 @@ mod tests {
         assert!(format!("{:?}", amm_event).contains("AmmUpdate"));
         assert!(format!("{:?}", revision_event).contains("RevisionUpdate"));
     }
+
+    #[test]
+    fn test_parses_trigger_payloads() {
+        let sdex = LiquidityUpdateEvent::from_payload(
+            r#"{"type" : "sdex_update", "base_asset" : "native", "counter_asset" : "USDC:GISSUER", "ledger_sequence" : 12}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            sdex,
+            LiquidityUpdateEvent::SdexUpdate {
+                base_asset: "native".to_string(),
+                counter_asset: "USDC:GISSUER".to_string(),
+                ledger_sequence: 12,
+            }
+        );
+        assert_eq!(sdex.pair(), ("native", "USDC:GISSUER"));
+
+        let amm = LiquidityUpdateEvent::from_payload(
+            r#"{"type":"amm_update","pool_address":"CPOOL","asset_a":"USDC:GISSUER","asset_b":"native","ledger_sequence":11}"#,
+        )
+        .unwrap();
+        assert_eq!(amm.pair(), ("USDC:GISSUER", "native"));
+
+        assert!(LiquidityUpdateEvent::from_payload("sdex").is_err());
+        assert!(LiquidityUpdateEvent::from_payload(r#"{"type":"other"}"#).is_err());
+    }
 }
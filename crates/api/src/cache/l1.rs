@@ -0,0 +1,302 @@
+//! Bounded in-process cache in front of Redis
+//!
+//! Entries are serialized JSON with their own expiry, so the L1 holds exactly
+//! what Redis holds and is read without a lock on the whole map. When full it
+//! evicts with the CLOCK algorithm: entries queue in insertion order and one
+//! read since it was last passed earns an entry another trip round the queue.
+
+use dashmap::DashMap;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default number of entries kept in process
+pub const DEFAULT_L1_CAPACITY: usize = 10_000;
+
+/// Default longest time an entry is served from process memory. Other
+/// instances only see invalidations through Redis, so this bounds how long
+/// they can serve a value another instance has invalidated.
+pub const DEFAULT_L1_MAX_TTL: Duration = Duration::from_secs(1);
+
+/// L1 sizing
+#[derive(Debug, Clone, Copy)]
+pub struct L1Config {
+    pub capacity: usize,
+    pub max_ttl: Duration,
+}
+
+impl Default for L1Config {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_L1_CAPACITY,
+            max_ttl: DEFAULT_L1_MAX_TTL,
+        }
+    }
+}
+
+impl L1Config {
+    /// Read `CACHE_L1_CAPACITY` (default 10000; 0 disables the L1) and
+    /// `CACHE_L1_MAX_TTL_MS` (default 1000)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let env = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<u64>().ok());
+        Self {
+            capacity: env("CACHE_L1_CAPACITY")
+                .map(|v| v as usize)
+                .unwrap_or(defaults.capacity),
+            max_ttl: env("CACHE_L1_MAX_TTL_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.max_ttl),
+        }
+    }
+}
+
+struct L1Entry {
+    value: Arc<str>,
+    expires_at: Instant,
+    /// Tells this insertion apart from later ones of the same key in the clock
+    id: u64,
+    /// Read since the clock hand last passed it
+    referenced: AtomicBool,
+}
+
+/// In-process cache with per-key TTLs and a fixed capacity
+pub struct L1Cache {
+    config: L1Config,
+    entries: DashMap<String, L1Entry>,
+    /// Keys in clock order with the id they were inserted under; ids no
+    /// longer in `entries` are skipped when reached
+    clock: Mutex<VecDeque<(String, u64)>>,
+    next_id: AtomicU64,
+}
+
+impl L1Cache {
+    pub fn new(config: L1Config) -> Self {
+        Self {
+            config,
+            entries: DashMap::new(),
+            clock: Mutex::new(VecDeque::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Unexpired value of `key`
+    pub fn get(&self, key: &str) -> Option<Arc<str>> {
+        let now = Instant::now();
+        let value = {
+            let entry = self.entries.get(key)?;
+            (entry.expires_at > now).then(|| {
+                if !entry.referenced.load(Ordering::Relaxed) {
+                    entry.referenced.store(true, Ordering::Relaxed);
+                }
+                entry.value.clone()
+            })
+        };
+        if value.is_none() {
+            self.entries.remove_if(key, |_, e| e.expires_at <= now);
+        }
+        value
+    }
+
+    /// Store `value` for `ttl`, capped at the configured maximum
+    pub fn insert(&self, key: &str, value: Arc<str>, ttl: Duration) {
+        let ttl = ttl.min(self.config.max_ttl);
+        if self.config.capacity == 0 || ttl.is_zero() {
+            return;
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut clock = self.clock.lock();
+        if !self.entries.contains_key(key) && self.entries.len() >= self.config.capacity {
+            self.evict_one(&mut clock);
+        }
+        self.entries.insert(
+            key.to_string(),
+            L1Entry {
+                value,
+                expires_at: Instant::now() + ttl,
+                id,
+                referenced: AtomicBool::new(false),
+            },
+        );
+        clock.push_back((key.to_string(), id));
+
+        // Overwrites and removals leave stale slots behind; drop them before
+        // they outnumber the live ones
+        while clock.len() > 2 * self.config.capacity {
+            let Some((key, id)) = clock.pop_front() else {
+                break;
+            };
+            if self.entries.get(&key).is_some_and(|e| e.id == id) {
+                clock.push_back((key, id));
+            }
+        }
+    }
+
+    pub fn remove(&self, key: &str) {
+        self.entries.remove(key);
+    }
+
+    /// Remove every key matching a Redis glob pattern (`*` and `?` wildcards).
+    /// Returns the number of entries removed.
+    pub fn remove_matching(&self, pattern: &str) -> u64 {
+        let before = self.entries.len();
+        self.entries.retain(|key, _| !glob_match(pattern, key));
+        before.saturating_sub(self.entries.len()) as u64
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Advance the clock hand until it removes an entry: an expired one, or
+    /// one not read since the hand last passed it
+    fn evict_one(&self, clock: &mut VecDeque<(String, u64)>) {
+        let now = Instant::now();
+        while let Some((key, id)) = clock.pop_front() {
+            let mut live = false;
+            let evicted = self.entries.remove_if(&key, |_, e| {
+                if e.id != id {
+                    return false;
+                }
+                live = true;
+                e.expires_at <= now || !e.referenced.swap(false, Ordering::Relaxed)
+            });
+            if evicted.is_some() {
+                return;
+            }
+            if live {
+                clock.push_back((key, id));
+            }
+        }
+    }
+}
+
+/// Match `key` against a Redis glob pattern supporting `*` and `?`
+fn glob_match(pattern: &str, key: &str) -> bool {
+    let (p, k) = (pattern.as_bytes(), key.as_bytes());
+    let (mut pi, mut ki) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ki < k.len() {
+        match p.get(pi) {
+            Some(b'*') => {
+                star = Some((pi, ki));
+                pi += 1;
+            }
+            Some(&c) if c == b'?' || c == k[ki] => {
+                pi += 1;
+                ki += 1;
+            }
+            _ => match star {
+                // Let the last `*` absorb one more byte
+                Some((sp, sk)) => {
+                    pi = sp + 1;
+                    ki = sk + 1;
+                    star = Some((sp, sk + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    p[pi..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(capacity: usize) -> L1Cache {
+        L1Cache::new(L1Config {
+            capacity,
+            max_ttl: Duration::from_secs(60),
+        })
+    }
+
+    #[test]
+    fn entries_expire_after_their_own_ttl() {
+        let l1 = cache(10);
+        l1.insert("short", Arc::from("1"), Duration::from_millis(20));
+        l1.insert("long", Arc::from("2"), Duration::from_secs(30));
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(l1.get("short").is_none());
+        assert_eq!(l1.get("long").as_deref(), Some("2"));
+        assert_eq!(l1.len(), 1);
+    }
+
+    #[test]
+    fn ttl_is_capped_at_max_ttl() {
+        let l1 = L1Cache::new(L1Config {
+            capacity: 10,
+            max_ttl: Duration::from_millis(20),
+        });
+        l1.insert("k", Arc::from("v"), Duration::from_secs(30));
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(l1.get("k").is_none());
+    }
+
+    #[test]
+    fn full_cache_evicts_the_oldest_unread_entry() {
+        let l1 = cache(2);
+        l1.insert("a", Arc::from("1"), Duration::from_secs(5));
+        l1.insert("b", Arc::from("2"), Duration::from_secs(50));
+        l1.insert("c", Arc::from("3"), Duration::from_secs(50));
+
+        assert_eq!(l1.len(), 2);
+        assert!(l1.get("a").is_none());
+        assert!(l1.get("b").is_some() && l1.get("c").is_some());
+    }
+
+    #[test]
+    fn recently_read_entries_survive_eviction() {
+        let l1 = cache(2);
+        l1.insert("a", Arc::from("1"), Duration::from_secs(50));
+        l1.insert("b", Arc::from("2"), Duration::from_secs(50));
+        assert!(l1.get("a").is_some());
+
+        l1.insert("c", Arc::from("3"), Duration::from_secs(50));
+        assert!(l1.get("b").is_none());
+        assert!(l1.get("a").is_some() && l1.get("c").is_some());
+    }
+
+    #[test]
+    fn overwrites_do_not_grow_the_clock() {
+        let l1 = cache(4);
+        for i in 0..100 {
+            l1.insert("k", Arc::from(i.to_string()), Duration::from_secs(50));
+        }
+        assert_eq!(l1.get("k").as_deref(), Some("99"));
+        assert!(l1.clock.lock().len() <= 8);
+    }
+
+    #[test]
+    fn zero_capacity_disables_the_cache() {
+        let l1 = cache(0);
+        l1.insert("k", Arc::from("v"), Duration::from_secs(5));
+        assert!(l1.is_empty());
+    }
+
+    #[test]
+    fn remove_matching_uses_redis_glob_semantics() {
+        let l1 = cache(10);
+        for key in [
+            "v2:quote:native:USDC:1.0000000:50:sell:false",
+            "v2:quote:native:EURC:1.0000000:50:sell:false",
+            "orderbook:native:USDC",
+        ] {
+            l1.insert(key, Arc::from("{}"), Duration::from_secs(5));
+        }
+
+        assert_eq!(l1.remove_matching("*quote:native:USDC:*"), 1);
+        assert_eq!(l1.len(), 2);
+        assert!(glob_match("orderbook:?ative:*", "orderbook:native:USDC"));
+        assert!(!glob_match("orderbook:*:EURC", "orderbook:native:USDC"));
+    }
+}
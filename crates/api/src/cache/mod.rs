@@ -1,65 +1,176 @@
-//! Redis caching layer
+//! Two-tier caching layer
+//!
+//! [`CacheManager`] fronts Redis (L2) with a bounded in-process cache (L1,
+//! [`l1::L1Cache`]). It is cheap to clone and needs no lock: the L1 is a
+//! sharded map and the Redis `ConnectionManager` multiplexes one connection.
 
 pub mod adaptive_ttl;
 pub mod invalidation;
+pub mod l1;
 
 use redis::{aio::ConnectionManager, AsyncCommands, RedisError};
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, instrument, warn};
 
 pub use invalidation::{CacheInvalidationManager, LiquidityUpdateEvent};
+pub use l1::{L1Cache, L1Config};
 
 pub use adaptive_ttl::{
     AdaptiveTtlConfig, AdaptiveTtlEngine, AdaptiveTtlStats, DepthAggregator, MarketMetrics,
     TtlDecision, TtlReason, VolatilityCalculator,
 };
 
-/// Cache manager for Redis operations
+/// Keys deleted per `DEL` when clearing a pattern
+const SCAN_DELETE_BATCH: usize = 500;
+
+/// Hit/miss counters of one cache tier
+#[derive(Debug, Default)]
+pub struct TierCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TierCounters {
+    fn record(&self, tier: &str, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        crate::metrics::record_cache_tier(tier, hit);
+    }
+
+    /// `(hits, misses)`
+    pub fn snapshot(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// A cached value and when (Unix ms) it stops being fresh
+#[derive(Serialize, Deserialize)]
+struct TimedEntry<T> {
+    fresh_until_ms: i64,
+    value: T,
+}
+
+/// Outcome of [`CacheManager::get_fresh`]
+#[derive(Debug)]
+pub enum Freshness<T> {
+    /// Within its TTL
+    Fresh(T),
+    /// Past its TTL but inside the grace window: serve it and refresh
+    Stale(T),
+    Miss,
+}
+
+/// Two-tier cache: in-process L1 in front of Redis
 #[derive(Clone)]
 pub struct CacheManager {
+    redis: redis::Client,
     client: ConnectionManager,
+    l1: Arc<L1Cache>,
+    l1_counters: Arc<TierCounters>,
+    l2_counters: Arc<TierCounters>,
 }
 
 impl CacheManager {
-    /// Create a new cache manager
+    /// Create a new cache manager with the L1 sized by [`L1Config::from_env`]
     pub async fn new(redis_url: &str) -> Result<Self, RedisError> {
-        let client = redis::Client::open(redis_url)?;
-        let conn = ConnectionManager::new(client).await?;
+        Self::with_l1_config(redis_url, L1Config::from_env()).await
+    }
 
-        debug!("Redis cache manager initialized");
-        Ok(Self { client: conn })
+    pub async fn with_l1_config(redis_url: &str, l1: L1Config) -> Result<Self, RedisError> {
+        let redis = redis::Client::open(redis_url)?;
+        let conn = ConnectionManager::new(redis.clone()).await?;
+
+        debug!(
+            "Redis cache manager initialized (L1 capacity {}, max TTL {:?})",
+            l1.capacity, l1.max_ttl
+        );
+        Ok(Self {
+            redis,
+            client: conn,
+            l1: Arc::new(L1Cache::new(l1)),
+            l1_counters: Arc::default(),
+            l2_counters: Arc::default(),
+        })
     }
 
-    /// Get a cached value
-    #[instrument(skip(self), fields(cache.hit = tracing::field::Empty))]
-    pub async fn get<T: DeserializeOwned>(&mut self, key: &str) -> Option<T> {
-        match self.client.get::<_, String>(key).await {
-            Ok(json) => match serde_json::from_str(&json) {
+    /// Get a cached value, from the L1 if present and otherwise from Redis
+    #[instrument(skip(self), fields(cache.hit = tracing::field::Empty, cache.tier = tracing::field::Empty))]
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let span = tracing::Span::current();
+
+        if let Some(json) = self.l1.get(key) {
+            match serde_json::from_str(&json) {
                 Ok(value) => {
-                    tracing::Span::current().record("cache.hit", true);
-                    debug!("Cache hit for key: {}", key);
-                    Some(value)
+                    self.l1_counters.record("l1", true);
+                    span.record("cache.hit", true);
+                    span.record("cache.tier", "l1");
+                    debug!("L1 cache hit for key: {}", key);
+                    return Some(value);
                 }
                 Err(e) => {
-                    tracing::Span::current().record("cache.hit", false);
-                    warn!("Failed to deserialize cached value for {}: {}", key, e);
-                    None
+                    warn!("Failed to deserialize L1 value for {}: {}", key, e);
+                    self.l1.remove(key);
                 }
-            },
-            Err(_) => {
-                tracing::Span::current().record("cache.hit", false);
+            }
+        }
+        self.l1_counters.record("l1", false);
+
+        // Value and remaining TTL in one round trip, so the L1 copy expires with Redis
+        let mut conn = self.client.clone();
+        let fetched: Result<(Option<String>, i64), RedisError> = redis::pipe()
+            .get(key)
+            .pttl(key)
+            .query_async(&mut conn)
+            .await;
+
+        let (json, pttl_ms) = match fetched {
+            Ok((Some(json), pttl_ms)) => (json, pttl_ms),
+            Ok((None, _)) => {
+                self.l2_counters.record("l2", false);
+                span.record("cache.hit", false);
                 debug!("Cache miss for key: {}", key);
+                return None;
+            }
+            Err(e) => {
+                self.l2_counters.record("l2", false);
+                span.record("cache.hit", false);
+                warn!("Redis read failed for {}: {}", key, e);
+                return None;
+            }
+        };
+
+        match serde_json::from_str(&json) {
+            Ok(value) => {
+                self.l2_counters.record("l2", true);
+                span.record("cache.hit", true);
+                span.record("cache.tier", "l2");
+                debug!("Cache hit for key: {}", key);
+                // Keys without an expiry (pttl -1) are not promoted
+                if pttl_ms > 0 {
+                    self.l1
+                        .insert(key, Arc::from(json), Duration::from_millis(pttl_ms as u64));
+                }
+                Some(value)
+            }
+            Err(e) => {
+                self.l2_counters.record("l2", false);
+                span.record("cache.hit", false);
+                warn!("Failed to deserialize cached value for {}: {}", key, e);
                 None
             }
         }
     }
 
-    /// Set a cached value with TTL
+    /// Set a cached value with TTL in both tiers
     #[instrument(skip(self, value), fields(cache.ttl_ms = ttl.as_millis() as u64))]
     pub async fn set<T: Serialize>(
-        &mut self,
+        &self,
         key: &str,
         value: &T,
         ttl: Duration,
@@ -72,43 +183,143 @@ impl CacheManager {
             ))
         })?;
 
+        // Millisecond expiry so sub-second TTLs are honoured
+        let ttl_ms = (ttl.as_millis() as u64).max(1);
         self.client
-            .set_ex::<_, _, ()>(key, json, ttl.as_secs())
+            .clone()
+            .pset_ex::<_, _, ()>(key, &json, ttl_ms)
             .await?;
+        self.l1.insert(key, Arc::from(json), ttl);
 
         debug!("Cached key: {} with TTL: {:?}", key, ttl);
         Ok(())
     }
 
-    /// Delete a cached value
-    pub async fn delete(&mut self, key: &str) -> Result<(), RedisError> {
-        self.client.del::<_, ()>(key).await?;
+    /// Get a value stored with [`set_fresh`](Self::set_fresh), telling
+    /// whether it is still within its TTL
+    pub async fn get_fresh<T: DeserializeOwned>(&self, key: &str) -> Freshness<T> {
+        match self.get::<TimedEntry<T>>(key).await {
+            Some(entry) if chrono::Utc::now().timestamp_millis() < entry.fresh_until_ms => {
+                Freshness::Fresh(entry.value)
+            }
+            Some(entry) => Freshness::Stale(entry.value),
+            None => Freshness::Miss,
+        }
+    }
+
+    /// Cache a value that is fresh for `ttl` and may be served stale for
+    /// `grace` after that while it is refreshed
+    pub async fn set_fresh<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Duration,
+        grace: Duration,
+    ) -> Result<(), RedisError> {
+        let fresh_until_ms =
+            chrono::Utc::now().timestamp_millis() + i64::try_from(ttl.as_millis()).unwrap_or(0);
+        self.set(
+            key,
+            &TimedEntry {
+                fresh_until_ms,
+                value,
+            },
+            ttl + grace,
+        )
+        .await
+    }
+
+    /// Delete a cached value from both tiers
+    pub async fn delete(&self, key: &str) -> Result<(), RedisError> {
+        self.l1.remove(key);
+        self.client.clone().del::<_, ()>(key).await?;
         debug!("Deleted cache key: {}", key);
         Ok(())
     }
 
-    /// Delete all cached values that match a Redis glob pattern
-    pub async fn delete_by_pattern(&mut self, pattern: &str) -> Result<u64, RedisError> {
-        let keys: Vec<String> = self.client.keys(pattern).await?;
+    /// Delete all cached values that match a Redis glob pattern from both tiers
+    ///
+    /// Redis is walked with `SCAN`, so a large keyspace does not block it.
+    pub async fn delete_by_pattern(&self, pattern: &str) -> Result<u64, RedisError> {
+        let local = self.delete_local_by_pattern(pattern);
+
+        let mut conn = self.client.clone();
+        let keys: Vec<String> = {
+            let mut iter = conn.scan_match::<_, String>(pattern).await?;
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
         if keys.is_empty() {
-            return Ok(0);
+            return Ok(local);
         }
 
-        let deleted: u64 = self.client.del(keys).await?;
+        let mut deleted = 0u64;
+        for chunk in keys.chunks(SCAN_DELETE_BATCH) {
+            deleted += conn.del::<_, u64>(chunk).await?;
+        }
         debug!(
             "Deleted {} cache keys matching pattern: {}",
             deleted, pattern
         );
-        Ok(deleted)
+        Ok(deleted.max(local))
+    }
+
+    /// Delete the values matching a Redis glob pattern from this process's
+    /// L1 only, leaving Redis to whichever replica clears it
+    pub fn delete_local_by_pattern(&self, pattern: &str) -> u64 {
+        self.l1.remove_matching(pattern)
+    }
+
+    /// Set `key` if it does not exist yet, expiring after `ttl`. Returns
+    /// whether this call set it, so one replica can claim shared work.
+    pub async fn claim(&self, key: &str, ttl: Duration) -> Result<bool, RedisError> {
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg((ttl.as_millis() as u64).max(1))
+            .query_async(&mut self.client.clone())
+            .await?;
+        Ok(claimed.is_some())
     }
 
     /// Check if cache is healthy
-    pub async fn is_healthy(&mut self) -> bool {
+    pub async fn is_healthy(&self) -> bool {
         self.client
+            .clone()
             .get::<_, Option<String>>("_health")
             .await
             .is_ok()
     }
+
+    /// Shared Redis connection, for commands outside the cache API
+    pub fn connection(&self) -> ConnectionManager {
+        self.client.clone()
+    }
+
+    /// New dedicated Redis connection in subscriber mode
+    pub async fn pubsub(&self) -> Result<redis::aio::PubSub, RedisError> {
+        Ok(self.redis.get_async_connection().await?.into_pubsub())
+    }
+
+    /// Hit/miss counters of the in-process tier
+    pub fn l1_stats(&self) -> &TierCounters {
+        &self.l1_counters
+    }
+
+    /// Hit/miss counters of the Redis tier (consulted only on L1 misses)
+    pub fn l2_stats(&self) -> &TierCounters {
+        &self.l2_counters
+    }
+
+    /// Entries currently held in process
+    pub fn l1_len(&self) -> usize {
+        self.l1.len()
+    }
 }
 
 /// SingleFlight manager to prevent cache stampedes
@@ -215,6 +426,26 @@ impl<T: Send + Sync + 'static> SingleFlight<T> {
     }
 }
 
+impl SingleFlight<()> {
+    /// Run `refresh` in the background unless a refresh of `key` is already
+    /// running, in which case this one just waits for it
+    pub fn spawn(
+        self: &Arc<Self>,
+        key: String,
+        refresh: impl std::future::Future<Output = ()> + Send + 'static,
+    ) {
+        let flights = Arc::clone(self);
+        tokio::spawn(async move {
+            flights
+                .execute(&key, || async move {
+                    refresh.await;
+                    Arc::new(())
+                })
+                .await;
+        });
+    }
+}
+
 impl<T: Send + Sync + 'static> Default for SingleFlight<T> {
     fn default() -> Self {
         Self::new()
@@ -229,6 +460,11 @@ impl<T: Send + Sync + 'static> Default for SingleFlight<T> {
 /// - orderbook:{base}:{quote} -> Orderbook for a specific pair
 /// - v1:quote:{base}:{quote}:{amount}:{slippage_bps}:{quote_type} -> Result of a quote request
 /// - liquidity:revision:{base}:{quote} -> Latest observed ledger revision for a pair
+/// - liquidity:invalidated:{a}:{b}:{version} -> Claim on clearing Redis for a pair's liquidity change
+/// - v1:route_job:{job_key} -> Result of a background route computation job
+///
+/// Quotes and orderbooks are stored with their freshness deadline
+/// ([`CacheManager::set_fresh`]) so they can be served stale while refreshed.
 pub mod keys {
     /// Cache key for trading pairs list
     pub fn pairs_list() -> String {
@@ -276,21 +512,81 @@ pub mod keys {
     }
 
     /// Normalize amounts to a canonical string (7 decimal precision)
+    ///
+    /// Parsed exactly, so distinct amounts never share a key however many
+    /// digits they have.
     fn normalize_amount(amount: &str) -> String {
-        match amount.parse::<f64>() {
-            Ok(val) => format!("{:.7}", val),
+        match amount.parse::<stellarroute_routing::Amount>() {
+            Ok(val) => val.to_string(),
             Err(_) => amount.to_string(), // Fallback if invalid
         }
     }
 
     /// Key used to track the latest liquidity revision observed for a pair
     pub fn liquidity_revision(base: &str, quote: &str) -> String {
-        format!("liquidity:revision:{}:{}", normalize_asset(base), normalize_asset(quote))
+        format!(
+            "liquidity:revision:{}:{}",
+            normalize_asset(base),
+            normalize_asset(quote)
+        )
+    }
+
+    /// Pair name the adaptive TTL engine tracks market metrics under
+    pub fn market_pair(base: &str, quote: &str) -> String {
+        format!("{}/{}", normalize_asset(base), normalize_asset(quote))
+    }
+
+    /// Cache key for the result of a background route computation job
+    pub fn route_job_result(job_key: &str) -> String {
+        format!("v1:route_job:{}", job_key)
     }
 
     /// Pattern that matches all cached quotes for a pair
     pub fn quote_pair_pattern(base: &str, quote: &str) -> String {
-        format!("*quote:{}:{}:*", normalize_asset(base), normalize_asset(quote))
+        format!(
+            "*quote:{}:{}:*",
+            normalize_asset(base),
+            normalize_asset(quote)
+        )
+    }
+
+    /// Marker claimed by the one replica that clears Redis for a pair's
+    /// liquidity change at `version` (its ledger or revision)
+    pub fn pair_invalidation_claim(a: &str, b: &str, version: u64) -> String {
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+        format!("liquidity:invalidated:{}:{}:{}", a, b, version)
+    }
+
+    /// Pattern that matches all cached route job results for a pair
+    pub fn route_job_pair_pattern(base: &str, quote: &str) -> String {
+        format!("v1:route_job:route:{}:{}:*", base, quote)
+    }
+
+    /// Every key or pattern holding data derived from the liquidity of a pair:
+    /// quotes, orderbooks and route job results, in both directions. Takes
+    /// canonical assets (`native` or `CODE:ISSUER`) and also covers requests
+    /// that named an issued asset by its bare code.
+    pub fn pair_invalidation_patterns(a: &str, b: &str) -> Vec<String> {
+        let mut patterns = Vec::new();
+        for (base, quote) in [(a, b), (b, a)] {
+            for base_alias in asset_aliases(base) {
+                for quote_alias in asset_aliases(quote) {
+                    patterns.push(quote_pair_pattern(base_alias, quote_alias));
+                    patterns.push(orderbook(base_alias, quote_alias));
+                }
+            }
+            patterns.push(route_job_pair_pattern(base, quote));
+        }
+        patterns.dedup();
+        patterns
+    }
+
+    /// Names a request may use for a canonical asset
+    fn asset_aliases(canonical: &str) -> Vec<&str> {
+        match canonical.split_once(':') {
+            Some((code, _)) => vec![canonical, code],
+            None => vec![canonical],
+        }
     }
 }
 
@@ -311,7 +607,36 @@ mod tests {
             keys::liquidity_revision("xlm", "USDC"),
             "liquidity:revision:native:USDC"
         );
-        assert_eq!(keys::quote_pair_pattern("XLM", "usdc"), "*quote:native:USDC:*");
+        assert_eq!(
+            keys::quote_pair_pattern("XLM", "usdc"),
+            "*quote:native:USDC:*"
+        );
+        assert_eq!(
+            keys::pair_invalidation_claim("native", "USDC:GISSUER", 7),
+            keys::pair_invalidation_claim("USDC:GISSUER", "native", 7)
+        );
+        assert_eq!(
+            keys::route_job_result("route:native:USDC:100.0000000:sell"),
+            "v1:route_job:route:native:USDC:100.0000000:sell"
+        );
+    }
+
+    #[test]
+    fn test_pair_invalidation_patterns_cover_both_directions_and_bare_codes() {
+        let patterns = keys::pair_invalidation_patterns("native", "USDC:GISSUER");
+        for expected in [
+            "*quote:native:USDC:GISSUER:*",
+            "*quote:native:USDC:*",
+            "*quote:USDC:GISSUER:native:*",
+            "*quote:USDC:native:*",
+            "orderbook:native:USDC:GISSUER",
+            "orderbook:USDC:native",
+            "v1:route_job:route:native:USDC:GISSUER:*",
+            "v1:route_job:route:USDC:GISSUER:native:*",
+        ] {
+            assert!(patterns.iter().any(|p| p == expected), "{}", expected);
+        }
+        assert_eq!(patterns.len(), 10);
     }
 
     #[tokio::test]
@@ -320,10 +645,69 @@ mod tests {
         let key1 = keys::quote("XLM", "USDC", "100", 50, "sell", false);
         let key2 = keys::quote("xlm", "usdc", "100.000", 50, "sell", false);
         let key3 = keys::quote("native", "USDC", "100.0000000", 50, "sell", false);
-        
+
         assert_eq!(key1, "v2:quote:native:USDC:100.0000000:50:sell:false");
         assert_eq!(key1, key2);
         assert_eq!(key2, key3);
+
+        // Beyond f64 precision, neighbouring amounts still get their own keys
+        let big = keys::quote("XLM", "USDC", "922337203685.4775807", 50, "sell", false);
+        let next = keys::quote("XLM", "USDC", "922337203685.4775806", 50, "sell", false);
+        assert_ne!(big, next);
+        assert!(big.contains(":922337203685.4775807:"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running Redis instance (set REDIS_URL)"]
+    async fn test_two_tier_cache_promotes_and_invalidates() {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".to_string());
+        let writer = CacheManager::new(&url).await.expect("Redis unavailable");
+        let reader = CacheManager::new(&url).await.expect("Redis unavailable");
+        let key = format!("v2:quote:native:TIER{}:1:50:sell:false", std::process::id());
+
+        writer
+            .set(&key, &42u64, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(writer.get::<u64>(&key).await, Some(42));
+        assert_eq!(writer.l1_stats().snapshot(), (1, 0));
+
+        // A second instance misses its L1, hits Redis and promotes the value
+        assert_eq!(reader.get::<u64>(&key).await, Some(42));
+        assert_eq!(reader.get::<u64>(&key).await, Some(42));
+        assert_eq!(reader.l1_stats().snapshot(), (1, 1));
+        assert_eq!(reader.l2_stats().snapshot(), (1, 0));
+
+        let pattern = keys::quote_pair_pattern("native", &format!("TIER{}", std::process::id()));
+        assert!(reader.delete_by_pattern(&pattern).await.unwrap() >= 1);
+        assert_eq!(reader.get::<u64>(&key).await, None);
+    }
+
+    #[test]
+    fn test_market_pair_normalizes_assets() {
+        assert_eq!(keys::market_pair("xlm", "usdc"), "native/USDC");
+        assert_eq!(
+            keys::market_pair("native", "USDC:GA5Z"),
+            keys::market_pair("XLM", "usdc:ga5z")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_spawn_runs_one_refresh_per_key() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let flights = Arc::new(SingleFlight::<()>::new());
+        let runs = Arc::new(AtomicU64::new(0));
+        for _ in 0..5 {
+            let runs = runs.clone();
+            flights.spawn("refresh".to_string(), async move {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                runs.fetch_add(1, Ordering::Relaxed);
+            });
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(150)).await;
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
     }
 
     #[tokio::test]
@@ -3,9 +3,10 @@
 use utoipa::OpenApi;
 
 use crate::models::{
-    AssetInfo, CacheMetricsResponse, DependenciesHealthResponse, ErrorResponse, HealthResponse,
-    OrderbookLevel, OrderbookResponse, PairsResponse, PathStep, QuoteRationaleMetadata,
-    QuoteResponse, RouteResponse, TradingPair, VenueEvaluation,
+    AssetInfo, CacheMetricsResponse, CacheTierMetrics, DependenciesHealthResponse, ErrorResponse,
+    HealthResponse, IssuedQuoteResponse, OrderbookLevel, OrderbookResponse, PairsResponse,
+    PathStep, QuoteRationaleMetadata, QuoteResponse, RegionHealth, RouteJobStatusResponse,
+    RouteJobSubmitResponse, RouteResponse, SwapBuildResponse, TradingPair, VenueEvaluation,
 };
 
 /// OpenAPI documentation
@@ -19,19 +20,29 @@ use crate::models::{
         crate::routes::pairs::list_markets,
         crate::routes::orderbook::get_orderbook,
         crate::routes::quote::get_quote,
+        crate::routes::quote::get_issued_quote,
         crate::routes::quote::get_route,
+        crate::routes::jobs::submit_route_job,
+        crate::routes::jobs::get_route_job,
+        crate::routes::swap::build_swap,
     ),
     components(schemas(
         HealthResponse,
         DependenciesHealthResponse,
+        RegionHealth,
         CacheMetricsResponse,
+        CacheTierMetrics,
         PairsResponse,
         TradingPair,
         AssetInfo,
         OrderbookResponse,
         OrderbookLevel,
         QuoteResponse,
+        IssuedQuoteResponse,
         RouteResponse,
+        RouteJobSubmitResponse,
+        RouteJobStatusResponse,
+        SwapBuildResponse,
         QuoteRationaleMetadata,
         VenueEvaluation,
         PathStep,
@@ -48,6 +48,12 @@ pub enum ApiError {
     #[error("No route found for trading pair")]
     NoRouteFound,
 
+    #[error("No region can serve the requested consistency: {0}")]
+    ConsistencyUnavailable(String),
+
+    #[error("Account cannot execute this trade: {0}")]
+    AccountConstraint(String),
+
     #[error("All market data inputs are stale ({stale_count} stale, {fresh_count} fresh)")]
     StaleMarketData {
         stale_count: usize,
@@ -69,17 +75,39 @@ impl From<sqlx::Error> for ApiError {
     }
 }
 
+impl From<stellarroute_routing::contract::ContractXdrError> for ApiError {
+    fn from(err: stellarroute_routing::contract::ContractXdrError) -> Self {
+        Self::Validation(err.to_string())
+    }
+}
+
+impl From<stellarroute_indexer::error::IndexerError> for ApiError {
+    fn from(err: stellarroute_indexer::error::IndexerError) -> Self {
+        match err {
+            stellarroute_indexer::error::IndexerError::DatabaseQuery(e) => {
+                Self::Database(Arc::new(e))
+            }
+            other => Self::Internal(Arc::new(other.into())),
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, ApiError>;
 
-impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
+impl ApiError {
+    /// HTTP status and body this error is returned with
+    pub fn to_error_response(&self) -> (StatusCode, ErrorResponse) {
         let (status, error_type, message) = match self {
-            ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, ApiErrorCode::BadRequest, msg),
-            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, ApiErrorCode::NotFound, msg),
+            ApiError::BadRequest(msg) => (
+                StatusCode::BAD_REQUEST,
+                ApiErrorCode::BadRequest,
+                msg.clone(),
+            ),
+            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, ApiErrorCode::NotFound, msg.clone()),
             ApiError::Validation(message) => (
                 StatusCode::BAD_REQUEST,
                 ApiErrorCode::ValidationError,
-                message,
+                message.clone(),
             ),
             ApiError::RateLimitExceeded => (
                 StatusCode::TOO_MANY_REQUESTS,
@@ -89,24 +117,42 @@ impl IntoResponse for ApiError {
             ApiError::Overloaded(msg) => (
                 StatusCode::SERVICE_UNAVAILABLE,
                 ApiErrorCode::Overloaded,
-                msg,
+                msg.clone(),
+            ),
+            ApiError::Unauthorized(msg) => (
+                StatusCode::UNAUTHORIZED,
+                ApiErrorCode::Unauthorized,
+                msg.clone(),
+            ),
+            ApiError::InvalidAsset(msg) => (
+                StatusCode::BAD_REQUEST,
+                ApiErrorCode::InvalidAsset,
+                msg.clone(),
+            ),
+            ApiError::InvalidAmount(msg) => (
+                StatusCode::BAD_REQUEST,
+                ApiErrorCode::InvalidAmount,
+                msg.clone(),
+            ),
+            ApiError::InvalidSlippage(msg) => (
+                StatusCode::BAD_REQUEST,
+                ApiErrorCode::InvalidSlippage,
+                msg.clone(),
             ),
-            ApiError::Unauthorized(msg) => {
-                (StatusCode::UNAUTHORIZED, ApiErrorCode::Unauthorized, msg)
-            }
-            ApiError::InvalidAsset(msg) => {
-                (StatusCode::BAD_REQUEST, ApiErrorCode::InvalidAsset, msg)
-            }
-            ApiError::InvalidAmount(msg) => {
-                (StatusCode::BAD_REQUEST, ApiErrorCode::InvalidAmount, msg)
-            }
-            ApiError::InvalidSlippage(msg) => {
-                (StatusCode::BAD_REQUEST, ApiErrorCode::InvalidSlippage, msg)
-            }
             ApiError::InvalidAssetFormat(msg) => (
                 StatusCode::BAD_REQUEST,
                 ApiErrorCode::InvalidAssetFormat,
-                msg,
+                msg.clone(),
+            ),
+            ApiError::ConsistencyUnavailable(msg) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                ApiErrorCode::ConsistencyUnavailable,
+                msg.clone(),
+            ),
+            ApiError::AccountConstraint(msg) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ApiErrorCode::AccountConstraint,
+                msg.clone(),
             ),
             ApiError::NoRouteFound => (
                 StatusCode::NOT_FOUND,
@@ -125,14 +171,12 @@ impl IntoResponse for ApiError {
                     "threshold_secs_sdex": threshold_secs_sdex,
                     "threshold_secs_amm": threshold_secs_amm,
                 });
-                let body = Json(
-                    ErrorResponse::new(
-                        ApiErrorCode::StaleMarketData,
-                        "All market data inputs are stale",
-                    )
-                    .with_details(details),
-                );
-                return (StatusCode::UNPROCESSABLE_ENTITY, body).into_response();
+                let body = ErrorResponse::new(
+                    ApiErrorCode::StaleMarketData,
+                    "All market data inputs are stale",
+                )
+                .with_details(details);
+                return (StatusCode::UNPROCESSABLE_ENTITY, body);
             }
             ApiError::Database(_) | ApiError::Internal(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -141,8 +185,14 @@ impl IntoResponse for ApiError {
             ),
         };
 
-        let body = Json(ErrorResponse::new(error_type, message));
-        (status, body).into_response()
+        (status, ErrorResponse::new(error_type, message))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, body) = self.to_error_response();
+        (status, Json(body)).into_response()
     }
 }
 
@@ -218,6 +268,22 @@ mod tests {
         assert_eq!(json["error"], "not_found");
     }
 
+    #[tokio::test]
+    async fn consistency_unavailable_mapping() {
+        let err = ApiError::ConsistencyUnavailable("all regions exhausted".to_string());
+        let (status, json) = response_parts(err).await;
+        assert_eq!(status, 503);
+        assert_eq!(json["error"], "consistency_unavailable");
+    }
+
+    #[tokio::test]
+    async fn account_constraint_mapping() {
+        let err = ApiError::AccountConstraint("no trustline for USDC".to_string());
+        let (status, json) = response_parts(err).await;
+        assert_eq!(status, 422);
+        assert_eq!(json["error"], "account_constraint");
+    }
+
     #[tokio::test]
     async fn validation_mapping() {
         let err = ApiError::Validation("amount low".to_string());
@@ -2,9 +2,17 @@
 use arc_swap::ArcSwap;
 use sqlx::{postgres::PgListener, PgPool, Row};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+use stellarroute_routing::optimizer::STABLE_VENUE_TYPE;
 use stellarroute_routing::pathfinder::LiquidityEdge;
+use stellarroute_routing::{Amount, Price};
+
+/// How long to keep collecting `liquidity_update` notifications after the
+/// first one before reloading, so the per-pair notifications of one ledger
+/// cause a single reload
+const SYNC_DEBOUNCE: Duration = Duration::from_millis(250);
 
 /// Daemon that maintains an active in-memory cache of the routing graph
 pub struct GraphManager {
@@ -69,6 +77,17 @@ impl GraphManager {
                             "Received liquidity update notification: protocol={}",
                             notification.payload()
                         );
+                        // Coalesce the rest of the burst into this reload
+                        let deadline = tokio::time::Instant::now() + SYNC_DEBOUNCE;
+                        let mut coalesced = 0usize;
+                        while let Ok(Ok(_)) =
+                            tokio::time::timeout_at(deadline, listener.recv()).await
+                        {
+                            coalesced += 1;
+                        }
+                        if coalesced > 0 {
+                            debug!("Coalesced {} further liquidity updates", coalesced);
+                        }
                         if let Err(e) = manager.sync_graph().await {
                             error!("Failed to sync routing graph after notification: {}", e);
                         }
@@ -135,7 +154,8 @@ impl GraphManager {
 
         let rows = sqlx::query(
             r#"
-            SELECT selling_asset_id, buying_asset_id, venue_type, venue_ref, price, available_amount
+            SELECT selling_asset_id, buying_asset_id, venue_type, venue_ref,
+                   price::text AS price, available_amount::text AS available_amount, amp
             FROM normalized_liquidity
             WHERE available_amount > 0
             "#,
@@ -154,20 +174,30 @@ impl GraphManager {
                 let avail_str: String = r.get("available_amount");
                 let venue_type: String = r.get("venue_type");
 
-                let price = price_str.parse::<f64>().ok();
-                let avail = avail_str.parse::<f64>().ok();
+                let price = price_str.parse::<Price>().ok();
+                let avail = avail_str.parse::<Amount>().ok();
 
                 if let (Some(p), Some(a)) = (price, avail) {
-                    if p > 0.0 && a > 0.0 {
-                        let is_amm = venue_type == "amm";
+                    if p.is_positive() && a.is_positive() {
+                        let amp = r
+                            .get::<Option<i32>, _>("amp")
+                            .and_then(|amp| u32::try_from(amp).ok());
+                        let is_amm = match venue_type.as_str() {
+                            "amm" => true,
+                            // A StableSwap pool cannot be priced without its own curve
+                            STABLE_VENUE_TYPE if amp.is_some() => true,
+                            STABLE_VENUE_TYPE => continue,
+                            _ => false,
+                        };
                         next_edges.push(LiquidityEdge {
                             from: e_from.clone(),
                             to: e_to.clone(),
                             venue_type,
                             venue_ref: r.get("venue_ref"),
-                            liquidity: (a * 1e7) as i128,
+                            liquidity: a.stroops(),
                             price: p,
                             fee_bps: if is_amm { 30 } else { 20 },
+                            amp,
                         });
                     }
                 }
@@ -201,8 +231,9 @@ mod tests {
             venue_type: "sdex".to_string(),
             venue_ref: "1".to_string(),
             liquidity: 100,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30,
+            amp: None,
         }];
 
         // Set initial state
@@ -220,8 +251,9 @@ mod tests {
             venue_type: "sdex".to_string(),
             venue_ref: "2".to_string(),
             liquidity: 200,
-            price: 0.99,
+            price: "0.99".parse().unwrap(),
             fee_bps: 30,
+            amp: None,
         }];
         manager.edges.store(Arc::new(new_edges));
 
@@ -246,8 +278,9 @@ mod tests {
             venue_type: "sdex".to_string(),
             venue_ref: "1".to_string(),
             liquidity: 100,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30,
+            amp: None,
         }];
         manager.edges.store(Arc::new(initial_edges));
 
@@ -272,8 +305,9 @@ mod tests {
                     venue_type: "sdex".to_string(),
                     venue_ref: "1".to_string(),
                     liquidity: 100,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 }];
                 m2.edges.store(Arc::new(edges));
                 tokio::time::sleep(std::time::Duration::from_millis(1)).await;
@@ -285,4 +319,38 @@ mod tests {
         }
         updater.await.unwrap();
     }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL database with the indexer schema (set DATABASE_URL)"]
+    async fn sync_graph_builds_stable_pool_edges() {
+        let db = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+            .await
+            .unwrap();
+        let assets: Vec<uuid::Uuid> = sqlx::query_scalar("SELECT id FROM assets LIMIT 2")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        let pool = format!("CSTABLEGRAPH{}", std::process::id());
+        sqlx::query("SELECT upsert_amm_pool_reserve($1, $2, $3, 1000, 1001, 4, 1, 250)")
+            .bind(&pool)
+            .bind(assets[0])
+            .bind(assets[1])
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let manager = GraphManager::new(db.clone());
+        let synced = manager.sync_graph().await;
+        sqlx::query("DELETE FROM amm_pool_reserves WHERE pool_address = $1")
+            .bind(&pool)
+            .execute(&db)
+            .await
+            .unwrap();
+        synced.unwrap();
+
+        let edges = manager.get_edges();
+        let edge = edges.iter().find(|e| e.venue_ref == pool).unwrap();
+        assert_eq!(edge.venue_type, STABLE_VENUE_TYPE);
+        assert_eq!(edge.amp, Some(250));
+    }
 }
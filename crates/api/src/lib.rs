@@ -2,6 +2,7 @@
 //!
 //! Provides REST API endpoints for price quotes and orderbook data.
 
+pub mod accounts;
 pub mod cache;
 pub mod docs;
 pub mod error;
@@ -11,6 +12,7 @@ pub mod load_test;
 pub mod metrics;
 pub mod middleware;
 pub mod models;
+pub mod quotes;
 pub mod regions;
 pub mod replay;
 pub mod routes;
@@ -18,6 +20,7 @@ pub mod server;
 pub mod state;
 pub mod telemetry;
 pub mod tracing_config;
+pub mod txbuild;
 pub mod worker;
 
 pub use cache::CacheManager;
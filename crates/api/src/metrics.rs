@@ -3,7 +3,7 @@
 //! Exposes metrics for:
 //! - Quote request latency (p50/p95)
 //! - Route computation time
-//! - Cache hit ratio
+//! - Cache hit ratio, overall and per tier of the two-tier cache
 
 use lazy_static::lazy_static;
 use prometheus::{
@@ -48,6 +48,15 @@ lazy_static! {
     )
     .expect("Can't create CACHE_MISSES counter");
 
+    /// Two-tier cache lookups
+    /// Labels: tier (l1/l2), outcome (hit/miss)
+    pub static ref CACHE_TIER_LOOKUPS: IntCounterVec = register_int_counter_vec!(
+        "stellarroute_cache_tier_lookups_total",
+        "Cache lookups per tier of the two-tier cache",
+        &["tier", "outcome"]
+    )
+    .expect("Can't create CACHE_TIER_LOOKUPS counter");
+
     /// Quote request counter
     pub static ref QUOTE_REQUESTS: IntCounterVec = register_int_counter_vec!(
         "stellarroute_quote_requests_total",
@@ -91,6 +100,12 @@ pub fn record_cache_miss(cache_type: &str) {
     CACHE_MISSES.with_label_values(&[cache_type]).inc();
 }
 
+/// Record a lookup in one tier (`l1` or `l2`) of the two-tier cache
+pub fn record_cache_tier(tier: &str, hit: bool) {
+    let outcome = if hit { "hit" } else { "miss" };
+    CACHE_TIER_LOOKUPS.with_label_values(&[tier, outcome]).inc();
+}
+
 /// Get cache hit ratio for a given cache type
 pub fn get_cache_hit_ratio(cache_type: &str) -> f64 {
     let hits = CACHE_HITS.with_label_values(&[cache_type]).get() as f64;
@@ -1,40 +1,51 @@
 //! Rate limiting middleware
 //!
-//! Implements a sliding-window rate limiter backed by Redis (with an
-//! in-memory fallback when Redis is unavailable).
+//! Implements a cost-weighted GCRA (generic cell rate algorithm) limiter backed
+//! by an atomic Redis script, with an in-memory fallback running the same
+//! algorithm when Redis is unavailable.
 //!
-//! Per-endpoint limits (configurable via env vars):
+//! Per-endpoint limits (configurable via env vars), in cost units:
 //!
 //! | Route prefix          | Default limit | Window |
 //! |-----------------------|---------------|--------|
-//! | `/api/v1/pairs`       | 60 req / min  | 60 s   |
-//! | `/api/v1/orderbook/*` | 30 req / min  | 60 s   |
-//! | `/api/v1/quote/*`     | 100 req / min | 60 s   |
-//! | everything else       | 200 req / min | 60 s   |
+//! | `/api/v1/pairs`       | 60 / min      | 60 s   |
+//! | `/api/v1/orderbook/*` | 60 / min      | 60 s   |
+//! | `/api/v1/quote/*`     | 20 / min      | 60 s   |
+//! | everything else       | 120 / min     | 60 s   |
+//!
+//! Each limit refills continuously at `limit / window`. Most requests cost 1;
+//! `/api/v1/routes` costs one unit per `max_hops`, route jobs likewise, and
+//! `/api/v1/batch/quote` one unit per item (see [`CostWeights`]). Tenants may
+//! spend a tier-dependent burst on top of the limit (see [`BurstAllowances`]).
+//!
+//! Only tenants named in `RATE_LIMIT_TENANT_TIERS` (or given an override) get
+//! their own bucket and burst; requests with an unknown API key or bearer
+//! token are limited by client IP like anonymous ones.
 //!
 //! # Response headers
 //!
 //! Every response (allowed or denied) receives:
-//! - `X-RateLimit-Limit`     — maximum requests in the window
-//! - `X-RateLimit-Remaining` — remaining quota (clamped to 0 on deny)
-//! - `X-RateLimit-Reset`     — UTC Unix timestamp when the window resets
+//! - `X-RateLimit-Limit`     — bucket capacity (limit plus burst allowance)
+//! - `X-RateLimit-Remaining` — cost units that could be spent right now
+//! - `X-RateLimit-Reset`     — UTC Unix timestamp when the bucket is full again
+//! - `X-RateLimit-Cost`      — cost charged for this request
 //!
 //! Denied responses additionally include:
-//! - `Retry-After` — seconds until the window resets
+//! - `Retry-After` — seconds until this request would be allowed
 
 use axum::{
     body::Body,
     extract::Request,
-    http::{header::HeaderName, HeaderValue, StatusCode},
+    http::{header::HeaderName, HeaderValue, Method, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use redis::{aio::ConnectionManager, AsyncCommands};
+use redis::{aio::ConnectionManager, Script};
 use std::{
     collections::HashMap,
     net::IpAddr,
     sync::Arc,
-    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::Mutex;
 use tower::{Layer, Service};
@@ -47,9 +58,9 @@ use crate::models::{ApiErrorCode, ErrorResponse};
 // -----------------------------------------------------------------------/// Rate limit configuration for a single endpoint group.
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
-    /// Maximum requests allowed within the window.
+    /// Cost units allowed per window, refilled continuously.
     pub max_requests: u32,
-    /// Length of the sliding window.
+    /// Time over which `max_requests` units refill.
     pub window: Duration,
 }
 
@@ -62,6 +73,165 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Tenant tier, which decides how far a tenant may burst above its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TenantTier {
+    /// Requests identified only by client IP, including unknown keys
+    Anonymous,
+    /// Configured tenants not assigned another tier
+    Standard,
+    /// Tenants explicitly assigned the premium tier
+    Premium,
+}
+
+impl TenantTier {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "anonymous" => Some(Self::Anonymous),
+            "standard" => Some(Self::Standard),
+            "premium" => Some(Self::Premium),
+            _ => None,
+        }
+    }
+}
+
+/// Parse `RATE_LIMIT_TENANT_TIERS`: comma-separated `tenant=tier` pairs, where
+/// tenants are named as the middleware identifies them (`apikey:<key>` or
+/// `token:<token>`). Malformed entries are skipped with a warning.
+fn parse_tenant_tiers(spec: &str) -> HashMap<String, TenantTier> {
+    let mut tiers = HashMap::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match entry
+            .rsplit_once('=')
+            .and_then(|(tenant, tier)| Some((tenant.trim(), TenantTier::parse(tier)?)))
+        {
+            Some((tenant, tier)) if !tenant.is_empty() => {
+                tiers.insert(tenant.to_string(), tier);
+            }
+            _ => warn!(
+                "Ignoring malformed RATE_LIMIT_TENANT_TIERS entry: {}",
+                entry
+            ),
+        }
+    }
+    tiers
+}
+
+/// Burst allowance per tenant tier, as a percentage of the endpoint limit.
+#[derive(Debug, Clone)]
+pub struct BurstAllowances {
+    pub anonymous_pct: u32,
+    pub standard_pct: u32,
+    pub premium_pct: u32,
+}
+
+impl Default for BurstAllowances {
+    fn default() -> Self {
+        Self {
+            anonymous_pct: 0,
+            standard_pct: std::env::var("RATE_LIMIT_BURST_STANDARD_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(50),
+            premium_pct: std::env::var("RATE_LIMIT_BURST_PREMIUM_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(200),
+        }
+    }
+}
+
+impl BurstAllowances {
+    fn pct(&self, tier: TenantTier) -> u32 {
+        match tier {
+            TenantTier::Anonymous => self.anonymous_pct,
+            TenantTier::Standard => self.standard_pct,
+            TenantTier::Premium => self.premium_pct,
+        }
+    }
+}
+
+/// Hops charged when a route computation omits `max_hops`.
+const DEFAULT_MAX_HOPS: u32 = 3;
+/// Largest `max_hops` the route handlers accept.
+const MAX_MAX_HOPS: u32 = 6;
+/// Largest request body buffered to price a batch or route job.
+const MAX_COSTED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Cost of a request in rate-limit units.
+#[derive(Debug, Clone)]
+pub struct CostWeights {
+    /// Base cost of endpoints without an entry in `endpoints`
+    pub default: u32,
+    /// Base cost per endpoint slug
+    pub endpoints: HashMap<String, u32>,
+    /// Added per hop of `max_hops` on `/api/v1/routes` and route jobs
+    pub per_hop: u32,
+    /// Added per item of `/api/v1/batch/quote`
+    pub per_batch_item: u32,
+}
+
+impl Default for CostWeights {
+    fn default() -> Self {
+        Self {
+            default: 1,
+            // Priced entirely by hops and items
+            endpoints: HashMap::from([
+                ("routes".to_string(), 0),
+                ("route_jobs".to_string(), 0),
+                ("batch_quote".to_string(), 0),
+            ]),
+            per_hop: 1,
+            per_batch_item: 1,
+        }
+    }
+}
+
+impl CostWeights {
+    /// Whether pricing a request to `slug` needs its body.
+    fn needs_body(slug: &str, method: &Method) -> bool {
+        method == Method::POST && matches!(slug, "route_jobs" | "batch_quote")
+    }
+
+    /// Cost of a request to `slug` with the given query string and body.
+    ///
+    /// Never less than 1. Unparseable input is charged as if the parameters
+    /// were omitted; the handler rejects it anyway.
+    pub fn cost(&self, slug: &str, query: Option<&str>, body: Option<&[u8]>) -> u32 {
+        let base = self.endpoints.get(slug).copied().unwrap_or(self.default);
+        let json = || body.and_then(|b| serde_json::from_slice::<serde_json::Value>(b).ok());
+
+        let extra = match slug {
+            "routes" => {
+                let hops = query
+                    .into_iter()
+                    .flat_map(|q| q.split('&'))
+                    .find_map(|pair| pair.strip_prefix("max_hops="))
+                    .and_then(|v| v.parse().ok());
+                self.per_hop * clamp_hops(hops)
+            }
+            "route_jobs" => {
+                let hops = json().and_then(|v| v["max_hops"].as_u64());
+                self.per_hop * clamp_hops(hops.map(|h| h.min(u32::MAX as u64) as u32))
+            }
+            "batch_quote" => {
+                let items = json()
+                    .and_then(|v| v["quotes"].as_array().map(Vec::len))
+                    .unwrap_or(0);
+                self.per_batch_item
+                    .saturating_mul(items.min(u32::MAX as usize) as u32)
+            }
+            _ => 0,
+        };
+
+        base.saturating_add(extra).max(1)
+    }
+}
+
+fn clamp_hops(hops: Option<u32>) -> u32 {
+    hops.unwrap_or(DEFAULT_MAX_HOPS).clamp(1, MAX_MAX_HOPS)
+}
+
 /// Per-endpoint rate limit configurations.
 #[derive(Debug, Clone)]
 pub struct EndpointConfig {
@@ -71,6 +241,10 @@ pub struct EndpointConfig {
     pub default: RateLimitConfig,
     /// Optional overrides for specific tenant IDs (e.g. from API Keys)
     pub tenant_overrides: HashMap<String, RateLimitConfig>,
+    /// Tier of specific tenant IDs (from `RATE_LIMIT_TENANT_TIERS`)
+    pub tenant_tiers: HashMap<String, TenantTier>,
+    pub bursts: BurstAllowances,
+    pub costs: CostWeights,
 }
 
 impl Default for EndpointConfig {
@@ -109,6 +283,11 @@ impl Default for EndpointConfig {
                 window,
             },
             tenant_overrides: HashMap::new(),
+            tenant_tiers: std::env::var("RATE_LIMIT_TENANT_TIERS")
+                .map(|spec| parse_tenant_tiers(&spec))
+                .unwrap_or_default(),
+            bursts: BurstAllowances::default(),
+            costs: CostWeights::default(),
         }
     }
 }
@@ -122,18 +301,145 @@ impl EndpointConfig {
             }
         }
 
-        if path.starts_with("/api/v1/pairs") {
+        if is_under(path, "/api/v1/pairs") {
             &self.pairs
-        } else if path.starts_with("/api/v1/orderbook") {
+        } else if is_under(path, "/api/v1/orderbook") {
             &self.orderbook
-        } else if path.starts_with("/api/v1/quote") {
+        } else if is_under(path, "/api/v1/quote") {
             &self.quote
         } else {
             &self.default
         }
     }
+
+    /// Whether `identity` names a configured tenant rather than a client IP.
+    fn is_known_tenant(&self, identity: &str) -> bool {
+        self.tenant_tiers.contains_key(identity) || self.tenant_overrides.contains_key(identity)
+    }
+
+    /// Identity to limit `req` by: its API key or bearer token if that names a
+    /// configured tenant, otherwise its client IP.
+    fn identify(&self, req: &Request<Body>) -> String {
+        let identity = extract_identity(req);
+        if identity.starts_with("ip:") || self.is_known_tenant(&identity) {
+            identity
+        } else {
+            format!("ip:{}", extract_ip(req))
+        }
+    }
+
+    /// Tier of the tenant behind `identity` (as built by the middleware).
+    pub fn tier_for(&self, identity: &str) -> TenantTier {
+        match self.tenant_tiers.get(identity) {
+            Some(tier) => *tier,
+            None if identity.starts_with("ip:") => TenantTier::Anonymous,
+            None => TenantTier::Standard,
+        }
+    }
+
+    /// GCRA parameters for `config` with the burst allowance of `tier`.
+    fn quota(&self, config: &RateLimitConfig, tier: TenantTier) -> Quota {
+        Quota::new(config, self.bursts.pct(tier))
+    }
 }
 
+// ---------------------------------------------------------------------------
+// GCRA
+// ---------------------------------------------------------------------------
+
+/// GCRA parameters of one bucket, in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quota {
+    /// Cost units the bucket holds when full.
+    capacity: u32,
+    /// Time to refill one cost unit.
+    interval_us: u64,
+}
+
+impl Quota {
+    fn new(config: &RateLimitConfig, burst_pct: u32) -> Self {
+        let max = config.max_requests.max(1);
+        let burst = (max as u64 * burst_pct as u64 / 100).min(u32::MAX as u64) as u32;
+        Self {
+            capacity: max.saturating_add(burst),
+            interval_us: (config.window.as_micros() as u64 / max as u64).max(1),
+        }
+    }
+
+    /// How far the theoretical arrival time may run ahead of now.
+    fn tolerance_us(&self) -> u64 {
+        self.capacity as u64 * self.interval_us
+    }
+}
+
+/// Outcome of charging a bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Decision {
+    allowed: bool,
+    /// Units that could still be spent immediately.
+    remaining: u32,
+    /// Microseconds until the bucket is full again.
+    reset_after_us: u64,
+    /// Microseconds until the denied request would fit; 0 when allowed.
+    retry_after_us: u64,
+}
+
+/// Charge `cost` units against a bucket whose theoretical arrival time is
+/// `tat_us`, returning the decision and the new arrival time (unchanged when
+/// denied). [`GCRA_SCRIPT`] is a line-for-line port of this function.
+fn gcra(tat_us: Option<u64>, now_us: u64, quota: Quota, cost: u32) -> (Decision, u64) {
+    let tolerance = quota.tolerance_us();
+    // A request dearer than the whole bucket is charged the whole bucket
+    let cost = cost.clamp(1, quota.capacity);
+
+    let tat = tat_us.unwrap_or(now_us).max(now_us);
+    let new_tat = tat + cost as u64 * quota.interval_us;
+    let allow_at = new_tat.saturating_sub(tolerance);
+
+    if allow_at > now_us {
+        let decision = Decision {
+            allowed: false,
+            remaining: (tolerance.saturating_sub(tat - now_us) / quota.interval_us) as u32,
+            reset_after_us: tat - now_us,
+            retry_after_us: allow_at - now_us,
+        };
+        return (decision, tat);
+    }
+
+    let decision = Decision {
+        allowed: true,
+        remaining: (tolerance.saturating_sub(new_tat - now_us) / quota.interval_us) as u32,
+        reset_after_us: new_tat - now_us,
+        retry_after_us: 0,
+    };
+    (decision, new_tat)
+}
+
+/// Atomic Redis port of [`gcra`], timed by the Redis clock so every replica
+/// shares one timeline.
+///
+/// `KEYS[1]` is the bucket; `ARGV` is interval, tolerance and cost (the cost
+/// already clamped). Returns `{allowed, remaining, reset_after_us, retry_after_us}`.
+const GCRA_SCRIPT: &str = r#"
+local interval = tonumber(ARGV[1])
+local tolerance = tonumber(ARGV[2])
+local cost = tonumber(ARGV[3])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000000 + tonumber(time[2])
+
+local tat = tonumber(redis.call('GET', KEYS[1])) or now
+if tat < now then tat = now end
+local new_tat = tat + cost * interval
+local allow_at = new_tat - tolerance
+
+if allow_at > now then
+    return {0, math.floor(math.max(0, tolerance - (tat - now)) / interval), tat - now, allow_at - now}
+end
+
+redis.call('SET', KEYS[1], string.format('%d', new_tat), 'PX', math.ceil((new_tat - now) / 1000))
+return {1, math.floor(math.max(0, tolerance - (new_tat - now)) / interval), new_tat - now, 0}
+"#;
+
 // ---------------------------------------------------------------------------
 // Rate-limit info returned after checking
 // ---------------------------------------------------------------------------
@@ -143,50 +449,55 @@ impl EndpointConfig {
 pub struct RateLimitInfo {
     pub limit: u32,
     pub remaining: u32,
-    /// Unix timestamp (seconds) when the window resets.
+    /// Unix timestamp (seconds) when the bucket is full again.
     pub reset: u64,
+    /// Seconds until a denied request would be allowed; 0 when allowed.
+    pub retry_after: u64,
+    /// Cost units charged for the request.
+    pub cost: u32,
     /// True when the request has been denied.
     pub denied: bool,
 }
 
+impl RateLimitInfo {
+    fn new(quota: Quota, cost: u32, decision: Decision) -> Self {
+        Self {
+            limit: quota.capacity,
+            remaining: decision.remaining,
+            reset: unix_now() + decision.reset_after_us.div_ceil(1_000_000),
+            retry_after: decision.retry_after_us.div_ceil(1_000_000),
+            cost,
+            denied: !decision.allowed,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // In-memory backend (used as fallback and in tests)
 // ---------------------------------------------------------------------------
 
+/// Buckets kept before fully refilled ones are dropped.
+const IN_MEMORY_PRUNE_THRESHOLD: usize = 10_000;
+
 #[derive(Default)]
 struct InMemoryStore {
-    /// Identity+endpoint → (count, window_start)
-    windows: HashMap<String, (u32, Instant)>,
+    /// Identity+endpoint → theoretical arrival time (µs since the Unix epoch)
+    buckets: HashMap<String, u64>,
 }
 
 impl InMemoryStore {
-    fn check(&mut self, key: &str, config: &RateLimitConfig) -> RateLimitInfo {
-        let now = Instant::now();
-        let entry = self.windows.entry(key.to_string()).or_insert((0, now));
+    fn check(&mut self, key: &str, quota: Quota, cost: u32) -> RateLimitInfo {
+        let now = unix_now_us();
 
-        // Reset if window expired
-        if now.duration_since(entry.1) >= config.window {
-            *entry = (0, now);
+        if self.buckets.len() >= IN_MEMORY_PRUNE_THRESHOLD {
+            self.buckets.retain(|_, tat| *tat > now);
         }
 
-        let reset_unix = unix_now() + (config.window - now.duration_since(entry.1)).as_secs();
-
-        if entry.0 < config.max_requests {
-            entry.0 += 1;
-            RateLimitInfo {
-                limit: config.max_requests,
-                remaining: config.max_requests - entry.0,
-                reset: reset_unix,
-                denied: false,
-            }
-        } else {
-            RateLimitInfo {
-                limit: config.max_requests,
-                remaining: 0,
-                reset: reset_unix,
-                denied: true,
-            }
+        let (decision, tat) = gcra(self.buckets.get(key).copied(), now, quota, cost);
+        if decision.allowed {
+            self.buckets.insert(key.to_string(), tat);
         }
+        RateLimitInfo::new(quota, cost, decision)
     }
 }
 
@@ -196,82 +507,68 @@ impl InMemoryStore {
 
 async fn redis_check(
     conn: &mut ConnectionManager,
+    script: &Script,
     key: &str,
-    config: &RateLimitConfig,
+    quota: Quota,
+    cost: u32,
 ) -> Option<RateLimitInfo> {
-    let window_secs = config.window.as_secs();
-
-    // Atomically increment and set expiry
-    let count: u32 = match conn.incr::<_, _, u32>(key, 1u32).await {
-        Ok(c) => c,
+    let result: Result<(u8, u32, u64, u64), _> = script
+        .key(key)
+        .arg(quota.interval_us)
+        .arg(quota.tolerance_us())
+        .arg(cost.clamp(1, quota.capacity))
+        .invoke_async(conn)
+        .await;
+
+    match result {
+        Ok((allowed, remaining, reset_after_us, retry_after_us)) => {
+            let decision = Decision {
+                allowed: allowed == 1,
+                remaining,
+                reset_after_us,
+                retry_after_us,
+            };
+            Some(RateLimitInfo::new(quota, cost, decision))
+        }
         Err(e) => {
-            warn!("Redis INCR failed ({}), falling back to allow", e);
-            return None;
+            warn!(
+                "Redis rate limit script failed ({}), using in-memory fallback",
+                e
+            );
+            None
         }
-    };
-
-    // Set TTL only on first request in window
-    if count == 1 {
-        let _: Result<(), _> = conn.expire(key, window_secs as i64).await;
     }
-
-    // Fetch remaining TTL so we can calculate the reset timestamp
-    let ttl_secs: u64 = conn.ttl::<_, u64>(key).await.unwrap_or(window_secs);
-
-    let reset = unix_now() + ttl_secs;
-    let denied = count > config.max_requests;
-
-    Some(RateLimitInfo {
-        limit: config.max_requests,
-        remaining: if denied {
-            0
-        } else {
-            config.max_requests.saturating_sub(count)
-        },
-        reset,
-        denied,
-    })
 }
 
 // ---------------------------------------------------------------------------
 // Backend enum
 // ---------------------------------------------------------------------------
 
+#[derive(Clone)]
 enum Backend {
-    Redis(Arc<Mutex<ConnectionManager>>),
+    Redis {
+        conn: ConnectionManager,
+        script: Arc<Script>,
+        /// Enforces the same limits per instance while Redis is unreachable
+        fallback: Arc<Mutex<InMemoryStore>>,
+    },
     InMemory(Arc<Mutex<InMemoryStore>>),
 }
 
-impl Clone for Backend {
-    fn clone(&self) -> Self {
-        match self {
-            Backend::Redis(c) => Backend::Redis(c.clone()),
-            Backend::InMemory(s) => Backend::InMemory(s.clone()),
-        }
-    }
-}
-
 impl Backend {
-    async fn check(&self, key: &str, config: &RateLimitConfig) -> RateLimitInfo {
+    async fn check(&self, key: &str, quota: Quota, cost: u32) -> RateLimitInfo {
         match self {
-            Backend::Redis(conn) => {
-                let mut guard = conn.lock().await;
-                match redis_check(&mut guard, key, config).await {
-                    Some(info) => info,
-                    None => {
-                        // Redis unavailable — soft fail: allow request
-                        RateLimitInfo {
-                            limit: config.max_requests,
-                            remaining: config.max_requests,
-                            reset: unix_now() + config.window.as_secs(),
-                            denied: false,
-                        }
-                    }
-                }
-            }
+            Backend::Redis {
+                conn,
+                script,
+                fallback,
+            } => match redis_check(&mut conn.clone(), script, key, quota, cost).await {
+                Some(info) => info,
+                None => fallback.lock().await.check(key, quota, cost),
+            },
             Backend::InMemory(store) => {
                 let mut guard = store.lock().await;
-                guard.check(key, config)
+                guard.check(key, quota, cost)
             }
         }
     }
@@ -292,7 +589,11 @@ impl RateLimitLayer {
     /// Create a layer backed by a Redis connection manager.
     pub fn with_redis(conn: ConnectionManager, endpoint_config: EndpointConfig) -> Self {
         Self {
-            backend: Backend::Redis(Arc::new(Mutex::new(conn))),
+            backend: Backend::Redis {
+                conn,
+                script: Arc::new(Script::new(GCRA_SCRIPT)),
+                fallback: Arc::new(Mutex::new(InMemoryStore::default())),
+            },
             endpoint_config: Arc::new(endpoint_config),
         }
     }
@@ -316,6 +617,16 @@ impl RateLimitLayer {
         }
         self
     }
+
+    /// Assign a tenant to a tier, which sets its burst allowance.
+    pub fn with_tier(mut self, tenant_id: impl Into<String>, tier: TenantTier) -> Self {
+        if let Some(cfg) = Arc::get_mut(&mut self.endpoint_config) {
+            cfg.tenant_tiers.insert(tenant_id.into(), tier);
+        } else {
+            warn!("Could not set rate limit tier: EndpointConfig is shared");
+        }
+        self
+    }
 }
 
 impl Default for RateLimitLayer {
@@ -369,18 +680,47 @@ where
 
         Box::pin(async move {
             let path = req.uri().path().to_owned();
-            let identity = extract_identity(&req);
+            let identity = endpoint_config.identify(&req);
             let config = endpoint_config.for_path(&path, Some(&identity));
+            let quota = endpoint_config.quota(config, endpoint_config.tier_for(&identity));
             let endpoint_slug = path_to_slug(&path);
             let key = format!("rate_limit:{}:{}", endpoint_slug, identity);
 
-            debug!("Rate limit check: key={}", key);
-
-            let info = backend.check(&key, config).await;
+            // Batches and route jobs are priced by their body, so buffer it
+            // and hand the handler a copy
+            let (req, cost) = if CostWeights::needs_body(&endpoint_slug, req.method()) {
+                let (parts, body) = req.into_parts();
+                let bytes = match axum::body::to_bytes(body, MAX_COSTED_BODY_BYTES).await {
+                    Ok(bytes) => bytes,
+                    Err(_) => {
+                        return Ok((
+                            StatusCode::PAYLOAD_TOO_LARGE,
+                            Json(ErrorResponse::new(
+                                ApiErrorCode::BadRequest,
+                                "Request body is too large".to_string(),
+                            )),
+                        )
+                            .into_response());
+                    }
+                };
+                let cost =
+                    endpoint_config
+                        .costs
+                        .cost(&endpoint_slug, parts.uri.query(), Some(&bytes));
+                (Request::from_parts(parts, Body::from(bytes)), cost)
+            } else {
+                let cost = endpoint_config
+                    .costs
+                    .cost(&endpoint_slug, req.uri().query(), None);
+                (req, cost)
+            };
+
+            debug!("Rate limit check: key={} cost={}", key, cost);
+
+            let info = backend.check(&key, quota, cost).await;
 
             if info.denied {
                 debug!("Rate limit denied: key={}", key);
-                let retry_after = info.reset.saturating_sub(unix_now());
                 let mut response = (
                     StatusCode::TOO_MANY_REQUESTS,
                     Json(ErrorResponse::new(
@@ -393,7 +733,7 @@ where
                 add_rate_limit_headers(response.headers_mut(), &info);
                 response.headers_mut().insert(
                     HeaderName::from_static("retry-after"),
-                    HeaderValue::from_str(&retry_after.to_string())
+                    HeaderValue::from_str(&info.retry_after.max(1).to_string())
                         .unwrap_or_else(|_| HeaderValue::from_static("60")),
                 );
                 return Ok(response);
@@ -461,24 +801,40 @@ fn extract_ip(req: &Request<Body>) -> IpAddr {
 
 /// Convert a URI path to a slug safe for use in Redis keys.
 fn path_to_slug(path: &str) -> String {
-    if path.starts_with("/api/v1/pairs") {
+    if is_under(path, "/api/v1/pairs") {
         "pairs".to_string()
-    } else if path.starts_with("/api/v1/orderbook") {
+    } else if is_under(path, "/api/v1/orderbook") {
         "orderbook".to_string()
-    } else if path.starts_with("/api/v1/quote") {
+    } else if is_under(path, "/api/v1/quote") {
         "quote".to_string()
+    } else if is_under(path, "/api/v1/quotes") {
+        "quotes".to_string()
+    } else if path.starts_with("/api/v1/routes/") {
+        "routes".to_string()
+    } else if path == "/api/v1/batch/quote" {
+        "batch_quote".to_string()
+    } else if path == "/api/v1/jobs/route" {
+        "route_jobs".to_string()
     } else {
         // Strip leading slash and replace slashes with underscores
         path.trim_start_matches('/').replace('/', "_")
     }
 }
 
+/// Whether `path` is `prefix` or a path below it, matching whole segments so
+/// `/api/v1/quote` does not match `/api/v1/quotes/:id`.
+fn is_under(path: &str, prefix: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
 /// Inject X-RateLimit-* headers into a response.
 fn add_rate_limit_headers(headers: &mut axum::http::HeaderMap, info: &RateLimitInfo) {
     let pairs: &[(&'static str, String)] = &[
         ("x-ratelimit-limit", info.limit.to_string()),
         ("x-ratelimit-remaining", info.remaining.to_string()),
         ("x-ratelimit-reset", info.reset.to_string()),
+        ("x-ratelimit-cost", info.cost.to_string()),
     ];
 
     for (name, value) in pairs {
@@ -498,6 +854,14 @@ fn unix_now() -> u64 {
         .as_secs()
 }
 
+/// Current UTC time in microseconds since the Unix epoch.
+fn unix_now_us() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as u64
+}
+
 // ---------------------------------------------------------------------------
 // Unit tests
 // ---------------------------------------------------------------------------
@@ -540,8 +904,15 @@ mod tests {
     fn endpoint_config_selects_correct_limit() {
         let cfg = EndpointConfig::default();
         assert_eq!(cfg.for_path("/api/v1/pairs", None).max_requests, 60);
-        assert_eq!(cfg.for_path("/api/v1/orderbook/XLM/USDC", None).max_requests, 60);
-        assert_eq!(cfg.for_path("/api/v1/quote/XLM/USDC", None).max_requests, 20);
+        assert_eq!(
+            cfg.for_path("/api/v1/orderbook/XLM/USDC", None)
+                .max_requests,
+            60
+        );
+        assert_eq!(
+            cfg.for_path("/api/v1/quote/XLM/USDC", None).max_requests,
+            20
+        );
         assert_eq!(cfg.for_path("/health", None).max_requests, 120);
         assert_eq!(cfg.for_path("/swagger-ui", None).max_requests, 120);
     }
@@ -552,7 +923,7 @@ mod tests {
         let config = default_config(5);
 
         for i in 1..=5 {
-            let info = store.check("test_key", &config);
+            let info = store.check("test_key", Quota::new(&config, 0), 1);
             assert!(!info.denied, "request {} should be allowed", i);
         }
     }
@@ -563,11 +934,11 @@ mod tests {
         let config = default_config(3);
 
         for _ in 0..3 {
-            let info = store.check("key2", &config);
+            let info = store.check("key2", Quota::new(&config, 0), 1);
             assert!(!info.denied);
         }
 
-        let info = store.check("key2", &config);
+        let info = store.check("key2", Quota::new(&config, 0), 1);
         assert!(info.denied, "4th request should be denied");
         assert_eq!(info.remaining, 0);
     }
@@ -577,10 +948,10 @@ mod tests {
         let mut store = InMemoryStore::default();
         let config = default_config(10);
 
-        let info1 = store.check("key3", &config);
+        let info1 = store.check("key3", Quota::new(&config, 0), 1);
         assert_eq!(info1.remaining, 9);
 
-        let info2 = store.check("key3", &config);
+        let info2 = store.check("key3", Quota::new(&config, 0), 1);
         assert_eq!(info2.remaining, 8);
     }
 
@@ -622,12 +993,22 @@ mod tests {
         assert_eq!(path_to_slug("/health"), "health");
     }
 
+    #[test]
+    fn issued_quote_lookups_are_not_quote_requests() {
+        let id = "/api/v1/quotes/0b6f0f6e-0000-4000-8000-000000000000";
+        assert_eq!(path_to_slug(id), "quotes");
+        assert_eq!(path_to_slug("/api/v1/quotes/another-id"), "quotes");
+        assert_eq!(path_to_slug("/api/v1/pairsx"), "api_v1_pairsx");
+    }
+
     #[tokio::test]
     async fn in_memory_backend_allows_requests() {
         let backend = Backend::InMemory(Arc::new(Mutex::new(InMemoryStore::default())));
         let config = default_config(3);
 
-        let info = backend.check("backend_key", &config).await;
+        let info = backend
+            .check("backend_key", Quota::new(&config, 0), 1)
+            .await;
         assert!(!info.denied);
         assert_eq!(info.limit, 3);
     }
@@ -637,9 +1018,9 @@ mod tests {
         let backend = Backend::InMemory(Arc::new(Mutex::new(InMemoryStore::default())));
         let config = default_config(2);
 
-        backend.check("over_key", &config).await;
-        backend.check("over_key", &config).await;
-        let info = backend.check("over_key", &config).await;
+        backend.check("over_key", Quota::new(&config, 0), 1).await;
+        backend.check("over_key", Quota::new(&config, 0), 1).await;
+        let info = backend.check("over_key", Quota::new(&config, 0), 1).await;
         assert!(info.denied);
         assert_eq!(info.remaining, 0);
     }
@@ -682,11 +1063,256 @@ mod tests {
 
         // Path-based remains the same for others
         assert_eq!(cfg.for_path("/api/v1/quote", None).max_requests, 20);
-        
+
         // Override applied for the specific tenant
         assert_eq!(
             cfg.for_path("/api/v1/quote", Some(tenant_id)).max_requests,
             1000
         );
     }
+
+    #[test]
+    fn gcra_refills_one_unit_per_interval() {
+        let quota = Quota::new(&default_config(60), 0);
+        assert_eq!(quota.interval_us, 1_000_000);
+
+        let mut tat = None;
+        for _ in 0..60 {
+            let (decision, next) = gcra(tat, 0, quota, 1);
+            assert!(decision.allowed);
+            tat = Some(next);
+        }
+        let (denied, _) = gcra(tat, 0, quota, 1);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after_us, 1_000_000);
+
+        // One interval later exactly one more unit is available
+        let (decision, next) = gcra(tat, 1_000_000, quota, 1);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+        assert!(!gcra(Some(next), 1_000_000, quota, 1).0.allowed);
+    }
+
+    #[test]
+    fn gcra_charges_cost_and_denies_without_consuming() {
+        let quota = Quota::new(&default_config(10), 0);
+
+        let (decision, tat) = gcra(None, 0, quota, 6);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 4);
+
+        // A second 6-unit request does not fit and leaves the bucket untouched
+        let (denied, unchanged) = gcra(Some(tat), 0, quota, 6);
+        assert!(!denied.allowed);
+        assert_eq!(denied.remaining, 4);
+        assert_eq!(unchanged, tat);
+        assert_eq!(denied.retry_after_us, 2 * quota.interval_us);
+
+        // ... but cheaper requests still do
+        assert!(gcra(Some(tat), 0, quota, 4).0.allowed);
+    }
+
+    #[test]
+    fn gcra_clamps_cost_to_capacity() {
+        let quota = Quota::new(&default_config(5), 0);
+        let (decision, _) = gcra(None, 0, quota, 50);
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+    }
+
+    #[test]
+    fn burst_allowance_depends_on_tier() {
+        let cfg = EndpointConfig {
+            bursts: BurstAllowances {
+                anonymous_pct: 0,
+                standard_pct: 50,
+                premium_pct: 200,
+            },
+            tenant_tiers: parse_tenant_tiers("apikey:vip=premium"),
+            ..Default::default()
+        };
+
+        assert_eq!(cfg.tier_for("ip:10.0.0.1"), TenantTier::Anonymous);
+        assert_eq!(cfg.tier_for("apikey:vip"), TenantTier::Premium);
+
+        let limit = default_config(20);
+        assert_eq!(cfg.quota(&limit, TenantTier::Anonymous).capacity, 20);
+        assert_eq!(cfg.quota(&limit, TenantTier::Standard).capacity, 30);
+        assert_eq!(cfg.quota(&limit, TenantTier::Premium).capacity, 60);
+
+        // Bursting never raises the sustained rate
+        let anonymous = cfg.quota(&limit, TenantTier::Anonymous);
+        let premium = cfg.quota(&limit, TenantTier::Premium);
+        assert_eq!(anonymous.interval_us, premium.interval_us);
+    }
+
+    #[test]
+    fn tenant_tiers_parse_from_config() {
+        let tiers = parse_tenant_tiers(
+            " apikey:a=premium, token:b=Standard,apikey:c==anonymous,broken,apikey:d=gold,=premium,",
+        );
+        assert_eq!(tiers.len(), 3);
+        assert_eq!(tiers["apikey:a"], TenantTier::Premium);
+        assert_eq!(tiers["token:b"], TenantTier::Standard);
+        assert_eq!(tiers["apikey:c="], TenantTier::Anonymous);
+    }
+
+    #[test]
+    fn unknown_api_keys_are_limited_by_ip() {
+        use axum::http::Request;
+        let mut cfg = EndpointConfig {
+            tenant_tiers: parse_tenant_tiers("apikey:known=standard"),
+            ..Default::default()
+        };
+        cfg.tenant_overrides
+            .insert("token:partner".to_string(), default_config(1000));
+
+        let request = |header: &str, value: &str| {
+            Request::builder()
+                .header(header, value)
+                .header("x-real-ip", "10.1.2.3")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        assert_eq!(cfg.identify(&request("x-api-key", "known")), "apikey:known");
+        assert_eq!(
+            cfg.identify(&request("authorization", "Bearer partner")),
+            "token:partner"
+        );
+        assert_eq!(cfg.identify(&request("x-api-key", "random")), "ip:10.1.2.3");
+        assert_eq!(
+            cfg.identify(&request("authorization", "Bearer random")),
+            "ip:10.1.2.3"
+        );
+        assert_eq!(cfg.tier_for("ip:10.1.2.3"), TenantTier::Anonymous);
+        assert_eq!(cfg.tier_for("apikey:known"), TenantTier::Standard);
+    }
+
+    #[test]
+    fn cost_weights_price_hops_and_batch_items() {
+        let costs = CostWeights::default();
+
+        assert_eq!(costs.cost("pairs", None, None), 1);
+        assert_eq!(costs.cost("quote", Some("amount=10"), None), 1);
+
+        assert_eq!(costs.cost("routes", None, None), DEFAULT_MAX_HOPS);
+        assert_eq!(costs.cost("routes", Some("amount=1&max_hops=6"), None), 6);
+        assert_eq!(
+            costs.cost("routes", Some("max_hops=99"), None),
+            MAX_MAX_HOPS
+        );
+        assert_eq!(
+            costs.cost("routes", Some("max_hops=x"), None),
+            DEFAULT_MAX_HOPS
+        );
+
+        let job = br#"{"base":"native","quote":"USDC","amount":"1","max_hops":5}"#;
+        assert_eq!(costs.cost("route_jobs", None, Some(job)), 5);
+        assert_eq!(
+            costs.cost("route_jobs", None, Some(b"{}")),
+            DEFAULT_MAX_HOPS
+        );
+
+        let batch = br#"{"quotes":[{"base":"a","quote":"b"},{"base":"a","quote":"c"},{"base":"b","quote":"c"}]}"#;
+        assert_eq!(costs.cost("batch_quote", None, Some(batch)), 3);
+        assert_eq!(costs.cost("batch_quote", None, Some(b"not json")), 1);
+    }
+
+    #[test]
+    fn path_to_slug_names_costed_endpoints() {
+        assert_eq!(path_to_slug("/api/v1/routes/native/USDC"), "routes");
+        assert_eq!(path_to_slug("/api/v1/batch/quote"), "batch_quote");
+        assert_eq!(path_to_slug("/api/v1/jobs/route"), "route_jobs");
+    }
+
+    /// Client for a local server that answers every command with a `LOADING`
+    /// error, as Redis does while it restores its dataset. The connection
+    /// handshake ignores the errors; every rate limit script call fails.
+    async fn unavailable_redis() -> redis::Client {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut socket = BufReader::new(socket);
+                    let mut line = String::new();
+                    // Each command is an array (`*<n>`) of bulk strings (`$<len>`)
+                    while socket.read_line(&mut line).await.unwrap_or(0) > 0 {
+                        let args: usize = line.trim_start_matches('*').trim().parse().unwrap_or(0);
+                        for _ in 0..args {
+                            line.clear();
+                            socket.read_line(&mut line).await.unwrap();
+                            let len: usize = line.trim_start_matches('$').trim().parse().unwrap();
+                            let mut arg = vec![0; len + 2];
+                            socket.read_exact(&mut arg).await.unwrap();
+                        }
+                        line.clear();
+                        let reply = b"-LOADING Redis is loading the dataset in memory\r\n";
+                        if socket.get_mut().write_all(reply).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        redis::Client::open(format!("redis://{}", addr)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn redis_fallback_applies_the_same_limits() {
+        let conn = ConnectionManager::new(unavailable_redis().await)
+            .await
+            .expect("connection manager starts against an unavailable Redis");
+        let layer = RateLimitLayer::with_redis(conn, EndpointConfig::default());
+        let config = default_config(2);
+
+        for _ in 0..2 {
+            assert!(
+                !layer
+                    .backend
+                    .check("fallback_key", Quota::new(&config, 0), 1)
+                    .await
+                    .denied
+            );
+        }
+        assert!(
+            layer
+                .backend
+                .check("fallback_key", Quota::new(&config, 0), 1)
+                .await
+                .denied
+        );
+
+        let Backend::Redis { fallback, .. } = &layer.backend else {
+            panic!("expected the Redis backend");
+        };
+        assert!(fallback.lock().await.buckets.contains_key("fallback_key"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running Redis instance (set REDIS_URL)"]
+    async fn redis_script_matches_in_memory_gcra() {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".into());
+        let conn = ConnectionManager::new(redis::Client::open(url).unwrap())
+            .await
+            .unwrap();
+        let backend = RateLimitLayer::with_redis(conn.clone(), EndpointConfig::default()).backend;
+        let mut store = InMemoryStore::default();
+        let key = format!("rate_limit:test:{}", unix_now_us());
+        let quota = Quota::new(&default_config(10), 50);
+
+        for cost in [4, 6, 3, 2, 1, 9] {
+            let redis = backend.check(&key, quota, cost).await;
+            let memory = store.check(&key, quota, cost);
+            assert_eq!(redis.denied, memory.denied, "cost {}", cost);
+            assert_eq!(redis.remaining, memory.remaining, "cost {}", cost);
+        }
+
+        let _: () = redis::AsyncCommands::del(&mut conn.clone(), &key)
+            .await
+            .unwrap();
+    }
 }
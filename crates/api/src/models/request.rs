@@ -1,6 +1,9 @@
 //! API request models
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use stellarroute_routing::{Amount, Rounding};
+
+use super::response::{QuoteResponse, RouteResponse};
 
 /// Default slippage tolerance in basis points (0.50%)
 pub const DEFAULT_SLIPPAGE_BPS: u32 = 50;
@@ -19,10 +22,13 @@ pub struct QuoteParams {
     pub quote_type: QuoteType,
     /// Explain the route selection with decision diagnostics
     pub explain: Option<bool>,
+    /// Account (`G...`) that will execute the trade; checked for a trustline
+    /// to the asset received and a balance of the asset spent
+    pub account: Option<String>,
 }
 
 /// Request item for batch quotes
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct QuoteRequestItem {
     pub base: String,
     pub quote: String,
@@ -32,7 +38,7 @@ pub struct QuoteRequestItem {
 }
 
 /// Batch quote request
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct BatchQuoteRequest {
     pub quotes: Vec<QuoteRequestItem>,
 }
@@ -44,6 +50,46 @@ pub struct RoutesParams {
     pub limit: Option<usize>,
     pub max_hops: Option<usize>,
     pub environment: Option<String>,
+    /// Account (`G...`) that will execute the trade; routes only pass
+    /// through assets it can hold
+    pub account: Option<String>,
+}
+
+/// Body of `POST /api/v1/jobs/route`
+#[derive(Debug, Deserialize)]
+pub struct RouteJobRequest {
+    pub base: String,
+    pub quote: String,
+    pub amount: String,
+    pub slippage_bps: Option<u32>,
+    pub quote_type: Option<QuoteType>,
+    pub max_hops: Option<usize>,
+}
+
+/// Body of `POST /api/v1/swap/build`
+#[derive(Debug, Deserialize)]
+pub struct SwapBuildRequest {
+    /// Account that signs and pays for the transaction (`G...` or `M...`)
+    pub source_account: String,
+    /// Current sequence number of `source_account`; the transaction uses the next one
+    pub sequence: String,
+    /// Account (or, for Soroban swaps, contract) receiving the output;
+    /// defaults to `source_account`
+    pub destination: Option<String>,
+    /// Slippage tolerance in basis points; defaults to the route's own, or
+    /// 50 for quotes
+    pub slippage_bps: Option<u32>,
+    /// Per-operation fee in stroops (default 100)
+    pub base_fee: Option<u32>,
+    /// Seconds from now until the transaction expires (default 300)
+    pub timeout_secs: Option<u64>,
+    /// Last ledger the router may execute a Soroban swap in; unbounded by
+    /// default, the transaction's time bounds still apply
+    pub deadline_ledger: Option<u64>,
+    /// Quote to execute, as returned by `GET /api/v1/quote/{base}/{quote}`
+    pub quote: Option<QuoteResponse>,
+    /// Route to execute as a sell, as returned by `GET /api/v1/route/{base}/{quote}`
+    pub route: Option<RouteResponse>,
 }
 
 impl QuoteParams {
@@ -55,13 +101,13 @@ impl QuoteParams {
     /// Validate the parameters for common requirements
     pub fn validate(&self) -> std::result::Result<(), (String, String)> {
         if let Some(ref amount_str) = self.amount {
-            let amount: f64 = amount_str.parse().map_err(|_| {
+            let amount: Amount = amount_str.parse().map_err(|_| {
                 (
                     "invalid_amount".to_string(),
-                    "Amount must be a numeric string".to_string(),
+                    "Amount must be a decimal string with at most 7 decimal places".to_string(),
                 )
             })?;
-            if amount <= 0.0 {
+            if !amount.is_positive() {
                 return Err((
                     "invalid_amount".to_string(),
                     "Amount must be greater than zero".to_string(),
@@ -69,6 +115,11 @@ impl QuoteParams {
             }
         }
 
+        if let Some(ref account) = self.account {
+            crate::accounts::validate_account_id(account)
+                .map_err(|message| ("invalid_account".to_string(), message))?;
+        }
+
         if self.slippage_bps() > MAX_SLIPPAGE_BPS {
             return Err((
                 "invalid_slippage".to_string(),
@@ -88,7 +139,7 @@ fn default_quote_type() -> QuoteType {
 }
 
 /// Type of quote requested
-#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum QuoteType {
     /// Selling the base asset
@@ -97,6 +148,17 @@ pub enum QuoteType {
     Buy,
 }
 
+impl QuoteType {
+    /// Rounding for the quote total: a sell total is the output received and
+    /// rounds down, a buy total is the input paid and rounds up
+    pub fn total_rounding(self) -> Rounding {
+        match self {
+            Self::Sell => Rounding::Down,
+            Self::Buy => Rounding::Up,
+        }
+    }
+}
+
 /// Asset identifier in path parameters
 #[derive(Debug, Clone, Deserialize)]
 pub struct AssetPath {
@@ -200,6 +262,7 @@ mod tests {
             slippage_bps: None,
             quote_type: QuoteType::Sell,
             explain: None,
+            account: None,
         };
         assert_eq!(params.slippage_bps(), DEFAULT_SLIPPAGE_BPS);
         assert!(params.validate().is_ok());
@@ -212,6 +275,7 @@ mod tests {
             slippage_bps: Some(100),
             quote_type: QuoteType::Sell,
             explain: None,
+            account: None,
         };
         assert_eq!(params.slippage_bps(), 100);
         assert!(params.validate().is_ok());
@@ -224,6 +288,7 @@ mod tests {
             slippage_bps: Some(MAX_SLIPPAGE_BPS),
             quote_type: QuoteType::Sell,
             explain: None,
+            account: None,
         };
         assert_eq!(params.slippage_bps(), MAX_SLIPPAGE_BPS);
         assert!(params.validate().is_ok());
@@ -236,6 +301,7 @@ mod tests {
             slippage_bps: Some(MAX_SLIPPAGE_BPS + 1),
             quote_type: QuoteType::Sell,
             explain: None,
+            account: None,
         };
         let result = params.validate();
         assert!(result.is_err());
@@ -249,6 +315,7 @@ mod tests {
             slippage_bps: None,
             quote_type: QuoteType::Sell,
             explain: None,
+            account: None,
         };
         let result = params.validate();
         assert!(result.is_err());
@@ -262,9 +329,42 @@ mod tests {
             slippage_bps: None,
             quote_type: QuoteType::Sell,
             explain: None,
+            account: None,
         };
         let result = params.validate();
         assert!(result.is_err());
         assert_eq!(result.unwrap_err().0, "invalid_amount");
     }
+
+    #[test]
+    fn test_quote_params_rejects_sub_stroop_amount() {
+        let params = QuoteParams {
+            amount: Some("1.00000001".to_string()),
+            slippage_bps: None,
+            quote_type: QuoteType::Sell,
+            explain: None,
+            account: None,
+        };
+        let result = params.validate();
+        assert_eq!(result.unwrap_err().0, "invalid_amount");
+    }
+
+    #[test]
+    fn test_quote_params_invalid_account() {
+        let params = QuoteParams {
+            amount: None,
+            slippage_bps: None,
+            quote_type: QuoteType::Sell,
+            explain: None,
+            account: Some("GNOTANACCOUNT".to_string()),
+        };
+        let result = params.validate();
+        assert_eq!(result.unwrap_err().0, "invalid_account");
+    }
+
+    #[test]
+    fn test_quote_type_total_rounding() {
+        assert_eq!(QuoteType::Sell.total_rounding(), Rounding::Down);
+        assert_eq!(QuoteType::Buy.total_rounding(), Rounding::Up);
+    }
 }
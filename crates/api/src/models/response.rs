@@ -28,6 +28,28 @@ pub struct DependenciesHealthResponse {
     pub timestamp: String,
     /// Per-dependency status map
     pub components: std::collections::HashMap<String, String>,
+    /// Per-region read replica health (multi-region deployments only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<RegionHealth>,
+}
+
+/// Health of one read region
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RegionHealth {
+    /// Region identifier (e.g. "us-east")
+    pub region: String,
+    /// "healthy", "degraded", "unhealthy" or "circuit_open"
+    pub status: String,
+    /// Consecutive failed health checks
+    pub consecutive_failures: u32,
+    /// Average health check response time in milliseconds
+    pub avg_response_time_ms: u32,
+    /// Measured replica lag in seconds
+    pub replica_lag_secs: u32,
+    /// Unix timestamp of the last successful health check
+    pub last_success_ts: i64,
+    /// Unix timestamp of the last failed health check (0 if none)
+    pub last_failure_ts: i64,
 }
 
 /// Cache metrics response
@@ -41,6 +63,40 @@ pub struct CacheMetricsResponse {
     pub stale_quote_rejections: u64,
     /// Total stale inputs excluded across all successful quotes
     pub stale_inputs_excluded: u64,
+    /// Quote hits served stale inside the grace window while a refresh ran
+    pub quote_stale_hits: u64,
+    /// In-process tier of the two-tier cache, across all cached data
+    pub l1: CacheTierMetrics,
+    /// Redis tier, consulted only on L1 misses
+    pub l2: CacheTierMetrics,
+}
+
+/// Lookups served by one cache tier
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct CacheTierMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    /// hits / (hits + misses)
+    pub hit_ratio: f64,
+    /// Entries currently held (L1 only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entries: Option<u64>,
+}
+
+impl CacheTierMetrics {
+    pub fn new(hits: u64, misses: u64, entries: Option<u64>) -> Self {
+        let hit_ratio = if hits + misses > 0 {
+            hits as f64 / (hits + misses) as f64
+        } else {
+            0.0
+        };
+        Self {
+            hits,
+            misses,
+            hit_ratio,
+            entries,
+        }
+    }
 }
 
 /// Trading pair information — matches GET /api/v1/pairs spec
@@ -178,6 +234,9 @@ pub struct QuoteResponse {
     /// Time-to-live in seconds for client-side staleness detection
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ttl_seconds: Option<u32>,
+    /// Why the quote got its TTL, from market volatility and depth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_reason: Option<crate::cache::TtlReason>,
     /// Rationale for quote venue selection
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rationale: Option<QuoteRationaleMetadata>,
@@ -190,15 +249,83 @@ pub struct QuoteResponse {
     /// Freshness metadata about the data sources used to compute this quote
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data_freshness: Option<DataFreshness>,
+    /// Identifier to fetch this quote with `GET /api/v1/quotes/{id}` until it expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<String>,
+    /// Hex ed25519 signature over the quote's `signed_payload`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Public key (`G...`) that produced `signature`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    /// Better venues left out because they trade an asset the requested
+    /// `account` cannot hold
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_routes: Vec<SkippedRoute>,
+}
+
+/// A quote previously issued by `/api/v1/quote`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IssuedQuoteResponse {
+    pub quote: QuoteResponse,
+    /// Base64 XDR `ScVal` of the router contract's `SignedQuote`; the
+    /// quote's `signature` covers exactly these bytes
+    pub signed_payload: String,
 }
 
 /// Response for a batch quote request
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BatchQuoteResponse {
-    /// Array of quotes in the same order as requested
-    pub quotes: Vec<QuoteResponse>,
+    /// One result per requested item, in the same order as requested
+    pub results: Vec<BatchQuoteResult>,
     /// Total number of quotes successfully fetched
     pub total: usize,
+    /// Number of items that failed
+    pub failed: usize,
+}
+
+/// Outcome of one batch item: `{"ok": QuoteResponse}` or `{"error": ErrorResponse}`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchQuoteResult {
+    Ok(Box<QuoteResponse>),
+    Error(ErrorResponse),
+}
+
+/// Response for a submitted route computation job
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RouteJobSubmitResponse {
+    /// Job id to poll with `GET /api/v1/jobs/{id}`
+    pub job_id: i64,
+    /// Deduplication key of the job
+    pub job_key: String,
+    /// Current job status: "pending", "processing", "completed" or "failed"
+    pub status: String,
+    /// True when an identical job already existed and was returned instead
+    pub deduplicated: bool,
+}
+
+/// Status and result of a route computation job
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RouteJobStatusResponse {
+    pub job_id: i64,
+    pub job_key: String,
+    /// Job status: "pending", "processing", "completed" or "failed"
+    pub status: String,
+    /// Number of failed attempts so far
+    pub attempt: u32,
+    pub max_retries: u32,
+    /// Last error, if any attempt failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Computed route, present once the job has completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<crate::worker::RouteComputationResult>,
+    /// Creation time (Unix timestamp in milliseconds)
+    pub created_at: i64,
+    /// Last status change (Unix timestamp in milliseconds)
+    pub updated_at: i64,
 }
 
 /// Trading route response (path only, no pricing)
@@ -213,6 +340,37 @@ pub struct RouteResponse {
     pub timestamp: i64,
 }
 
+/// Unsigned transaction executing a quote or route
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SwapBuildResponse {
+    /// Base64 XDR of the unsigned `TransactionEnvelope`
+    pub envelope_xdr: String,
+    /// Hex hash of the transaction on `network_passphrase`; Soroban swaps get
+    /// a new hash once simulation data is attached
+    pub hash: String,
+    pub network_passphrase: String,
+    /// "path_payment_strict_send", "path_payment_strict_receive" or
+    /// "invoke_host_function"
+    pub operation: String,
+    pub source_account: String,
+    /// Sequence number of the transaction (the account's current one plus one)
+    pub sequence: String,
+    /// Total fee in stroops, before any Soroban resource fee
+    pub fee: u32,
+    pub send_asset: AssetInfo,
+    pub dest_asset: AssetInfo,
+    /// Most of `send_asset` the transaction can spend
+    pub max_send: String,
+    /// Least of `dest_asset` the destination receives
+    pub min_receive: String,
+    /// Time bounds (Unix seconds)
+    pub min_time: u64,
+    pub max_time: u64,
+    /// True for Soroban swaps, which must go through `simulateTransaction`
+    /// for their footprint, resource fee and auth entries before signing
+    pub requires_simulation: bool,
+}
+
 /// A comprehensive set of multiple ranked execution routes
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RoutesResponse {
@@ -221,6 +379,10 @@ pub struct RoutesResponse {
     pub amount: String,
     pub routes: Vec<RouteCandidate>,
     pub timestamp: i64,
+    /// Routes that would have returned more but pass through an asset the
+    /// requested `account` cannot hold
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_routes: Vec<SkippedRoute>,
 }
 
 /// A single proposed N-hop route with pricing metrics
@@ -233,6 +395,16 @@ pub struct RouteCandidate {
     pub path: Vec<RouteHop>,
 }
 
+/// A better route left out for the requesting account
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkippedRoute {
+    pub route: RouteCandidate,
+    /// Assets on the route the account has no authorized trustline for
+    pub missing_trustlines: Vec<AssetInfo>,
+    /// Human-readable explanation
+    pub reason: String,
+}
+
 /// A specific swap execution step inside a RouteCandidate
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct RouteHop {
@@ -313,6 +485,10 @@ pub struct PathStep {
     pub to_asset: AssetInfo,
     pub price: String,
     pub source: String, // "sdex" or "amm:{pool_address}"
+    /// Least this hop may output within the request's slippage tolerance;
+    /// maps to `RouteHop.min_amount_out` on-chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_amount_out: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -372,6 +548,10 @@ pub enum ApiErrorCode {
     NoRoute,
     /// Underlying market data is too stale to provide a quote
     StaleMarketData,
+    /// No read region satisfies the requested consistency level
+    ConsistencyUnavailable,
+    /// The account named in the request cannot hold or spend the assets involved
+    AccountConstraint,
 }
 
 impl ApiErrorCode {
@@ -390,12 +570,14 @@ impl ApiErrorCode {
             Self::InvalidAssetFormat => "invalid_asset_format",
             Self::NoRoute => "no_route",
             Self::StaleMarketData => "stale_market_data",
+            Self::ConsistencyUnavailable => "consistency_unavailable",
+            Self::AccountConstraint => "account_constraint",
         }
     }
 }
 
 /// Error response
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: ApiErrorCode,
     pub message: String,
@@ -0,0 +1,194 @@
+//! PostgreSQL persistence for idempotent batch quote requests
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Row};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::error::{ApiError, Result};
+use crate::models::{request::BatchQuoteRequest, BatchQuoteResponse};
+
+/// Request header naming a retryable batch
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set when a stored batch result is replayed
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// How long a batch result is replayed for its `Idempotency-Key`
+pub const BATCH_IDEMPOTENCY_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Longest accepted `Idempotency-Key`
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Check an `Idempotency-Key` header value
+pub fn validate_idempotency_key(key: &str) -> Result<()> {
+    if key.is_empty()
+        || key.len() > MAX_IDEMPOTENCY_KEY_LEN
+        || !key.bytes().all(|b| b.is_ascii_graphic())
+    {
+        return Err(ApiError::Validation(format!(
+            "Idempotency-Key must be 1 to {} visible ASCII characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// SHA-256 of the request as the API understood it, so formatting-only
+/// differences between retries still match
+pub fn request_hash(request: &BatchQuoteRequest) -> Result<Vec<u8>> {
+    let body = serde_json::to_vec(request).map_err(|e| {
+        ApiError::Internal(anyhow::anyhow!("Failed to serialize batch request: {}", e).into())
+    })?;
+    Ok(Sha256::digest(body).to_vec())
+}
+
+pub struct BatchIdempotencyStore;
+
+impl BatchIdempotencyStore {
+    /// Stored response for `key`, if any and unexpired. Returns
+    /// `ApiError::Validation` when the key was used for a different request.
+    pub async fn fetch(
+        db: &PgPool,
+        key: &str,
+        request_hash: &[u8],
+    ) -> Result<Option<BatchQuoteResponse>> {
+        let row = sqlx::query(
+            r#"
+            SELECT request_hash, response
+            FROM batch_quote_idempotency
+            WHERE idempotency_key = $1 AND expires_at > NOW()
+            "#,
+        )
+        .bind(key)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to fetch batch result: {}", e).into())
+        })?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let stored_hash: Vec<u8> = row.get("request_hash");
+        if stored_hash != request_hash {
+            return Err(ApiError::Validation(format!(
+                "Idempotency-Key {} was already used for a different batch request",
+                key
+            )));
+        }
+        let response = serde_json::from_value(row.get("response")).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to deserialize batch result: {}", e).into())
+        })?;
+
+        Ok(Some(response))
+    }
+
+    /// Record the response for `key`. Returns `false` when another request
+    /// stored a result for the key first.
+    pub async fn insert(
+        db: &PgPool,
+        key: &str,
+        request_hash: &[u8],
+        response: &BatchQuoteResponse,
+    ) -> Result<bool> {
+        let payload = serde_json::to_value(response).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to serialize batch result: {}", e).into())
+        })?;
+        let expires_at = Utc::now()
+            + chrono::Duration::from_std(BATCH_IDEMPOTENCY_TTL)
+                .unwrap_or_else(|_| chrono::Duration::minutes(10));
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO batch_quote_idempotency
+                (idempotency_key, request_hash, response, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (idempotency_key) DO UPDATE
+                SET request_hash = EXCLUDED.request_hash,
+                    response     = EXCLUDED.response,
+                    expires_at   = EXCLUDED.expires_at,
+                    created_at   = NOW()
+                WHERE batch_quote_idempotency.expires_at <= NOW()
+            "#,
+        )
+        .bind(key)
+        .bind(request_hash)
+        .bind(payload)
+        .bind(expires_at)
+        .execute(db)
+        .await
+        .map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to store batch result: {}", e).into())
+        })?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Delete results that expired before `now`. Returns the number of rows deleted.
+    pub async fn prune_expired(db: &PgPool, now: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query(r#"DELETE FROM batch_quote_idempotency WHERE expires_at <= $1"#)
+            .bind(now)
+            .execute(db)
+            .await
+            .map_err(|e| {
+                ApiError::Internal(anyhow::anyhow!("Failed to prune batch results: {}", e).into())
+            })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Prune expired results every `interval` for the life of the process
+    pub fn spawn_pruner(db: PgPool, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match Self::prune_expired(&db, Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(n) => debug!("Pruned {} expired batch results", n),
+                    Err(e) => warn!("Failed to prune expired batch results: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::request::{QuoteRequestItem, QuoteType};
+
+    fn request(amount: &str) -> BatchQuoteRequest {
+        BatchQuoteRequest {
+            quotes: vec![QuoteRequestItem {
+                base: "native".to_string(),
+                quote: "USDC".to_string(),
+                amount: Some(amount.to_string()),
+                slippage_bps: None,
+                quote_type: Some(QuoteType::Sell),
+            }],
+        }
+    }
+
+    #[test]
+    fn idempotency_key_must_be_visible_ascii() {
+        assert!(validate_idempotency_key("batch-7f3c2a").is_ok());
+        assert!(validate_idempotency_key("").is_err());
+        assert!(validate_idempotency_key("has space").is_err());
+        assert!(validate_idempotency_key(&"k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn request_hash_tracks_request_content() {
+        assert_eq!(
+            request_hash(&request("100")).unwrap(),
+            request_hash(&request("100")).unwrap()
+        );
+        assert_ne!(
+            request_hash(&request("100")).unwrap(),
+            request_hash(&request("200")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Firm, signed quotes
+//!
+//! Every quote `/api/v1/quote` computes is given a `quote_id`, signed with
+//! the API's ed25519 key and recorded in `issued_quotes`, from which
+//! `GET /api/v1/quotes/{id}` serves it until it expires.
+//!
+//! The signature covers the XDR of the router contract's `SignedQuote`
+//! ([`xdr::SignedQuote`]), so partners can check it with any Stellar XDR
+//! library and the router can check it on-chain with `verify_quote` once the
+//! signer is on its allowlist.
+//!
+//! Batch results sent with an `Idempotency-Key` are kept in
+//! `batch_quote_idempotency` ([`idempotency`]) so retries replay them.
+
+pub mod idempotency;
+pub mod store;
+
+pub use idempotency::BatchIdempotencyStore;
+pub use store::IssuedQuoteStore;
+
+use std::fmt;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use stellar_strkey::ed25519::{PrivateKey, PublicKey};
+use stellarroute_routing::Amount;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::error::{ApiError, Result};
+use crate::models::{AssetInfo, QuoteResponse};
+use crate::txbuild::xdr;
+
+/// Signs issued quotes
+pub struct QuoteSigner {
+    key: SigningKey,
+}
+
+impl fmt::Debug for QuoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuoteSigner")
+            .field("public_key", &self.public_key())
+            .finish()
+    }
+}
+
+impl QuoteSigner {
+    /// Signer for a Stellar secret seed (`S...`)
+    pub fn from_secret(seed: &str) -> Result<Self> {
+        let key = PrivateKey::from_string(seed.trim()).map_err(|_| {
+            ApiError::Validation("quote signing key must be a Stellar secret seed".to_string())
+        })?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&key.0),
+        })
+    }
+
+    /// Signer with a random key. Its signatures cannot be checked against
+    /// any allowlist, so it is only meant for development.
+    pub fn ephemeral() -> Self {
+        Self {
+            key: SigningKey::from_bytes(&rand::random::<[u8; 32]>()),
+        }
+    }
+
+    /// Read `QUOTE_SIGNING_KEY`.
+    ///
+    /// Without it an ephemeral key is used only when `QUOTE_SIGNING_EPHEMERAL`
+    /// opts in for development; an invalid key is always an error.
+    pub fn from_env() -> Result<Self> {
+        match std::env::var("QUOTE_SIGNING_KEY") {
+            Ok(seed) if !seed.trim().is_empty() => Self::from_secret(&seed)
+                .map_err(|e| ApiError::Validation(format!("Invalid QUOTE_SIGNING_KEY: {}", e))),
+            _ if allow_ephemeral_key() => {
+                warn!("⚠️  QUOTE_SIGNING_KEY not set, signing quotes with an ephemeral key");
+                Ok(Self::ephemeral())
+            }
+            _ => Err(ApiError::Validation(
+                "QUOTE_SIGNING_KEY is required (set QUOTE_SIGNING_EPHEMERAL=true to sign with a random key in development)"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Public key (`G...`) partners and the router allowlist
+    pub fn public_key(&self) -> String {
+        PublicKey(self.key.verifying_key().to_bytes()).to_string()
+    }
+
+    /// Give `quote` a new `quote_id` and sign it. Returns the signed
+    /// payload; `quote` is left untouched on error.
+    pub fn issue(&self, quote: &mut QuoteResponse) -> Result<Vec<u8>> {
+        let quote_id = Uuid::new_v4().to_string();
+        let payload = signed_quote(&QuoteResponse {
+            quote_id: Some(quote_id.clone()),
+            ..quote.clone()
+        })?
+        .to_xdr()?;
+        let signature = self.key.sign(&payload).to_bytes();
+
+        quote.quote_id = Some(quote_id);
+        quote.signature = Some(signature.iter().map(|b| format!("{:02x}", b)).collect());
+        quote.signer = Some(self.public_key());
+        Ok(payload)
+    }
+}
+
+/// Whether `QUOTE_SIGNING_EPHEMERAL` allows signing with a random key
+fn allow_ephemeral_key() -> bool {
+    std::env::var("QUOTE_SIGNING_EPHEMERAL").is_ok_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+/// The router contract `SignedQuote` an issued quote's signature covers
+pub fn signed_quote(quote: &QuoteResponse) -> Result<xdr::SignedQuote> {
+    let quote_id = quote
+        .quote_id
+        .as_deref()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(|| ApiError::Validation("quote has no valid quote_id".to_string()))?;
+    let expires_at = quote
+        .expires_at
+        .ok_or_else(|| ApiError::Validation("quote has no expires_at".to_string()))?;
+
+    Ok(xdr::SignedQuote {
+        quote_id: *quote_id.as_bytes(),
+        base_asset: asset_string(&quote.base_asset),
+        quote_asset: asset_string(&quote.quote_asset),
+        quote_type: quote.quote_type.clone(),
+        amount: parse_amount("amount", &quote.amount)?.stroops(),
+        price: quote.price.clone(),
+        total: parse_amount("total", &quote.total)?.stroops(),
+        issued_at: unix_millis("timestamp", quote.timestamp)?,
+        expires_at: unix_millis("expires_at", expires_at)?,
+    })
+}
+
+/// Check an issued quote's `signature` against its `signer`
+pub fn verify(quote: &QuoteResponse) -> Result<()> {
+    let invalid = || ApiError::Validation("quote signature is invalid".to_string());
+
+    let signer = quote
+        .signer
+        .as_deref()
+        .and_then(|signer| PublicKey::from_string(signer).ok())
+        .and_then(|signer| VerifyingKey::from_bytes(&signer.0).ok())
+        .ok_or_else(invalid)?;
+    let signature = quote
+        .signature
+        .as_deref()
+        .and_then(decode_hex)
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+        .map(|bytes| Signature::from_bytes(&bytes))
+        .ok_or_else(invalid)?;
+
+    let payload = signed_quote(quote)?.to_xdr()?;
+    signer.verify(&payload, &signature).map_err(|_| invalid())
+}
+
+/// `native`, `CODE:ISSUER`, or `CODE` when the issuer is unknown
+fn asset_string(asset: &AssetInfo) -> String {
+    match (&asset.asset_code, &asset.asset_issuer) {
+        (Some(code), Some(issuer)) => format!("{}:{}", code, issuer),
+        (Some(code), None) => code.clone(),
+        (None, _) => "native".to_string(),
+    }
+}
+
+fn parse_amount(field: &str, value: &str) -> Result<Amount> {
+    value
+        .parse()
+        .map_err(|_| ApiError::Validation(format!("quote {} is not a valid amount", field)))
+}
+
+fn unix_millis(field: &str, value: i64) -> Result<u64> {
+    u64::try_from(value)
+        .map_err(|_| ApiError::Validation(format!("quote {} is before the epoch", field)))
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PathStep;
+
+    const SEED: &str = "SADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP54X";
+    const SIGNER: &str = "GDVEU3DD4KOFECV66VIHWEZOYX4ZKR3WV27L464SIIPOU2IUI3JCZA57";
+
+    fn quote() -> QuoteResponse {
+        QuoteResponse {
+            base_asset: AssetInfo::native(),
+            quote_asset: AssetInfo::credit(
+                "USDC".to_string(),
+                Some("GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string()),
+            ),
+            amount: "100.0000000".to_string(),
+            price: "0.1000000".to_string(),
+            total: "10.0000000".to_string(),
+            quote_type: "sell".to_string(),
+            path: vec![PathStep {
+                from_asset: AssetInfo::native(),
+                to_asset: AssetInfo::credit(
+                    "USDC".to_string(),
+                    Some("GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string()),
+                ),
+                price: "0.1000000".to_string(),
+                source: "sdex".to_string(),
+                min_amount_out: None,
+            }],
+            timestamp: 1_700_000_000_000,
+            expires_at: Some(1_700_000_002_000),
+            source_timestamp: None,
+            ttl_seconds: Some(2),
+            ttl_reason: None,
+            rationale: None,
+            price_impact: None,
+            exclusion_diagnostics: None,
+            data_freshness: None,
+            quote_id: None,
+            signature: None,
+            signer: None,
+            skipped_routes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn issued_quote_verifies() {
+        let signer = QuoteSigner::from_secret(SEED).unwrap();
+        let mut quote = quote();
+        signer.issue(&mut quote).unwrap();
+
+        assert!(Uuid::parse_str(quote.quote_id.as_deref().unwrap()).is_ok());
+        assert_eq!(quote.signer.as_deref(), Some(SIGNER));
+        assert_eq!(quote.signature.as_ref().unwrap().len(), 128);
+        verify(&quote).unwrap();
+    }
+
+    #[test]
+    fn each_issue_gets_a_new_id() {
+        let signer = QuoteSigner::ephemeral();
+        let mut first = quote();
+        let mut second = quote();
+        signer.issue(&mut first).unwrap();
+        signer.issue(&mut second).unwrap();
+        assert_ne!(first.quote_id, second.quote_id);
+    }
+
+    #[test]
+    fn tampered_quote_fails_verification() {
+        let signer = QuoteSigner::ephemeral();
+        let mut quote = quote();
+        signer.issue(&mut quote).unwrap();
+
+        quote.total = "10.0000001".to_string();
+        assert!(matches!(verify(&quote), Err(ApiError::Validation(_))));
+    }
+
+    #[test]
+    fn signature_from_another_key_fails_verification() {
+        let mut quote = quote();
+        QuoteSigner::ephemeral().issue(&mut quote).unwrap();
+
+        quote.signer = Some(QuoteSigner::ephemeral().public_key());
+        assert!(verify(&quote).is_err());
+    }
+
+    #[test]
+    fn failed_issue_leaves_quote_unsigned() {
+        let mut quote = quote();
+        quote.expires_at = None;
+
+        assert!(QuoteSigner::ephemeral().issue(&mut quote).is_err());
+        assert!(quote.quote_id.is_none());
+        assert!(quote.signature.is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_secret() {
+        assert!(QuoteSigner::from_secret(SIGNER).is_err());
+        assert!(QuoteSigner::from_secret("not a seed").is_err());
+    }
+
+    #[test]
+    fn signed_quote_matches_contract_layout() {
+        let mut quote = quote();
+        quote.quote_id = Some("00112233-4455-6677-8899-aabbccddeeff".to_string());
+        let signed = signed_quote(&quote).unwrap();
+
+        assert_eq!(signed.quote_id[0], 0x00);
+        assert_eq!(signed.quote_id[15], 0xff);
+        assert_eq!(signed.base_asset, "native");
+        assert_eq!(
+            signed.quote_asset,
+            "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+        );
+        assert_eq!(signed.amount, 1_000_000_000);
+        assert_eq!(signed.total, 100_000_000);
+        assert_eq!(signed.issued_at, 1_700_000_000_000);
+        assert_eq!(signed.expires_at, 1_700_000_002_000);
+    }
+
+    #[test]
+    fn signed_payload_is_contract_scval() {
+        use base64::Engine;
+
+        let mut quote = quote();
+        quote.quote_id = Some("00112233-4455-6677-8899-aabbccddeeff".to_string());
+        let payload = signed_quote(&quote).unwrap().to_xdr().unwrap();
+
+        // ScVal::Map keyed by field name in sorted order, as `to_xdr` of the
+        // router's `SignedQuote` encodes it
+        assert_eq!(
+            base64::engine::general_purpose::STANDARD.encode(payload),
+            "AAAAEQAAAAEAAAAJAAAADwAAAAZhbW91bnQAAAAAAAoAAAAAAAAAAAAAAAA7msoAAAAADwAAAApiYXNlX2Fzc2V0AAAAAAAOAAAABm5hdGl2ZQAAAAAADwAAAApleHBpcmVzX2F0AAAAAAAFAAABi8/lb9AAAAAPAAAACWlzc3VlZF9hdAAAAAAAAAUAAAGLz+VoAAAAAA8AAAAFcHJpY2UAAAAAAAAOAAAACTAuMTAwMDAwMAAAAAAAAA8AAAALcXVvdGVfYXNzZXQAAAAADgAAAD1VU0RDOkdBNVpTRUpZQjM3SlJDNUFWQ0lBNU1PUDRSSFRNMzM1WDJLR1gzSUhPSkFQUDVSRTM0SzRLWlZOAAAAAAAADwAAAAhxdW90ZV9pZAAAAA0AAAAQABEiM0RVZneImaq7zN3u/wAAAA8AAAAKcXVvdGVfdHlwZQAAAAAADwAAAARzZWxsAAAADwAAAAV0b3RhbAAAAAAAAAoAAAAAAAAAAAAAAAAF9eEA"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! PostgreSQL persistence for issued quotes
+
+use chrono::{DateTime, TimeZone, Utc};
+use sqlx::{PgPool, Row};
+use std::time::Duration;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::error::{ApiError, Result};
+use crate::models::QuoteResponse;
+
+/// Issued quote and the payload its signature covers
+#[derive(Debug, Clone)]
+pub struct IssuedQuote {
+    pub quote: QuoteResponse,
+    pub signed_payload: Vec<u8>,
+}
+
+pub struct IssuedQuoteStore;
+
+impl IssuedQuoteStore {
+    /// Record an issued quote until its `expires_at`
+    pub async fn insert(db: &PgPool, quote: &QuoteResponse, signed_payload: &[u8]) -> Result<()> {
+        let quote_id = quote
+            .quote_id
+            .as_deref()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .ok_or_else(|| ApiError::Validation("quote has no valid quote_id".to_string()))?;
+        let expires_at = quote
+            .expires_at
+            .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+            .ok_or_else(|| ApiError::Validation("quote has no expires_at".to_string()))?;
+        let payload = serde_json::to_value(quote).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to serialize quote: {}", e).into())
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO issued_quotes (quote_id, quote, signed_payload, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(quote_id)
+        .bind(payload)
+        .bind(signed_payload)
+        .bind(expires_at)
+        .execute(db)
+        .await
+        .map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to insert issued quote: {}", e).into())
+        })?;
+
+        Ok(())
+    }
+
+    /// Fetch an unexpired quote. Returns `ApiError::NotFound` once it has
+    /// expired or if it was never issued.
+    pub async fn fetch(db: &PgPool, quote_id: Uuid) -> Result<IssuedQuote> {
+        let row = sqlx::query(
+            r#"
+            SELECT quote, signed_payload
+            FROM issued_quotes
+            WHERE quote_id = $1 AND expires_at > NOW()
+            "#,
+        )
+        .bind(quote_id)
+        .fetch_optional(db)
+        .await
+        .map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to fetch issued quote: {}", e).into())
+        })?;
+
+        let row = row.ok_or_else(|| {
+            ApiError::NotFound(format!("Quote not found or expired: {}", quote_id))
+        })?;
+        let quote = serde_json::from_value(row.get("quote")).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to deserialize issued quote: {}", e).into())
+        })?;
+
+        Ok(IssuedQuote {
+            quote,
+            signed_payload: row.get("signed_payload"),
+        })
+    }
+
+    /// Delete quotes that expired before `now`. Returns the number of rows deleted.
+    pub async fn prune_expired(db: &PgPool, now: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query(r#"DELETE FROM issued_quotes WHERE expires_at <= $1"#)
+            .bind(now)
+            .execute(db)
+            .await
+            .map_err(|e| {
+                ApiError::Internal(anyhow::anyhow!("Failed to prune issued quotes: {}", e).into())
+            })?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Prune expired quotes every `interval` for the life of the process
+    pub fn spawn_pruner(db: PgPool, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match Self::prune_expired(&db, Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(n) => debug!("Pruned {} expired quotes", n),
+                    Err(e) => warn!("Failed to prune expired quotes: {}", e),
+                }
+            }
+        })
+    }
+}
@@ -87,6 +87,9 @@ pub struct ConsistencyConstraint {
 
     /// If true, prefer primary region even if slightly slower
     pub prefer_primary: bool,
+
+    /// Lowest ledger the read must reflect (session read-your-observations)
+    pub min_ledger: Option<u32>,
 }
 
 impl ConsistencyConstraint {
@@ -98,6 +101,7 @@ impl ConsistencyConstraint {
             require_version_match: true,
             max_ledger_skew: Some(0),
             prefer_primary: true,
+            min_ledger: None,
         }
     }
 
@@ -109,6 +113,7 @@ impl ConsistencyConstraint {
             require_version_match: false,
             max_ledger_skew: None,
             prefer_primary: false,
+            min_ledger: None,
         }
     }
 
@@ -120,12 +125,28 @@ impl ConsistencyConstraint {
             require_version_match: true,
             max_ledger_skew: Some(5),
             prefer_primary: true,
+            min_ledger: None,
         }
     }
 
-    /// Check if version satisfies staleness requirement
+    /// Require reads to reflect at least `ledger`, e.g. the last version a
+    /// client has seen
+    pub fn with_min_ledger(mut self, ledger: u32) -> Self {
+        self.min_ledger = Some(ledger);
+        self
+    }
+
+    /// Zero ledger skew can only be guaranteed by the primary region
+    pub fn requires_primary(&self) -> bool {
+        self.require_version_match && self.max_ledger_skew == Some(0)
+    }
+
+    /// Check if version satisfies staleness and minimum ledger requirements
     pub fn satisfies(&self, version: &DataVersion) -> bool {
         !version.is_stale(self.max_age_secs)
+            && self
+                .min_ledger
+                .is_none_or(|min| version.ledger_sequence >= min)
     }
 
     /// Check if version satisfies both staleness and version-match requirements
@@ -275,6 +296,21 @@ mod tests {
         assert!(constraint.allow_degraded);
     }
 
+    #[test]
+    fn test_strong_requires_primary() {
+        assert!(ConsistencyConstraint::strong().requires_primary());
+        assert!(!ConsistencyConstraint::session(5).requires_primary());
+        assert!(!ConsistencyConstraint::eventual(60).requires_primary());
+    }
+
+    #[test]
+    fn test_min_ledger_rejects_older_versions() {
+        let constraint = ConsistencyConstraint::session(10).with_min_ledger(100);
+        assert!(!constraint.satisfies(&DataVersion::new(99)));
+        assert!(constraint.satisfies(&DataVersion::new(100)));
+        assert!(constraint.satisfies(&DataVersion::new(150)));
+    }
+
     #[test]
     fn test_version_tracker_convergence() {
         let tracker = VersionTracker::new();
@@ -17,6 +17,15 @@ pub enum HealthStatus {
 }
 
 impl HealthStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthStatus::Healthy => "healthy",
+            HealthStatus::Degraded => "degraded",
+            HealthStatus::Unhealthy => "unhealthy",
+            HealthStatus::CircuitOpen => "circuit_open",
+        }
+    }
+
     pub fn is_healthy(&self) -> bool {
         matches!(self, HealthStatus::Healthy)
     }
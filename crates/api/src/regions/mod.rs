@@ -36,6 +36,13 @@
 /// println!("Routed to {}, took {}us", decision.region_id, decision.response_time_us);
 /// ```
 ///
+/// # HTTP Reads
+///
+/// Pairs, orderbook and quote handlers read through [`regional_read`]. Clients
+/// choose a level with `X-Consistency: strong|session|eventual`; responses
+/// carry `X-Served-Region` and `X-Data-Version` (ledger sequence). Session
+/// clients send their last `X-Data-Version` back to avoid reading older data.
+///
 /// # Consistency Models
 ///
 /// - **Strong**: Fresh data from primary only (max_age: 1s, no degraded)
@@ -46,17 +53,21 @@
 ///
 /// Reads are attempted in priority order:
 /// 1. Primary region (lowest priority number)
-/// 2. Secondary regions (higher priority numbers)
-/// 3. All enabled regions if allow_degraded
+/// 2. Secondary regions (higher priority numbers), unless strong consistency
+/// 3. Degraded regions only if allow_degraded
+///
+/// Request errors (not found, no route) are returned without failing over.
 ///
 /// Each region has a circuit breaker that opens after N consecutive failures.
 /// Open circuits won't accept requests for a configured timeout period.
 pub mod config;
 pub mod consistency;
 pub mod health;
+pub mod reads;
 pub mod router;
 
 pub use config::{RegionConfig, RegionId, RegionRegistry};
 pub use consistency::{ConsistencyConstraint, DataVersion, VersionTracker};
 pub use health::{HealthSnapshot, HealthStatus, RegionalHealthManager};
-pub use router::{MultiRegionRouter, RoutingDecision, RoutingMetrics};
+pub use reads::{regional_read, DataVersionCache, ReadConsistency, ServedBy};
+pub use router::{fetch_data_version, MultiRegionRouter, RoutingDecision, RoutingMetrics};
@@ -0,0 +1,427 @@
+//! Region-aware reads for HTTP handlers
+//!
+//! Clients pick a consistency level with the `X-Consistency` header
+//! (`strong`, `session` or `eventual`, default `eventual`). Session reads may
+//! echo the last `X-Data-Version` they saw so they never observe older data.
+//! Responses carry the serving region and the data version they reflect.
+
+use async_trait::async_trait;
+use axum::{
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap, HeaderName, HeaderValue},
+    response::{IntoResponseParts, ResponseParts},
+};
+use parking_lot::Mutex;
+use sqlx::PgPool;
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::{
+    consistency::{ConsistencyConstraint, DataVersion},
+    router::fetch_data_version,
+};
+use crate::{
+    error::{ApiError, Result},
+    state::{AppState, DatabasePools},
+};
+
+/// Request header selecting the read consistency level
+pub const CONSISTENCY_HEADER: &str = "x-consistency";
+/// Response header naming the region that served the read
+pub const SERVED_REGION_HEADER: &str = "x-served-region";
+/// Ledger the response reflects; session clients send it back on later reads
+pub const DATA_VERSION_HEADER: &str = "x-data-version";
+
+/// Served region reported when no multi-region router is configured
+pub const LOCAL_REGION: &str = "local";
+
+/// Staleness accepted by session reads
+const SESSION_MAX_AGE_SECS: u64 = 10;
+/// Staleness accepted by eventual reads
+const EVENTUAL_MAX_AGE_SECS: u64 = 30;
+/// How long a fetched data version answers later reads of the same pool
+const VERSION_CACHE_TTL: Duration = Duration::from_secs(1);
+
+/// Consistency level requested by a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadConsistency {
+    /// Fresh data from the primary region only
+    Strong,
+    /// Data no older than `min_ledger`, the last version the client saw
+    Session { min_ledger: Option<u32> },
+    /// Any healthy region within the staleness budget
+    Eventual,
+}
+
+impl ReadConsistency {
+    /// Parse the consistency headers of a request
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self> {
+        let level = match headers.get(CONSISTENCY_HEADER) {
+            None => return Ok(Self::Eventual),
+            Some(value) => value
+                .to_str()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase(),
+        };
+
+        match level.as_str() {
+            "strong" => Ok(Self::Strong),
+            "eventual" => Ok(Self::Eventual),
+            "session" => {
+                let min_ledger = headers
+                    .get(DATA_VERSION_HEADER)
+                    .map(|value| {
+                        value
+                            .to_str()
+                            .ok()
+                            .and_then(|v| v.trim().parse::<u32>().ok())
+                            .ok_or_else(|| {
+                                ApiError::Validation(
+                                    "x-data-version must be a ledger sequence".to_string(),
+                                )
+                            })
+                    })
+                    .transpose()?;
+                Ok(Self::Session { min_ledger })
+            }
+            other => Err(ApiError::Validation(format!(
+                "x-consistency must be one of strong, session, eventual (got '{}')",
+                other
+            ))),
+        }
+    }
+
+    /// Routing constraint for this level
+    pub fn constraint(&self) -> ConsistencyConstraint {
+        match self {
+            Self::Strong => ConsistencyConstraint::strong(),
+            Self::Session { min_ledger } => {
+                let constraint = ConsistencyConstraint::session(SESSION_MAX_AGE_SECS);
+                match min_ledger {
+                    Some(ledger) => constraint.with_min_ledger(*ledger),
+                    None => constraint,
+                }
+            }
+            Self::Eventual => ConsistencyConstraint::eventual(EVENTUAL_MAX_AGE_SECS),
+        }
+    }
+
+    /// Whether response caches may answer this read
+    pub fn allows_cached(&self) -> bool {
+        !matches!(self, Self::Strong)
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ReadConsistency
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+/// Where a read was served from; rendered as response headers
+#[derive(Debug, Clone)]
+pub struct ServedBy {
+    pub region: String,
+    pub version: DataVersion,
+}
+
+impl IntoResponseParts for ServedBy {
+    type Error = Infallible;
+
+    fn into_response_parts(
+        self,
+        mut res: ResponseParts,
+    ) -> std::result::Result<ResponseParts, Self::Error> {
+        if let Ok(region) = HeaderValue::from_str(&self.region) {
+            res.headers_mut()
+                .insert(HeaderName::from_static(SERVED_REGION_HEADER), region);
+        }
+        res.headers_mut().insert(
+            HeaderName::from_static(DATA_VERSION_HEADER),
+            HeaderValue::from(self.version.ledger_sequence),
+        );
+        Ok(res)
+    }
+}
+
+/// Data versions recently read from each pool, so reads do not each pay a
+/// version query
+#[derive(Default)]
+pub struct DataVersionCache {
+    entries: Mutex<HashMap<String, (Instant, DataVersion)>>,
+}
+
+impl DataVersionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Version of a primary pool, for the response headers only
+    ///
+    /// The primary always serves current data, so the cached ledger is
+    /// stamped with the time of the read; it is refetched only past the TTL
+    /// or when it is older than the session's `min_ledger`.
+    pub async fn primary(
+        &self,
+        key: &str,
+        pool: &PgPool,
+        constraint: &ConsistencyConstraint,
+    ) -> Result<DataVersion> {
+        let version = self
+            .get_or_fetch(
+                key,
+                |version| {
+                    constraint
+                        .min_ledger
+                        .is_none_or(|min| version.ledger_sequence >= min)
+                },
+                || fetch_data_version(pool),
+            )
+            .await?;
+        Ok(DataVersion::new(version.ledger_sequence))
+    }
+
+    /// Version of a replica pool, refetched past the TTL or when the cached
+    /// one no longer satisfies `constraint`
+    pub async fn replica(
+        &self,
+        key: &str,
+        pool: &PgPool,
+        constraint: &ConsistencyConstraint,
+    ) -> Result<DataVersion> {
+        self.get_or_fetch(
+            key,
+            |version| constraint.satisfies(version),
+            || fetch_data_version(pool),
+        )
+        .await
+    }
+
+    async fn get_or_fetch<F, Fut>(
+        &self,
+        key: &str,
+        accept: impl Fn(&DataVersion) -> bool,
+        fetch: F,
+    ) -> Result<DataVersion>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<DataVersion>>,
+    {
+        if let Some((fetched_at, version)) = self.entries.lock().get(key) {
+            if fetched_at.elapsed() < VERSION_CACHE_TTL && accept(version) {
+                return Ok(version.clone());
+            }
+        }
+
+        let version = fetch().await?;
+        self.entries
+            .lock()
+            .insert(key.to_string(), (Instant::now(), version.clone()));
+        Ok(version)
+    }
+}
+
+/// Run `read` against the region selected for `consistency`
+///
+/// `read` receives a copy of the state whose read pool points at the chosen
+/// region, so existing query helpers work unchanged. Without a multi-region
+/// router, strong and session reads use the primary pool and eventual reads
+/// the local replica unless it lags beyond the staleness budget. Versions come
+/// from [`DataVersionCache`], so most reads skip the version query.
+pub async fn regional_read<T, F, Fut>(
+    state: &Arc<AppState>,
+    consistency: ReadConsistency,
+    read: F,
+) -> Result<(T, ServedBy)>
+where
+    F: Fn(Arc<AppState>) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let constraint = consistency.constraint();
+
+    let versions = &state.data_versions;
+    let Some(router) = &state.region_router else {
+        // Only an eventual read of a configured replica needs its version checked
+        if consistency == ReadConsistency::Eventual && state.db.has_replica() {
+            let pool = state.db.read_pool().clone();
+            let version = versions.replica("replica", &pool, &constraint).await?;
+            if constraint.satisfies(&version) {
+                let data = read(scoped_state(state, pool)).await?;
+                return Ok((data, local_served_by(version)));
+            }
+        }
+        // The primary always has the newest data
+        let pool = state.db.write_pool().clone();
+        let version = versions.primary("primary", &pool, &constraint).await?;
+        let data = read(scoped_state(state, pool)).await?;
+        return Ok((data, local_served_by(version)));
+    };
+
+    let primary_region = router.primary_region();
+    let (data, decision) = router
+        .read_with_failover(&constraint, |router, region| {
+            let state = state.clone();
+            let read = &read;
+            let constraint = &constraint;
+            async move {
+                let pool = router.pool(region).cloned().ok_or_else(|| {
+                    ApiError::Internal(Arc::new(anyhow::anyhow!(
+                        "no connection pool for region {}",
+                        region
+                    )))
+                })?;
+                // Read the version first so the data is at least that fresh
+                let versions = &state.data_versions;
+                let version = if Some(region) == primary_region {
+                    versions.primary(region.as_str(), &pool, constraint).await?
+                } else {
+                    versions.replica(region.as_str(), &pool, constraint).await?
+                };
+                let data = read(scoped_state(&state, pool)).await?;
+                Ok((data, version))
+            }
+        })
+        .await?;
+
+    Ok((
+        data,
+        ServedBy {
+            region: decision.region_id.as_str().to_string(),
+            version: decision.data_version,
+        },
+    ))
+}
+
+fn local_served_by(version: DataVersion) -> ServedBy {
+    ServedBy {
+        region: LOCAL_REGION.to_string(),
+        version,
+    }
+}
+
+/// Copy of `state` whose read pool is `pool`; writes still go to the primary
+fn scoped_state(state: &Arc<AppState>, pool: PgPool) -> Arc<AppState> {
+    let mut scoped = AppState::clone(state);
+    scoped.db = DatabasePools::new(state.db.write_pool().clone(), Some(pool));
+    Arc::new(scoped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn defaults_to_eventual() {
+        let consistency = ReadConsistency::from_headers(&HeaderMap::new()).unwrap();
+        assert_eq!(consistency, ReadConsistency::Eventual);
+        assert!(consistency.allows_cached());
+    }
+
+    #[test]
+    fn parses_levels_case_insensitively() {
+        let strong = ReadConsistency::from_headers(&headers(&[(CONSISTENCY_HEADER, "Strong")]));
+        assert_eq!(strong.unwrap(), ReadConsistency::Strong);
+
+        let session = ReadConsistency::from_headers(&headers(&[
+            (CONSISTENCY_HEADER, "session"),
+            (DATA_VERSION_HEADER, "51234"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            session,
+            ReadConsistency::Session {
+                min_ledger: Some(51234)
+            }
+        );
+        assert_eq!(session.constraint().min_ledger, Some(51234));
+    }
+
+    #[test]
+    fn rejects_unknown_level_and_bad_version() {
+        let err = ReadConsistency::from_headers(&headers(&[(CONSISTENCY_HEADER, "linearizable")]))
+            .unwrap_err();
+        assert!(matches!(err, ApiError::Validation(_)));
+
+        let err = ReadConsistency::from_headers(&headers(&[
+            (CONSISTENCY_HEADER, "session"),
+            (DATA_VERSION_HEADER, "latest"),
+        ]))
+        .unwrap_err();
+        assert!(matches!(err, ApiError::Validation(_)));
+    }
+
+    #[test]
+    fn strong_reads_bypass_caches_and_require_primary() {
+        assert!(!ReadConsistency::Strong.allows_cached());
+        assert!(ReadConsistency::Strong.constraint().requires_primary());
+    }
+
+    #[tokio::test]
+    async fn version_cache_reuses_fresh_versions() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let cache = DataVersionCache::new();
+        let fetches = AtomicU32::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            Ok(DataVersion::new(100))
+        };
+
+        let first = cache
+            .get_or_fetch("replica", |_| true, fetch)
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_fetch("replica", |_| true, fetch)
+            .await
+            .unwrap();
+        assert_eq!(first.ledger_sequence, 100);
+        assert_eq!(second.ledger_sequence, 100);
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+        // A cached version the read cannot accept is refetched
+        cache
+            .get_or_fetch("replica", |v| v.ledger_sequence > 100, fetch)
+            .await
+            .unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        // Pools are cached separately
+        cache
+            .get_or_fetch("primary", |_| true, fetch)
+            .await
+            .unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn served_by_renders_headers() {
+        let parts = ServedBy {
+            region: "eu-west".to_string(),
+            version: DataVersion::new(777),
+        };
+        let response = axum::response::IntoResponse::into_response((parts, "ok"));
+        assert_eq!(response.headers()[SERVED_REGION_HEADER], "eu-west");
+        assert_eq!(response.headers()[DATA_VERSION_HEADER], "777");
+    }
+}
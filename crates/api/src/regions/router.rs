@@ -2,11 +2,12 @@ use super::config::{RegionConfig, RegionId, RegionRegistry};
 use super::consistency::{ConsistencyConstraint, DataVersion, VersionTracker};
 use super::health::{HealthStatus, RegionalHealthManager};
 use crate::error::{ApiError, Result};
+use sqlx::postgres::PgPoolOptions;
 use sqlx::{PgPool, Pool, Postgres};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Routing decision explains why a region was selected
 #[derive(Debug, Clone)]
@@ -102,35 +103,42 @@ pub struct MultiRegionRouter {
 
 impl MultiRegionRouter {
     /// Create a new multi-region router
+    ///
+    /// Pools connect lazily so an unreachable region does not block startup;
+    /// it is reported through health checks instead.
     pub async fn new(registry: RegionRegistry) -> Result<Self> {
-        let configs: Vec<RegionConfig> = registry
-            .enabled_regions()
-            .into_iter()
-            .filter_map(|r| registry.get_config(r))
-            .collect();
-
-        // Create connection pools for each region
         let mut pools = HashMap::new();
-        for config in &configs {
-            tracing::info!(
-                region = %config.region_id,
-                pool_size = config.pool_size,
-                "Creating connection pool"
-            );
-
-            let pool = PgPool::connect_with(
-                sqlx::postgres::PgConnectOptions::new()
-                    .host(&config.database_url)
-                    .to_owned(),
-            )
-            .await?;
+        for config in Self::enabled_configs(&registry) {
+            pools.insert(config.region_id, Self::connect_lazy(&config)?);
+        }
+
+        Ok(Self::with_pools(registry, pools))
+    }
 
+    /// Create a router that reuses `primary` for the primary region and
+    /// lazily connects to the others
+    pub fn with_primary_pool(registry: RegionRegistry, primary: PgPool) -> Result<Self> {
+        let primary_region = registry.primary_region();
+        let mut pools = HashMap::new();
+        for config in Self::enabled_configs(&registry) {
+            let pool = if Some(config.region_id) == primary_region {
+                primary.clone()
+            } else {
+                Self::connect_lazy(&config)?
+            };
             pools.insert(config.region_id, pool);
         }
 
-        let health = Arc::new(RegionalHealthManager::new(configs));
+        Ok(Self::with_pools(registry, pools))
+    }
 
-        Ok(MultiRegionRouter {
+    /// Create a router over existing connection pools
+    ///
+    /// Regions without a pool are never routed to.
+    pub fn with_pools(registry: RegionRegistry, pools: HashMap<RegionId, PgPool>) -> Self {
+        let health = Arc::new(RegionalHealthManager::new(Self::enabled_configs(&registry)));
+
+        MultiRegionRouter {
             registry,
             health,
             pools: Arc::new(pools),
@@ -139,7 +147,38 @@ impl MultiRegionRouter {
             total_decisions: Arc::new(AtomicU64::new(0)),
             primary_used_count: Arc::new(AtomicU64::new(0)),
             fallback_count: Arc::new(AtomicU64::new(0)),
-        })
+        }
+    }
+
+    fn enabled_configs(registry: &RegionRegistry) -> Vec<RegionConfig> {
+        registry
+            .enabled_regions()
+            .into_iter()
+            .filter_map(|r| registry.get_config(r))
+            .collect()
+    }
+
+    fn connect_lazy(config: &RegionConfig) -> Result<PgPool> {
+        tracing::info!(
+            region = %config.region_id,
+            pool_size = config.pool_size,
+            "Creating connection pool"
+        );
+
+        Ok(PgPoolOptions::new()
+            .max_connections(config.pool_size)
+            .acquire_timeout(Duration::from_secs(5))
+            .connect_lazy(&config.database_url)?)
+    }
+
+    /// Region that defines the newest data, if one is configured
+    pub fn primary_region(&self) -> Option<RegionId> {
+        self.registry.primary_region()
+    }
+
+    /// Connection pool for a region
+    pub fn pool(&self, region_id: RegionId) -> Option<&PgPool> {
+        self.pools.get(&region_id)
     }
 
     /// Execute a read function across regions with failover
@@ -160,7 +199,14 @@ impl MultiRegionRouter {
     {
         self.total_decisions.fetch_add(1, Ordering::Relaxed);
 
-        let all_regions = self.registry.all_regions();
+        let primary = self.registry.primary_region();
+        let all_regions: Vec<RegionId> = self
+            .registry
+            .enabled_regions()
+            .into_iter()
+            .filter(|r| self.pools.contains_key(r))
+            .filter(|r| !constraint.requires_primary() || Some(*r) == primary)
+            .collect();
         let mut last_error: Option<String> = None;
 
         // Try regions in priority order
@@ -189,9 +235,19 @@ impl MultiRegionRouter {
                 Ok((data, version)) => {
                     let elapsed_us = start.elapsed().as_micros() as u64;
 
-                    // Check consistency (staleness + optional ledger skew)
-                    let baseline_ledgers = self.versions.current().ledger_sequence;
-                    if !constraint.satisfies_with_baseline(&version, Some(baseline_ledgers)) {
+                    self.versions
+                        .observe_version(region_id.as_str(), version.clone());
+
+                    // Check consistency. The primary defines the baseline, so only
+                    // replicas are held to the ledger skew limit.
+                    let satisfied = if Some(*region_id) == primary {
+                        constraint.satisfies(&version)
+                    } else {
+                        let baseline = self.versions.current().ledger_sequence;
+                        constraint
+                            .satisfies_with_baseline(&version, (baseline > 0).then_some(baseline))
+                    };
+                    if !satisfied {
                         last_error = Some(format!(
                             "Data violates consistency policy from region {}",
                             region_id
@@ -200,7 +256,7 @@ impl MultiRegionRouter {
                     }
 
                     // Record success if primary
-                    if Some(*region_id) == self.registry.primary_region() {
+                    if Some(*region_id) == primary {
                         self.primary_used_count.fetch_add(1, Ordering::Relaxed);
                         self.versions.update_from_primary(version.clone());
 
@@ -234,6 +290,8 @@ impl MultiRegionRouter {
                         },
                     ));
                 }
+                // Errors about the request itself would fail the same way everywhere
+                Err(e) if !is_region_failure(&e) => return Err(e),
                 Err(e) => {
                     last_error = Some(e.to_string());
                     // Record failure in health check
@@ -246,10 +304,10 @@ impl MultiRegionRouter {
         }
 
         // All regions failed
-        Err(ApiError::Internal(Arc::new(anyhow::anyhow!(
+        Err(ApiError::ConsistencyUnavailable(format!(
             "All regions exhausted: {}",
-            last_error.unwrap_or_else(|| "Unknown error".to_string())
-        ))))
+            last_error.unwrap_or_else(|| "no region available".to_string())
+        )))
     }
 
     /// Get routing metrics
@@ -288,13 +346,19 @@ impl MultiRegionRouter {
             if let Some(pool) = self.pools.get(&region_id) {
                 let start = Instant::now();
 
-                match sqlx::query_scalar::<_, i64>("SELECT EXTRACT(EPOCH FROM NOW())::INT")
+                match sqlx::query_scalar::<_, i64>("SELECT EXTRACT(EPOCH FROM NOW())::BIGINT")
                     .fetch_one(pool)
                     .await
                 {
                     Ok(_) => {
                         let response_time = start.elapsed().as_millis() as u32;
-                        let lag_secs = 0; // TODO: Query replication lag
+                        let lag_secs = match replica_lag_secs(pool).await {
+                            Ok(lag) => lag,
+                            Err(e) => {
+                                tracing::warn!(region = %region_id, error = %e, "Replica lag query failed");
+                                0
+                            }
+                        };
 
                         if let Some(checker) = self.health.get_checker(region_id) {
                             checker.record_success(response_time, lag_secs);
@@ -312,6 +376,74 @@ impl MultiRegionRouter {
 
         Ok(())
     }
+
+    /// Run health checks in the background at the shortest configured interval
+    pub fn start_health_checks(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let interval_secs = self
+            .enabled_regions()
+            .into_iter()
+            .filter_map(|r| self.registry.get_config(r))
+            .map(|c| c.health_check_interval_secs)
+            .min()
+            .unwrap_or(3)
+            .max(1);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.run_health_checks().await {
+                    tracing::warn!(error = %e, "Regional health checks failed");
+                }
+            }
+        })
+    }
+}
+
+/// Read the data version visible through `pool`
+///
+/// The ledger is the newest offer update ingested; the timestamp is pushed
+/// back by the replica's replay lag so staleness checks see replica age.
+pub async fn fetch_data_version(pool: &PgPool) -> Result<DataVersion> {
+    let (ledger, lag_micros): (i64, i64) = sqlx::query_as(
+        r#"
+        select
+            coalesce((select max(last_modified_ledger) from sdex_offers), 0)::bigint,
+            case when pg_is_in_recovery()
+                then coalesce(
+                    (extract(epoch from now() - pg_last_xact_replay_timestamp()) * 1000000)::bigint,
+                    0)
+                else 0
+            end
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let mut version = DataVersion::new(u32::try_from(ledger).unwrap_or(u32::MAX));
+    version.timestamp_micros -= lag_micros.max(0);
+    Ok(version)
+}
+
+/// Replication lag of `pool` in whole seconds (0 on a primary)
+async fn replica_lag_secs(pool: &PgPool) -> Result<u32> {
+    let lag: f64 = sqlx::query_scalar(
+        r#"
+        select case when pg_is_in_recovery()
+            then coalesce(extract(epoch from now() - pg_last_xact_replay_timestamp()), 0)
+            else 0
+        end::float8
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(lag.max(0.0) as u32)
+}
+
+/// Whether a read error reflects the region rather than the request
+fn is_region_failure(error: &ApiError) -> bool {
+    matches!(error, ApiError::Database(_) | ApiError::Internal(_))
 }
 
 impl Clone for MultiRegionRouter {
@@ -354,4 +486,13 @@ mod tests {
         assert!(!decision.is_fallback);
         assert_eq!(decision.regions_evaluated, 1);
     }
+
+    #[test]
+    fn test_only_infrastructure_errors_trigger_failover() {
+        assert!(!is_region_failure(&ApiError::NoRouteFound));
+        assert!(!is_region_failure(&ApiError::NotFound("asset".into())));
+        assert!(is_region_failure(&ApiError::Internal(Arc::new(
+            anyhow::anyhow!("boom")
+        ))));
+    }
 }
@@ -142,11 +142,13 @@ mod tests {
                 to_asset: AssetInfo::native(),
                 price: "1.0000000".to_string(),
                 source: "sdex".to_string(),
+                min_amount_out: None,
             }],
             timestamp: 0,
             expires_at: None,
             source_timestamp: None,
             ttl_seconds: None,
+            ttl_reason: None,
             rationale: None,
             price_impact: None,
             exclusion_diagnostics: None,
@@ -155,6 +157,10 @@ mod tests {
                 stale_count: 0,
                 max_staleness_secs: 0,
             }),
+            quote_id: None,
+            signature: None,
+            signer: None,
+            skipped_routes: Vec::new(),
         }
     }
 
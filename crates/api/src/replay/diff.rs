@@ -187,6 +187,7 @@ mod tests {
                 to_asset: AssetInfo::native(),
                 price: price.to_string(),
                 source: source.to_string(),
+                min_amount_out: None,
             }],
             is_deterministic: true,
             replayed_at: Utc::now(),
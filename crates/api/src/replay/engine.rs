@@ -7,6 +7,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use stellarroute_routing::{Amount, Price};
 use uuid::Uuid;
 
 use crate::error::{ApiError, Result};
@@ -65,7 +66,7 @@ impl ReplayEngine {
         }
 
         // Parse amount
-        let amount: f64 = artifact
+        let amount: Amount = artifact
             .amount
             .parse()
             .map_err(|_| ApiError::BadRequest("Invalid amount in artifact".to_string()))?;
@@ -98,18 +99,19 @@ impl ReplayEngine {
         let path = vec![PathStep {
             from_asset: base_info,
             to_asset: quote_info,
-            price: format!("{:.7}", selected.price),
+            price: selected.price.to_string(),
             source: if selected.venue_type == "amm" {
                 format!("amm:{}", selected.venue_ref)
             } else {
                 "sdex".to_string()
             },
+            min_amount_out: None,
         }];
 
         Ok(ReplayOutput {
             artifact_id: artifact.id,
             selected_source,
-            price: format!("{:.7}", selected.price),
+            price: selected.price.to_string(),
             path,
             is_deterministic,
             replayed_at: Utc::now(),
@@ -125,13 +127,13 @@ impl ReplayEngine {
 struct ReplayCandidate {
     venue_type: String,
     venue_ref: String,
-    price: f64,
-    available_amount: f64,
+    price: Price,
+    available_amount: Amount,
 }
 
 fn parse_candidate(row: &LiquidityCandidate) -> Option<ReplayCandidate> {
-    let price: f64 = row.price.parse().ok()?;
-    let available_amount: f64 = row.available_amount.parse().ok()?;
+    let price: Price = row.price.parse().ok()?;
+    let available_amount: Amount = row.available_amount.parse().ok()?;
     Some(ReplayCandidate {
         venue_type: row.venue_type.clone(),
         venue_ref: row.venue_ref.clone(),
@@ -144,7 +146,7 @@ fn parse_candidate(row: &LiquidityCandidate) -> Option<ReplayCandidate> {
 /// then pick the first candidate with sufficient liquidity and positive price.
 fn select_best_venue(
     mut candidates: Vec<ReplayCandidate>,
-    amount: f64,
+    amount: Amount,
 ) -> Result<(ReplayCandidate, Vec<ReplayCandidate>)> {
     if candidates.is_empty() {
         return Err(ApiError::NoRouteFound);
@@ -152,15 +154,14 @@ fn select_best_venue(
 
     candidates.sort_by(|a, b| {
         a.price
-            .partial_cmp(&b.price)
-            .unwrap_or(std::cmp::Ordering::Equal)
+            .cmp(&b.price)
             .then_with(|| a.venue_type.cmp(&b.venue_type))
             .then_with(|| a.venue_ref.cmp(&b.venue_ref))
     });
 
     let selected = candidates
         .iter()
-        .find(|c| c.available_amount >= amount && c.price > 0.0)
+        .find(|c| c.available_amount >= amount && c.price.is_positive())
         .cloned()
         .ok_or(ApiError::NoRouteFound)?;
 
@@ -5,7 +5,7 @@ use std::{collections::HashMap, sync::Arc};
 use tracing::warn;
 
 use crate::{
-    models::{DependenciesHealthResponse, HealthResponse},
+    models::{DependenciesHealthResponse, HealthResponse, RegionHealth},
     state::AppState,
 };
 
@@ -41,20 +41,12 @@ pub async fn health_check(State(state): State<Arc<AppState>>) -> impl IntoRespon
 
     // --- Redis (optional) ---
     let redis_status = if let Some(cache) = &state.cache {
-        match cache.try_lock() {
-            Ok(mut guard) => {
-                if guard.is_healthy().await {
-                    "healthy".to_string()
-                } else {
-                    warn!("Redis health check failed");
-                    all_healthy = false;
-                    "unhealthy".to_string()
-                }
-            }
-            Err(_) => {
-                // Lock contention — treat as healthy rather than a false alert
-                "healthy".to_string()
-            }
+        if cache.is_healthy().await {
+            "healthy".to_string()
+        } else {
+            warn!("Redis health check failed");
+            all_healthy = false;
+            "unhealthy".to_string()
         }
     } else {
         // Redis not configured — report as not_configured so callers know
@@ -112,19 +104,11 @@ pub async fn dependency_health(State(state): State<Arc<AppState>>) -> impl IntoR
 
     // --- Redis (optional) ---
     let redis_status = if let Some(cache) = &state.cache {
-        match cache.try_lock() {
-            Ok(mut guard) => {
-                if guard.is_healthy().await {
-                    "healthy".to_string()
-                } else {
-                    all_ok = false;
-                    "degraded".to_string()
-                }
-            }
-            Err(_) => {
-                // Lock contention — treat as healthy rather than a false alert
-                "healthy".to_string()
-            }
+        if cache.is_healthy().await {
+            "healthy".to_string()
+        } else {
+            all_ok = false;
+            "degraded".to_string()
         }
     } else {
         "not_configured".to_string()
@@ -137,11 +121,47 @@ pub async fn dependency_health(State(state): State<Arc<AppState>>) -> impl IntoR
     components.insert("horizon".to_string(), "not_configured".to_string());
     components.insert("soroban_rpc".to_string(), "not_configured".to_string());
 
+    // --- Read regions (optional) ---
+    // Readiness only fails when no region can serve reads; a lagging or
+    // failing secondary is reported as degraded.
+    let mut regions = Vec::new();
+    let regions_status = if let Some(router) = &state.region_router {
+        let mut snapshots = router.health_snapshots();
+        snapshots.sort_by_key(|s| s.region_id);
+        let usable = snapshots.iter().filter(|s| s.status.is_usable()).count();
+        let healthy = snapshots.iter().filter(|s| s.status.is_healthy()).count();
+        regions = snapshots
+            .into_iter()
+            .map(|s| RegionHealth {
+                region: s.region_id.as_str().to_string(),
+                status: s.status.as_str().to_string(),
+                consecutive_failures: s.consecutive_failures,
+                avg_response_time_ms: s.avg_response_time_ms,
+                replica_lag_secs: s.replica_lag_secs,
+                last_success_ts: s.last_success_ts,
+                last_failure_ts: s.last_failure_ts,
+            })
+            .collect();
+
+        if usable == 0 {
+            all_ok = false;
+        }
+        if healthy == regions.len() {
+            "healthy"
+        } else {
+            "degraded"
+        }
+    } else {
+        "not_configured"
+    };
+    components.insert("regions".to_string(), regions_status.to_string());
+
     let status = if all_ok { "ok" } else { "degraded" }.to_string();
     let body = DependenciesHealthResponse {
         status,
         timestamp,
         components,
+        regions,
     };
 
     let http_status = if all_ok {
@@ -0,0 +1,317 @@
+//! Async route computation jobs
+//!
+//! For computations that don't fit the synchronous `/routes` latency budget
+//! (large amounts, deep hop limits, bulk pricing). Jobs are queued in
+//! `route_computation_jobs` and executed by the background route workers.
+//!
+//! - `POST /api/v1/jobs/route` — submit a job, returns its id
+//! - `GET  /api/v1/jobs/:id`   — job status and, once completed, its result
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use stellarroute_routing::Amount;
+use tracing::debug;
+
+use crate::{
+    error::{ApiError, Result},
+    models::{
+        request::{AssetPath, QuoteType, RouteJobRequest, DEFAULT_SLIPPAGE_BPS, MAX_SLIPPAGE_BPS},
+        RouteJobStatusResponse, RouteJobSubmitResponse,
+    },
+    routes::quote::find_asset_id,
+    state::AppState,
+    worker::{JobRecord, RouteComputationResult, RouteComputationTaskPayload},
+};
+
+/// Default hop limit for route jobs
+const DEFAULT_JOB_MAX_HOPS: usize = 3;
+/// Deepest hop limit accepted for route jobs
+const MAX_JOB_MAX_HOPS: usize = 6;
+
+/// Submit an async route computation job
+///
+/// Identical requests share one job: the id of the existing job is returned
+/// while it is queued, running, or its result is still fresh.
+#[utoipa::path(
+    post,
+    path = "/api/v1/jobs/route",
+    tag = "trading",
+    responses(
+        (status = 202, description = "Job accepted", body = RouteJobSubmitResponse),
+        (status = 400, description = "Invalid parameters", body = ErrorResponse),
+        (status = 404, description = "Asset not found", body = ErrorResponse),
+        (status = 503, description = "Job queue overloaded", body = ErrorResponse),
+    )
+)]
+pub async fn submit_route_job(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<RouteJobRequest>,
+) -> Result<(StatusCode, Json<RouteJobSubmitResponse>)> {
+    let job = ValidatedRouteJob::parse(request)?;
+    debug!(
+        "Submitting route job {}/{} amount={} max_hops={}",
+        job.base_canonical, job.quote_canonical, job.amount, job.max_hops
+    );
+
+    let base_asset_id = find_asset_id(&state, &job.base).await?;
+    let quote_asset_id = find_asset_id(&state, &job.quote).await?;
+
+    let payload = RouteComputationTaskPayload {
+        base_asset: job.base_canonical.clone(),
+        quote_asset: job.quote_canonical.clone(),
+        base_asset_id,
+        quote_asset_id,
+        amount: job.amount,
+        slippage_bps: job.slippage_bps,
+        quote_type: job.quote_type.to_string(),
+        max_hops: Some(job.max_hops),
+    };
+
+    let outcome = state
+        .worker_pool
+        .submit_job(&job.base_canonical, &job.quote_canonical, payload)
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(RouteJobSubmitResponse {
+            job_id: outcome.id,
+            job_key: outcome.job_key,
+            status: outcome.status,
+            deduplicated: !outcome.enqueued,
+        }),
+    ))
+}
+
+/// Get the status and result of a route computation job
+#[utoipa::path(
+    get,
+    path = "/api/v1/jobs/{id}",
+    tag = "trading",
+    params(
+        ("id" = i64, Path, description = "Job id returned by POST /api/v1/jobs/route"),
+    ),
+    responses(
+        (status = 200, description = "Job status", body = RouteJobStatusResponse),
+        (status = 404, description = "Job not found", body = ErrorResponse),
+    )
+)]
+pub async fn get_route_job(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<RouteJobStatusResponse>> {
+    let record = state
+        .worker_pool
+        .get_job(id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Route job not found: {}", id)))?;
+
+    Ok(Json(status_response(record)?))
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// A [`RouteJobRequest`] with defaults applied and inputs validated
+#[derive(Debug)]
+struct ValidatedRouteJob {
+    base: AssetPath,
+    quote: AssetPath,
+    base_canonical: String,
+    quote_canonical: String,
+    amount: Amount,
+    slippage_bps: u32,
+    quote_type: &'static str,
+    max_hops: usize,
+}
+
+impl ValidatedRouteJob {
+    fn parse(request: RouteJobRequest) -> Result<Self> {
+        let base = AssetPath::parse(&request.base)
+            .map_err(|e| ApiError::InvalidAsset(format!("Invalid base asset: {}", e)))?;
+        let quote = AssetPath::parse(&request.quote)
+            .map_err(|e| ApiError::InvalidAsset(format!("Invalid quote asset: {}", e)))?;
+
+        let amount: Amount = request
+            .amount
+            .parse()
+            .map_err(|e| ApiError::InvalidAmount(format!("Invalid amount: {e}")))?;
+        if !amount.is_positive() {
+            return Err(ApiError::InvalidAmount("amount must be positive".into()));
+        }
+
+        let slippage_bps = request.slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_BPS);
+        if slippage_bps > MAX_SLIPPAGE_BPS {
+            return Err(ApiError::InvalidSlippage(format!(
+                "slippage_bps must be between 0 and {} (100%)",
+                MAX_SLIPPAGE_BPS
+            )));
+        }
+
+        let max_hops = request.max_hops.unwrap_or(DEFAULT_JOB_MAX_HOPS);
+        if !(1..=MAX_JOB_MAX_HOPS).contains(&max_hops) {
+            return Err(ApiError::Validation(format!(
+                "max_hops must be between 1 and {}",
+                MAX_JOB_MAX_HOPS
+            )));
+        }
+
+        let quote_type = match request.quote_type.unwrap_or(QuoteType::Sell) {
+            QuoteType::Sell => "sell",
+            QuoteType::Buy => "buy",
+        };
+
+        Ok(Self {
+            base_canonical: base.to_canonical(),
+            quote_canonical: quote.to_canonical(),
+            base,
+            quote,
+            amount,
+            slippage_bps,
+            quote_type,
+            max_hops,
+        })
+    }
+}
+
+fn status_response(record: JobRecord) -> Result<RouteJobStatusResponse> {
+    let result = record
+        .result
+        .map(serde_json::from_value::<RouteComputationResult>)
+        .transpose()
+        .map_err(|e| {
+            ApiError::Internal(Arc::new(anyhow::anyhow!(
+                "Stored result of job {} is invalid: {}",
+                record.id,
+                e
+            )))
+        })?;
+
+    Ok(RouteJobStatusResponse {
+        job_id: record.id,
+        job_key: record.job_key,
+        status: record.status,
+        attempt: record.attempt,
+        max_retries: record.max_retries,
+        error: record.error_message,
+        result,
+        created_at: record.created_at.timestamp_millis(),
+        updated_at: record.updated_at.timestamp_millis(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn request(json: serde_json::Value) -> RouteJobRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn parse_applies_defaults() {
+        let job = ValidatedRouteJob::parse(request(serde_json::json!({
+            "base": "native",
+            "quote": "USDC:GISSUER",
+            "amount": "2500"
+        })))
+        .unwrap();
+
+        assert_eq!(job.base_canonical, "native");
+        assert_eq!(job.quote_canonical, "USDC:GISSUER");
+        assert_eq!(job.slippage_bps, DEFAULT_SLIPPAGE_BPS);
+        assert_eq!(job.quote_type, "sell");
+        assert_eq!(job.max_hops, DEFAULT_JOB_MAX_HOPS);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_amount() {
+        for amount in ["abc", "0", "-5", "inf"] {
+            let err = ValidatedRouteJob::parse(request(serde_json::json!({
+                "base": "native",
+                "quote": "USDC",
+                "amount": amount
+            })))
+            .unwrap_err();
+            assert!(matches!(err, ApiError::InvalidAmount(_)), "{}", amount);
+        }
+    }
+
+    #[test]
+    fn parse_keeps_amount_exact() {
+        let job = ValidatedRouteJob::parse(request(serde_json::json!({
+            "base": "native",
+            "quote": "USDC",
+            "amount": "922337203685.4775807"
+        })))
+        .unwrap();
+        assert_eq!(job.amount.to_string(), "922337203685.4775807");
+    }
+
+    #[test]
+    fn parse_rejects_out_of_range_max_hops() {
+        for max_hops in [0, MAX_JOB_MAX_HOPS + 1] {
+            let err = ValidatedRouteJob::parse(request(serde_json::json!({
+                "base": "native",
+                "quote": "USDC",
+                "amount": "1",
+                "max_hops": max_hops
+            })))
+            .unwrap_err();
+            assert!(matches!(err, ApiError::Validation(_)));
+        }
+    }
+
+    #[test]
+    fn parse_rejects_excessive_slippage() {
+        let err = ValidatedRouteJob::parse(request(serde_json::json!({
+            "base": "native",
+            "quote": "USDC",
+            "amount": "1",
+            "slippage_bps": MAX_SLIPPAGE_BPS + 1
+        })))
+        .unwrap_err();
+        assert!(matches!(err, ApiError::InvalidSlippage(_)));
+    }
+
+    #[test]
+    fn status_response_decodes_result() {
+        let result = RouteComputationResult {
+            job_id: "route:native:USDC:1.0000000:sell:s50:h3".to_string(),
+            quote_type: "sell".to_string(),
+            price: "0.1".parse().unwrap(),
+            total: "0.1".parse().unwrap(),
+            slippage_limit: "0.0995".parse().unwrap(),
+            impact_bps: 5,
+            path: vec![],
+            computed_at: Utc::now(),
+            ttl_millis: 2000,
+        };
+        let record = JobRecord {
+            id: 7,
+            job_key: result.job_id.clone(),
+            status: "completed".to_string(),
+            attempt: 0,
+            max_retries: 3,
+            error_message: None,
+            result: Some(serde_json::to_value(&result).unwrap()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let response = status_response(record).unwrap();
+        assert_eq!(response.job_id, 7);
+        assert_eq!(response.status, "completed");
+        assert_eq!(response.result.unwrap().impact_bps, 5);
+    }
+}
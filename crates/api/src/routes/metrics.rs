@@ -3,7 +3,10 @@
 use axum::{extract::State, Json};
 use std::sync::Arc;
 
-use crate::{models::CacheMetricsResponse, state::AppState};
+use crate::{
+    models::{CacheMetricsResponse, CacheTierMetrics},
+    state::AppState,
+};
 
 /// Cache metrics endpoint
 #[utoipa::path(
@@ -11,7 +14,7 @@ use crate::{models::CacheMetricsResponse, state::AppState};
     path = "/metrics/cache",
     tag = "health",
     responses(
-        (status = 200, description = "Cache hit/miss metrics", body = CacheMetricsResponse),
+        (status = 200, description = "Cache hit/miss metrics, overall and per cache tier", body = CacheMetricsResponse),
     )
 )]
 pub async fn cache_metrics(State(state): State<Arc<AppState>>) -> Json<CacheMetricsResponse> {
@@ -24,11 +27,26 @@ pub async fn cache_metrics(State(state): State<Arc<AppState>>) -> Json<CacheMetr
         0.0
     };
 
+    let (l1, l2) = match &state.cache {
+        Some(cache) => {
+            let (l1_hits, l1_misses) = cache.l1_stats().snapshot();
+            let (l2_hits, l2_misses) = cache.l2_stats().snapshot();
+            (
+                CacheTierMetrics::new(l1_hits, l1_misses, Some(cache.l1_len() as u64)),
+                CacheTierMetrics::new(l2_hits, l2_misses, None),
+            )
+        }
+        None => Default::default(),
+    };
+
     Json(CacheMetricsResponse {
         quote_hits,
         quote_misses,
         hit_ratio,
         stale_quote_rejections,
         stale_inputs_excluded,
+        quote_stale_hits: state.cache_metrics.quote_stale_hits(),
+        l1,
+        l2,
     })
 }
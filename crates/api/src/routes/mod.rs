@@ -1,14 +1,17 @@
 //! API routes
 
 pub mod health;
+pub mod jobs;
 pub mod metrics;
 pub mod orderbook;
 pub mod pairs;
 pub mod prometheus;
 pub mod quote;
 
+pub mod reconciliation;
 pub mod replay;
 pub mod routes_endpoint;
+pub mod swap;
 
 pub mod ws;
 use axum::{
@@ -36,6 +39,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             get(orderbook::get_orderbook),
         )
         .route("/api/v1/quote/:base/:quote", get(quote::get_quote))
+        .route("/api/v1/quotes/:id", get(quote::get_issued_quote))
         .route(
             "/api/v1/route/:base/:quote",
             get(quote::get_route).route_layer(axum::middleware::from_fn(legacy_route_deprecation)),
@@ -44,11 +48,29 @@ pub fn create_router(state: Arc<AppState>) -> Router {
             "/api/v1/batch/quote",
             axum::routing::post(quote::get_batch_quotes),
         )
+        // Unsigned swap transactions
+        .route("/api/v1/swap/build", post(swap::build_swap))
+        // Async route computation jobs
+        .route("/api/v1/jobs/route", post(jobs::submit_route_job))
+        .route("/api/v1/jobs/:id", get(jobs::get_route_job))
         // Replay routes
         .route("/api/v1/replay", get(replay::list_artifacts))
         .route("/api/v1/replay/:id", get(replay::get_artifact))
         .route("/api/v1/replay/:id/run", post(replay::run_replay))
         .route("/api/v1/replay/:id/diff", post(replay::diff_replay))
+        // Reconciliation ops routes
+        .route(
+            "/api/v1/ops/reconciliation/runs",
+            get(reconciliation::list_runs),
+        )
+        .route(
+            "/api/v1/ops/reconciliation/drift",
+            get(reconciliation::list_drift),
+        )
+        .route(
+            "/api/v1/ops/reconciliation/repairs",
+            get(reconciliation::list_repairs),
+        )
         .route(
             "/api/v1/routes/:base/:quote",
             get(routes_endpoint::get_routes),
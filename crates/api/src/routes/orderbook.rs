@@ -5,13 +5,14 @@ use axum::{
     Json,
 };
 use sqlx::Row;
-use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use std::{collections::BTreeMap, sync::Arc};
 use tracing::{debug, warn};
 
 use crate::{
     cache,
     error::{ApiError, Result},
     models::{request::AssetPath, AssetInfo, OrderbookLevel, OrderbookResponse},
+    regions::{regional_read, ReadConsistency, ServedBy},
     state::AppState,
 };
 
@@ -25,6 +26,7 @@ use crate::{
     params(
         ("base" = String, Path, description = "Base asset (e.g., 'native', 'USDC', or 'USDC:ISSUER')"),
         ("quote" = String, Path, description = "Quote asset (e.g., 'native', 'USDC', or 'USDC:ISSUER')"),
+        ("x-consistency" = Option<String>, Header, description = "Read consistency: strong, session or eventual (default)"),
     ),
     responses(
         (status = 200, description = "Orderbook data", body = OrderbookResponse),
@@ -36,42 +38,91 @@ use crate::{
 pub async fn get_orderbook(
     State(state): State<Arc<AppState>>,
     Path((base, quote)): Path<(String, String)>,
-) -> Result<Json<OrderbookResponse>> {
-    debug!("Fetching orderbook for {}/{}", base, quote);
+    consistency: ReadConsistency,
+) -> Result<(ServedBy, Json<OrderbookResponse>)> {
+    debug!(
+        "Fetching orderbook for {}/{} ({:?})",
+        base, quote, consistency
+    );
 
-    // Try to get from cache first
-    if let Some(cache) = &state.cache {
-        if let Ok(mut cache) = cache.try_lock() {
-            if let Some(cached) = cache
-                .get::<OrderbookResponse>(&cache::keys::orderbook(&base, &quote))
-                .await
-            {
-                debug!("Returning cached orderbook for {}/{}", base, quote);
-                return Ok(Json(cached));
-            }
-        }
-    }
+    let (response, served_by) = regional_read(&state, consistency, |state| {
+        load_orderbook(
+            state,
+            base.clone(),
+            quote.clone(),
+            consistency.allows_cached(),
+        )
+    })
+    .await?;
+
+    Ok((served_by, Json(response)))
+}
 
+async fn load_orderbook(
+    state: Arc<AppState>,
+    base: String,
+    quote: String,
+    use_cache: bool,
+) -> Result<OrderbookResponse> {
     // Parse asset identifiers
     let base_asset = AssetPath::parse(&base)
         .map_err(|e| ApiError::InvalidAsset(format!("Invalid base asset: {}", e)))?;
     let quote_asset = AssetPath::parse(&quote)
         .map_err(|e| ApiError::InvalidAsset(format!("Invalid quote asset: {}", e)))?;
+    // Cache under canonical names, which liquidity invalidations target
+    let key = cache::keys::orderbook(&base_asset.to_canonical(), &quote_asset.to_canonical());
+
+    // Try to get from cache first
+    if let Some(cache) = state.cache.as_ref().filter(|_| use_cache) {
+        match cache.get_fresh::<OrderbookResponse>(&key).await {
+            cache::Freshness::Fresh(cached) => {
+                debug!("Returning cached orderbook for {}/{}", base, quote);
+                return Ok(cached);
+            }
+            cache::Freshness::Stale(cached) => {
+                debug!(
+                    "Returning stale orderbook for {}/{} while refreshing",
+                    base, quote
+                );
+                let refresh_state = state.clone();
+                state.cache_refreshes.spawn(key, async move {
+                    if let Err(e) = fetch_orderbook(&refresh_state, &base_asset, &quote_asset).await
+                    {
+                        debug!("Background orderbook refresh failed: {}", e);
+                    }
+                });
+                return Ok(cached);
+            }
+            cache::Freshness::Miss => {}
+        }
+    }
+
+    fetch_orderbook(&state, &base_asset, &quote_asset).await
+}
+
+/// Read the orderbook from the database and cache it
+async fn fetch_orderbook(
+    state: &AppState,
+    base_asset: &AssetPath,
+    quote_asset: &AssetPath,
+) -> Result<OrderbookResponse> {
+    let base = base_asset.to_canonical();
+    let quote = quote_asset.to_canonical();
 
     // Get asset IDs from database
-    let base_id = find_asset_id(&state, &base_asset).await?;
-    let quote_id = find_asset_id(&state, &quote_asset).await?;
+    let base_id = find_asset_id(state, base_asset).await?;
+    let quote_id = find_asset_id(state, quote_asset).await?;
 
     // Fetch asks (selling base for quote)
-    let asks = fetch_orderbook_side(&state, base_id, quote_id, true).await?;
+    let asks = fetch_orderbook_side(state, base_id, quote_id, true).await?;
 
     // Fetch bids (buying base with quote - reverse pair)
-    let bids = fetch_orderbook_side(&state, quote_id, base_id, false).await?;
+    let bids = fetch_orderbook_side(state, quote_id, base_id, false).await?;
 
     let timestamp = chrono::Utc::now().timestamp();
 
-    let base_info = asset_path_to_info(&base_asset);
-    let quote_info = asset_path_to_info(&quote_asset);
+    let base_info = asset_path_to_info(base_asset);
+    let quote_info = asset_path_to_info(quote_asset);
 
     debug!(
         "Orderbook for {}/{}: {} asks, {} bids",
@@ -89,20 +140,23 @@ pub async fn get_orderbook(
         timestamp,
     };
 
-    // Cache the response (TTL: 5 seconds for orderbook data)
+    // Cache the response for as long as the TTL engine allows for this pair
     if let Some(cache) = &state.cache {
-        if let Ok(mut cache) = cache.try_lock() {
-            let _ = cache
-                .set(
-                    &cache::keys::orderbook(&base, &quote),
-                    &response,
-                    Duration::from_secs(5),
-                )
-                .await;
-        }
+        let ttl = state
+            .ttl_engine
+            .compute_ttl(&cache::keys::market_pair(&base, &quote))
+            .await;
+        let _ = cache
+            .set_fresh(
+                &cache::keys::orderbook(&base, &quote),
+                &response,
+                ttl.ttl,
+                state.cache_policy.stale_grace,
+            )
+            .await;
     }
 
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Find asset ID in database
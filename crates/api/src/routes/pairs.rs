@@ -9,6 +9,7 @@ use crate::{
     cache,
     error::{ApiError, Result},
     models::{AssetInfo, PairsResponse, TradingPair},
+    regions::{regional_read, ReadConsistency, ServedBy},
     state::AppState,
 };
 
@@ -21,21 +22,35 @@ use crate::{
     get,
     path = "/api/v1/pairs",
     tag = "trading",
+    params(
+        ("x-consistency" = Option<String>, Header, description = "Read consistency: strong, session or eventual (default)"),
+    ),
     responses(
         (status = 200, description = "List of trading pairs", body = PairsResponse),
+        (status = 400, description = "Invalid consistency header", body = crate::models::ErrorResponse),
         (status = 500, description = "Internal server error", body = crate::models::ErrorResponse),
     )
 )]
-pub async fn list_pairs(State(state): State<Arc<AppState>>) -> Result<Json<PairsResponse>> {
-    debug!("Fetching trading pairs");
+pub async fn list_pairs(
+    State(state): State<Arc<AppState>>,
+    consistency: ReadConsistency,
+) -> Result<(ServedBy, Json<PairsResponse>)> {
+    debug!("Fetching trading pairs ({:?})", consistency);
+
+    let (response, served_by) = regional_read(&state, consistency, |state| {
+        load_pairs(state, consistency.allows_cached())
+    })
+    .await?;
+
+    Ok((served_by, Json(response)))
+}
 
+async fn load_pairs(state: Arc<AppState>, use_cache: bool) -> Result<PairsResponse> {
     // Try to get from cache first
-    if let Some(cache) = &state.cache {
-        if let Ok(mut cache) = cache.try_lock() {
-            if let Some(cached) = cache.get::<PairsResponse>(&cache::keys::pairs_list()).await {
-                debug!("Returning cached pairs");
-                return Ok(Json(cached));
-            }
+    if let Some(cache) = state.cache.as_ref().filter(|_| use_cache) {
+        if let Some(cached) = cache.get::<PairsResponse>(&cache::keys::pairs_list()).await {
+            debug!("Returning cached pairs");
+            return Ok(cached);
         }
     }
 
@@ -119,18 +134,16 @@ pub async fn list_pairs(State(state): State<Arc<AppState>>) -> Result<Json<Pairs
 
     // Cache the response for 10 s to keep latency well under the 100 ms SLA.
     if let Some(cache) = &state.cache {
-        if let Ok(mut cache) = cache.try_lock() {
-            let _ = cache
-                .set(
-                    &cache::keys::pairs_list(),
-                    &response,
-                    Duration::from_secs(10),
-                )
-                .await;
-        }
+        let _ = cache
+            .set(
+                &cache::keys::pairs_list(),
+                &response,
+                Duration::from_secs(10),
+            )
+            .await;
     }
 
-    Ok(Json(response))
+    Ok(response)
 }
 
 /// Alias of `/api/v1/pairs` for backward compatibility.
@@ -138,12 +151,18 @@ pub async fn list_pairs(State(state): State<Arc<AppState>>) -> Result<Json<Pairs
     get,
     path = "/api/v1/markets",
     tag = "trading",
+    params(
+        ("x-consistency" = Option<String>, Header, description = "Read consistency: strong, session or eventual (default)"),
+    ),
     responses(
         (status = 200, description = "List of active markets", body = PairsResponse),
         (status = 400, description = "Invalid pagination parameters", body = crate::models::ErrorResponse),
         (status = 500, description = "Internal server error", body = crate::models::ErrorResponse),
     )
 )]
-pub async fn list_markets(State(state): State<Arc<AppState>>) -> Result<Json<PairsResponse>> {
-    list_pairs(State(state)).await
+pub async fn list_markets(
+    State(state): State<Arc<AppState>>,
+    consistency: ReadConsistency,
+) -> Result<(ServedBy, Json<PairsResponse>)> {
+    list_pairs(State(state), consistency).await
 }
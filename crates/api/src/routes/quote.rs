@@ -10,8 +10,14 @@
 //!
 //! Request logs and decision stages include matching `request_id` values.
 
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use base64::Engine;
+use futures_util::stream::{self, StreamExt};
 use sqlx::Row;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::timeout;
@@ -23,6 +29,8 @@ use stellarroute_routing::health::policy::{ExclusionPolicy, OverrideRegistry};
 use stellarroute_routing::health::scorer::{
     AmmScorer, HealthScorer, HealthScoringConfig, SdexScorer, VenueScorerInput, VenueType,
 };
+use stellarroute_routing::policy::RoutingPolicy;
+use stellarroute_routing::{Amount, Price, Rounding};
 
 use crate::{
     cache,
@@ -30,10 +38,18 @@ use crate::{
     middleware::{validation::ValidatedQuoteRequest, RequestId},
     models::{
         request::{AssetPath, QuoteParams},
-        AssetInfo, ExcludedVenueInfo as ApiExcludedVenueInfo,
+        AssetInfo, BatchQuoteResponse, BatchQuoteResult, ExcludedVenueInfo as ApiExcludedVenueInfo,
         ExclusionDiagnostics as ApiExclusionDiagnostics, ExclusionReason as ApiExclusionReason,
-        PathStep, QuoteRationaleMetadata, QuoteResponse, VenueEvaluation,
+        IssuedQuoteResponse, PathStep, QuoteRationaleMetadata, QuoteResponse, RouteCandidate,
+        RouteHop, SkippedRoute, VenueEvaluation,
+    },
+    quotes::{
+        idempotency::{
+            self, validate_idempotency_key, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER,
+        },
+        BatchIdempotencyStore, IssuedQuoteStore,
     },
+    regions::{regional_read, ReadConsistency, ServedBy},
     state::AppState,
 };
 
@@ -50,11 +66,14 @@ use crate::{
         ("amount" = Option<String>, Query, description = "Amount to trade (default: 1)"),
         ("slippage_bps" = Option<u32>, Query, description = "Slippage tolerance in basis points (default: 50)"),
         ("quote_type" = Option<String>, Query, description = "Type of quote: 'sell' or 'buy' (default: sell)"),
+        ("account" = Option<String>, Query, description = "Account (G...) that will execute the trade; rejected with 422 if it cannot hold or spend the assets"),
+        ("x-consistency" = Option<String>, Header, description = "Read consistency: strong, session or eventual (default)"),
     ),
     responses(
         (status = 200, description = "Price quote", body = QuoteResponse),
         (status = 400, description = "Invalid parameters", body = ErrorResponse),
         (status = 404, description = "No route found", body = ErrorResponse),
+        (status = 422, description = "Account cannot execute the trade", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse),
     )
 )]
@@ -62,8 +81,9 @@ pub async fn get_quote(
     State(state): State<Arc<AppState>>,
     headers: axum::http::HeaderMap,
     request_id: RequestId,
+    consistency: ReadConsistency,
     request: crate::middleware::validation::ValidatedQuoteRequest,
-) -> Result<Json<QuoteResponse>> {
+) -> Result<(ServedBy, Json<QuoteResponse>)> {
     let ValidatedQuoteRequest {
         base: base_asset,
         quote: quote_asset,
@@ -93,8 +113,31 @@ pub async fn get_quote(
     );
 
     async move {
-        match get_quote_inner(state, base_asset, quote_asset, params, explain).await {
-            Ok((quote, cache_hit)) => {
+        let quote = match account_policy(&state, &base_asset, &quote_asset, &params).await {
+            Ok(policy) => {
+                regional_read(&state, consistency, |state| {
+                    get_quote_inner(
+                        state,
+                        base_asset.clone(),
+                        quote_asset.clone(),
+                        params.clone(),
+                        policy.clone(),
+                        explain,
+                    )
+                })
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        // A buy's spent amount is only known once it is priced
+        let quote = match (quote, params.account.as_deref()) {
+            (Ok(ok), Some(account)) => check_account(&state, account, &(ok.0).0).await.map(|_| ok),
+            (quote, _) => quote,
+        };
+
+        match quote {
+            Ok(((quote, cache_hit), served_by)) => {
                 let error_class = "none";
                 let latency_ms = start_time.elapsed().as_millis() as u64;
 
@@ -114,13 +157,14 @@ pub async fn get_quote(
                     "Quote pipeline completed"
                 );
 
-                Ok(Json(quote))
+                Ok((served_by, Json(quote)))
             }
             Err(e) => {
                 let error_class = match &e {
                     ApiError::Validation(_) | ApiError::InvalidAsset(_) => "validation",
                     ApiError::NotFound(_) | ApiError::NoRouteFound => "not_found",
                     ApiError::StaleMarketData { .. } => "stale_market_data",
+                    ApiError::AccountConstraint(_) => "account",
                     _ => "internal",
                 };
                 let latency_ms = start_time.elapsed().as_millis() as u64;
@@ -149,48 +193,227 @@ pub async fn get_quote(
     .await
 }
 
+/// Items of one batch evaluated at a time
+pub const BATCH_QUOTE_CONCURRENCY: usize = 8;
+
+/// Largest accepted batch
+pub const MAX_BATCH_QUOTES: usize = 25;
+
 /// POST /api/v1/batch/quote
+///
+/// Evaluates up to 25 quotes concurrently and returns one `ok` or `error`
+/// result per item, in request order. With an `Idempotency-Key` header the
+/// first result is stored and returned again for retries of the same request.
 pub async fn get_batch_quotes(
     State(state): State<Arc<AppState>>,
+    headers: axum::http::HeaderMap,
     Json(payload): Json<crate::models::request::BatchQuoteRequest>,
-) -> Result<Json<crate::models::response::BatchQuoteResponse>> {
+) -> Result<(axum::http::HeaderMap, Json<BatchQuoteResponse>)> {
     debug!("Getting {} batch quotes", payload.quotes.len());
 
     if payload.quotes.is_empty() {
         return Err(ApiError::Validation("Empty batch request".to_string()));
     }
 
-    if payload.quotes.len() > 25 {
-        return Err(ApiError::Validation(
-            "Batch size cannot exceed 25 items".to_string(),
-        ));
+    if payload.quotes.len() > MAX_BATCH_QUOTES {
+        return Err(ApiError::Validation(format!(
+            "Batch size cannot exceed {} items",
+            MAX_BATCH_QUOTES
+        )));
     }
 
-    let mut quotes = Vec::with_capacity(payload.quotes.len());
-    for item in payload.quotes {
-        let params = QuoteParams {
-            amount: item.amount,
-            slippage_bps: item.slippage_bps,
-            quote_type: item
-                .quote_type
-                .unwrap_or(crate::models::request::QuoteType::Sell),
-            explain: None,
-        };
+    let idempotency_key = match headers.get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => {
+            let key = value.to_str().map_err(|_| {
+                ApiError::Validation("Idempotency-Key must be visible ASCII".to_string())
+            })?;
+            validate_idempotency_key(key)?;
+            Some((key.to_string(), idempotency::request_hash(&payload)?))
+        }
+        None => None,
+    };
+
+    if let Some((key, hash)) = &idempotency_key {
+        let stored = BatchIdempotencyStore::fetch(state.db.write_pool(), key, hash).await?;
+        if let Some(response) = stored {
+            debug!("Replaying batch result for Idempotency-Key {}", key);
+            return Ok((replayed_headers(), Json(response)));
+        }
+    }
 
-        let base_asset = AssetPath::parse(&item.base)
-            .map_err(|e| ApiError::InvalidAsset(format!("Invalid base asset: {}", e)))?;
-        let quote_asset = AssetPath::parse(&item.quote)
-            .map_err(|e| ApiError::InvalidAsset(format!("Invalid quote asset: {}", e)))?;
+    let response = evaluate_batch(&state, payload).await;
 
-        let (quote, _) =
-            get_quote_inner(state.clone(), base_asset, quote_asset, params, false).await?;
-        quotes.push(quote);
+    if let Some((key, hash)) = &idempotency_key {
+        let db = state.db.write_pool();
+        match BatchIdempotencyStore::insert(db, key, hash, &response).await {
+            Ok(true) => {}
+            // A concurrent retry stored its result first; return that one
+            Ok(false) => {
+                if let Some(stored) = BatchIdempotencyStore::fetch(db, key, hash).await? {
+                    return Ok((replayed_headers(), Json(stored)));
+                }
+            }
+            Err(e) => warn!(
+                "Failed to store batch result for Idempotency-Key {}: {}",
+                key, e
+            ),
+        }
     }
 
-    let total = quotes.len();
-    Ok(Json(crate::models::response::BatchQuoteResponse {
-        quotes,
+    Ok((axum::http::HeaderMap::new(), Json(response)))
+}
+
+fn replayed_headers() -> axum::http::HeaderMap {
+    let mut headers = axum::http::HeaderMap::new();
+    headers.insert(
+        IDEMPOTENT_REPLAYED_HEADER,
+        axum::http::HeaderValue::from_static("true"),
+    );
+    headers
+}
+
+/// Quote every item with bounded concurrency, resolving each distinct asset
+/// once and loading liquidity once for the whole batch
+async fn evaluate_batch(
+    state: &Arc<AppState>,
+    payload: crate::models::request::BatchQuoteRequest,
+) -> BatchQuoteResponse {
+    let items: Vec<Result<(AssetPath, AssetPath, QuoteParams)>> = payload
+        .quotes
+        .into_iter()
+        .map(|item| {
+            let params = QuoteParams {
+                amount: item.amount,
+                slippage_bps: item.slippage_bps,
+                quote_type: item
+                    .quote_type
+                    .unwrap_or(crate::models::request::QuoteType::Sell),
+                explain: None,
+                account: None,
+            };
+            params
+                .validate()
+                .map_err(|(code, message)| match code.as_str() {
+                    "invalid_amount" => ApiError::InvalidAmount(message),
+                    "invalid_slippage" => ApiError::InvalidSlippage(message),
+                    _ => ApiError::Validation(message),
+                })?;
+
+            let base_asset = AssetPath::parse(&item.base)
+                .map_err(|e| ApiError::InvalidAsset(format!("Invalid base asset: {}", e)))?;
+            let quote_asset = AssetPath::parse(&item.quote)
+                .map_err(|e| ApiError::InvalidAsset(format!("Invalid quote asset: {}", e)))?;
+            Ok((base_asset, quote_asset, params))
+        })
+        .collect();
+
+    // Shared asset-id lookups: each distinct asset hits the database once
+    let mut distinct: HashMap<String, AssetPath> = HashMap::new();
+    for (base_asset, quote_asset, _) in items.iter().flatten() {
+        for asset in [base_asset, quote_asset] {
+            distinct
+                .entry(asset.to_canonical())
+                .or_insert_with(|| asset.clone());
+        }
+    }
+    let asset_ids: HashMap<String, Result<uuid::Uuid>> = stream::iter(distinct)
+        .map(|(canonical, asset)| async move { (canonical, find_asset_id(state, &asset).await) })
+        .buffer_unordered(BATCH_QUOTE_CONCURRENCY)
+        .collect()
+        .await;
+    let asset_ids = &asset_ids;
+
+    // One liquidity read shared by every item
+    let mut pairs: Vec<(uuid::Uuid, uuid::Uuid)> = Vec::new();
+    for (base_asset, quote_asset, _) in items.iter().flatten() {
+        let ids = (
+            asset_ids.get(&base_asset.to_canonical()),
+            asset_ids.get(&quote_asset.to_canonical()),
+        );
+        if let (Some(Ok(base_id)), Some(Ok(quote_id))) = ids {
+            if !pairs.contains(&(*base_id, *quote_id)) {
+                pairs.push((*base_id, *quote_id));
+            }
+        }
+    }
+    let liquidity = &PairLiquidity::load(state, &pairs).await;
+
+    let results: Vec<BatchQuoteResult> = stream::iter(items)
+        .map(|item| async move {
+            let (base_asset, quote_asset, params) = item?;
+            let lookup = |asset: &AssetPath| {
+                asset_ids
+                    .get(&asset.to_canonical())
+                    .cloned()
+                    .unwrap_or_else(|| Err(ApiError::NotFound(asset.asset_code.clone())))
+            };
+            let base_id = lookup(&base_asset)?;
+            let quote_id = lookup(&quote_asset)?;
+
+            quote_for_assets(
+                state.clone(),
+                base_asset,
+                quote_asset,
+                base_id,
+                quote_id,
+                params,
+                RoutingPolicy::default(),
+                Some(liquidity.clone()?),
+                false,
+            )
+            .await
+            .map(|(quote, _)| quote)
+        })
+        .buffered(BATCH_QUOTE_CONCURRENCY)
+        .map(|result| match result {
+            Ok(quote) => BatchQuoteResult::Ok(Box::new(quote)),
+            Err(e) => BatchQuoteResult::Error(e.to_error_response().1),
+        })
+        .collect()
+        .await;
+
+    let total = results
+        .iter()
+        .filter(|r| matches!(r, BatchQuoteResult::Ok(_)))
+        .count();
+    BatchQuoteResponse {
+        failed: results.len() - total,
+        results,
         total,
+    }
+}
+
+/// Get an issued quote
+///
+/// Returns a quote previously issued by `/api/v1/quote` until it expires,
+/// with the payload its signature covers
+#[utoipa::path(
+    get,
+    path = "/api/v1/quotes/{id}",
+    tag = "trading",
+    params(
+        ("id" = String, Path, description = "quote_id returned with the quote"),
+    ),
+    responses(
+        (status = 200, description = "Issued quote", body = IssuedQuoteResponse),
+        (status = 400, description = "Invalid quote id", body = ErrorResponse),
+        (status = 404, description = "Quote not found or expired", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    )
+)]
+pub async fn get_issued_quote(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<IssuedQuoteResponse>> {
+    let quote_id = uuid::Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation(format!("Invalid quote id: {}", id)))?;
+    // Quotes are read back seconds after they are issued, before a replica
+    // may have caught up
+    let issued = IssuedQuoteStore::fetch(state.db.write_pool(), quote_id).await?;
+
+    Ok(Json(IssuedQuoteResponse {
+        quote: issued.quote,
+        signed_payload: base64::engine::general_purpose::STANDARD.encode(issued.signed_payload),
     }))
 }
 
@@ -199,6 +422,7 @@ async fn get_quote_inner(
     base_asset: AssetPath,
     quote_asset: AssetPath,
     params: QuoteParams,
+    policy: RoutingPolicy,
     explain: bool,
 ) -> Result<(QuoteResponse, bool)> {
     let base = base_asset.to_canonical();
@@ -209,28 +433,49 @@ async fn get_quote_inner(
         base, quote, params.amount, params.quote_type
     );
 
-    // Parse amount (default to 1)
-    let amount: f64 = params
-        .amount
-        .as_deref()
-        .unwrap_or("1")
-        .parse()
-        .unwrap_or(1.0); // Already validated in extractor
+    let base_id = find_asset_id(&state, &base_asset).await?;
+    let quote_id = find_asset_id(&state, &quote_asset).await?;
+
+    quote_for_assets(
+        state,
+        base_asset,
+        quote_asset,
+        base_id,
+        quote_id,
+        params,
+        policy,
+        None,
+        explain,
+    )
+    .await
+}
 
+/// Quote a pair whose asset ids are already resolved, routing within `policy`
+/// over `liquidity` when a batch loaded it
+#[allow(clippy::too_many_arguments)]
+async fn quote_for_assets(
+    state: Arc<AppState>,
+    base_asset: AssetPath,
+    quote_asset: AssetPath,
+    base_id: uuid::Uuid,
+    quote_id: uuid::Uuid,
+    params: QuoteParams,
+    policy: RoutingPolicy,
+    liquidity: Option<PairLiquidity>,
+    explain: bool,
+) -> Result<(QuoteResponse, bool)> {
+    let base = base_asset.to_canonical();
+    let quote = quote_asset.to_canonical();
+    let amount = requested_amount(&params)?;
     let slippage_bps = params.slippage_bps();
     let quote_type_str = match params.quote_type {
         crate::models::request::QuoteType::Sell => "sell",
         crate::models::request::QuoteType::Buy => "buy",
     };
 
-    let base_id = find_asset_id(&state, &base_asset).await?;
-    let quote_id = find_asset_id(&state, &quote_asset).await?;
-
-    maybe_invalidate_quote_cache(&state, &base, &quote, base_id, quote_id).await?;
-
     // Use single flight for quote computation
-    let amount_str = format!("{:.7}", amount);
-    let quote_cache_key = cache::keys::quote(
+    let amount_str = amount.to_string();
+    let mut quote_cache_key = cache::keys::quote(
         &base,
         &quote,
         &amount_str,
@@ -238,25 +483,35 @@ async fn get_quote_inner(
         quote_type_str,
         explain,
     );
+    // Account-constrained quotes carry that account's skipped routes
+    if let Some(account) = &params.account {
+        quote_cache_key = format!("{}:{}", quote_cache_key, account);
+    }
 
+    let job = QuoteJob {
+        base_asset,
+        quote_asset,
+        base_id,
+        quote_id,
+        amount,
+        slippage_bps,
+        quote_type: params.quote_type,
+        policy,
+        liquidity,
+        cache_key: quote_cache_key.clone(),
+    };
     let state_c = state.clone();
-    let base_c = base.clone();
-    let quote_c = quote.clone();
-    let quote_cache_key_c = quote_cache_key.clone();
 
     // Use single-flight to coalesce identical concurrent requests
     let result_arc: Arc<crate::error::Result<(QuoteResponse, bool)>> = state
         .quote_single_flight
         .execute(&quote_cache_key, || async move {
             let state = state_c;
-            let base = base_c;
-            let quote = quote_c;
-            let quote_cache_key = quote_cache_key_c;
 
             // Try to get from cache first (inside single-flight so we only compute once if miss)
             if let Some(cache) = &state.cache {
-                if let Ok(mut cache) = cache.try_lock() {
-                    if let Some(cached) = cache.get::<QuoteResponse>(&quote_cache_key).await {
+                match cache.get_fresh::<QuoteResponse>(&job.cache_key).await {
+                    cache::Freshness::Fresh(cached) => {
                         state.cache_metrics.inc_quote_hit();
                         crate::metrics::record_cache_hit("quote");
                         tracing::Span::current().record("cache_hit", true);
@@ -264,112 +519,213 @@ async fn get_quote_inner(
                         // SingleFlight expects Arc<Result<(QuoteResponse, bool)>>
                         return Arc::new(Ok((cached, true)));
                     }
+                    cache::Freshness::Stale(cached) => {
+                        // Still inside the grace window, and so before its signed
+                        // `expires_at`: serve it while one refresh recomputes it
+                        state.cache_metrics.inc_quote_hit();
+                        state.cache_metrics.inc_quote_stale_hit();
+                        crate::metrics::record_cache_hit("quote");
+                        tracing::Span::current().record("cache_hit", true);
+                        debug!(
+                            "Returning stale quote for {}/{} while refreshing",
+                            base, quote
+                        );
+                        let refresh_state = state.clone();
+                        let refresh_job = job.clone();
+                        state
+                            .cache_refreshes
+                            .spawn(job.cache_key.clone(), async move {
+                                if let Err(e) = refresh_job.run(&refresh_state).await {
+                                    debug!("Background quote refresh failed: {}", e);
+                                }
+                            });
+                        return Arc::new(Ok((cached, true)));
+                    }
+                    cache::Freshness::Miss => {}
                 }
             }
 
             // Cache miss
             crate::metrics::record_cache_miss("quote");
 
-            // Compute best price with freshness scoring
-            let compute_res =
-                find_best_price(&state, &base_asset, &quote_asset, base_id, quote_id, amount).await;
-
-            let (
-                price,
-                path,
-                rationale,
-                api_diagnostics,
-                freshness_outcome,
-                fresh_timestamps,
-                liquidity_snapshot,
-            ) = match compute_res {
-                Ok(res) => res,
-                Err(e) => return Arc::new(Err(e)),
-            };
+            Arc::new(job.run(&state).await.map(|response| (response, false)))
+        })
+        .await;
 
-            // Increment stale inputs metrics
-            let stale_count = freshness_outcome.stale.len();
-            if stale_count > 0 {
-                state
-                    .cache_metrics
-                    .add_stale_inputs_excluded(stale_count as u64);
-            }
+    match Arc::try_unwrap(result_arc) {
+        Ok(res) => res,
+        Err(arc_res) => arc_res.as_ref().clone(),
+    }
+}
 
-            let total = amount * price;
-            let timestamp = chrono::Utc::now().timestamp_millis();
-            let ttl_seconds = u32::try_from(state.cache_policy.quote_ttl.as_secs()).ok();
-            let expires_at = i64::try_from(state.cache_policy.quote_ttl.as_millis())
-                .ok()
-                .map(|ttl_ms| timestamp + ttl_ms);
+/// Everything needed to compute and cache one quote, so a stale cache entry
+/// can be recomputed in the background
+#[derive(Clone)]
+struct QuoteJob {
+    base_asset: AssetPath,
+    quote_asset: AssetPath,
+    base_id: uuid::Uuid,
+    quote_id: uuid::Uuid,
+    amount: Amount,
+    slippage_bps: u32,
+    quote_type: crate::models::request::QuoteType,
+    policy: RoutingPolicy,
+    /// Liquidity already loaded for a batch; read per quote when `None`
+    liquidity: Option<PairLiquidity>,
+    cache_key: String,
+}
 
-            let source_timestamp = fresh_timestamps
-                .iter()
-                .min()
-                .map(|ts| ts.timestamp_millis());
-
-            let data_freshness = Some(crate::models::DataFreshness {
-                fresh_count: freshness_outcome.fresh.len(),
-                stale_count: freshness_outcome.stale.len(),
-                max_staleness_secs: freshness_outcome.max_staleness_secs,
-            });
-
-            let response = QuoteResponse {
-                base_asset: asset_path_to_info(&base_asset),
-                quote_asset: asset_path_to_info(&quote_asset),
-                amount: format!("{:.7}", amount),
-                price: format!("{:.7}", price),
-                total: format!("{:.7}", total),
-                quote_type: quote_type_str.to_string(),
-                path,
-                timestamp,
-                expires_at,
-                source_timestamp,
-                ttl_seconds,
-                rationale: Some(rationale),
-                exclusion_diagnostics: Some(api_diagnostics),
-                data_freshness,
-                price_impact: None, // Will be computed in Phase 3
-            };
+impl QuoteJob {
+    /// Price, sign, record and cache the quote
+    async fn run(&self, state: &AppState) -> Result<QuoteResponse> {
+        let base = self.base_asset.to_canonical();
+        let quote = self.quote_asset.to_canonical();
+        let amount = self.amount;
+        let amount_str = amount.to_string();
+        let slippage_bps = self.slippage_bps;
+        let quote_type_str = match self.quote_type {
+            crate::models::request::QuoteType::Sell => "sell",
+            crate::models::request::QuoteType::Buy => "buy",
+        };
 
-            // Cache the response
-            if let Some(cache) = &state.cache {
-                if let Ok(mut cache) = cache.try_lock() {
-                    let _ = cache
-                        .set(&quote_cache_key, &response, state.cache_policy.quote_ttl)
-                        .await;
+        // Compute best price with freshness scoring
+        let (
+            price,
+            path,
+            rationale,
+            api_diagnostics,
+            freshness_outcome,
+            fresh_timestamps,
+            liquidity_snapshot,
+            skipped_routes,
+        ) = find_best_price(
+            state,
+            &self.base_asset,
+            &self.quote_asset,
+            self.base_id,
+            self.quote_id,
+            amount,
+            &self.policy,
+            self.liquidity.as_ref(),
+        )
+        .await?;
+
+        // Increment stale inputs metrics
+        let stale_count = freshness_outcome.stale.len();
+        if stale_count > 0 {
+            state
+                .cache_metrics
+                .add_stale_inputs_excluded(stale_count as u64);
+        }
+
+        // Feed the adaptive TTL engine before asking it for this quote's TTL
+        let pair = cache::keys::market_pair(&base, &quote);
+        let depth = liquidity_snapshot
+            .iter()
+            .filter_map(|c| c.available_amount.parse::<f64>().ok())
+            .sum();
+        state.ttl_engine.observe(&pair, price.to_f64(), depth).await;
+        let ttl = state.ttl_engine.compute_ttl(&pair).await;
+
+        let path = with_hop_min_outputs(path, amount, slippage_bps);
+        let total = amount
+            .mul_price(price, self.quote_type.total_rounding())
+            .map_err(|_| ApiError::InvalidAmount("Amount is too large to quote".to_string()))?;
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let ttl_seconds = u32::try_from(ttl.ttl_ms.div_ceil(1000)).ok();
+        // Valid through the grace window, so a quote served stale is still firm
+        let expires_at = i64::try_from((ttl.ttl + state.cache_policy.stale_grace).as_millis())
+            .ok()
+            .map(|ttl_ms| timestamp + ttl_ms);
+
+        let source_timestamp = fresh_timestamps
+            .iter()
+            .min()
+            .map(|ts| ts.timestamp_millis());
+
+        let data_freshness = Some(crate::models::DataFreshness {
+            fresh_count: freshness_outcome.fresh.len(),
+            stale_count: freshness_outcome.stale.len(),
+            max_staleness_secs: freshness_outcome.max_staleness_secs,
+        });
+
+        let mut response = QuoteResponse {
+            base_asset: asset_path_to_info(&self.base_asset),
+            quote_asset: asset_path_to_info(&self.quote_asset),
+            amount: amount_str.clone(),
+            price: price.to_string(),
+            total: total.to_string(),
+            quote_type: quote_type_str.to_string(),
+            path,
+            timestamp,
+            expires_at,
+            source_timestamp,
+            ttl_seconds,
+            ttl_reason: Some(ttl.reason),
+            rationale: Some(rationale),
+            exclusion_diagnostics: Some(api_diagnostics),
+            data_freshness,
+            price_impact: None, // Will be computed in Phase 3
+            quote_id: None,
+            signature: None,
+            signer: None,
+            skipped_routes,
+        };
+
+        // Sign the quote and record it so it can be fetched by id until it
+        // expires. A quote that could not be recorded is served unsigned, as
+        // its id would not resolve.
+        match state.quote_signer.issue(&mut response) {
+            Ok(signed_payload) => {
+                if let Err(e) =
+                    IssuedQuoteStore::insert(state.db.write_pool(), &response, &signed_payload)
+                        .await
+                {
+                    warn!("Failed to record issued quote, serving it unsigned: {}", e);
+                    response.quote_id = None;
+                    response.signature = None;
+                    response.signer = None;
                 }
             }
+            Err(e) => warn!("Failed to sign quote: {}", e),
+        }
 
-            // [Replay] Non-blocking capture — fire-and-forget, zero latency impact
-            if let Some(hook) = &state.replay_capture {
-                use stellarroute_routing::health::scorer::HealthScoringConfig;
-                let hc = HealthScoringConfig::default();
-                let health_config = crate::replay::artifact::HealthConfigSnapshot {
-                    freshness_threshold_secs_sdex: hc.freshness_threshold_secs.sdex,
-                    freshness_threshold_secs_amm: hc.freshness_threshold_secs.amm,
-                    staleness_threshold_secs: hc.staleness_threshold_secs,
-                    min_tvl_threshold_e7: hc.min_tvl_threshold_e7,
-                };
-                hook.capture(
-                    &base,
-                    &quote,
-                    &format!("{:.7}", amount),
-                    slippage_bps,
-                    quote_type_str,
-                    liquidity_snapshot,
-                    health_config,
+        // Cache the response
+        if let Some(cache) = &state.cache {
+            let _ = cache
+                .set_fresh(
+                    &self.cache_key,
                     &response,
-                    None,
-                );
-            }
+                    ttl.ttl,
+                    state.cache_policy.stale_grace,
+                )
+                .await;
+        }
 
-            Arc::new(Ok((response, false)))
-        })
-        .await;
+        // [Replay] Non-blocking capture — fire-and-forget, zero latency impact
+        if let Some(hook) = &state.replay_capture {
+            use stellarroute_routing::health::scorer::HealthScoringConfig;
+            let hc = HealthScoringConfig::default();
+            let health_config = crate::replay::artifact::HealthConfigSnapshot {
+                freshness_threshold_secs_sdex: hc.freshness_threshold_secs.sdex,
+                freshness_threshold_secs_amm: hc.freshness_threshold_secs.amm,
+                staleness_threshold_secs: hc.staleness_threshold_secs,
+                min_tvl_threshold_e7: hc.min_tvl_threshold_e7,
+            };
+            hook.capture(
+                &base,
+                &quote,
+                &amount_str,
+                slippage_bps,
+                quote_type_str,
+                liquidity_snapshot,
+                health_config,
+                &response,
+                None,
+            );
+        }
 
-    match Arc::try_unwrap(result_arc) {
-        Ok(res) => res,
-        Err(arc_res) => arc_res.as_ref().clone(),
+        Ok(response)
     }
 }
 
@@ -409,13 +765,7 @@ pub async fn get_route(
         base_asset.asset_code, quote_asset.asset_code, params
     );
 
-    // Parse amount (default to 1)
-    let amount: f64 = params
-        .amount
-        .as_deref()
-        .unwrap_or("1")
-        .parse()
-        .unwrap_or(1.0); // Already validated in extractor
+    let amount = requested_amount(&params)?;
 
     let slippage_bps = params.slippage_bps();
 
@@ -423,14 +773,23 @@ pub async fn get_route(
     let quote_id = find_asset_id(&state, &quote_asset).await?;
 
     // For route endpoint, we reuse the same logic but return a simplified response
-    let (_, path, _, _, _, _, _) =
-        find_best_price(&state, &base_asset, &quote_asset, base_id, quote_id, amount).await?;
+    let (_, path, _, _, _, _, _, _) = find_best_price(
+        &state,
+        &base_asset,
+        &quote_asset,
+        base_id,
+        quote_id,
+        amount,
+        &RoutingPolicy::default(),
+        None,
+    )
+    .await?;
 
     let response = crate::models::RouteResponse {
         base_asset: asset_path_to_info(&base_asset),
         quote_asset: asset_path_to_info(&quote_asset),
-        amount: format!("{:.7}", amount),
-        path,
+        amount: amount.to_string(),
+        path: with_hop_min_outputs(path, amount, slippage_bps),
         slippage_bps,
         timestamp: chrono::Utc::now().timestamp_millis(),
     };
@@ -440,18 +799,19 @@ pub async fn get_route(
 
 /// Find best price for a trading pair
 type FindBestPriceResult = (
-    f64,
+    Price,
     Vec<PathStep>,
     QuoteRationaleMetadata,
     ApiExclusionDiagnostics,
     FreshnessOutcome,
     Vec<chrono::DateTime<chrono::Utc>>,
     Vec<crate::replay::artifact::LiquidityCandidate>, // snapshot for replay capture
+    Vec<SkippedRoute>,
 );
 
 #[tracing::instrument(
     name = "find_best_price",
-    skip(state, base_id, quote_id),
+    skip(state, base_id, quote_id, policy, liquidity),
     fields(
         candidates_count = tracing::field::Empty,
         stale_count = tracing::field::Empty,
@@ -459,49 +819,38 @@ type FindBestPriceResult = (
         scored_count = tracing::field::Empty
     )
 )]
+#[allow(clippy::too_many_arguments)]
 async fn find_best_price(
     state: &AppState,
     base: &AssetPath,
     quote: &AssetPath,
     base_id: uuid::Uuid,
     quote_id: uuid::Uuid,
-    amount: f64,
+    amount: Amount,
+    policy: &RoutingPolicy,
+    liquidity: Option<&PairLiquidity>,
 ) -> Result<FindBestPriceResult> {
-    // Parallel multi-source quote computation
-    let sdex_timeout = Duration::from_millis(500);
-    let amm_timeout = Duration::from_millis(500);
-
-    let sdex_task = fetch_source_candidates(state, base_id, quote_id, "sdex");
-    let amm_task = fetch_source_candidates(state, base_id, quote_id, "amm");
-
-    let (sdex_res, amm_res) = tokio::join!(
-        timeout(sdex_timeout, sdex_task),
-        timeout(amm_timeout, amm_task)
-    );
-
-    let mut candidates = Vec::new();
-
-    match sdex_res {
-        Ok(Ok(mut res)) => candidates.append(&mut res),
-        Ok(Err(e)) => warn!("SDEX source error: {:?}", e),
-        Err(_) => warn!("SDEX source timed out"),
-    }
-
-    match amm_res {
-        Ok(Ok(mut res)) => candidates.append(&mut res),
-        Ok(Err(e)) => warn!("AMM source error: {:?}", e),
-        Err(_) => warn!("AMM source timed out"),
-    }
+    let mut candidates = match liquidity {
+        Some(liquidity) => liquidity.candidates(base_id, quote_id),
+        None => fetch_candidates(state, base_id, quote_id).await,
+    };
 
     // Deterministic merge: sort by price, then venue type, then ref
     candidates.sort_by(|a, b| {
         a.price
-            .partial_cmp(&b.price)
-            .unwrap_or(std::cmp::Ordering::Equal)
+            .cmp(&b.price)
             .then_with(|| a.venue_type.cmp(&b.venue_type))
             .then_with(|| a.venue_ref.cmp(&b.venue_ref))
     });
 
+    let (candidates, ruled_out) = apply_routing_policy(candidates, base, quote, policy);
+    if candidates.is_empty() && !ruled_out.is_empty() {
+        return Err(ApiError::AccountConstraint(format!(
+            "Every route passes through an asset the account has no trustline for: {}",
+            missing_assets(base, quote, policy).join(", ")
+        )));
+    }
+
     // Capture a single wall-clock instant for both scorer_inputs construction and freshness eval
     let now = chrono::Utc::now();
 
@@ -605,14 +954,14 @@ async fn find_best_price(
         .collect();
     let scored = scorer.score_venues(&fresh_inputs_owned);
 
-    let policy = ExclusionPolicy {
+    let exclusion_policy = ExclusionPolicy {
         thresholds: health_config.thresholds.clone(),
         overrides: OverrideRegistry::from_entries(health_config.overrides.clone()),
         circuit_breaker: Some(state.circuit_breaker.clone()),
     };
 
     // Apply filter (pass empty edges — we just need diagnostics for this single-hop path)
-    let filter = GraphFilter::new(&policy);
+    let filter = GraphFilter::new(&exclusion_policy);
     let (_, routing_diagnostics) = filter.filter_edges(&[], &scored);
 
     tracing::info!(
@@ -667,18 +1016,40 @@ async fn find_best_price(
         .map(|c| crate::replay::artifact::LiquidityCandidate {
             venue_type: c.venue_type.clone(),
             venue_ref: c.venue_ref.clone(),
-            price: format!("{:.7}", c.price),
-            available_amount: format!("{:.7}", c.available_amount),
+            price: c.price.to_string(),
+            available_amount: c.available_amount.to_string(),
         })
         .collect();
 
     let path = vec![PathStep {
         from_asset: asset_path_to_info(base),
         to_asset: asset_path_to_info(quote),
-        price: format!("{:.7}", selected.price),
+        price: selected.price.to_string(),
         source: selected.path_source(),
+        min_amount_out: None,
     }];
 
+    // Better executable venues the policy ruled out for the account
+    let missing_trustlines: Vec<AssetInfo> = [base, quote]
+        .into_iter()
+        .filter(|asset| !policy.is_asset_allowed(&asset.to_canonical()))
+        .map(asset_path_to_info)
+        .collect();
+    let skipped_routes = ruled_out
+        .iter()
+        .filter(|c| c.is_executable(amount) && c.price < selected.price)
+        .map(|c| SkippedRoute {
+            route: c.route_candidate(base, quote, amount),
+            missing_trustlines: missing_trustlines.clone(),
+            reason: format!(
+                "Prices at {} instead of {} but the account has no trustline for {}",
+                c.price,
+                selected.price,
+                missing_assets(base, quote, policy).join(", ")
+            ),
+        })
+        .collect();
+
     Ok((
         selected.price,
         path,
@@ -687,15 +1058,19 @@ async fn find_best_price(
         freshness_outcome,
         fresh_timestamps,
         liquidity_snapshot,
+        skipped_routes,
     ))
 }
 
+/// Rationale strategy of single-hop direct venue quotes
+const DIRECT_VENUE_STRATEGY: &str = "single_hop_direct_venue_comparison";
+
 #[derive(Debug, Clone)]
 struct DirectVenueCandidate {
     venue_type: String,
     venue_ref: String,
-    price: f64,
-    available_amount: f64,
+    price: Price,
+    available_amount: Amount,
     price_e7: i64,
     available_amount_e7: i64,
 }
@@ -705,6 +1080,10 @@ impl DirectVenueCandidate {
         format!("{}:{}", self.venue_type, self.venue_ref)
     }
 
+    fn is_executable(&self, amount: Amount) -> bool {
+        self.available_amount >= amount && self.price.is_positive()
+    }
+
     fn path_source(&self) -> String {
         if self.venue_type == "amm" {
             format!("amm:{}", self.venue_ref)
@@ -712,11 +1091,60 @@ impl DirectVenueCandidate {
             "sdex".to_string()
         }
     }
+
+    /// This venue as a one-hop route for `amount`
+    fn route_candidate(
+        &self,
+        base: &AssetPath,
+        quote: &AssetPath,
+        amount: Amount,
+    ) -> RouteCandidate {
+        let out = amount
+            .mul_price(self.price, Rounding::Down)
+            .unwrap_or(Amount::ZERO);
+        RouteCandidate {
+            estimated_output: out.to_string(),
+            impact_bps: 0,
+            score: 0.0,
+            policy_used: DIRECT_VENUE_STRATEGY.to_string(),
+            path: vec![RouteHop {
+                from_asset: asset_path_to_info(base),
+                to_asset: asset_path_to_info(quote),
+                price: self.price.to_string(),
+                amount_out_of_hop: out.to_string(),
+                fee_bps: 0,
+                source: self.path_source(),
+            }],
+        }
+    }
+}
+
+/// Drop venues `policy` does not allow and split off those it rules out for
+/// trading an asset outside its allowlist
+fn apply_routing_policy(
+    candidates: Vec<DirectVenueCandidate>,
+    base: &AssetPath,
+    quote: &AssetPath,
+    policy: &RoutingPolicy,
+) -> (Vec<DirectVenueCandidate>, Vec<DirectVenueCandidate>) {
+    let assets_allowed = missing_assets(base, quote, policy).is_empty();
+    candidates
+        .into_iter()
+        .filter(|c| policy.is_venue_allowed(&c.venue_type))
+        .partition(|_| assets_allowed)
+}
+
+/// Canonical ids of the pair's assets outside `policy`'s asset allowlist
+fn missing_assets(base: &AssetPath, quote: &AssetPath, policy: &RoutingPolicy) -> Vec<String> {
+    [base.to_canonical(), quote.to_canonical()]
+        .into_iter()
+        .filter(|asset| !policy.is_asset_allowed(asset))
+        .collect()
 }
 
 fn evaluate_single_hop_direct_venues(
     mut candidates: Vec<DirectVenueCandidate>,
-    amount: f64,
+    amount: Amount,
 ) -> Result<(DirectVenueCandidate, QuoteRationaleMetadata)> {
     if candidates.is_empty() {
         return Err(ApiError::NoRouteFound);
@@ -724,8 +1152,7 @@ fn evaluate_single_hop_direct_venues(
 
     candidates.sort_by(|a, b| {
         a.price
-            .partial_cmp(&b.price)
-            .unwrap_or(std::cmp::Ordering::Equal)
+            .cmp(&b.price)
             .then_with(|| a.venue_type.cmp(&b.venue_type))
             .then_with(|| a.venue_ref.cmp(&b.venue_ref))
     });
@@ -734,64 +1161,120 @@ fn evaluate_single_hop_direct_venues(
         .iter()
         .map(|candidate| VenueEvaluation {
             source: candidate.comparison_source(),
-            price: format!("{:.7}", candidate.price),
-            available_amount: format!("{:.7}", candidate.available_amount),
-            executable: candidate.available_amount >= amount && candidate.price > 0.0,
+            price: candidate.price.to_string(),
+            available_amount: candidate.available_amount.to_string(),
+            executable: candidate.is_executable(amount),
         })
         .collect::<Vec<_>>();
 
     let selected = candidates
         .iter()
-        .find(|candidate| candidate.available_amount >= amount && candidate.price > 0.0)
+        .find(|candidate| candidate.is_executable(amount))
         .cloned()
         .ok_or(ApiError::NoRouteFound)?;
 
     Ok((
         selected.clone(),
         QuoteRationaleMetadata {
-            strategy: "single_hop_direct_venue_comparison".to_string(),
+            strategy: DIRECT_VENUE_STRATEGY.to_string(),
             selected_source: selected.comparison_source(),
             compared_venues,
         },
     ))
 }
 
-async fn maybe_invalidate_quote_cache(
+/// Fetch a pair's candidates from every source in parallel, skipping any
+/// source that fails or times out
+async fn fetch_candidates(
     state: &AppState,
-    base: &str,
-    quote: &str,
     base_id: uuid::Uuid,
     quote_id: uuid::Uuid,
-) -> Result<()> {
-    let liquidity_revision = get_liquidity_revision(state, base_id, quote_id).await?;
-
-    if let Some(cache) = &state.cache {
-        if let Ok(mut cache) = cache.try_lock() {
-            let revision_key = cache::keys::liquidity_revision(base, quote);
-            let cached_revision = cache.get::<String>(&revision_key).await;
-
-            if cached_revision.as_deref() != Some(liquidity_revision.as_str()) {
-                if cached_revision.is_some() {
-                    let pattern = cache::keys::quote_pair_pattern(base, quote);
-                    let deleted = cache.delete_by_pattern(&pattern).await.unwrap_or(0);
-                    debug!(
-                        "Liquidity revision changed for {}/{}; invalidated {} quote cache keys",
-                        base, quote, deleted
-                    );
-                }
+) -> Vec<DirectVenueCandidate> {
+    let sdex_timeout = Duration::from_millis(500);
+    let amm_timeout = Duration::from_millis(500);
 
-                let _ = cache
-                    .set(
-                        &revision_key,
-                        &liquidity_revision,
-                        std::time::Duration::from_secs(3600),
-                    )
-                    .await;
-            }
+    let sdex_task = fetch_source_candidates(state, base_id, quote_id, "sdex");
+    let amm_task = fetch_source_candidates(state, base_id, quote_id, "amm");
+
+    let (sdex_res, amm_res) = tokio::join!(
+        timeout(sdex_timeout, sdex_task),
+        timeout(amm_timeout, amm_task)
+    );
+
+    let mut candidates = Vec::new();
+
+    match sdex_res {
+        Ok(Ok(mut res)) => candidates.append(&mut res),
+        Ok(Err(e)) => warn!("SDEX source error: {:?}", e),
+        Err(_) => warn!("SDEX source timed out"),
+    }
+
+    match amm_res {
+        Ok(Ok(mut res)) => candidates.append(&mut res),
+        Ok(Err(e)) => warn!("AMM source error: {:?}", e),
+        Err(_) => warn!("AMM source timed out"),
+    }
+
+    candidates
+}
+
+/// Direct-venue candidates for a set of pairs, read in one query so a batch
+/// quotes every item against the same liquidity
+#[derive(Clone, Default)]
+struct PairLiquidity {
+    by_pair: Arc<HashMap<(uuid::Uuid, uuid::Uuid), Vec<DirectVenueCandidate>>>,
+}
+
+impl PairLiquidity {
+    /// Load every source's candidates for `pairs` of (selling, buying) asset ids
+    async fn load(state: &AppState, pairs: &[(uuid::Uuid, uuid::Uuid)]) -> Result<Self> {
+        if pairs.is_empty() {
+            return Ok(Self::default());
+        }
+        let (selling, buying): (Vec<uuid::Uuid>, Vec<uuid::Uuid>) = pairs.iter().copied().unzip();
+        state.cache_metrics.inc_liquidity_load();
+        let rows = sqlx::query(
+            r#"
+            select
+                selling_asset_id,
+                buying_asset_id,
+                venue_type,
+                venue_ref,
+                price::text as price,
+                available_amount::text as available_amount,
+                price_e7,
+                available_amount_e7
+            from normalized_liquidity
+            where (selling_asset_id, buying_asset_id) in (
+                select * from unnest($1::uuid[], $2::uuid[])
+            )
+              and venue_type in ('sdex', 'amm')
+            "#,
+        )
+        .bind(&selling)
+        .bind(&buying)
+        .fetch_all(state.db.read_pool())
+        .await?;
+
+        let mut by_pair: HashMap<_, Vec<_>> = HashMap::new();
+        for row in &rows {
+            let pair = (row.get("selling_asset_id"), row.get("buying_asset_id"));
+            by_pair
+                .entry(pair)
+                .or_default()
+                .push(candidate_from_row(row));
         }
+        Ok(Self {
+            by_pair: Arc::new(by_pair),
+        })
     }
 
-    Ok(())
+    fn candidates(&self, base_id: uuid::Uuid, quote_id: uuid::Uuid) -> Vec<DirectVenueCandidate> {
+        self.by_pair
+            .get(&(base_id, quote_id))
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 /// Fetch candidates from a specific source
@@ -801,6 +1284,7 @@ async fn fetch_source_candidates(
     quote_id: uuid::Uuid,
     venue_type: &str,
 ) -> Result<Vec<DirectVenueCandidate>> {
+    state.cache_metrics.inc_liquidity_load();
     let rows = sqlx::query(
         r#"
                 select
@@ -822,54 +1306,31 @@ async fn fetch_source_candidates(
     .fetch_all(state.db.read_pool())
     .await?;
 
-    Ok(rows
-        .into_iter()
-        .map(|row| {
-            let venue_type: String = row.get("venue_type");
-            let venue_ref: String = row.get("venue_ref");
-            let price: f64 = row.get::<String, _>("price").parse().unwrap_or(0.0);
-            let available_amount: f64 = row
-                .get::<String, _>("available_amount")
-                .parse()
-                .unwrap_or(0.0);
-            let price_e7: i64 = row.get("price_e7");
-            let available_amount_e7: i64 = row.get("available_amount_e7");
-            DirectVenueCandidate {
-                venue_type,
-                venue_ref,
-                price,
-                available_amount,
-                price_e7,
-                available_amount_e7,
-            }
-        })
-        .collect())
+    Ok(rows.iter().map(candidate_from_row).collect())
 }
 
-async fn get_liquidity_revision(
-    state: &AppState,
-    base_id: uuid::Uuid,
-    quote_id: uuid::Uuid,
-) -> Result<String> {
-    let row = sqlx::query(
-        r#"
-        select coalesce(max(source_ledger), 0)::bigint as revision
-        from normalized_liquidity
-        where (selling_asset_id = $1 and buying_asset_id = $2)
-           or (selling_asset_id = $2 and buying_asset_id = $1)
-        "#,
-    )
-    .bind(base_id)
-    .bind(quote_id)
-    .fetch_one(state.db.read_pool())
-    .await?;
-
-    let revision: i64 = row.get("revision");
-    Ok(revision.to_string())
+fn candidate_from_row(row: &sqlx::postgres::PgRow) -> DirectVenueCandidate {
+    let venue_type: String = row.get("venue_type");
+    let venue_ref: String = row.get("venue_ref");
+    let price: Price = row.get::<String, _>("price").parse().unwrap_or(Price::ZERO);
+    let available_amount: Amount = row
+        .get::<String, _>("available_amount")
+        .parse()
+        .unwrap_or(Amount::ZERO);
+    let price_e7: i64 = row.get("price_e7");
+    let available_amount_e7: i64 = row.get("available_amount_e7");
+    DirectVenueCandidate {
+        venue_type,
+        venue_ref,
+        price,
+        available_amount,
+        price_e7,
+        available_amount_e7,
+    }
 }
 
 /// Find asset ID in database
-async fn find_asset_id(state: &AppState, asset: &AssetPath) -> Result<uuid::Uuid> {
+pub(crate) async fn find_asset_id(state: &AppState, asset: &AssetPath) -> Result<uuid::Uuid> {
     use sqlx::Row;
 
     let asset_type = asset.to_asset_type();
@@ -911,6 +1372,86 @@ async fn find_asset_id(state: &AppState, asset: &AssetPath) -> Result<uuid::Uuid
     }
 }
 
+/// Routing constraint for the requesting account, if any
+///
+/// Checks before routing that the account can receive what the quote pays
+/// out and, for a sell, spend the amount it takes in, then limits routing to
+/// the assets the account can hold plus the one it sends.
+async fn account_policy(
+    state: &AppState,
+    base_asset: &AssetPath,
+    quote_asset: &AssetPath,
+    params: &QuoteParams,
+) -> Result<RoutingPolicy> {
+    let Some(account) = params.account.as_deref() else {
+        return Ok(RoutingPolicy::default());
+    };
+    let holdings = state.accounts.holdings(account).await?;
+    let base = base_asset.to_canonical();
+    let counter = quote_asset.to_canonical();
+    // A buy's cost is only known once it is priced; see `check_account`
+    let (source, destination, spent) = match params.quote_type {
+        crate::models::request::QuoteType::Sell => (base, counter, requested_amount(params)?),
+        crate::models::request::QuoteType::Buy => (counter, base, Amount::ZERO),
+    };
+    holdings.ensure_can_trade(&source, &destination, spent)?;
+
+    Ok(RoutingPolicy::default().with_asset_allowlist(holdings.route_assets(&source)))
+}
+
+/// Check `account` can spend what a buy quote costs
+async fn check_account(state: &AppState, account: &str, quote: &QuoteResponse) -> Result<()> {
+    if quote.quote_type != "buy" {
+        return Ok(());
+    }
+    let holdings = state.accounts.holdings(account).await?;
+    let spent: Amount = quote.total.parse().map_err(|e| {
+        ApiError::Internal(
+            anyhow::anyhow!("Quote total {} is not decimal: {}", quote.total, e).into(),
+        )
+    })?;
+
+    holdings.ensure_can_spend(&quote.quote_asset.to_canonical(), spent)
+}
+
+/// Parse the requested amount to stroops (default: 1)
+fn requested_amount(params: &QuoteParams) -> Result<Amount> {
+    params.amount.as_deref().map_or(Ok(Amount::ONE), |amount| {
+        amount
+            .parse()
+            .map_err(|e| ApiError::InvalidAmount(format!("Invalid amount: {e}")))
+    })
+}
+
+/// Fill each step's `min_amount_out` from the quoted hop output less `slippage_bps`
+///
+/// Every hop gets the full tolerance against its own expected output, so the
+/// last hop's minimum equals the route-level minimum and an intermediate hop
+/// that alone eats the whole tolerance aborts on-chain before later hops run.
+/// Expected outputs and minimums are outputs, so both round down.
+fn with_hop_min_outputs(
+    mut path: Vec<PathStep>,
+    amount: Amount,
+    slippage_bps: u32,
+) -> Vec<PathStep> {
+    let keep_bps = 10_000 - i128::from(slippage_bps.min(10_000));
+    let mut expected = amount;
+    for step in &mut path {
+        let Ok(price) = step.price.parse::<Price>() else {
+            break;
+        };
+        let Ok(next) = expected.mul_price(price, Rounding::Down) else {
+            break;
+        };
+        expected = next;
+        step.min_amount_out = expected
+            .mul_ratio(keep_bps, 10_000, Rounding::Down)
+            .ok()
+            .map(|min| min.to_string());
+    }
+    path
+}
+
 /// Convert AssetPath to AssetInfo
 fn asset_path_to_info(asset: &AssetPath) -> AssetInfo {
     if asset.asset_code == "native" {
@@ -928,29 +1469,38 @@ mod tests {
     fn candidate(
         venue_type: &str,
         venue_ref: &str,
-        price: f64,
-        available_amount: f64,
+        price: &str,
+        available_amount: &str,
     ) -> DirectVenueCandidate {
+        let price: Price = price.parse().unwrap();
+        let available_amount: Amount = available_amount.parse().unwrap();
         DirectVenueCandidate {
             venue_type: venue_type.to_string(),
             venue_ref: venue_ref.to_string(),
             price,
             available_amount,
-            price_e7: (price * 1e7) as i64,
-            available_amount_e7: (available_amount * 1e7) as i64,
+            price_e7: Amount::ONE
+                .mul_price(price, Rounding::Down)
+                .unwrap()
+                .stroops() as i64,
+            available_amount_e7: available_amount.stroops() as i64,
         }
     }
 
+    fn amount(s: &str) -> Amount {
+        s.parse().unwrap()
+    }
+
     #[test]
     fn selects_best_executable_direct_venue() {
         let candidates = vec![
-            candidate("amm", "pool1", 1.02, 100.0),
-            candidate("sdex", "offer2", 1.01, 25.0),
-            candidate("sdex", "offer1", 1.00, 75.0),
+            candidate("amm", "pool1", "1.02", "100.0"),
+            candidate("sdex", "offer2", "1.01", "25.0"),
+            candidate("sdex", "offer1", "1.00", "75.0"),
         ];
 
-        let (selected, rationale) =
-            evaluate_single_hop_direct_venues(candidates, 50.0).expect("must select a venue");
+        let (selected, rationale) = evaluate_single_hop_direct_venues(candidates, amount("50.0"))
+            .expect("must select a venue");
 
         assert_eq!(selected.venue_type, "sdex");
         assert_eq!(selected.venue_ref, "offer1");
@@ -958,16 +1508,52 @@ mod tests {
         assert_eq!(rationale.compared_venues.len(), 3);
     }
 
+    #[test]
+    fn hop_min_outputs_apply_slippage_to_each_expected_output() {
+        let step = |price: &str| PathStep {
+            from_asset: AssetInfo::native(),
+            to_asset: AssetInfo::native(),
+            price: price.to_string(),
+            source: "sdex".to_string(),
+            min_amount_out: None,
+        };
+
+        let path = with_hop_min_outputs(vec![step("2.0"), step("0.5")], amount("100"), 50);
+
+        assert_eq!(path[0].min_amount_out.as_deref(), Some("199.0000000"));
+        assert_eq!(path[1].min_amount_out.as_deref(), Some("99.5000000"));
+    }
+
+    #[test]
+    fn hop_min_outputs_round_down_to_the_stroop() {
+        let step = PathStep {
+            from_asset: AssetInfo::native(),
+            to_asset: AssetInfo::native(),
+            price: "0.3333333".to_string(),
+            source: "sdex".to_string(),
+            min_amount_out: None,
+        };
+
+        // The hop output (307445703817.2524040…) and its 0.5% minimum are both
+        // truncated to the stroop; f64 cannot hold either exactly.
+        let path = with_hop_min_outputs(vec![step], amount("922337203685.4775807"), 50);
+
+        assert_eq!(
+            path[0].min_amount_out.as_deref(),
+            Some("305908475298.1661419")
+        );
+    }
+
     #[test]
     fn tie_break_is_deterministic_by_venue_then_ref() {
         let candidates = vec![
-            candidate("sdex", "offer2", 1.0, 100.0),
-            candidate("amm", "pool1", 1.0, 100.0),
-            candidate("sdex", "offer1", 1.0, 100.0),
+            candidate("sdex", "offer2", "1.0", "100.0"),
+            candidate("amm", "pool1", "1.0", "100.0"),
+            candidate("sdex", "offer1", "1.0", "100.0"),
         ];
 
-        let (selected, rationale) =
-            evaluate_single_hop_direct_venues(candidates, 10.0).expect("must select a venue");
+        let (selected, rationale) = evaluate_single_hop_direct_venues(candidates, amount("10.0"))
+            .expect("must select a venue");
 
         assert_eq!(selected.comparison_source(), "amm:pool1");
         assert_eq!(
@@ -987,14 +1573,43 @@ mod tests {
     #[test]
     fn insufficient_liquidity_returns_no_route() {
         let candidates = vec![
-            candidate("amm", "pool1", 1.0, 5.0),
-            candidate("sdex", "offer1", 0.99, 2.0),
+            candidate("amm", "pool1", "1.0", "5.0"),
+            candidate("sdex", "offer1", "0.99", "2.0"),
         ];
 
-        let result = evaluate_single_hop_direct_venues(candidates, 10.0);
+        let result = evaluate_single_hop_direct_venues(candidates, amount("10.0"));
         assert!(matches!(result, Err(ApiError::NoRouteFound)));
     }
 
+    #[test]
+    fn routing_policy_rules_out_venues_trading_untrusted_assets() {
+        let base = AssetPath::parse("native").unwrap();
+        let usdc =
+            AssetPath::parse("USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN")
+                .unwrap();
+        let candidates = || {
+            vec![
+                candidate("amm", "pool1", "1.0", "100.0"),
+                candidate("sdex", "offer1", "0.99", "100.0"),
+            ]
+        };
+
+        let open = RoutingPolicy::default().with_venue_denylist(vec!["amm".to_string()]);
+        let (kept, ruled_out) = apply_routing_policy(candidates(), &base, &usdc, &open);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].venue_ref, "offer1");
+        assert!(ruled_out.is_empty());
+
+        let account = RoutingPolicy::default().with_asset_allowlist(vec!["native".to_string()]);
+        let (kept, ruled_out) = apply_routing_policy(candidates(), &base, &usdc, &account);
+        assert!(kept.is_empty());
+        assert_eq!(ruled_out.len(), 2);
+        assert_eq!(
+            missing_assets(&base, &usdc, &account),
+            vec![usdc.to_canonical()]
+        );
+    }
+
     // --- Req 4.1: stale_quote_rejections counter ---
 
     #[test]
@@ -1086,10 +1701,10 @@ mod tests {
         // The stale candidate has been excluded by freshness filtering before this call.
         // Only the fresh-but-low-liquidity candidate reaches evaluate_single_hop_direct_venues.
         let fresh_candidates = vec![
-            candidate("sdex", "offer_fresh", 1.0, 5.0), // fresh but only 5 units available
+            candidate("sdex", "offer_fresh", "1.0", "5.0"), // fresh but only 5 units available
         ];
         // Request 100 units — exceeds the fresh candidate's available_amount.
-        let result = evaluate_single_hop_direct_venues(fresh_candidates, 100.0);
+        let result = evaluate_single_hop_direct_venues(fresh_candidates, amount("100.0"));
 
         // Must be NoRouteFound, not StaleMarketData.
         assert!(
@@ -1108,10 +1723,10 @@ mod tests {
     fn mixed_freshness_with_sufficient_fresh_liquidity_succeeds() {
         // Stale candidate already filtered out; only these fresh candidates remain.
         let fresh_candidates = vec![
-            candidate("amm", "pool_fresh", 1.05, 200.0),
-            candidate("sdex", "offer_fresh", 1.02, 150.0),
+            candidate("amm", "pool_fresh", "1.05", "200.0"),
+            candidate("sdex", "offer_fresh", "1.02", "150.0"),
         ];
-        let amount = 100.0;
+        let amount = amount("100");
 
         let (selected, rationale) = evaluate_single_hop_direct_venues(fresh_candidates, amount)
             .expect("must select a venue when fresh candidates have sufficient liquidity");
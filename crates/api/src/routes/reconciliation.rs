@@ -0,0 +1,183 @@
+//! Reconciliation ops endpoints.
+//!
+//! Read-only views over the data written by the indexer's reconciliation
+//! scheduler, so operators can inspect data health without querying Postgres.
+//!
+//! - `GET /api/v1/ops/reconciliation/runs`    — recent reconciliation cycles
+//! - `GET /api/v1/ops/reconciliation/drift`   — drift summary + recent events
+//! - `GET /api/v1/ops/reconciliation/repairs` — recent repair actions
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use stellarroute_indexer::reconciliation::{
+    DriftCategorySummary, DriftMetrics, ReconciliationRun, RepairAction, RepairActionType,
+};
+
+use crate::{
+    error::{ApiError, Result},
+    state::AppState,
+};
+
+// ---------------------------------------------------------------------------
+// Query params
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+pub struct RunsParams {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DriftParams {
+    pub category: Option<String>,
+    #[serde(default)]
+    pub breach_only: bool,
+    #[serde(default = "default_window_hours")]
+    pub window_hours: i64,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepairsParams {
+    pub action_type: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+fn default_window_hours() -> i64 {
+    6
+}
+
+// ---------------------------------------------------------------------------
+// Response wrappers
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize)]
+pub struct RunsResponse {
+    pub runs: Vec<ReconciliationRun>,
+}
+
+#[derive(Serialize)]
+pub struct DriftResponse {
+    pub window_hours: i64,
+    pub summary: Vec<DriftCategorySummary>,
+    pub events: Vec<DriftMetrics>,
+}
+
+#[derive(Serialize)]
+pub struct RepairsResponse {
+    pub repairs: Vec<RepairAction>,
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+/// List the most recent reconciliation runs.
+pub async fn list_runs(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<RunsParams>,
+) -> Result<Json<RunsResponse>> {
+    let limit = params.limit.clamp(1, 100);
+    let runs = ReconciliationRun::list_recent(state.db.read_pool(), limit).await?;
+    Ok(Json(RunsResponse { runs }))
+}
+
+/// Summarise drift per category and list recent drift events.
+pub async fn list_drift(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DriftParams>,
+) -> Result<Json<DriftResponse>> {
+    let limit = params.limit.clamp(1, 500);
+    let window_hours = params.window_hours.clamp(1, 24 * 7);
+    let pool = state.db.read_pool();
+
+    let summary = DriftCategorySummary::load(pool, window_hours).await?;
+    let events = DriftMetrics::list_recent(
+        pool,
+        params.category.as_deref(),
+        params.breach_only,
+        window_hours,
+        limit,
+    )
+    .await?;
+
+    Ok(Json(DriftResponse {
+        window_hours,
+        summary,
+        events,
+    }))
+}
+
+/// List the most recent repair actions, optionally filtered by action type.
+pub async fn list_repairs(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<RepairsParams>,
+) -> Result<Json<RepairsResponse>> {
+    let limit = params.limit.clamp(1, 100);
+    let action_type = params
+        .action_type
+        .as_deref()
+        .map(parse_action_type)
+        .transpose()?;
+    let repairs = RepairAction::list_recent(state.db.read_pool(), action_type, limit).await?;
+    Ok(Json(RepairsResponse { repairs }))
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn parse_action_type(s: &str) -> Result<RepairActionType> {
+    let action = RepairActionType::from_string(s);
+    if action.to_string() == s {
+        Ok(action)
+    } else {
+        Err(ApiError::Validation(format!(
+            "Unknown repair action type: {}",
+            s
+        )))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_action_type_valid() {
+        assert_eq!(
+            parse_action_type("refetch_horizon").unwrap(),
+            RepairActionType::RefetchHorizon
+        );
+    }
+
+    #[test]
+    fn parse_action_type_invalid_returns_validation_error() {
+        let err = parse_action_type("reboot").unwrap_err();
+        assert!(matches!(err, ApiError::Validation(_)));
+    }
+
+    #[test]
+    fn drift_params_defaults() {
+        let params: DriftParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(params.window_hours, 6);
+        assert_eq!(params.limit, 20);
+        assert!(!params.breach_only);
+        assert!(params.category.is_none());
+    }
+}
@@ -7,14 +7,17 @@ use axum::{
 use std::sync::Arc;
 use tracing::debug;
 
-use stellarroute_routing::optimizer::HybridOptimizer;
+use stellarroute_routing::optimizer::{HybridOptimizer, RouteMetrics};
+use stellarroute_routing::pathfinder::SwapPath;
 use stellarroute_routing::policy::RoutingPolicy;
+use stellarroute_routing::{Amount, Rounding};
 
 use crate::{
+    accounts::validate_account_id,
     error::{ApiError, Result},
     models::{
         request::{AssetPath, RoutesParams},
-        AssetInfo, RouteCandidate, RouteHop, RoutesResponse,
+        AssetInfo, RouteCandidate, RouteHop, RoutesResponse, SkippedRoute,
     },
     state::AppState,
 };
@@ -51,6 +54,9 @@ fn asset_path_to_info(asset: &AssetPath) -> AssetInfo {
 /// - `limit`: Max routes to return (default: 5)
 /// - `max_hops`: Max hops per route (default: 3)
 /// - `environment`: Optimizer policy environment (default: "production")
+/// - `account`: Account that will execute the trade; routes only pass through
+///   assets it holds trustlines for, and better routes left out are listed in
+///   `skipped_routes`
 #[utoipa::path(
     get,
     path = "/api/v1/routes/{base}/{quote}",
@@ -62,11 +68,13 @@ fn asset_path_to_info(asset: &AssetPath) -> AssetInfo {
         ("limit" = Option<usize>, Query, description = "Maximum number of routes to return (default: 5)"),
         ("max_hops" = Option<usize>, Query, description = "Maximum number of hops (default: 3)"),
         ("environment" = Option<String>, Query, description = "Optimizer policy environment"),
+        ("account" = Option<String>, Query, description = "Account (G...) that will execute the trade"),
     ),
     responses(
         (status = 200, description = "Ranked route candidates", body = RoutesResponse),
         (status = 400, description = "Invalid request parameters"),
         (status = 404, description = "No routes found"),
+        (status = 422, description = "Account cannot hold or spend the assets involved"),
         (status = 500, description = "Internal server error"),
     )
 )]
@@ -83,17 +91,17 @@ pub async fn get_routes(
     let quote_asset = AssetPath::parse(&quote)
         .map_err(|e| ApiError::InvalidAsset(format!("Invalid quote asset: {e}")))?;
 
-    let amount: f64 = params
+    let amount: Amount = params
         .amount
         .as_deref()
         .unwrap_or("1")
         .parse()
-        .map_err(|_| ApiError::Validation("amount must be a valid number".into()))?;
+        .map_err(|_| {
+            ApiError::Validation("amount must be a decimal with at most 7 decimal places".into())
+        })?;
 
-    if amount <= 0.0 || !amount.is_finite() {
-        return Err(ApiError::Validation(
-            "amount must be a positive, finite number".into(),
-        ));
+    if !amount.is_positive() {
+        return Err(ApiError::Validation("amount must be positive".into()));
     }
 
     let limit_param = params.limit.unwrap_or(5).min(20); // cap at 20
@@ -103,10 +111,33 @@ pub async fn get_routes(
         .clone()
         .unwrap_or_else(|| "production".into());
 
+    // ── Account constraints ─────────────────────────────────────────────────
+    // Checked before single-flight so each caller gets its own account's
+    // trustline and balance errors
+    let holdings = match params.account.as_deref() {
+        Some(account) => {
+            validate_account_id(account).map_err(ApiError::Validation)?;
+            let holdings = state.accounts.holdings(account).await?;
+            holdings.ensure_can_trade(
+                &base_asset.to_canonical(),
+                &quote_asset.to_canonical(),
+                amount,
+            )?;
+            Some(holdings)
+        }
+        None => None,
+    };
+
     // ── Single-flight deduplication key ────────────────────────────────────
     let sf_key = format!(
-        "routes:{}:{}:{:.7}:{}:{}:{}",
-        base, quote, amount, limit_param, max_hops_param, env_param
+        "routes:{}:{}:{}:{}:{}:{}:{}",
+        base,
+        quote,
+        amount,
+        limit_param,
+        max_hops_param,
+        env_param,
+        params.account.as_deref().unwrap_or("")
     );
 
     // Clone everything needed to move into the async closure
@@ -126,7 +157,11 @@ pub async fn get_routes(
                 return Arc::new(Err(ApiError::NoRouteFound));
             }
 
-            let amount_e7 = (amount * 1e7) as i128;
+            let amount_e7 = amount.stroops();
+            let base_canonical = asset_path_to_info(&base_c).to_canonical();
+            let quote_canonical = asset_path_to_info(&quote_c).to_canonical();
+            let allowed_assets = holdings.as_ref().map(|h| h.route_assets(&base_canonical));
+            let allowed_c = allowed_assets.clone();
 
             // Offload CPU-bound BFS to blocking thread pool to prevent async starvation
             let spawn_result = tokio::task::spawn_blocking(move || {
@@ -138,21 +173,29 @@ pub async fn get_routes(
                     ..Default::default()
                 };
 
-                let base_canonical = asset_path_to_info(&base_c).to_canonical();
-                let quote_canonical = asset_path_to_info(&quote_c).to_canonical();
+                let find = |policy: &RoutingPolicy| {
+                    optimizer.find_optimal_routes(
+                        &base_canonical,
+                        &quote_canonical,
+                        &edges,
+                        amount_e7,
+                        policy,
+                    )
+                };
 
-                optimizer.find_optimal_routes(
-                    &base_canonical,
-                    &quote_canonical,
-                    &edges,
-                    amount_e7,
-                    &routing_policy,
-                )
+                match allowed_c {
+                    // Route for the account, and without it to spot what it misses
+                    Some(allowed) => (
+                        find(&routing_policy.clone().with_asset_allowlist(allowed)),
+                        find(&routing_policy).ok(),
+                    ),
+                    None => (find(&routing_policy), None),
+                }
             })
             .await;
 
             // Handle task-join error (thread panic)
-            let join_result = match spawn_result {
+            let (join_result, unconstrained) = match spawn_result {
                 Ok(r) => r,
                 Err(e) => {
                     return Arc::new(Err(ApiError::Validation(format!(
@@ -161,10 +204,33 @@ pub async fn get_routes(
                 }
             };
 
+            let missing_assets = |path: &SwapPath| -> Vec<String> {
+                let Some(allowed) = &allowed_assets else {
+                    return Vec::new();
+                };
+                let mut missing: Vec<String> = Vec::new();
+                for hop in &path.hops {
+                    for asset in [&hop.source_asset, &hop.destination_asset] {
+                        if !allowed.contains(asset) && !missing.contains(asset) {
+                            missing.push(asset.clone());
+                        }
+                    }
+                }
+                missing
+            };
+
             // Handle routing error (no path found)
-            let diag = match join_result {
-                Ok(d) => d,
-                Err(_) => return Arc::new(Err(ApiError::NoRouteFound)),
+            let diag = match (join_result, &unconstrained) {
+                (Ok(d), _) => d,
+                (Err(_), Some(other)) if !missing_assets(&other.selected_path).is_empty() => {
+                    let account = holdings.as_ref().map(|h| h.account_id.as_str());
+                    return Arc::new(Err(ApiError::AccountConstraint(format!(
+                        "Every route passes through an asset account {} has no trustline for: {}",
+                        account.unwrap_or_default(),
+                        missing_assets(&other.selected_path).join(", ")
+                    ))));
+                }
+                (Err(_), _) => return Arc::new(Err(ApiError::NoRouteFound)),
             };
 
             // Record route compute time metric
@@ -174,33 +240,36 @@ pub async fn get_routes(
             );
 
             // ── Map diagnostics → response DTO ─────────────────────────────
-            let build_candidate = |path: &stellarroute_routing::pathfinder::SwapPath,
-                                   metrics: &stellarroute_routing::optimizer::RouteMetrics|
-             -> RouteCandidate {
+            let build_candidate = |path: &SwapPath, metrics: &RouteMetrics| -> RouteCandidate {
                 let mut hops = Vec::new();
-                let mut active = amount_e7;
+                let mut active = amount;
 
+                // Hop outputs round down, like the sell side of a quote
                 for h in &path.hops {
-                    let after_fee = (active * (10000 - h.fee_bps as i128)) / 10000;
-                    let out = (after_fee as f64 * h.price) as i128;
+                    let out = active
+                        .mul_ratio(10_000 - i128::from(h.fee_bps), 10_000, Rounding::Down)
+                        .and_then(|after_fee| after_fee.mul_price(h.price, Rounding::Down))
+                        .unwrap_or(Amount::ZERO);
 
                     hops.push(RouteHop {
                         from_asset: parse_asset_to_info(&h.source_asset),
                         to_asset: parse_asset_to_info(&h.destination_asset),
-                        price: format!("{:.7}", h.price),
-                        amount_out_of_hop: format!("{:.7}", out as f64 / 1e7),
+                        price: h.price.to_string(),
+                        amount_out_of_hop: out.to_string(),
                         fee_bps: h.fee_bps,
-                        source: if h.venue_type == "amm" {
-                            format!("amm:{}", h.venue_ref)
-                        } else {
+                        // Pools keep their venue type, so StableSwap hops
+                        // build as `AmmStable`
+                        source: if h.venue_type == "sdex" {
                             "sdex".into()
+                        } else {
+                            format!("{}:{}", h.venue_type, h.venue_ref)
                         },
                     });
                     active = out;
                 }
 
                 RouteCandidate {
-                    estimated_output: format!("{:.7}", metrics.output_amount as f64 / 1e7),
+                    estimated_output: Amount::from_stroops(metrics.output_amount).to_string(),
                     impact_bps: metrics.impact_bps,
                     score: metrics.score,
                     policy_used: diag.policy.environment.clone(),
@@ -215,12 +284,44 @@ pub async fn get_routes(
                 routes.push(build_candidate(path, metric));
             }
 
+            // ── Better routes the account's trustlines ruled out ───────────
+            let mut skipped_routes = Vec::new();
+            if let (Some(other), Some(holdings)) = (unconstrained, &holdings) {
+                let best = diag.metrics.output_amount;
+                let candidates = std::iter::once((&other.selected_path, &other.metrics))
+                    .chain(other.alternatives.iter().map(|(p, m)| (p, m)));
+
+                for (path, metrics) in candidates {
+                    let missing = missing_assets(path);
+                    if metrics.output_amount <= best || missing.is_empty() {
+                        continue;
+                    }
+                    skipped_routes.push(SkippedRoute {
+                        reason: format!(
+                            "Returns {} more but account {} has no trustline for {}",
+                            Amount::from_stroops(metrics.output_amount - best),
+                            holdings.account_id,
+                            missing.join(", ")
+                        ),
+                        route: build_candidate(path, metrics),
+                        missing_trustlines: missing
+                            .iter()
+                            .map(|a| parse_asset_to_info(a))
+                            .collect(),
+                    });
+                    if skipped_routes.len() == limit_param {
+                        break;
+                    }
+                }
+            }
+
             Arc::new(Ok(RoutesResponse {
                 base_asset: asset_path_to_info(&base_asset),
                 quote_asset: asset_path_to_info(&quote_asset),
-                amount: format!("{:.7}", amount),
+                amount: amount.to_string(),
                 routes,
                 timestamp: chrono::Utc::now().timestamp_millis(),
+                skipped_routes,
             }))
         })
         .await;
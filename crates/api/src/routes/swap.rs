@@ -0,0 +1,42 @@
+//! Swap transaction building
+//!
+//! - `POST /api/v1/swap/build` — unsigned transaction XDR for a quote or route
+
+use axum::{extract::State, Json};
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::{
+    error::Result,
+    models::{request::SwapBuildRequest, SwapBuildResponse},
+    state::AppState,
+    txbuild::build_swap_transaction,
+};
+
+/// Build an unsigned transaction executing a quote or route
+///
+/// Send exactly one of `quote` or `route`, as returned by the quote and route
+/// endpoints. SDEX and classic pool paths become a path payment; Soroban pool
+/// paths become a router `execute_swap` call that must be simulated before
+/// signing (`requires_simulation`).
+#[utoipa::path(
+    post,
+    path = "/api/v1/swap/build",
+    tag = "trading",
+    responses(
+        (status = 200, description = "Unsigned transaction", body = SwapBuildResponse),
+        (status = 400, description = "Invalid request or unbuildable path", body = ErrorResponse),
+    )
+)]
+pub async fn build_swap(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SwapBuildRequest>,
+) -> Result<Json<SwapBuildResponse>> {
+    let now = u64::try_from(chrono::Utc::now().timestamp()).unwrap_or_default();
+    let response = build_swap_transaction(&state.tx_build, &request, now)?;
+    debug!(
+        "Built {} for {} (sequence {})",
+        response.operation, response.source_account, response.sequence
+    );
+    Ok(Json(response))
+}
@@ -1,12 +1,21 @@
 //! Quote broadcaster background task.
 //!
-//! [`run_broadcaster`] polls the database for liquidity changes and fans out
+//! [`run_broadcaster`] polls the database for liquidity changes on subscribed
+//! pairs, computes one [`PairUpdate`] per changed pair and fans it out as
 //! [`ServerMessage::QuoteUpdate`] messages to all matching subscribers.
+//!
+//! With Redis configured, only the replica holding the broadcaster lock polls
+//! and computes updates. It publishes them through [`super::fanout`] and every
+//! replica delivers them to its own subscribers. Without Redis, or while it
+//! is unreachable, the broadcaster serves the subscribers of this process
+//! directly.
 
+use serde::{Deserialize, Serialize};
 use sqlx::Row;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use std::time::Duration;
+use stellarroute_routing::{Amount, Price, Rounding};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
 use tracing::{debug, warn};
@@ -17,19 +26,37 @@ use crate::models::request::AssetPath;
 use crate::models::{AssetInfo, PathStep, QuoteRationaleMetadata, QuoteResponse, VenueEvaluation};
 use crate::state::AppState;
 
+use super::fanout::RedisFanout;
 use super::messages::{ServerMessage, ServerPayload};
 use super::registry::SubscriptionRegistry;
 
+/// Quotes for every amount filter subscribed to on one pair, computed once
+/// and delivered to all matching subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairUpdate {
+    pub base: String,
+    pub quote: String,
+    pub quotes: Vec<AmountQuote>,
+}
+
+/// The quote for one amount filter, or `None` when no route was found
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmountQuote {
+    pub amount: Option<String>,
+    pub quote: Option<Box<QuoteResponse>>,
+}
+
 // ---------------------------------------------------------------------------
 // Public entry point
 // ---------------------------------------------------------------------------
 
 /// Run the quote broadcaster forever.
 ///
-/// This function is intended to be spawned as a long-lived `tokio` task.
+/// This function is intended to be spawned once as a long-lived `tokio` task.
 /// It polls the database every `poll_interval_ms` milliseconds, computes
 /// quotes for all active subscriptions, and fans out updates to connected
-/// clients.
+/// clients. With Redis configured it also starts the replica's leader
+/// election, demand advertisement and update subscription tasks.
 ///
 /// The task is wrapped in a restart loop: if the inner loop panics the error
 /// is logged and the task restarts after a 1-second delay.
@@ -38,8 +65,22 @@ pub async fn run_broadcaster(
     registry: Arc<RwLock<SubscriptionRegistry>>,
     poll_interval_ms: u64,
 ) {
+    let fanout = state
+        .cache
+        .as_ref()
+        .map(|cache| RedisFanout::new(cache.clone(), poll_interval_ms));
+    if let Some(fanout) = &fanout {
+        fanout.spawn_replica_tasks(registry.clone());
+    }
+
     loop {
-        let result = broadcaster_loop(state.clone(), registry.clone(), poll_interval_ms).await;
+        let result = broadcaster_loop(
+            state.clone(),
+            registry.clone(),
+            poll_interval_ms,
+            fanout.clone(),
+        )
+        .await;
         // broadcaster_loop only returns on an unrecoverable error / panic
         // (it loops internally). Log and restart.
         warn!(
@@ -50,6 +91,55 @@ pub async fn run_broadcaster(
     }
 }
 
+/// Deliver a [`PairUpdate`] to the local subscribers of its pair.
+///
+/// Each subscription receives the quote for its own amount filter, unless
+/// the price has not moved enough since its last emission.
+pub async fn deliver_update(registry: &Arc<RwLock<SubscriptionRegistry>>, update: &PairUpdate) {
+    let subs = {
+        let reg = registry.read().await;
+        reg.get_connections_for_pair(&update.base, &update.quote)
+    };
+
+    for (conn_id, tx, sub) in subs {
+        let Some(result) = update.quotes.iter().find(|q| q.amount == sub.amount) else {
+            continue;
+        };
+
+        let Some(quote) = &result.quote else {
+            let msg = ServerMessage::now(ServerPayload::Error {
+                code: "no_route_found".into(),
+                message: format!("No liquidity found for {}/{}", update.base, update.quote),
+                request_id: None,
+            });
+            send_or_remove(registry, conn_id, &tx, msg).await;
+            continue;
+        };
+
+        // Dedup — skip if price hasn't changed beyond threshold
+        let price = quote
+            .price
+            .parse::<Price>()
+            .map(|p| p.to_f64())
+            .unwrap_or_default();
+        if should_skip_emission(sub.last_emitted_price, price, sub.amount.is_some()) {
+            debug!("broadcaster: price unchanged for sub {}, skipping", sub.id);
+            continue;
+        }
+
+        let msg = ServerMessage::now(ServerPayload::QuoteUpdate {
+            subscription_id: sub.id,
+            quote: quote.clone(),
+        });
+
+        // Update last_emitted_price on success
+        if send_or_remove(registry, conn_id, &tx, msg).await {
+            let mut reg = registry.write().await;
+            reg.update_last_emitted_price(conn_id, sub.id, price);
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Inner loop (restartable)
 // ---------------------------------------------------------------------------
@@ -58,176 +148,219 @@ async fn broadcaster_loop(
     state: Arc<AppState>,
     registry: Arc<RwLock<SubscriptionRegistry>>,
     poll_interval_ms: u64,
+    fanout: Option<Arc<RedisFanout>>,
 ) -> Result<(), String> {
-    // Track the last-seen ledger revision per (base, quote) pair so we can
-    // detect changes without re-querying every subscription individually.
-    let mut last_revisions: HashMap<(String, String), String> = HashMap::new();
+    // Track the last-seen ledger revision and amount filters per (base, quote)
+    // pair so we can detect changes without recomputing unchanged pairs.
+    let mut last_seen: HashMap<(String, String), LastSeen> = HashMap::new();
 
     loop {
         sleep(Duration::from_millis(poll_interval_ms)).await;
 
-        // Collect unique (base, quote) pairs from all active subscriptions.
-        let pairs: HashSet<(String, String)> = {
-            let reg = registry.read().await;
-            reg.all_pairs()
-        };
-
-        for (base, quote) in &pairs {
-            // ----------------------------------------------------------------
-            // 1. Resolve asset IDs
-            // ----------------------------------------------------------------
-            let base_asset = match AssetPath::parse(base) {
-                Ok(a) => a,
-                Err(e) => {
-                    warn!("broadcaster: invalid base asset '{}': {}", base, e);
-                    continue;
-                }
-            };
-            let quote_asset = match AssetPath::parse(quote) {
-                Ok(a) => a,
-                Err(e) => {
-                    warn!("broadcaster: invalid quote asset '{}': {}", quote, e);
-                    continue;
-                }
-            };
-
-            let base_id = match find_asset_id(&state, &base_asset).await {
-                Ok(id) => id,
-                Err(ApiError::NotFound(_)) => {
-                    send_no_route_to_pair(&state, &registry, base, quote).await;
-                    continue;
-                }
-                Err(e) => {
-                    warn!("broadcaster: find_asset_id({}) error: {:?}", base, e);
+        // Collect the pairs and amounts subscribed to, on every replica when
+        // fanning out through Redis. Without Redis, or while it is
+        // unreachable and nobody can lead, serve this replica's subscribers.
+        let (demand, publisher) = match fanout.as_ref().filter(|f| f.is_available()) {
+            Some(fanout) => {
+                if !fanout.is_leader() {
+                    // Whoever leads next starts from a clean slate
+                    last_seen.clear();
                     continue;
                 }
-            };
-            let quote_id = match find_asset_id(&state, &quote_asset).await {
-                Ok(id) => id,
-                Err(ApiError::NotFound(_)) => {
-                    send_no_route_to_pair(&state, &registry, base, quote).await;
-                    continue;
-                }
-                Err(e) => {
-                    warn!("broadcaster: find_asset_id({}) error: {:?}", quote, e);
-                    continue;
-                }
-            };
-
-            // ----------------------------------------------------------------
-            // 2. Check liquidity revision — skip if unchanged
-            // ----------------------------------------------------------------
-            let revision = match get_liquidity_revision(&state, base_id, quote_id).await {
-                Ok(r) => r,
-                Err(e) => {
-                    warn!("broadcaster: get_liquidity_revision error: {:?}", e);
-                    continue;
+                match fanout.read_demand().await {
+                    Ok(demand) => (demand, Some(fanout)),
+                    Err(e) => {
+                        warn!(
+                            "broadcaster: failed to read subscription demand, serving local subscribers: {}",
+                            e
+                        );
+                        (registry.read().await.demand(), None)
+                    }
                 }
-            };
-
-            let pair_key = (base.clone(), quote.clone());
-            let prev_revision = last_revisions.get(&pair_key).cloned();
-
-            // Always emit on first poll (prev_revision is None); otherwise
-            // only emit when the revision has changed.
-            if prev_revision.as_deref() == Some(revision.as_str()) {
-                debug!(
-                    "broadcaster: no revision change for {}/{}, skipping",
-                    base, quote
-                );
-                continue;
             }
+            None => (registry.read().await.demand(), None),
+        };
+        last_seen.retain(|pair, _| demand.contains_key(pair));
 
-            last_revisions.insert(pair_key.clone(), revision.clone());
-
-            // ----------------------------------------------------------------
-            // 3. Get subscriptions for this pair
-            // ----------------------------------------------------------------
-            let subs = {
-                let reg = registry.read().await;
-                reg.get_connections_for_pair(base, quote)
+        for ((base, quote), amounts) in demand {
+            let Some(update) = pair_update(&state, base, quote, amounts, &mut last_seen).await
+            else {
+                continue;
             };
 
-            for (conn_id, tx, sub) in subs {
-                // ------------------------------------------------------------
-                // 4. Compute quote
-                // ------------------------------------------------------------
-                let amount: f64 = sub
-                    .amount
-                    .as_deref()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(1.0);
-
-                let quote_result =
-                    find_best_price(&state, &base_asset, &quote_asset, base_id, quote_id, amount)
-                        .await;
-
-                let (price, path, rationale) = match quote_result {
-                    Ok(r) => r,
-                    Err(ApiError::NoRouteFound) => {
-                        let msg = ServerMessage::now(ServerPayload::Error {
-                            code: "no_route_found".into(),
-                            message: format!("No liquidity found for {}/{}", base, quote),
-                        });
-                        send_or_remove(&state, &registry, conn_id, &tx, msg).await;
-                        continue;
-                    }
-                    Err(e) => {
+            match publisher {
+                Some(fanout) => {
+                    if let Err(e) = fanout.publish(&update).await {
                         warn!(
-                            "broadcaster: find_best_price({}/{}) error: {:?}",
-                            base, quote, e
+                            "broadcaster: failed to publish update for {}/{}, delivering locally: {}",
+                            update.base, update.quote, e
                         );
-                        continue;
+                        deliver_update(&registry, &update).await;
                     }
-                };
-
-                // ------------------------------------------------------------
-                // 5. Dedup — skip if price hasn't changed beyond threshold
-                // ------------------------------------------------------------
-                if should_skip_emission(sub.last_emitted_price, price, sub.amount.is_some()) {
-                    debug!("broadcaster: price unchanged for sub {}, skipping", sub.id);
-                    continue;
                 }
+                None => deliver_update(&registry, &update).await,
+            }
+        }
+    }
+}
 
-                // ------------------------------------------------------------
-                // 6. Build and send the QuoteUpdate message
-                // ------------------------------------------------------------
-                let timestamp = chrono::Utc::now().timestamp_millis();
-                let quote_response = QuoteResponse {
-                    base_asset: asset_path_to_info(&base_asset),
-                    quote_asset: asset_path_to_info(&quote_asset),
-                    amount: format!("{:.7}", amount),
-                    price: format!("{:.7}", price),
-                    total: format!("{:.7}", amount * price),
-                    quote_type: "sell".to_string(),
-                    path,
-                    timestamp,
-                    expires_at: None,
-                    source_timestamp: None,
-                    ttl_seconds: None,
-                    rationale: Some(rationale),
-                    price_impact: None,
-                    exclusion_diagnostics: None,
-                    data_freshness: None,
-                };
-
-                let msg = ServerMessage::now(ServerPayload::QuoteUpdate {
-                    subscription_id: sub.id,
-                    quote: Box::new(quote_response),
-                });
+/// Ledger revision and amount filters a pair was last computed for
+type LastSeen = (String, BTreeSet<Option<String>>);
 
-                let sent = send_or_remove(&state, &registry, conn_id, &tx, msg).await;
+/// Compute the update for one pair.
+///
+/// Returns `None` when neither the pair's liquidity revision nor its amount
+/// filters changed since the last update, or when it cannot be computed.
+async fn pair_update(
+    state: &AppState,
+    base: String,
+    quote: String,
+    amounts: BTreeSet<Option<String>>,
+    last_seen: &mut HashMap<(String, String), LastSeen>,
+) -> Option<PairUpdate> {
+    // ----------------------------------------------------------------
+    // 1. Resolve asset IDs
+    // ----------------------------------------------------------------
+    let base_asset = match AssetPath::parse(&base) {
+        Ok(a) => a,
+        Err(e) => {
+            warn!("broadcaster: invalid base asset '{}': {}", base, e);
+            return None;
+        }
+    };
+    let quote_asset = match AssetPath::parse(&quote) {
+        Ok(a) => a,
+        Err(e) => {
+            warn!("broadcaster: invalid quote asset '{}': {}", quote, e);
+            return None;
+        }
+    };
 
-                // ------------------------------------------------------------
-                // 7. Update last_emitted_price on success
-                // ------------------------------------------------------------
-                if sent {
-                    let mut reg = registry.write().await;
-                    reg.update_last_emitted_price(conn_id, sub.id, price);
-                }
-            }
+    let no_route = |base: String, quote: String, amounts: BTreeSet<Option<String>>| PairUpdate {
+        base,
+        quote,
+        quotes: amounts
+            .into_iter()
+            .map(|amount| AmountQuote {
+                amount,
+                quote: None,
+            })
+            .collect(),
+    };
+    let base_id = match find_asset_id(state, &base_asset).await {
+        Ok(id) => id,
+        Err(ApiError::NotFound(_)) => return Some(no_route(base, quote, amounts)),
+        Err(e) => {
+            warn!("broadcaster: find_asset_id({}) error: {:?}", base, e);
+            return None;
         }
+    };
+    let quote_id = match find_asset_id(state, &quote_asset).await {
+        Ok(id) => id,
+        Err(ApiError::NotFound(_)) => return Some(no_route(base, quote, amounts)),
+        Err(e) => {
+            warn!("broadcaster: find_asset_id({}) error: {:?}", quote, e);
+            return None;
+        }
+    };
+
+    // ----------------------------------------------------------------
+    // 2. Check liquidity revision — skip if unchanged
+    // ----------------------------------------------------------------
+    let revision = match get_liquidity_revision(state, base_id, quote_id).await {
+        Ok(r) => r,
+        Err(e) => {
+            warn!("broadcaster: get_liquidity_revision error: {:?}", e);
+            return None;
+        }
+    };
+
+    // Always emit on first poll; otherwise only emit when the revision or
+    // the set of subscribed amounts has changed.
+    let pair_key = (base.clone(), quote.clone());
+    let seen = (revision, amounts);
+    if last_seen.get(&pair_key) == Some(&seen) {
+        debug!(
+            "broadcaster: no revision change for {}/{}, skipping",
+            base, quote
+        );
+        return None;
     }
+    let amounts = seen.1.clone();
+    last_seen.insert(pair_key, seen);
+
+    // ----------------------------------------------------------------
+    // 3. Compute one quote per amount filter
+    // ----------------------------------------------------------------
+    let mut quotes = Vec::with_capacity(amounts.len());
+    for amount_filter in amounts {
+        let amount: Amount = amount_filter
+            .as_deref()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(Amount::ONE);
+
+        let quote_result =
+            find_best_price(state, &base_asset, &quote_asset, base_id, quote_id, amount).await;
+
+        let (price, path, rationale) = match quote_result {
+            Ok(r) => r,
+            Err(ApiError::NoRouteFound) => {
+                quotes.push(AmountQuote {
+                    amount: amount_filter,
+                    quote: None,
+                });
+                continue;
+            }
+            Err(e) => {
+                warn!(
+                    "broadcaster: find_best_price({}/{}) error: {:?}",
+                    base, quote, e
+                );
+                continue;
+            }
+        };
+
+        let Ok(total) = amount.mul_price(price, Rounding::Down) else {
+            warn!(
+                "broadcaster: total overflows for {}/{} amount {}",
+                base, quote, amount
+            );
+            continue;
+        };
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let quote_response = QuoteResponse {
+            base_asset: asset_path_to_info(&base_asset),
+            quote_asset: asset_path_to_info(&quote_asset),
+            amount: amount.to_string(),
+            price: price.to_string(),
+            total: total.to_string(),
+            quote_type: "sell".to_string(),
+            path,
+            timestamp,
+            expires_at: None,
+            source_timestamp: None,
+            ttl_seconds: None,
+            ttl_reason: None,
+            rationale: Some(rationale),
+            price_impact: None,
+            exclusion_diagnostics: None,
+            data_freshness: None,
+            quote_id: None,
+            signature: None,
+            signer: None,
+            skipped_routes: Vec::new(),
+        };
+        quotes.push(AmountQuote {
+            amount: amount_filter,
+            quote: Some(Box::new(quote_response)),
+        });
+    }
+
+    Some(PairUpdate {
+        base,
+        quote,
+        quotes,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -263,7 +396,6 @@ pub fn should_skip_emission(
 ///
 /// Returns `true` if the message was sent successfully, `false` otherwise.
 async fn send_or_remove(
-    _state: &AppState,
     registry: &Arc<RwLock<SubscriptionRegistry>>,
     conn_id: uuid::Uuid,
     tx: &tokio::sync::mpsc::Sender<ServerMessage>,
@@ -289,35 +421,6 @@ async fn send_or_remove(
     }
 }
 
-/// Send a `no_route_found` error to all subscriptions for a given pair.
-async fn send_no_route_to_pair(
-    _state: &AppState,
-    registry: &Arc<RwLock<SubscriptionRegistry>>,
-    base: &str,
-    quote: &str,
-) {
-    let subs = {
-        let reg = registry.read().await;
-        reg.get_connections_for_pair(base, quote)
-    };
-    for (conn_id, tx, _sub) in subs {
-        let msg = ServerMessage::now(ServerPayload::Error {
-            code: "no_route_found".into(),
-            message: format!("No liquidity found for {}/{}", base, quote),
-        });
-        match tx.try_send(msg) {
-            Ok(()) => {}
-            Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
-                warn!("broadcaster: channel full for conn {} (no_route)", conn_id);
-            }
-            Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {
-                let mut reg = registry.write().await;
-                reg.remove_connection(conn_id);
-            }
-        }
-    }
-}
-
 // ---------------------------------------------------------------------------
 // DB helpers (inlined from routes/quote.rs — private functions)
 // ---------------------------------------------------------------------------
@@ -390,8 +493,8 @@ async fn find_best_price(
     quote: &AssetPath,
     base_id: Uuid,
     quote_id: Uuid,
-    amount: f64,
-) -> Result<(f64, Vec<PathStep>, QuoteRationaleMetadata), ApiError> {
+    amount: Amount,
+) -> Result<(Price, Vec<PathStep>, QuoteRationaleMetadata), ApiError> {
     let rows = sqlx::query(
         r#"
         select
@@ -414,24 +517,23 @@ async fn find_best_price(
         return Err(ApiError::NoRouteFound);
     }
 
-    let mut candidates: Vec<(String, String, f64, f64)> = rows
+    let mut candidates: Vec<(String, String, Price, Amount)> = rows
         .into_iter()
         .map(|row| {
             let venue_type: String = row.get("venue_type");
             let venue_ref: String = row.get("venue_ref");
-            let price: f64 = row.get::<String, _>("price").parse().unwrap_or(0.0);
-            let available: f64 = row
+            let price: Price = row.get::<String, _>("price").parse().unwrap_or(Price::ZERO);
+            let available: Amount = row
                 .get::<String, _>("available_amount")
                 .parse()
-                .unwrap_or(0.0);
+                .unwrap_or(Amount::ZERO);
             (venue_type, venue_ref, price, available)
         })
         .collect();
 
     // Sort by price asc, then venue_type, then venue_ref for determinism
     candidates.sort_by(|a, b| {
-        a.2.partial_cmp(&b.2)
-            .unwrap_or(std::cmp::Ordering::Equal)
+        a.2.cmp(&b.2)
             .then_with(|| a.0.cmp(&b.0))
             .then_with(|| a.1.cmp(&b.1))
     });
@@ -440,15 +542,15 @@ async fn find_best_price(
         .iter()
         .map(|(vt, vr, price, avail)| VenueEvaluation {
             source: format!("{}:{}", vt, vr),
-            price: format!("{:.7}", price),
-            available_amount: format!("{:.7}", avail),
-            executable: *avail >= amount && *price > 0.0,
+            price: price.to_string(),
+            available_amount: avail.to_string(),
+            executable: *avail >= amount && price.is_positive(),
         })
         .collect();
 
     let selected = candidates
         .iter()
-        .find(|(_, _, price, avail)| *avail >= amount && *price > 0.0)
+        .find(|(_, _, price, avail)| *avail >= amount && price.is_positive())
         .cloned()
         .ok_or(ApiError::NoRouteFound)?;
 
@@ -463,8 +565,9 @@ async fn find_best_price(
     let path = vec![PathStep {
         from_asset: asset_path_to_info(base),
         to_asset: asset_path_to_info(quote),
-        price: format!("{:.7}", price),
+        price: price.to_string(),
         source,
+        min_amount_out: None,
     }];
 
     let rationale = QuoteRationaleMetadata {
@@ -533,6 +636,77 @@ mod tests {
         }
     }
 
+    fn update(price: &str) -> PairUpdate {
+        let quote: QuoteResponse = serde_json::from_value(serde_json::json!({
+            "base_asset": { "asset_type": "native" },
+            "quote_asset": { "asset_type": "credit_alphanum4", "asset_code": "USDC" },
+            "amount": "5.0000000",
+            "price": price,
+            "total": "0",
+            "quote_type": "sell",
+            "path": [],
+            "timestamp": 0
+        }))
+        .unwrap();
+        PairUpdate {
+            base: "native".to_string(),
+            quote: "USDC".to_string(),
+            quotes: vec![
+                AmountQuote {
+                    amount: Some("5".to_string()),
+                    quote: Some(Box::new(quote)),
+                },
+                AmountQuote {
+                    amount: None,
+                    quote: None,
+                },
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn deliver_update_matches_amount_filters_and_dedups() {
+        use super::super::registry::Subscription;
+
+        let registry = SubscriptionRegistry::shared();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+        let conn_id = Uuid::new_v4();
+        for amount in [Some("5".to_string()), None] {
+            let sub = Subscription {
+                id: Uuid::new_v4(),
+                base: "native".to_string(),
+                quote: "USDC".to_string(),
+                amount,
+                last_emitted_price: None,
+            };
+            registry
+                .write()
+                .await
+                .add_subscription(conn_id, tx.clone(), sub)
+                .unwrap();
+        }
+
+        deliver_update(&registry, &update("0.1000000")).await;
+        let mut kinds = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            kinds.push(match msg.payload {
+                ServerPayload::QuoteUpdate { quote, .. } => quote.price,
+                ServerPayload::Error { code, .. } => code,
+                other => panic!("unexpected payload: {:?}", other),
+            });
+        }
+        kinds.sort();
+        assert_eq!(kinds, vec!["0.1000000", "no_route_found"]);
+
+        // The same price again is deduplicated; only the error repeats
+        deliver_update(&registry, &update("0.1000000")).await;
+        assert!(matches!(
+            rx.try_recv().unwrap().payload,
+            ServerPayload::Error { .. }
+        ));
+        assert!(rx.try_recv().is_err());
+    }
+
     #[test]
     fn dedup_no_last_price_always_emits() {
         assert!(!should_skip_emission(None, 1.5, true));
@@ -15,7 +15,7 @@ use tokio::sync::{mpsc, RwLock};
 use tokio::time::{interval, sleep};
 use uuid::Uuid;
 
-use super::messages::{ClientMessage, ServerMessage, ServerPayload};
+use super::messages::{ClientMessage, ServerMessage, ServerPayload, SubscriptionRequest};
 use super::rate_limit::MessageRateLimiter;
 use super::registry::{ConnId, Subscription, SubscriptionRegistry};
 
@@ -118,6 +118,7 @@ pub async fn run_connection(
                             let err = ServerMessage::now(ServerPayload::Error {
                                 code: "rate_limit_exceeded".into(),
                                 message: "Message rate limit exceeded (60/min). Connection closing.".into(),
+                                request_id: None,
                             });
                             if let Ok(json) = serde_json::to_string(&err) {
                                 let _ = socket.send(Message::Text(json)).await;
@@ -139,15 +140,14 @@ pub async fn run_connection(
                                 let reply = classify_parse_error(&text);
                                 send_server_message(&mut socket, reply).await;
                             }
-                            Ok(ClientMessage::Subscribe { subscription }) => {
+                            Ok(ClientMessage::Subscribe { subscription, request_id }) => {
                                 handle_subscribe(
                                     &mut socket,
                                     conn_id,
                                     &registry,
                                     tx_for_registry.clone(),
-                                    subscription.base,
-                                    subscription.quote,
-                                    subscription.amount,
+                                    subscription,
+                                    request_id,
                                 )
                                 .await;
                             }
@@ -282,15 +282,21 @@ fn classify_parse_error(raw: &str) -> ServerMessage {
         if let Some(action) = v.get("action").and_then(|a| a.as_str()) {
             if action == "subscribe" || action == "unsubscribe" {
                 // Recognised action but malformed body.
+                let request_id = v
+                    .get("request_id")
+                    .and_then(|id| id.as_str())
+                    .map(str::to_owned);
                 return ServerMessage::now(ServerPayload::Error {
                     code: "invalid_subscription".into(),
                     message: "Subscription object is missing or malformed.".into(),
+                    request_id,
                 });
             }
             // Unrecognised action value.
             return ServerMessage::now(ServerPayload::Error {
                 code: "unknown_action".into(),
                 message: format!("Unknown action: '{action}'."),
+                request_id: None,
             });
         }
     }
@@ -298,25 +304,26 @@ fn classify_parse_error(raw: &str) -> ServerMessage {
     ServerMessage::now(ServerPayload::Error {
         code: "unknown_action".into(),
         message: "Could not parse client message.".into(),
+        request_id: None,
     })
 }
 
-/// Handle a `subscribe` action from the client.
+/// Handle a `subscribe` action from the client. The reply echoes the
+/// client's `request_id`.
 async fn handle_subscribe(
     socket: &mut WebSocket,
     conn_id: ConnId,
     registry: &Arc<RwLock<SubscriptionRegistry>>,
     tx: mpsc::Sender<ServerMessage>,
-    base: String,
-    quote: String,
-    amount: Option<String>,
+    subscription: SubscriptionRequest,
+    request_id: Option<String>,
 ) {
     let sub_id: Uuid = Uuid::new_v4();
     let sub = Subscription {
         id: sub_id,
-        base,
-        quote,
-        amount,
+        base: subscription.base,
+        quote: subscription.quote,
+        amount: subscription.amount,
         last_emitted_price: None,
     };
 
@@ -325,14 +332,17 @@ async fn handle_subscribe(
     let reply = match result {
         Ok(()) => ServerMessage::now(ServerPayload::SubscriptionConfirmed {
             subscription_id: sub_id,
+            request_id,
         }),
         Err("subscription_limit_exceeded") => ServerMessage::now(ServerPayload::Error {
             code: "subscription_limit_exceeded".into(),
             message: "Maximum of 20 subscriptions per connection reached.".into(),
+            request_id,
         }),
         Err(other) => ServerMessage::now(ServerPayload::Error {
             code: other.into(),
             message: "Failed to register subscription.".into(),
+            request_id,
         }),
     };
 
@@ -0,0 +1,454 @@
+//! Cross-replica fan-out of the quote stream through Redis.
+//!
+//! One replica at a time holds [`LEADER_KEY`] and runs the broadcaster for
+//! everyone. Each replica advertises the pairs and amounts its clients
+//! subscribe to in the [`DEMAND_KEY`] hash, and forwards the updates the
+//! leader publishes on `ws:quotes:{base}:{quote}` to its own subscribers.
+
+use futures_util::StreamExt;
+use redis::{AsyncCommands, RedisError, Script};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::cache::CacheManager;
+
+use super::broadcaster::{deliver_update, PairUpdate};
+use super::registry::{Demand, SubscriptionRegistry};
+
+/// Lock held by the replica that runs the broadcaster
+pub const LEADER_KEY: &str = "ws:broadcaster:leader";
+
+/// Hash of each replica's subscribed pairs and amounts, by instance id
+pub const DEMAND_KEY: &str = "ws:demand";
+
+/// Pattern matching every per-pair update channel
+pub const UPDATES_CHANNEL_PATTERN: &str = "ws:quotes:*";
+
+/// Shortest lifetime of the leader lock and of advertised demand, so a
+/// short poll interval does not make leadership flap on a slow Redis call
+const MIN_LEASE: Duration = Duration::from_secs(5);
+
+/// Delay before resubscribing after the update subscription drops
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Extends the lock only if this instance still holds it
+const RENEW_SCRIPT: &str = r#"
+if redis.call('get', KEYS[1]) == ARGV[1] then
+    return redis.call('pexpire', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// Channel the updates of one pair are published on
+pub fn updates_channel(base: &str, quote: &str) -> String {
+    format!("ws:quotes:{}:{}", base, quote)
+}
+
+/// What one replica stores under its field of [`DEMAND_KEY`]
+#[derive(Debug, Serialize, Deserialize)]
+struct DemandEntry {
+    updated_at_ms: i64,
+    pairs: Vec<PairDemand>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PairDemand {
+    base: String,
+    quote: String,
+    amounts: Vec<Option<String>>,
+}
+
+/// One replica's side of the Redis fan-out
+pub struct RedisFanout {
+    cache: CacheManager,
+    instance_id: String,
+    poll_interval: Duration,
+    /// How long the leader lock and advertised demand outlive their last refresh
+    lease: Duration,
+    is_leader: AtomicBool,
+    redis_available: AtomicBool,
+}
+
+impl RedisFanout {
+    pub fn new(cache: CacheManager, poll_interval_ms: u64) -> Arc<Self> {
+        let poll_interval = Duration::from_millis(poll_interval_ms);
+        Arc::new(Self {
+            cache,
+            instance_id: Uuid::new_v4().to_string(),
+            poll_interval,
+            lease: (poll_interval * 3).max(MIN_LEASE),
+            is_leader: AtomicBool::new(false),
+            redis_available: AtomicBool::new(true),
+        })
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Whether this replica held the leader lock at its last refresh
+    pub fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::Acquire)
+    }
+
+    /// Whether Redis answered this replica's last leadership refresh. While
+    /// it does not, nobody can lead, so the replica broadcasts to its own
+    /// subscribers instead.
+    pub fn is_available(&self) -> bool {
+        self.redis_available.load(Ordering::Acquire)
+    }
+
+    /// Renew the leader lock if this replica holds it, or take it if it is free.
+    /// Returns whether this replica now leads.
+    pub async fn refresh_leadership(&self) -> Result<bool, RedisError> {
+        let result = self.try_lead().await;
+        let available = result.is_ok();
+        if self.redis_available.swap(available, Ordering::AcqRel) != available {
+            if available {
+                info!(instance = %self.instance_id, "Redis reachable again; resuming quote stream fan-out");
+            } else {
+                warn!(instance = %self.instance_id, "Redis unreachable; broadcasting quotes locally");
+            }
+        }
+        let leading = *result.as_ref().unwrap_or(&false);
+        if self.is_leader.swap(leading, Ordering::AcqRel) != leading {
+            if leading {
+                info!(instance = %self.instance_id, "Became quote stream broadcaster");
+            } else {
+                info!(instance = %self.instance_id, "No longer quote stream broadcaster");
+            }
+        }
+        result
+    }
+
+    async fn try_lead(&self) -> Result<bool, RedisError> {
+        let mut conn = self.cache.connection();
+        let lease_ms = self.lease.as_millis() as u64;
+
+        // Renew by lock value rather than the local flag: after a failed
+        // refresh the flag is cleared while this replica's lock may still be
+        // live, and `SET NX` alone would then lock everyone out until it expires
+        let renewed: i64 = Script::new(RENEW_SCRIPT)
+            .key(LEADER_KEY)
+            .arg(&self.instance_id)
+            .arg(lease_ms)
+            .invoke_async(&mut conn)
+            .await?;
+        if renewed == 1 {
+            return Ok(true);
+        }
+
+        let acquired: Option<String> = redis::cmd("SET")
+            .arg(LEADER_KEY)
+            .arg(&self.instance_id)
+            .arg("NX")
+            .arg("PX")
+            .arg(lease_ms)
+            .query_async(&mut conn)
+            .await?;
+        Ok(acquired.is_some())
+    }
+
+    /// Publish this replica's subscribed pairs and amounts for the leader
+    pub async fn advertise_demand(&self, demand: &Demand) -> Result<(), RedisError> {
+        let mut conn = self.cache.connection();
+        if demand.is_empty() {
+            return conn.hdel(DEMAND_KEY, &self.instance_id).await;
+        }
+
+        let entry = DemandEntry {
+            updated_at_ms: chrono::Utc::now().timestamp_millis(),
+            pairs: demand
+                .iter()
+                .map(|((base, quote), amounts)| PairDemand {
+                    base: base.clone(),
+                    quote: quote.clone(),
+                    amounts: amounts.iter().cloned().collect(),
+                })
+                .collect(),
+        };
+        let json = serde_json::to_string(&entry).unwrap_or_default();
+        conn.hset(DEMAND_KEY, &self.instance_id, json).await
+    }
+
+    /// Union of the demand advertised by all live replicas. Entries of
+    /// replicas that stopped refreshing them are removed.
+    pub async fn read_demand(&self) -> Result<Demand, RedisError> {
+        let mut conn = self.cache.connection();
+        let entries: HashMap<String, String> = conn.hgetall(DEMAND_KEY).await?;
+        let (demand, expired) =
+            merge_demand(entries, chrono::Utc::now().timestamp_millis(), self.lease);
+        if !expired.is_empty() {
+            debug!("Dropping quote stream demand of {} replicas", expired.len());
+            let _: () = conn.hdel(DEMAND_KEY, expired).await?;
+        }
+        Ok(demand)
+    }
+
+    /// Publish an update to every replica
+    pub async fn publish(&self, update: &PairUpdate) -> Result<(), RedisError> {
+        let json = serde_json::to_string(update).unwrap_or_default();
+        self.cache
+            .connection()
+            .publish(updates_channel(&update.base, &update.quote), json)
+            .await
+    }
+
+    /// Spawn the tasks every replica runs: leader election, demand
+    /// advertisement and delivery of published updates
+    pub fn spawn_replica_tasks(self: &Arc<Self>, registry: Arc<RwLock<SubscriptionRegistry>>) {
+        let fanout = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(fanout.lease / 3);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = fanout.refresh_leadership().await {
+                    warn!("Failed to refresh quote stream leadership: {}", e);
+                }
+            }
+        });
+
+        let fanout = self.clone();
+        let demand_registry = registry.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(fanout.poll_interval);
+            loop {
+                ticker.tick().await;
+                let demand = demand_registry.read().await.demand();
+                if let Err(e) = fanout.advertise_demand(&demand).await {
+                    warn!("Failed to advertise quote stream demand: {}", e);
+                }
+            }
+        });
+
+        let fanout = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = fanout.forward_updates(&registry).await {
+                    warn!("Quote update subscription failed: {}", e);
+                }
+                sleep(RESUBSCRIBE_DELAY).await;
+            }
+        });
+    }
+
+    /// Deliver published updates to local subscribers until the
+    /// subscription drops
+    async fn forward_updates(
+        &self,
+        registry: &Arc<RwLock<SubscriptionRegistry>>,
+    ) -> Result<(), RedisError> {
+        let mut pubsub = self.cache.pubsub().await?;
+        pubsub.psubscribe(UPDATES_CHANNEL_PATTERN).await?;
+        debug!("Subscribed to {}", UPDATES_CHANNEL_PATTERN);
+
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let payload: String = match msg.get_payload() {
+                Ok(payload) => payload,
+                Err(e) => {
+                    warn!(
+                        "Unreadable quote update on {}: {}",
+                        msg.get_channel_name(),
+                        e
+                    );
+                    continue;
+                }
+            };
+            match serde_json::from_str::<PairUpdate>(&payload) {
+                Ok(update) => deliver_update(registry, &update).await,
+                Err(e) => warn!(
+                    "Malformed quote update on {}: {}",
+                    msg.get_channel_name(),
+                    e
+                ),
+            }
+        }
+        warn!("Quote update subscription closed; resubscribing");
+        Ok(())
+    }
+}
+
+/// Merge replicas' demand entries, skipping those not refreshed within
+/// `lease`. Returns the merged demand and the fields to delete.
+fn merge_demand(
+    entries: HashMap<String, String>,
+    now_ms: i64,
+    lease: Duration,
+) -> (Demand, Vec<String>) {
+    let mut demand = Demand::new();
+    let mut expired = Vec::new();
+
+    for (instance, json) in entries {
+        let entry = match serde_json::from_str::<DemandEntry>(&json) {
+            Ok(entry) if now_ms - entry.updated_at_ms <= lease.as_millis() as i64 => entry,
+            _ => {
+                expired.push(instance);
+                continue;
+            }
+        };
+        for pair in entry.pairs {
+            demand
+                .entry((pair.base, pair.quote))
+                .or_default()
+                .extend(pair.amounts);
+        }
+    }
+
+    (demand, expired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::ws::broadcaster::AmountQuote;
+    use crate::routes::ws::messages::{ServerMessage, ServerPayload};
+    use crate::routes::ws::registry::Subscription;
+    use serde_json::json;
+
+    fn entry(updated_at_ms: i64, pairs: serde_json::Value) -> String {
+        json!({ "updated_at_ms": updated_at_ms, "pairs": pairs }).to_string()
+    }
+
+    #[test]
+    fn merge_demand_unions_live_replicas_and_expires_the_rest() {
+        let entries = HashMap::from([
+            (
+                "a".to_string(),
+                entry(
+                    10_000,
+                    json!([{ "base": "native", "quote": "USDC", "amounts": [null, "5"] }]),
+                ),
+            ),
+            (
+                "b".to_string(),
+                entry(
+                    9_000,
+                    json!([{ "base": "native", "quote": "USDC", "amounts": ["5", "10"] }]),
+                ),
+            ),
+            (
+                "gone".to_string(),
+                entry(
+                    1_000,
+                    json!([{ "base": "native", "quote": "EURC", "amounts": [null] }]),
+                ),
+            ),
+            ("garbled".to_string(), "not json".to_string()),
+        ]);
+
+        let (demand, mut expired) = merge_demand(entries, 10_000, Duration::from_secs(5));
+        expired.sort();
+
+        assert_eq!(expired, vec!["garbled", "gone"]);
+        assert_eq!(demand.len(), 1);
+        let amounts = &demand[&("native".to_string(), "USDC".to_string())];
+        assert_eq!(
+            amounts.iter().cloned().collect::<Vec<_>>(),
+            vec![None, Some("10".to_string()), Some("5".to_string())]
+        );
+    }
+
+    fn redis_url() -> String {
+        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running Redis instance (set REDIS_URL)"]
+    async fn one_replica_leads_and_updates_reach_every_replica() {
+        let cache = CacheManager::new(&redis_url()).await.unwrap();
+        let mut conn = cache.connection();
+        let _: () = conn.del(&[LEADER_KEY, DEMAND_KEY]).await.unwrap();
+
+        let leader = RedisFanout::new(cache.clone(), 100);
+        let follower = RedisFanout::new(cache.clone(), 100);
+        assert!(leader.refresh_leadership().await.unwrap());
+        assert!(!follower.refresh_leadership().await.unwrap());
+        assert!(leader.refresh_leadership().await.unwrap());
+
+        // A refresh that failed cleared the flag, but the live lock is renewed
+        leader.is_leader.store(false, Ordering::Release);
+        assert!(leader.refresh_leadership().await.unwrap());
+        assert!(!follower.refresh_leadership().await.unwrap());
+
+        // The leader sees what the follower's clients subscribe to
+        let registry = SubscriptionRegistry::shared();
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<ServerMessage>(8);
+        let sub_id = Uuid::new_v4();
+        registry
+            .write()
+            .await
+            .add_subscription(
+                Uuid::new_v4(),
+                tx,
+                Subscription {
+                    id: sub_id,
+                    base: "native".to_string(),
+                    quote: "USDC".to_string(),
+                    amount: Some("5".to_string()),
+                    last_emitted_price: None,
+                },
+            )
+            .unwrap();
+        follower
+            .advertise_demand(&registry.read().await.demand())
+            .await
+            .unwrap();
+        let demand = leader.read_demand().await.unwrap();
+        assert!(demand[&("native".to_string(), "USDC".to_string())].contains(&Some("5".into())));
+
+        // An update published by the leader is delivered by the follower
+        let forwarder = follower.clone();
+        let forward_registry = registry.clone();
+        let task = tokio::spawn(async move { forwarder.forward_updates(&forward_registry).await });
+        sleep(Duration::from_millis(200)).await;
+
+        let quote = serde_json::from_value(json!({
+            "base_asset": { "asset_type": "native" },
+            "quote_asset": { "asset_type": "credit_alphanum4", "asset_code": "USDC" },
+            "amount": "5.0000000",
+            "price": "0.1000000",
+            "total": "0.5000000",
+            "quote_type": "sell",
+            "path": [],
+            "timestamp": 0
+        }))
+        .unwrap();
+        leader
+            .publish(&PairUpdate {
+                base: "native".to_string(),
+                quote: "USDC".to_string(),
+                quotes: vec![AmountQuote {
+                    amount: Some("5".to_string()),
+                    quote: Some(Box::new(quote)),
+                }],
+            })
+            .await
+            .unwrap();
+
+        let msg = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("update not delivered")
+            .unwrap();
+        match msg.payload {
+            ServerPayload::QuoteUpdate {
+                subscription_id,
+                quote,
+            } => {
+                assert_eq!(subscription_id, sub_id);
+                assert_eq!(quote.price, "0.1000000");
+            }
+            other => panic!("unexpected payload: {:?}", other),
+        }
+
+        task.abort();
+        let _: () = conn.del(&[LEADER_KEY, DEMAND_KEY]).await.unwrap();
+    }
+}
@@ -24,14 +24,20 @@ pub type SubscriptionId = Uuid;
 ///
 /// Serialized with a discriminant `action` field:
 /// ```json
-/// { "action": "subscribe", "subscription": { ... } }
+/// { "action": "subscribe", "request_id": "abc", "subscription": { ... } }
 /// { "action": "unsubscribe", "subscription_id": "uuid" }
 /// ```
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "action", rename_all = "snake_case")]
 pub enum ClientMessage {
     /// Subscribe to a trading-pair quote stream.
-    Subscribe { subscription: SubscriptionRequest },
+    Subscribe {
+        subscription: SubscriptionRequest,
+        /// Optional client-chosen ID echoed in the reply, so clients with
+        /// several subscribes in flight can tell the replies apart.
+        #[serde(default)]
+        request_id: Option<String>,
+    },
     /// Cancel an existing subscription by its ID.
     Unsubscribe { subscription_id: SubscriptionId },
 }
@@ -91,23 +97,35 @@ impl ServerMessage {
 ///
 /// Serialized with a discriminant `type` field:
 /// ```json
-/// { "type": "subscription_confirmed", "subscription_id": "uuid" }
+/// { "type": "subscription_confirmed", "subscription_id": "uuid", "request_id": "abc" }
 /// { "type": "quote_update", "subscription_id": "uuid", "quote": { ... } }
-/// { "type": "error", "code": "...", "message": "..." }
+/// { "type": "error", "code": "...", "message": "...", "request_id": "abc" }
 /// { "type": "ping" }
 /// ```
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ServerPayload {
     /// Sent after a successful subscription.
-    SubscriptionConfirmed { subscription_id: SubscriptionId },
+    SubscriptionConfirmed {
+        subscription_id: SubscriptionId,
+        /// The `request_id` of the subscribe being confirmed, if it had one.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
     /// Sent when a new quote is available for a subscription.
     QuoteUpdate {
         subscription_id: SubscriptionId,
         quote: Box<QuoteResponse>,
     },
     /// Sent when an error occurs (connection remains open unless noted).
-    Error { code: String, message: String },
+    Error {
+        code: String,
+        message: String,
+        /// The `request_id` of the subscribe being rejected. Absent for
+        /// errors that do not answer a subscribe.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
+    },
     /// Keepalive ping sent every 30 seconds.
     Ping,
 }
@@ -7,11 +7,12 @@ pub mod registry;
 pub mod connection;
 
 pub mod broadcaster;
+pub mod fanout;
 pub mod rate_limit;
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -41,6 +42,8 @@ pub struct WsState {
     pub ip_rate_limiter: Arc<Mutex<HashMap<String, Vec<Instant>>>>,
     /// Broadcaster poll interval in milliseconds (from WS_POLL_INTERVAL_MS, default 1000).
     pub poll_interval_ms: u64,
+    /// Set once the broadcaster task has been spawned for this process.
+    pub broadcaster_started: AtomicBool,
     /// Keepalive ping interval in seconds (from WS_PING_INTERVAL_SECS, default 30).
     pub ping_interval_secs: u64,
     /// Pong response timeout in seconds (from WS_PONG_TIMEOUT_SECS, default 10).
@@ -79,6 +82,7 @@ impl WsState {
             max_connections,
             ip_rate_limiter: Arc::new(Mutex::new(HashMap::new())),
             poll_interval_ms,
+            broadcaster_started: AtomicBool::new(false),
             ping_interval_secs,
             pong_timeout_secs,
             backpressure_timeout_secs,
@@ -92,7 +96,7 @@ const IP_RATE_LIMIT_PER_MINUTE: usize = 10;
 /// WebSocket upgrade handler.
 ///
 /// Checks the connection cap and per-IP rate limit before accepting the
-/// upgrade. On success, spawns the broadcaster (if not already running) and
+/// upgrade. On success, spawns the broadcaster (on the first connection) and
 /// the per-connection task.
 ///
 /// NOTE: `ConnectInfo` requires the server to be served via
@@ -167,17 +171,19 @@ pub async fn ws_handler(
     let poll_interval_ms = ws_state.poll_interval_ms;
     let state_for_broadcaster = state.clone();
     let registry_for_broadcaster = registry.clone();
+    let start_broadcaster = !ws_state.broadcaster_started.swap(true, Ordering::AcqRel);
 
     ws.on_upgrade(move |socket| async move {
-        // Spawn the broadcaster task. It is idempotent — if it is already
-        // running the extra spawn will simply race to acquire the same
-        // registry and poll alongside the existing one (harmless for now;
-        // a future task can add a once-flag).
-        tokio::spawn(broadcaster::run_broadcaster(
-            state_for_broadcaster,
-            registry_for_broadcaster,
-            poll_interval_ms,
-        ));
+        // Spawn the broadcaster task once per process. With Redis it also
+        // joins the cross-replica fan-out, so a second copy would deliver
+        // every update twice.
+        if start_broadcaster {
+            tokio::spawn(broadcaster::run_broadcaster(
+                state_for_broadcaster,
+                registry_for_broadcaster,
+                poll_interval_ms,
+            ));
+        }
 
         run_connection(socket, conn_id, registry, connection_counter).await;
     })
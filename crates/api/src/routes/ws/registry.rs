@@ -4,7 +4,7 @@
 //! connected clients. It is shared between the connection tasks and the
 //! quote broadcaster via `Arc<RwLock<SubscriptionRegistry>>`.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 
 use tokio::sync::{mpsc, RwLock};
@@ -15,6 +15,9 @@ use super::messages::{ServerMessage, SubscriptionId};
 /// Opaque connection identifier (UUID v4).
 pub type ConnId = Uuid;
 
+/// Amount filters subscribed to, per `(base, quote)` pair.
+pub type Demand = BTreeMap<(String, String), BTreeSet<Option<String>>>;
+
 /// Tracks all active subscriptions and outbound senders for every connection.
 ///
 /// Wrap in `Arc<RwLock<SubscriptionRegistry>>` for shared access across tasks.
@@ -139,6 +142,23 @@ impl SubscriptionRegistry {
         pairs
     }
 
+    /// Return every subscribed `(base, quote)` pair with the distinct amount
+    /// filters requested for it (`None` for subscriptions without one).
+    ///
+    /// This is what the broadcaster computes quotes for.
+    pub fn demand(&self) -> Demand {
+        let mut demand = Demand::new();
+        for entry in self.connections.values() {
+            for sub in &entry.subscriptions {
+                demand
+                    .entry((sub.base.clone(), sub.quote.clone()))
+                    .or_default()
+                    .insert(sub.amount.clone());
+            }
+        }
+        demand
+    }
+
     /// Update the `last_emitted_price` for a specific subscription.
     ///
     /// Called by the broadcaster after successfully sending a `QuoteUpdate`.
@@ -12,17 +12,24 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
-    cache::CacheManager,
+    cache::{CacheInvalidationManager, CacheManager},
     docs::ApiDoc,
-    error::Result,
+    error::{ApiError, Result},
     middleware::{
         api_versioning_layer, request_id_layer, EndpointConfig, RateLimitLayer, RequestId,
         REQUEST_ID_HEADER,
     },
+    quotes::{BatchIdempotencyStore, IssuedQuoteStore, QuoteSigner},
+    regions::{MultiRegionRouter, RegionRegistry},
     routes,
     state::{AppState, CachePolicy, DatabasePools},
+    txbuild::TxBuildConfig,
+    worker::RouteWorkerRuntime,
 };
 
+/// How often expired quotes are deleted from `issued_quotes`
+const ISSUED_QUOTE_PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
 /// API server configuration
 #[derive(Clone)]
 pub struct ServerConfig {
@@ -36,8 +43,16 @@ pub struct ServerConfig {
     pub enable_compression: bool,
     /// Redis URL (optional)
     pub redis_url: Option<String>,
-    /// Quote cache TTL in seconds
+    /// Base quote cache TTL in seconds, scaled by the adaptive TTL engine
     pub quote_cache_ttl_seconds: u64,
+    /// How long past their TTL cached quotes and orderbooks are served while refreshed
+    pub quote_stale_grace_ms: u64,
+    /// Run background route computation workers
+    pub enable_route_workers: bool,
+    /// Route reads across the regions in `RegionRegistry::from_env`
+    pub multi_region: bool,
+    /// Key that signs issued quotes; `None` signs with an ephemeral key
+    pub quote_signer: Option<Arc<QuoteSigner>>,
 }
 
 impl std::fmt::Debug for ServerConfig {
@@ -49,6 +64,13 @@ impl std::fmt::Debug for ServerConfig {
             .field("enable_compression", &self.enable_compression)
             .field("redis_url", &self.redis_url.as_ref().map(|_| "[REDACTED]"))
             .field("quote_cache_ttl_seconds", &self.quote_cache_ttl_seconds)
+            .field("quote_stale_grace_ms", &self.quote_stale_grace_ms)
+            .field("enable_route_workers", &self.enable_route_workers)
+            .field("multi_region", &self.multi_region)
+            .field(
+                "quote_signer",
+                &self.quote_signer.as_ref().map(|signer| signer.public_key()),
+            )
             .finish()
     }
 }
@@ -62,6 +84,10 @@ impl Default for ServerConfig {
             enable_compression: true,
             redis_url: None,
             quote_cache_ttl_seconds: 2,
+            quote_stale_grace_ms: 1000,
+            enable_route_workers: true,
+            multi_region: false,
+            quote_signer: None,
         }
     }
 }
@@ -69,6 +95,7 @@ impl Default for ServerConfig {
 /// API Server
 pub struct Server {
     config: ServerConfig,
+    state: Arc<AppState>,
     app: Router,
 }
 
@@ -77,10 +104,17 @@ impl Server {
     pub async fn new(config: ServerConfig, db: DatabasePools) -> Self {
         let cache_policy = CachePolicy {
             quote_ttl: std::time::Duration::from_secs(config.quote_cache_ttl_seconds),
+            stale_grace: std::time::Duration::from_millis(config.quote_stale_grace_ms),
+        };
+
+        let region_router = if config.multi_region {
+            Self::build_region_router(&db)
+        } else {
+            None
         };
 
         // Try to connect to Redis if URL is provided
-        let (state, rate_limit_layer) = if let Some(redis_url) = &config.redis_url {
+        let (mut state, rate_limit_layer) = if let Some(redis_url) = &config.redis_url {
             match CacheManager::new(redis_url).await {
                 Ok(cache) => {
                     info!("✅ Redis cache connected");
@@ -104,18 +138,14 @@ impl Server {
                     };
 
                     (
-                        Arc::new(AppState::with_cache_and_policy(
-                            db,
-                            cache,
-                            cache_policy.clone(),
-                        )),
+                        AppState::with_cache_and_policy(db, cache, cache_policy.clone()),
                         rate_limit,
                     )
                 }
                 Err(e) => {
                     warn!("⚠️  Redis connection failed, running without cache: {}", e);
                     (
-                        Arc::new(AppState::new_with_policy(db, cache_policy.clone())),
+                        AppState::new_with_policy(db, cache_policy.clone()),
                         RateLimitLayer::in_memory(EndpointConfig::default()),
                     )
                 }
@@ -123,14 +153,54 @@ impl Server {
         } else {
             info!("ℹ️  Running without Redis cache");
             (
-                Arc::new(AppState::new_with_policy(db, cache_policy)),
+                AppState::new_with_policy(db, cache_policy),
                 RateLimitLayer::in_memory(EndpointConfig::default()),
             )
         };
 
-        let app = Self::build_app(state, &config, rate_limit_layer);
+        if let Some(router) = region_router {
+            router.clone().start_health_checks();
+            state = state.with_region_router(router);
+        }
+        let mut state = state.with_tx_build_config(TxBuildConfig::from_env());
+        match &config.quote_signer {
+            Some(signer) => state.quote_signer = signer.clone(),
+            None => {
+                warn!("⚠️  No quote signing key configured, signing quotes with an ephemeral key")
+            }
+        }
+        let state = Arc::new(state);
+        info!("🔏 Signing quotes as {}", state.quote_signer.public_key());
+
+        let app = Self::build_app(state.clone(), &config, rate_limit_layer);
 
-        Self { config, app }
+        Self { config, state, app }
+    }
+
+    /// Build the multi-region read router, reusing the primary pool
+    fn build_region_router(db: &DatabasePools) -> Option<Arc<MultiRegionRouter>> {
+        let router = RegionRegistry::from_env()
+            .map_err(ApiError::Validation)
+            .and_then(|registry| {
+                MultiRegionRouter::with_primary_pool(registry, db.write_pool().clone())
+            });
+
+        match router {
+            Ok(router) => {
+                info!(
+                    "✅ Multi-region reads enabled ({} regions)",
+                    router.enabled_regions().len()
+                );
+                Some(Arc::new(router))
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️  Multi-region router unavailable, serving local reads: {}",
+                    e
+                );
+                None
+            }
+        }
     }
 
     /// Build the application router
@@ -218,7 +288,33 @@ impl Server {
             .await
             .expect("Failed to bind address");
 
-        axum::serve(listener, self.app).await.expect("Server error");
+        let workers = self
+            .config
+            .enable_route_workers
+            .then(|| RouteWorkerRuntime::from_state(&self.state).spawn());
+        IssuedQuoteStore::spawn_pruner(
+            self.state.db.write_pool().clone(),
+            ISSUED_QUOTE_PRUNE_INTERVAL,
+        );
+        BatchIdempotencyStore::spawn_pruner(
+            self.state.db.write_pool().clone(),
+            ISSUED_QUOTE_PRUNE_INTERVAL,
+        );
+        // Drop cached data for pairs whose liquidity changes, as the indexer reports it
+        if let Some(cache) = &self.state.cache {
+            Arc::new(CacheInvalidationManager::new(cache.clone()))
+                .spawn_listener(self.state.db.write_pool().clone());
+        }
+
+        axum::serve(listener, self.app)
+            .with_graceful_shutdown(shutdown_signal())
+            .await
+            .expect("Server error");
+
+        // Let in-flight route jobs finish so their leases are released cleanly
+        if let Some(workers) = workers {
+            workers.shutdown().await;
+        }
 
         Ok(())
     }
@@ -235,6 +331,39 @@ impl Server {
     }
 }
 
+/// Resolves on Ctrl+C or SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    info!("Shutdown signal received, draining connections");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,5 +374,7 @@ mod tests {
         assert_eq!(config.host, "127.0.0.1");
         assert_eq!(config.port, 3000);
         assert!(config.enable_cors);
+        assert!(config.enable_route_workers);
+        assert!(!config.multi_region);
     }
 }
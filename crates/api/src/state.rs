@@ -3,14 +3,17 @@
 use sqlx::PgPool;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::{sync::Arc, time::Duration};
-use tokio::sync::Mutex;
 
-use crate::cache::{CacheManager, SingleFlight};
+use crate::accounts::AccountCache;
+use crate::cache::{AdaptiveTtlConfig, AdaptiveTtlEngine, CacheManager, SingleFlight};
 
 use crate::graph::GraphManager;
 use crate::models::{QuoteResponse, RoutesResponse};
+use crate::quotes::QuoteSigner;
+use crate::regions::{DataVersionCache, MultiRegionRouter};
 use crate::replay::capture::CaptureHook;
 use crate::routes::ws::WsState;
+use crate::txbuild::TxBuildConfig;
 use stellarroute_routing::health::circuit_breaker::CircuitBreakerRegistry;
 
 use crate::worker::{JobQueue, RouteWorkerPool, WorkerPoolConfig};
@@ -37,18 +40,42 @@ impl DatabasePools {
     pub fn write_pool(&self) -> &PgPool {
         &self.primary
     }
+
+    /// Whether reads have a replica distinct from the primary
+    pub fn has_replica(&self) -> bool {
+        self.replica.is_some()
+    }
 }
 
 /// Cache policy configuration
 #[derive(Debug, Clone)]
 pub struct CachePolicy {
+    /// Base TTL the adaptive TTL engine scales by volatility and depth
     pub quote_ttl: Duration,
+    /// How long past its TTL a cached quote or orderbook is still served
+    /// while a background refresh runs
+    pub stale_grace: Duration,
 }
 
 impl Default for CachePolicy {
     fn default() -> Self {
         Self {
             quote_ttl: Duration::from_secs(2),
+            stale_grace: Duration::from_secs(1),
+        }
+    }
+}
+
+impl CachePolicy {
+    /// Adaptive TTL settings around `quote_ttl`
+    pub fn adaptive_ttl_config(&self) -> AdaptiveTtlConfig {
+        let defaults = AdaptiveTtlConfig::default();
+        let base_ttl_ms = u64::try_from(self.quote_ttl.as_millis()).unwrap_or(u64::MAX);
+        AdaptiveTtlConfig {
+            base_ttl_ms,
+            min_ttl_ms: defaults.min_ttl_ms.min(base_ttl_ms),
+            max_ttl_ms: defaults.max_ttl_ms.max(base_ttl_ms),
+            ..defaults
         }
     }
 }
@@ -59,6 +86,8 @@ pub struct CacheMetrics {
     quote_misses: AtomicU64,
     stale_quote_rejections: AtomicU64,
     stale_inputs_excluded: AtomicU64,
+    quote_stale_hits: AtomicU64,
+    liquidity_loads: AtomicU64,
 }
 
 impl Default for CacheMetrics {
@@ -68,6 +97,8 @@ impl Default for CacheMetrics {
             quote_misses: AtomicU64::new(0),
             stale_quote_rejections: AtomicU64::new(0),
             stale_inputs_excluded: AtomicU64::new(0),
+            quote_stale_hits: AtomicU64::new(0),
+            liquidity_loads: AtomicU64::new(0),
         }
     }
 }
@@ -81,6 +112,24 @@ impl CacheMetrics {
         self.quote_misses.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Count a cached quote served past its TTL while being refreshed.
+    pub fn inc_quote_stale_hit(&self) {
+        self.quote_stale_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn quote_stale_hits(&self) -> u64 {
+        self.quote_stale_hits.load(Ordering::Relaxed)
+    }
+
+    /// Count one read of quote liquidity from the database.
+    pub fn inc_liquidity_load(&self) {
+        self.liquidity_loads.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn liquidity_loads(&self) -> u64 {
+        self.liquidity_loads.load(Ordering::Relaxed)
+    }
+
     /// Increment the stale-quote-rejection counter by one.
     pub fn inc_stale_rejection(&self) {
         self.stale_quote_rejections.fetch_add(1, Ordering::Relaxed);
@@ -111,14 +160,18 @@ impl CacheMetrics {
 pub struct AppState {
     /// Database connection pool
     pub db: DatabasePools,
-    /// Redis cache manager (optional)
-    pub cache: Option<Arc<Mutex<CacheManager>>>,
+    /// Two-tier (in-process + Redis) cache (optional)
+    pub cache: Option<CacheManager>,
     /// API version
     pub version: String,
     /// Cache policy settings
     pub cache_policy: CachePolicy,
     /// Cache hit/miss counters
     pub cache_metrics: Arc<CacheMetrics>,
+    /// Quote and orderbook TTLs from live market volatility and depth
+    pub ttl_engine: Arc<AdaptiveTtlEngine>,
+    /// Background refreshes of stale cache entries, one per key
+    pub cache_refreshes: Arc<SingleFlight<()>>,
     /// Route computation worker pool
     pub worker_pool: Arc<RouteWorkerPool>,
     /// Single-flight manager for quotes to prevent stampedes
@@ -135,6 +188,16 @@ pub struct AppState {
     pub ws: Option<Arc<WsState>>,
     /// Shared circuit breaker registry for liquidity providers
     pub circuit_breaker: Arc<CircuitBreakerRegistry>,
+    /// Multi-region read router (None for single-region deployments)
+    pub region_router: Option<Arc<MultiRegionRouter>>,
+    /// Data versions recently read from each pool by regional reads
+    pub data_versions: Arc<DataVersionCache>,
+    /// Network and router settings for `/api/v1/swap/build`
+    pub tx_build: TxBuildConfig,
+    /// Key that signs every issued quote
+    pub quote_signer: Arc<QuoteSigner>,
+    /// Trustlines and balances of accounts named by `account` parameters
+    pub accounts: Arc<AccountCache>,
 }
 
 impl AppState {
@@ -144,9 +207,10 @@ impl AppState {
     }
 
     pub fn new_with_policy(db: DatabasePools, cache_policy: CachePolicy) -> Self {
-        let worker_pool = Self::create_worker_pool(db.write_pool().clone());
+        let worker_pool = Self::create_worker_pool(db.write_pool().clone(), &cache_policy);
         let graph_manager = Arc::new(GraphManager::new(db.write_pool().clone()));
         graph_manager.clone().start_sync();
+        let ttl_engine = Arc::new(AdaptiveTtlEngine::new(cache_policy.adaptive_ttl_config()));
 
         Self {
             db,
@@ -154,6 +218,8 @@ impl AppState {
             version: env!("CARGO_PKG_VERSION").to_string(),
             cache_policy,
             cache_metrics: Arc::new(CacheMetrics::default()),
+            ttl_engine,
+            cache_refreshes: Arc::new(SingleFlight::new()),
             worker_pool,
             quote_single_flight: Arc::new(SingleFlight::<
                 crate::error::Result<(QuoteResponse, bool)>,
@@ -163,6 +229,11 @@ impl AppState {
             graph_manager,
             ws: None,
             circuit_breaker: Arc::new(CircuitBreakerRegistry::default()),
+            region_router: None,
+            data_versions: Arc::new(DataVersionCache::new()),
+            tx_build: TxBuildConfig::default(),
+            quote_signer: Arc::new(QuoteSigner::ephemeral()),
+            accounts: Arc::new(AccountCache::from_env()),
         }
     }
 
@@ -176,16 +247,19 @@ impl AppState {
         cache: CacheManager,
         cache_policy: CachePolicy,
     ) -> Self {
-        let worker_pool = Self::create_worker_pool(db.write_pool().clone());
+        let worker_pool = Self::create_worker_pool(db.write_pool().clone(), &cache_policy);
         let graph_manager = Arc::new(GraphManager::new(db.write_pool().clone()));
         graph_manager.clone().start_sync();
+        let ttl_engine = Arc::new(AdaptiveTtlEngine::new(cache_policy.adaptive_ttl_config()));
 
         Self {
             db,
-            cache: Some(Arc::new(Mutex::new(cache))),
+            cache: Some(cache),
             version: env!("CARGO_PKG_VERSION").to_string(),
             cache_policy,
             cache_metrics: Arc::new(CacheMetrics::default()),
+            ttl_engine,
+            cache_refreshes: Arc::new(SingleFlight::new()),
             worker_pool,
             quote_single_flight: Arc::new(SingleFlight::<
                 crate::error::Result<(QuoteResponse, bool)>,
@@ -195,13 +269,21 @@ impl AppState {
             graph_manager,
             ws: None,
             circuit_breaker: Arc::new(CircuitBreakerRegistry::default()),
+            region_router: None,
+            data_versions: Arc::new(DataVersionCache::new()),
+            tx_build: TxBuildConfig::default(),
+            quote_signer: Arc::new(QuoteSigner::ephemeral()),
+            accounts: Arc::new(AccountCache::from_env()),
         }
     }
 
     /// Create worker pool with configuration
-    fn create_worker_pool(db: PgPool) -> Arc<RouteWorkerPool> {
+    fn create_worker_pool(db: PgPool, cache_policy: &CachePolicy) -> Arc<RouteWorkerPool> {
         let queue = JobQueue::new(db);
-        let config = WorkerPoolConfig::default();
+        let config = WorkerPoolConfig {
+            result_ttl: cache_policy.quote_ttl,
+            ..WorkerPoolConfig::default()
+        };
         Arc::new(RouteWorkerPool::new(config, queue))
     }
 
@@ -228,4 +310,32 @@ impl AppState {
         self.ws = Some(ws);
         self
     }
+
+    /// Route pairs, orderbook and quote reads across regions.
+    /// Returns a new `AppState` with the router set.
+    pub fn with_region_router(mut self, router: Arc<MultiRegionRouter>) -> Self {
+        self.region_router = Some(router);
+        self
+    }
+
+    /// Set the network and router used to build swap transactions.
+    /// Returns a new `AppState` with the config set.
+    pub fn with_tx_build_config(mut self, config: TxBuildConfig) -> Self {
+        self.tx_build = config;
+        self
+    }
+
+    /// Set the key that signs issued quotes.
+    /// Returns a new `AppState` with the signer set.
+    pub fn with_quote_signer(mut self, signer: QuoteSigner) -> Self {
+        self.quote_signer = Arc::new(signer);
+        self
+    }
+
+    /// Set where account trustlines and balances are loaded from.
+    /// Returns a new `AppState` with the cache set.
+    pub fn with_account_cache(mut self, accounts: AccountCache) -> Self {
+        self.accounts = Arc::new(accounts);
+        self
+    }
 }
@@ -0,0 +1,788 @@
+//! Unsigned swap transactions for `POST /api/v1/swap/build`
+//!
+//! Turns a quote or route returned by this API into a transaction the client
+//! only has to sign and submit:
+//!
+//! - paths through SDEX offers and classic liquidity pools become a single
+//!   `path_payment_strict_send` (sells, routes) or
+//!   `path_payment_strict_receive` (buys);
+//! - paths through Soroban AMM pools become an `invoke_host_function`
+//!   calling the router's `execute_swap`. These still need a Soroban RPC
+//!   `simulateTransaction` to attach the footprint, resource fee and auth
+//!   entries before signing.
+//!
+//! The router cannot execute SDEX hops and only supports exact-input swaps,
+//! so paths mixing both venue kinds and buys through Soroban pools are
+//! rejected. Encoding is done locally by [`xdr`]; nothing here touches the
+//! network.
+
+pub mod xdr;
+
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use stellar_strkey::Strkey;
+use stellarroute_routing::optimizer::STABLE_VENUE_TYPE;
+use stellarroute_routing::{Amount, Price, Rounding};
+
+use crate::error::{ApiError, Result};
+use crate::models::{
+    request::{SwapBuildRequest, DEFAULT_SLIPPAGE_BPS, MAX_SLIPPAGE_BPS},
+    AssetInfo, PathStep, SwapBuildResponse,
+};
+use xdr::{
+    Asset, MuxedAccount, Operation, PoolType, RouterAsset, RouterHop, RouterRoute, ScAddress,
+    SwapParams, Transaction,
+};
+
+/// `asset_type` of Soroban token contracts; the contract strkey is the
+/// asset's `asset_issuer`, as the indexer stores it
+pub const SOROBAN_ASSET_TYPE: &str = "soroban";
+/// Passphrase of the public Stellar test network
+pub const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+/// Default per-operation fee in stroops
+pub const DEFAULT_BASE_FEE: u32 = 100;
+/// Lowest per-operation fee the network accepts, in stroops
+pub const MIN_BASE_FEE: u32 = 100;
+/// Default lifetime of a built transaction
+pub const DEFAULT_TIMEOUT_SECS: u64 = 300;
+/// Longest lifetime a built transaction may be given
+pub const MAX_TIMEOUT_SECS: u64 = 86_400;
+
+/// Network and contract settings for building transactions
+#[derive(Debug, Clone)]
+pub struct TxBuildConfig {
+    /// Passphrase of the network the transactions are built for
+    pub network_passphrase: String,
+    /// Router contract (`C...`) that executes Soroban swaps; Soroban routes
+    /// cannot be built without it
+    pub router_contract: Option<String>,
+}
+
+impl Default for TxBuildConfig {
+    fn default() -> Self {
+        Self {
+            network_passphrase: TESTNET_PASSPHRASE.to_string(),
+            router_contract: None,
+        }
+    }
+}
+
+impl TxBuildConfig {
+    /// Read `STELLAR_NETWORK_PASSPHRASE` (default: testnet) and
+    /// `ROUTER_CONTRACT_ADDRESS`
+    pub fn from_env() -> Self {
+        let non_empty = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            network_passphrase: non_empty("STELLAR_NETWORK_PASSPHRASE")
+                .unwrap_or_else(|| TESTNET_PASSPHRASE.to_string()),
+            router_contract: non_empty("ROUTER_CONTRACT_ADDRESS"),
+        }
+    }
+}
+
+/// Build the unsigned transaction for `request`, valid from `now_unix` for
+/// the requested timeout
+pub fn build_swap_transaction(
+    config: &TxBuildConfig,
+    request: &SwapBuildRequest,
+    now_unix: u64,
+) -> Result<SwapBuildResponse> {
+    let source = MuxedAccount::parse(&request.source_account)?;
+    let destination = request
+        .destination
+        .as_deref()
+        .unwrap_or(&request.source_account);
+
+    let current_sequence: i64 = request.sequence.trim().parse().map_err(|_| {
+        ApiError::Validation("sequence must be the account's current sequence number".to_string())
+    })?;
+    let seq_num = current_sequence
+        .checked_add(1)
+        .filter(|_| current_sequence >= 0)
+        .ok_or_else(|| ApiError::Validation("sequence is out of range".to_string()))?;
+
+    let fee = request.base_fee.unwrap_or(DEFAULT_BASE_FEE);
+    if fee < MIN_BASE_FEE {
+        return Err(ApiError::Validation(format!(
+            "base_fee must be at least {} stroops",
+            MIN_BASE_FEE
+        )));
+    }
+
+    let timeout_secs = request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+    if timeout_secs == 0 || timeout_secs > MAX_TIMEOUT_SECS {
+        return Err(ApiError::Validation(format!(
+            "timeout_secs must be between 1 and {}",
+            MAX_TIMEOUT_SECS
+        )));
+    }
+
+    let intent = SwapIntent::from_request(request)?;
+    let legs = intent.legs()?;
+    let expected = intent.expected_outputs(&legs)?;
+    let keep_bps = 10_000 - i128::from(intent.slippage_bps);
+
+    let (operation, max_send, min_receive) = match venue_kind(&legs)? {
+        VenueKind::Classic if intent.is_buy => {
+            let send_max = intent
+                .total
+                .mul_ratio(
+                    10_000 + i128::from(intent.slippage_bps),
+                    10_000,
+                    Rounding::Up,
+                )
+                .map_err(|_| amount_too_large())?;
+            let path = legs[..legs.len() - 1]
+                .iter()
+                .rev()
+                .map(|leg| classic_asset(leg.to))
+                .collect::<Result<Vec<_>>>()?;
+            let operation = Operation::PathPaymentStrictReceive {
+                send_asset: classic_asset(intent.quote_asset)?,
+                send_max: to_i64(send_max)?,
+                destination: MuxedAccount::parse(destination)?,
+                dest_asset: classic_asset(intent.base_asset)?,
+                dest_amount: to_i64(intent.amount)?,
+                path,
+            };
+            (operation, send_max, intent.amount)
+        }
+        VenueKind::Classic => {
+            let dest_min = min_output(expected[expected.len() - 1], keep_bps)?;
+            let path = legs[..legs.len() - 1]
+                .iter()
+                .map(|leg| classic_asset(leg.to))
+                .collect::<Result<Vec<_>>>()?;
+            let operation = Operation::PathPaymentStrictSend {
+                send_asset: classic_asset(intent.base_asset)?,
+                send_amount: to_i64(intent.amount)?,
+                destination: MuxedAccount::parse(destination)?,
+                dest_asset: classic_asset(intent.quote_asset)?,
+                dest_min: to_i64(dest_min)?,
+                path,
+            };
+            (operation, intent.amount, dest_min)
+        }
+        VenueKind::Soroban if intent.is_buy => {
+            return Err(ApiError::Validation(
+                "Buy quotes through Soroban pools cannot be built: the router only \
+                 executes exact-input swaps, request a sell quote instead"
+                    .to_string(),
+            ))
+        }
+        VenueKind::Soroban => {
+            let router = config.router_contract.as_deref().ok_or_else(|| {
+                ApiError::BadRequest(
+                    "Soroban swaps are unavailable: no router contract is configured".to_string(),
+                )
+            })?;
+            let router = match Strkey::from_string(router) {
+                Ok(Strkey::Contract(contract)) => contract.0,
+                _ => {
+                    return Err(ApiError::Internal(std::sync::Arc::new(anyhow::anyhow!(
+                        "configured router '{}' is not a contract address",
+                        router
+                    ))))
+                }
+            };
+
+            let hops = legs
+                .iter()
+                .zip(&expected)
+                .map(|(leg, hop_output)| {
+                    let (pool, pool_type) = leg.soroban_pool.ok_or_else(|| {
+                        ApiError::Validation("Route hop has no Soroban pool".to_string())
+                    })?;
+                    Ok(RouterHop {
+                        source: router_asset(leg.from)?,
+                        destination: router_asset(leg.to)?,
+                        pool: pool.to_string(),
+                        pool_type,
+                        min_amount_out: min_output(*hop_output, keep_bps)?.stroops(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let estimated = expected[expected.len() - 1];
+            let min_out = min_output(estimated, keep_bps)?;
+            let params = SwapParams {
+                route: RouterRoute {
+                    hops,
+                    estimated_output: estimated.stroops(),
+                    min_output: min_out.stroops(),
+                    expires_at: 0,
+                },
+                amount_in: intent.amount.stroops(),
+                min_amount_out: min_out.stroops(),
+                recipient: ScAddress::parse(destination).map(|_| destination.to_string())?,
+                deadline: request.deadline_ledger.unwrap_or(u64::MAX),
+                not_before: 0,
+                max_price_impact_bps: 0,
+                max_execution_spread_bps: 0,
+            };
+            let operation = Operation::ExecuteSwap {
+                router,
+                sender: ScAddress::Account(source.ed25519),
+                params,
+            };
+            (operation, intent.amount, min_out)
+        }
+    };
+
+    let requires_simulation = matches!(operation, Operation::ExecuteSwap { .. });
+    let operation_name = match operation {
+        Operation::PathPaymentStrictSend { .. } => "path_payment_strict_send",
+        Operation::PathPaymentStrictReceive { .. } => "path_payment_strict_receive",
+        Operation::ExecuteSwap { .. } => "invoke_host_function",
+    };
+    let (send_asset, dest_asset) = if intent.is_buy {
+        (intent.quote_asset.clone(), intent.base_asset.clone())
+    } else {
+        (intent.base_asset.clone(), intent.quote_asset.clone())
+    };
+
+    let tx = Transaction {
+        source_account: source,
+        fee,
+        seq_num,
+        min_time: 0,
+        max_time: now_unix.saturating_add(timeout_secs),
+        operation,
+    };
+    let network_id: [u8; 32] = Sha256::digest(config.network_passphrase.as_bytes()).into();
+    let hash = Sha256::digest(tx.signature_payload(&network_id)?);
+
+    Ok(SwapBuildResponse {
+        envelope_xdr: base64::engine::general_purpose::STANDARD.encode(tx.envelope_xdr()?),
+        hash: hash.iter().map(|b| format!("{:02x}", b)).collect(),
+        network_passphrase: config.network_passphrase.clone(),
+        operation: operation_name.to_string(),
+        source_account: request.source_account.clone(),
+        sequence: seq_num.to_string(),
+        fee,
+        send_asset,
+        dest_asset,
+        max_send: max_send.to_string(),
+        min_receive: min_receive.to_string(),
+        min_time: tx.min_time,
+        max_time: tx.max_time,
+        requires_simulation,
+    })
+}
+
+/// Quote or route reduced to what the transaction needs
+struct SwapIntent<'a> {
+    base_asset: &'a AssetInfo,
+    quote_asset: &'a AssetInfo,
+    /// Base amount sold (sell) or bought (buy)
+    amount: Amount,
+    /// Quote amount paid for a buy; unused for sells
+    total: Amount,
+    is_buy: bool,
+    slippage_bps: u32,
+    path: &'a [PathStep],
+}
+
+impl<'a> SwapIntent<'a> {
+    fn from_request(request: &'a SwapBuildRequest) -> Result<Self> {
+        let intent = match (&request.quote, &request.route) {
+            (Some(quote), None) => {
+                let is_buy = match quote.quote_type.as_str() {
+                    "sell" => false,
+                    "buy" => true,
+                    other => {
+                        return Err(ApiError::Validation(format!(
+                            "Unknown quote_type '{}'",
+                            other
+                        )))
+                    }
+                };
+                Self {
+                    base_asset: &quote.base_asset,
+                    quote_asset: &quote.quote_asset,
+                    amount: parse_amount("amount", &quote.amount)?,
+                    total: parse_amount("total", &quote.total)?,
+                    is_buy,
+                    slippage_bps: request.slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_BPS),
+                    path: &quote.path,
+                }
+            }
+            (None, Some(route)) => Self {
+                base_asset: &route.base_asset,
+                quote_asset: &route.quote_asset,
+                amount: parse_amount("amount", &route.amount)?,
+                total: Amount::ZERO,
+                is_buy: false,
+                slippage_bps: request.slippage_bps.unwrap_or(route.slippage_bps),
+                path: &route.path,
+            },
+            _ => {
+                return Err(ApiError::Validation(
+                    "Provide exactly one of `quote` or `route`".to_string(),
+                ))
+            }
+        };
+
+        if !intent.amount.is_positive() {
+            return Err(ApiError::InvalidAmount(
+                "Amount must be greater than zero".to_string(),
+            ));
+        }
+        if intent.slippage_bps > MAX_SLIPPAGE_BPS {
+            return Err(ApiError::InvalidSlippage(format!(
+                "slippage_bps must be between 0 and {} (100%)",
+                MAX_SLIPPAGE_BPS
+            )));
+        }
+        Ok(intent)
+    }
+
+    /// Path steps, checked to chain from the base to the quote asset
+    fn legs(&self) -> Result<Vec<Leg<'a>>> {
+        let (Some(first), Some(last)) = (self.path.first(), self.path.last()) else {
+            return Err(ApiError::Validation("Path is empty".to_string()));
+        };
+        if first.from_asset.to_canonical() != self.base_asset.to_canonical()
+            || last.to_asset.to_canonical() != self.quote_asset.to_canonical()
+        {
+            return Err(ApiError::Validation(
+                "Path must start at the base asset and end at the quote asset".to_string(),
+            ));
+        }
+        if self
+            .path
+            .windows(2)
+            .any(|pair| pair[0].to_asset.to_canonical() != pair[1].from_asset.to_canonical())
+        {
+            return Err(ApiError::Validation(
+                "Each path step must start at the previous step's asset".to_string(),
+            ));
+        }
+        if self.path.len() > xdr::MAX_PATH_LEN + 1 {
+            return Err(ApiError::Validation(format!(
+                "Paths may have at most {} steps",
+                xdr::MAX_PATH_LEN + 1
+            )));
+        }
+
+        self.path.iter().map(Leg::parse).collect()
+    }
+
+    /// Expected output of each hop, selling `amount` along the quoted prices
+    fn expected_outputs(&self, legs: &[Leg<'_>]) -> Result<Vec<Amount>> {
+        let mut amount = self.amount;
+        legs.iter()
+            .map(|leg| {
+                amount = amount
+                    .mul_price(leg.price, Rounding::Down)
+                    .map_err(|_| amount_too_large())?;
+                Ok(amount)
+            })
+            .collect()
+    }
+}
+
+/// One path step with its venue resolved
+struct Leg<'a> {
+    from: &'a AssetInfo,
+    to: &'a AssetInfo,
+    price: Price,
+    /// Pool contract (`C...`) and kind for Soroban AMM hops; `None` for SDEX
+    /// and classic pools
+    soroban_pool: Option<(&'a str, PoolType)>,
+}
+
+impl<'a> Leg<'a> {
+    fn parse(step: &'a PathStep) -> Result<Self> {
+        let price: Price = step.price.parse().map_err(|_| {
+            ApiError::Validation(format!("Invalid path step price '{}'", step.price))
+        })?;
+        let soroban_pool = match step.source.split_once(':') {
+            None if step.source == "sdex" => None,
+            // Classic liquidity pools are identified by their 32-byte hex id
+            // and are crossed by path payments like SDEX offers
+            Some(("amm", pool))
+                if pool.len() == 64 && pool.bytes().all(|b| b.is_ascii_hexdigit()) =>
+            {
+                None
+            }
+            Some((venue @ ("amm" | STABLE_VENUE_TYPE), pool)) => match Strkey::from_string(pool) {
+                Ok(Strkey::Contract(_)) if venue == STABLE_VENUE_TYPE => {
+                    Some((pool, PoolType::AmmStable))
+                }
+                Ok(Strkey::Contract(_)) => Some((pool, PoolType::AmmConstProd)),
+                _ => {
+                    return Err(ApiError::Validation(format!(
+                        "Unknown pool '{}' in path step source",
+                        pool
+                    )))
+                }
+            },
+            _ => {
+                return Err(ApiError::Validation(format!(
+                    "Unknown path step source '{}'",
+                    step.source
+                )))
+            }
+        };
+        Ok(Self {
+            from: &step.from_asset,
+            to: &step.to_asset,
+            price,
+            soroban_pool,
+        })
+    }
+}
+
+enum VenueKind {
+    Classic,
+    Soroban,
+}
+
+fn venue_kind(legs: &[Leg<'_>]) -> Result<VenueKind> {
+    let soroban_hops = legs.iter().filter(|leg| leg.soroban_pool.is_some()).count();
+    if soroban_hops == 0 {
+        Ok(VenueKind::Classic)
+    } else if soroban_hops == legs.len() {
+        Ok(VenueKind::Soroban)
+    } else {
+        Err(ApiError::Validation(
+            "Paths mixing SDEX and Soroban pool hops cannot be executed in one transaction"
+                .to_string(),
+        ))
+    }
+}
+
+fn parse_amount(field: &str, value: &str) -> Result<Amount> {
+    value.parse().map_err(|_| {
+        ApiError::InvalidAmount(format!(
+            "{} must be a decimal string with at most 7 decimal places",
+            field
+        ))
+    })
+}
+
+fn amount_too_large() -> ApiError {
+    ApiError::InvalidAmount("Amount is too large to build a transaction for".to_string())
+}
+
+/// `amount` less the slippage tolerance; an output bound, so it rounds down
+fn min_output(amount: Amount, keep_bps: i128) -> Result<Amount> {
+    amount
+        .mul_ratio(keep_bps, 10_000, Rounding::Down)
+        .map_err(|_| amount_too_large())
+}
+
+fn to_i64(amount: Amount) -> Result<i64> {
+    i64::try_from(amount.stroops()).map_err(|_| amount_too_large())
+}
+
+fn issued_parts(asset: &AssetInfo) -> Result<Option<(String, [u8; 32])>> {
+    if asset.asset_type == SOROBAN_ASSET_TYPE {
+        return Err(ApiError::InvalidAsset(format!(
+            "Soroban token {} cannot be sent with a path payment",
+            asset.asset_issuer.as_deref().unwrap_or_default()
+        )));
+    }
+    let Some(code) = asset
+        .asset_code
+        .as_ref()
+        .filter(|_| asset.asset_type != "native")
+    else {
+        return Ok(None);
+    };
+    let issuer = asset
+        .asset_issuer
+        .as_deref()
+        .ok_or_else(|| ApiError::InvalidAsset(format!("Asset {} has no issuer", code)))?;
+    match Strkey::from_string(issuer) {
+        Ok(Strkey::PublicKeyEd25519(key)) => Ok(Some((code.clone(), key.0))),
+        _ => Err(ApiError::InvalidAsset(format!(
+            "Asset {} has an invalid issuer '{}'",
+            code, issuer
+        ))),
+    }
+}
+
+fn classic_asset(asset: &AssetInfo) -> Result<Asset> {
+    Ok(match issued_parts(asset)? {
+        None => Asset::Native,
+        Some((code, issuer)) => Asset::Credit { code, issuer },
+    })
+}
+
+fn router_asset(asset: &AssetInfo) -> Result<RouterAsset> {
+    if asset.asset_type == SOROBAN_ASSET_TYPE {
+        let contract = asset.asset_issuer.as_deref().unwrap_or_default();
+        return match Strkey::from_string(contract) {
+            Ok(Strkey::Contract(_)) => Ok(RouterAsset::Soroban(contract.to_string())),
+            _ => Err(ApiError::InvalidAsset(format!(
+                "Soroban token '{}' is not a contract address",
+                contract
+            ))),
+        };
+    }
+    Ok(match issued_parts(asset)? {
+        None => RouterAsset::Native,
+        // `issued_parts` checked the issuer is an account strkey
+        Some((code, _)) => RouterAsset::Issued {
+            issuer: asset.asset_issuer.clone().unwrap_or_default(),
+            code,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{QuoteResponse, RouteResponse};
+
+    const SOURCE: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+    const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const POOL: &str = "CBCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEJ5HZ";
+    const ROUTER: &str = "CAZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGMZTGGJH";
+
+    fn usdc() -> AssetInfo {
+        AssetInfo::credit("USDC".to_string(), Some(ISSUER.to_string()))
+    }
+
+    fn step(from: AssetInfo, to: AssetInfo, price: &str, source: &str) -> PathStep {
+        PathStep {
+            from_asset: from,
+            to_asset: to,
+            price: price.to_string(),
+            source: source.to_string(),
+            min_amount_out: None,
+        }
+    }
+
+    fn quote(quote_type: &str, path: Vec<PathStep>) -> QuoteResponse {
+        QuoteResponse {
+            base_asset: AssetInfo::native(),
+            quote_asset: usdc(),
+            amount: "100.0000000".to_string(),
+            price: "0.1000000".to_string(),
+            total: "10.0000000".to_string(),
+            quote_type: quote_type.to_string(),
+            path,
+            timestamp: 0,
+            expires_at: None,
+            source_timestamp: None,
+            ttl_seconds: None,
+            ttl_reason: None,
+            rationale: None,
+            price_impact: None,
+            exclusion_diagnostics: None,
+            data_freshness: None,
+            quote_id: None,
+            signature: None,
+            signer: None,
+            skipped_routes: Vec::new(),
+        }
+    }
+
+    fn request(quote: QuoteResponse) -> SwapBuildRequest {
+        SwapBuildRequest {
+            source_account: SOURCE.to_string(),
+            sequence: "10".to_string(),
+            destination: None,
+            slippage_bps: None,
+            base_fee: None,
+            timeout_secs: None,
+            deadline_ledger: None,
+            quote: Some(quote),
+            route: None,
+        }
+    }
+
+    fn soroban_config() -> TxBuildConfig {
+        TxBuildConfig {
+            router_contract: Some(ROUTER.to_string()),
+            ..TxBuildConfig::default()
+        }
+    }
+
+    fn build(request: &SwapBuildRequest) -> Result<SwapBuildResponse> {
+        build_swap_transaction(&soroban_config(), request, 1_700_000_000)
+    }
+
+    fn sdex_sell() -> SwapBuildRequest {
+        request(quote(
+            "sell",
+            vec![step(AssetInfo::native(), usdc(), "0.1", "sdex")],
+        ))
+    }
+
+    #[test]
+    fn uses_next_sequence_and_default_fee_and_timeout() {
+        let built = build(&sdex_sell()).unwrap();
+        assert_eq!(built.sequence, "11");
+        assert_eq!(built.fee, DEFAULT_BASE_FEE);
+        assert_eq!(built.max_time, 1_700_000_000 + DEFAULT_TIMEOUT_SECS);
+        assert_eq!(built.min_receive, "9.9500000");
+    }
+
+    #[test]
+    fn classic_pool_ids_build_path_payments() {
+        let pool = format!("amm:{}", "0f".repeat(32));
+        let built = build(&request(quote(
+            "sell",
+            vec![step(AssetInfo::native(), usdc(), "0.1", &pool)],
+        )))
+        .unwrap();
+        assert_eq!(built.operation, "path_payment_strict_send");
+        assert!(!built.requires_simulation);
+    }
+
+    #[test]
+    fn rejects_mixed_sdex_and_soroban_hops() {
+        let eurc = AssetInfo::credit("EURC".to_string(), Some(ISSUER.to_string()));
+        let mut quote = quote(
+            "sell",
+            vec![
+                step(AssetInfo::native(), eurc.clone(), "0.1", "sdex"),
+                step(eurc, usdc(), "1", &format!("amm:{POOL}")),
+            ],
+        );
+        quote.quote_asset = usdc();
+        assert!(matches!(
+            build(&request(quote)),
+            Err(ApiError::Validation(msg)) if msg.contains("mixing")
+        ));
+    }
+
+    #[test]
+    fn rejects_buys_through_soroban_pools() {
+        let quote = quote(
+            "buy",
+            vec![step(
+                AssetInfo::native(),
+                usdc(),
+                "0.1",
+                &format!("amm:{POOL}"),
+            )],
+        );
+        assert!(matches!(
+            build(&request(quote)),
+            Err(ApiError::Validation(msg)) if msg.contains("exact-input")
+        ));
+    }
+
+    #[test]
+    fn soroban_swaps_need_a_router() {
+        let quote = quote(
+            "sell",
+            vec![step(
+                AssetInfo::native(),
+                usdc(),
+                "0.1",
+                &format!("amm:{POOL}"),
+            )],
+        );
+        let request = request(quote);
+        assert!(matches!(
+            build_swap_transaction(&TxBuildConfig::default(), &request, 0),
+            Err(ApiError::BadRequest(_))
+        ));
+        let built = build(&request).unwrap();
+        assert_eq!(built.operation, "invoke_host_function");
+        assert!(built.requires_simulation);
+    }
+
+    #[test]
+    fn soroban_hops_encode_their_pool_type_and_token_assets() {
+        let envelope = |source: &str, to: AssetInfo| {
+            let mut quote = quote(
+                "sell",
+                vec![step(AssetInfo::native(), to.clone(), "0.1", source)],
+            );
+            quote.quote_asset = to;
+            let built = build(&request(quote)).unwrap();
+            base64::engine::general_purpose::STANDARD
+                .decode(built.envelope_xdr)
+                .unwrap()
+        };
+        let contains =
+            |xdr: &[u8], needle: &str| xdr.windows(needle.len()).any(|w| w == needle.as_bytes());
+
+        let const_prod = envelope(&format!("amm:{POOL}"), usdc());
+        assert!(contains(&const_prod, "AmmConstProd"));
+        assert!(!contains(&const_prod, "AmmStable"));
+
+        let token = AssetInfo {
+            asset_type: SOROBAN_ASSET_TYPE.to_string(),
+            asset_code: None,
+            asset_issuer: Some(ROUTER.to_string()),
+        };
+        let stable = envelope(&format!("{STABLE_VENUE_TYPE}:{POOL}"), token);
+        assert!(contains(&stable, "AmmStable"));
+        assert!(contains(&stable, "Soroban"));
+    }
+
+    #[test]
+    fn rejects_disconnected_paths() {
+        let eurc = AssetInfo::credit("EURC".to_string(), Some(ISSUER.to_string()));
+        let quote = quote(
+            "sell",
+            vec![
+                step(AssetInfo::native(), eurc, "0.1", "sdex"),
+                step(AssetInfo::native(), usdc(), "1", "sdex"),
+            ],
+        );
+        assert!(matches!(
+            build(&request(quote)),
+            Err(ApiError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let mut bad_sequence = sdex_sell();
+        bad_sequence.sequence = "-1".to_string();
+        assert!(matches!(build(&bad_sequence), Err(ApiError::Validation(_))));
+
+        let mut bad_source = sdex_sell();
+        bad_source.source_account = POOL.to_string();
+        assert!(matches!(build(&bad_source), Err(ApiError::Validation(_))));
+
+        let mut bad_slippage = sdex_sell();
+        bad_slippage.slippage_bps = Some(10_001);
+        assert!(matches!(
+            build(&bad_slippage),
+            Err(ApiError::InvalidSlippage(_))
+        ));
+
+        let mut low_fee = sdex_sell();
+        low_fee.base_fee = Some(99);
+        assert!(matches!(build(&low_fee), Err(ApiError::Validation(_))));
+
+        let mut both = sdex_sell();
+        both.route = Some(RouteResponse {
+            base_asset: AssetInfo::native(),
+            quote_asset: usdc(),
+            amount: "1".to_string(),
+            path: Vec::new(),
+            slippage_bps: 50,
+            timestamp: 0,
+        });
+        assert!(matches!(build(&both), Err(ApiError::Validation(_))));
+    }
+
+    #[test]
+    fn rejects_issued_assets_without_issuer() {
+        let no_issuer = AssetInfo::credit("USDC".to_string(), None);
+        let mut quote = quote(
+            "sell",
+            vec![step(AssetInfo::native(), no_issuer.clone(), "0.1", "sdex")],
+        );
+        quote.quote_asset = no_issuer;
+        assert!(matches!(
+            build(&request(quote)),
+            Err(ApiError::InvalidAsset(_))
+        ));
+    }
+}
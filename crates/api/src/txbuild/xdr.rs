@@ -0,0 +1,315 @@
+//! Minimal XDR encoder for the transactions `/api/v1/swap/build` produces.
+//!
+//! Covers only what a swap needs: a v1 `TransactionEnvelope` with time
+//! bounds, no memo and a single path payment or `invoke_host_function`
+//! operation. The router's contract types (`SwapParams`, and `SignedQuote`
+//! for signed quotes) and their `ScVal` encoding come from
+//! [`stellarroute_routing::contract`], shared with the SDK's commitment
+//! hashes.
+
+use stellar_strkey::Strkey;
+
+pub use stellarroute_routing::contract::{
+    Asset as RouterAsset, PoolType, Route as RouterRoute, RouteHop as RouterHop, SignedQuote,
+    SwapParams,
+};
+
+use crate::error::{ApiError, Result};
+
+const ENVELOPE_TYPE_TX: u32 = 2;
+
+const KEY_TYPE_ED25519: u32 = 0;
+const KEY_TYPE_MUXED_ED25519: u32 = 0x100;
+const PUBLIC_KEY_TYPE_ED25519: u32 = 0;
+
+const ASSET_TYPE_NATIVE: u32 = 0;
+const ASSET_TYPE_CREDIT_ALPHANUM4: u32 = 1;
+const ASSET_TYPE_CREDIT_ALPHANUM12: u32 = 2;
+
+const PRECOND_TIME: u32 = 1;
+const MEMO_NONE: u32 = 0;
+
+const OP_PATH_PAYMENT_STRICT_RECEIVE: u32 = 2;
+const OP_PATH_PAYMENT_STRICT_SEND: u32 = 13;
+const OP_INVOKE_HOST_FUNCTION: u32 = 24;
+const HOST_FUNCTION_TYPE_INVOKE_CONTRACT: u32 = 0;
+
+const SCV_ADDRESS: u32 = 18;
+
+const SC_ADDRESS_TYPE_ACCOUNT: u32 = 0;
+const SC_ADDRESS_TYPE_CONTRACT: u32 = 1;
+
+/// Most intermediate assets a path payment accepts
+pub const MAX_PATH_LEN: usize = 5;
+
+/// Account that can send or receive a payment (`G...` or muxed `M...`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MuxedAccount {
+    pub ed25519: [u8; 32],
+    pub id: Option<u64>,
+}
+
+impl MuxedAccount {
+    pub fn parse(strkey: &str) -> Result<Self> {
+        match Strkey::from_string(strkey) {
+            Ok(Strkey::PublicKeyEd25519(key)) => Ok(Self {
+                ed25519: key.0,
+                id: None,
+            }),
+            Ok(Strkey::MuxedAccountEd25519(muxed)) => Ok(Self {
+                ed25519: muxed.ed25519,
+                id: Some(muxed.id),
+            }),
+            _ => Err(ApiError::Validation(format!(
+                "'{}' is not a valid Stellar account (G... or M...)",
+                strkey
+            ))),
+        }
+    }
+}
+
+/// Soroban address: an account or a contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScAddress {
+    Account([u8; 32]),
+    Contract([u8; 32]),
+}
+
+impl ScAddress {
+    pub fn parse(strkey: &str) -> Result<Self> {
+        match Strkey::from_string(strkey) {
+            Ok(Strkey::PublicKeyEd25519(key)) => Ok(Self::Account(key.0)),
+            Ok(Strkey::Contract(contract)) => Ok(Self::Contract(contract.0)),
+            _ => Err(ApiError::Validation(format!(
+                "'{}' is not a valid account (G...) or contract (C...) address",
+                strkey
+            ))),
+        }
+    }
+}
+
+/// Classic asset as it appears in operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Asset {
+    Native,
+    Credit { code: String, issuer: [u8; 32] },
+}
+
+/// The single operation of a built swap transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    PathPaymentStrictSend {
+        send_asset: Asset,
+        send_amount: i64,
+        destination: MuxedAccount,
+        dest_asset: Asset,
+        dest_min: i64,
+        path: Vec<Asset>,
+    },
+    PathPaymentStrictReceive {
+        send_asset: Asset,
+        send_max: i64,
+        destination: MuxedAccount,
+        dest_asset: Asset,
+        dest_amount: i64,
+        path: Vec<Asset>,
+    },
+    /// `router.execute_swap(sender, params)`, without auth entries
+    ExecuteSwap {
+        router: [u8; 32],
+        sender: ScAddress,
+        params: SwapParams,
+    },
+}
+
+/// Unsigned transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub source_account: MuxedAccount,
+    pub fee: u32,
+    pub seq_num: i64,
+    pub min_time: u64,
+    pub max_time: u64,
+    pub operation: Operation,
+}
+
+impl Transaction {
+    /// XDR of the `Transaction` itself (no envelope)
+    pub fn to_xdr(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        write_muxed_account(&mut out, &self.source_account);
+        write_u32(&mut out, self.fee);
+        out.extend_from_slice(&self.seq_num.to_be_bytes());
+        write_u32(&mut out, PRECOND_TIME);
+        write_u64(&mut out, self.min_time);
+        write_u64(&mut out, self.max_time);
+        write_u32(&mut out, MEMO_NONE);
+        // operations<100>: one operation without its own source account
+        write_u32(&mut out, 1);
+        write_u32(&mut out, 0);
+        write_operation(&mut out, &self.operation)?;
+        // ext: v0 (Soroban data is attached after simulation)
+        write_u32(&mut out, 0);
+        Ok(out)
+    }
+
+    /// XDR of a v1 `TransactionEnvelope` with no signatures
+    pub fn envelope_xdr(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        write_u32(&mut out, ENVELOPE_TYPE_TX);
+        out.extend_from_slice(&self.to_xdr()?);
+        write_u32(&mut out, 0);
+        Ok(out)
+    }
+
+    /// `TransactionSignaturePayload` bytes whose SHA-256 is the transaction hash
+    pub fn signature_payload(&self, network_id: &[u8; 32]) -> Result<Vec<u8>> {
+        let mut out = network_id.to_vec();
+        write_u32(&mut out, ENVELOPE_TYPE_TX);
+        out.extend_from_slice(&self.to_xdr()?);
+        Ok(out)
+    }
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn write_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// XDR variable-length opaque: length, bytes, zero padding to 4 bytes
+fn write_opaque(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_u32(out, bytes.len() as u32);
+    out.extend_from_slice(bytes);
+    out.resize(out.len() + (4 - bytes.len() % 4) % 4, 0);
+}
+
+fn write_account_id(out: &mut Vec<u8>, key: &[u8; 32]) {
+    write_u32(out, PUBLIC_KEY_TYPE_ED25519);
+    out.extend_from_slice(key);
+}
+
+fn write_muxed_account(out: &mut Vec<u8>, account: &MuxedAccount) {
+    match account.id {
+        None => {
+            write_u32(out, KEY_TYPE_ED25519);
+            out.extend_from_slice(&account.ed25519);
+        }
+        Some(id) => {
+            write_u32(out, KEY_TYPE_MUXED_ED25519);
+            write_u64(out, id);
+            out.extend_from_slice(&account.ed25519);
+        }
+    }
+}
+
+fn write_asset(out: &mut Vec<u8>, asset: &Asset) -> Result<()> {
+    match asset {
+        Asset::Native => write_u32(out, ASSET_TYPE_NATIVE),
+        Asset::Credit { code, issuer } => {
+            let bytes = code.as_bytes();
+            if bytes.is_empty() || bytes.len() > 12 || !bytes.iter().all(u8::is_ascii_alphanumeric)
+            {
+                return Err(ApiError::InvalidAsset(format!(
+                    "'{}' is not a valid asset code",
+                    code
+                )));
+            }
+            let width = if bytes.len() <= 4 {
+                write_u32(out, ASSET_TYPE_CREDIT_ALPHANUM4);
+                4
+            } else {
+                write_u32(out, ASSET_TYPE_CREDIT_ALPHANUM12);
+                12
+            };
+            out.extend_from_slice(bytes);
+            out.resize(out.len() + width - bytes.len(), 0);
+            write_account_id(out, issuer);
+        }
+    }
+    Ok(())
+}
+
+fn write_path(out: &mut Vec<u8>, path: &[Asset]) -> Result<()> {
+    if path.len() > MAX_PATH_LEN {
+        return Err(ApiError::Validation(format!(
+            "Path payments allow at most {} intermediate assets",
+            MAX_PATH_LEN
+        )));
+    }
+    write_u32(out, path.len() as u32);
+    for asset in path {
+        write_asset(out, asset)?;
+    }
+    Ok(())
+}
+
+fn write_operation(out: &mut Vec<u8>, operation: &Operation) -> Result<()> {
+    match operation {
+        Operation::PathPaymentStrictSend {
+            send_asset,
+            send_amount,
+            destination,
+            dest_asset,
+            dest_min,
+            path,
+        } => {
+            write_u32(out, OP_PATH_PAYMENT_STRICT_SEND);
+            write_asset(out, send_asset)?;
+            out.extend_from_slice(&send_amount.to_be_bytes());
+            write_muxed_account(out, destination);
+            write_asset(out, dest_asset)?;
+            out.extend_from_slice(&dest_min.to_be_bytes());
+            write_path(out, path)
+        }
+        Operation::PathPaymentStrictReceive {
+            send_asset,
+            send_max,
+            destination,
+            dest_asset,
+            dest_amount,
+            path,
+        } => {
+            write_u32(out, OP_PATH_PAYMENT_STRICT_RECEIVE);
+            write_asset(out, send_asset)?;
+            out.extend_from_slice(&send_max.to_be_bytes());
+            write_muxed_account(out, destination);
+            write_asset(out, dest_asset)?;
+            out.extend_from_slice(&dest_amount.to_be_bytes());
+            write_path(out, path)
+        }
+        Operation::ExecuteSwap {
+            router,
+            sender,
+            params,
+        } => {
+            write_u32(out, OP_INVOKE_HOST_FUNCTION);
+            write_u32(out, HOST_FUNCTION_TYPE_INVOKE_CONTRACT);
+            write_sc_address(out, &ScAddress::Contract(*router));
+            write_opaque(out, b"execute_swap");
+            // args<>: sender, params
+            write_u32(out, 2);
+            write_u32(out, SCV_ADDRESS);
+            write_sc_address(out, sender);
+            out.extend_from_slice(&params.to_xdr()?);
+            // auth<>: filled in by simulation
+            write_u32(out, 0);
+            Ok(())
+        }
+    }
+}
+
+fn write_sc_address(out: &mut Vec<u8>, address: &ScAddress) {
+    match address {
+        ScAddress::Account(key) => {
+            write_u32(out, SC_ADDRESS_TYPE_ACCOUNT);
+            write_account_id(out, key);
+        }
+        ScAddress::Contract(id) => {
+            write_u32(out, SC_ADDRESS_TYPE_CONTRACT);
+            out.extend_from_slice(id);
+        }
+    }
+}
@@ -0,0 +1,588 @@
+//! Background executor that drains the route computation queue
+
+use chrono::Utc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use stellarroute_routing::error::RoutingError;
+use stellarroute_routing::optimizer::{HybridOptimizer, OptimizerDiagnostics};
+use stellarroute_routing::pathfinder::LiquidityEdge;
+use stellarroute_routing::policy::RoutingPolicy;
+use stellarroute_routing::{Amount, Price, Rounding};
+
+use super::{
+    job::{RouteComputationJob, RouteComputationResult, RouteComputationTaskPayload},
+    pool::RouteWorkerPool,
+};
+use crate::{
+    cache::{self, CacheManager},
+    graph::GraphManager,
+    state::AppState,
+};
+
+/// Why a job attempt failed. `code` is matched against the retry strategy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobFailure {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl JobFailure {
+    fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Spawns `num_workers` loops that claim jobs from the [`RouteWorkerPool`],
+/// run the optimizer against the in-memory graph and cache the results.
+pub struct RouteWorkerRuntime {
+    pool: Arc<RouteWorkerPool>,
+    graph: Arc<GraphManager>,
+    cache: Option<CacheManager>,
+}
+
+impl RouteWorkerRuntime {
+    pub fn new(
+        pool: Arc<RouteWorkerPool>,
+        graph: Arc<GraphManager>,
+        cache: Option<CacheManager>,
+    ) -> Self {
+        Self { pool, graph, cache }
+    }
+
+    /// Build a runtime that shares the pool, graph and cache of `state`
+    pub fn from_state(state: &AppState) -> Self {
+        Self::new(
+            state.worker_pool.clone(),
+            state.graph_manager.clone(),
+            state.cache.clone(),
+        )
+    }
+
+    /// Start the workers and the lease maintenance task
+    pub fn spawn(self) -> RouteWorkerHandle {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let runtime = Arc::new(self);
+        let config = runtime.pool.config();
+        let instance = Uuid::new_v4().simple().to_string();
+
+        info!(
+            "Starting {} route workers (lease={:?}, poll={:?})",
+            config.num_workers, config.lease_duration, config.poll_interval
+        );
+
+        let mut tasks = Vec::with_capacity(config.num_workers + 1);
+        for idx in 0..config.num_workers {
+            let worker_id = format!("{}-{}", &instance[..8], idx);
+            tasks.push(tokio::spawn(
+                runtime.clone().run_worker(worker_id, shutdown_rx.clone()),
+            ));
+        }
+        tasks.push(tokio::spawn(runtime.run_maintenance(shutdown_rx)));
+
+        RouteWorkerHandle { shutdown_tx, tasks }
+    }
+
+    async fn run_worker(self: Arc<Self>, worker_id: String, mut shutdown: watch::Receiver<bool>) {
+        let poll_interval = self.pool.config().poll_interval;
+        debug!("Route worker {} started", worker_id);
+
+        while !*shutdown.borrow() {
+            match self.pool.get_next_job(&worker_id).await {
+                // An in-flight job always runs to completion so its lease is released
+                Ok(Some(job)) => self.process(&worker_id, job).await,
+                Ok(None) => wait_or_shutdown(poll_interval, &mut shutdown).await,
+                Err(e) => {
+                    warn!("Route worker {} failed to claim job: {}", worker_id, e);
+                    wait_or_shutdown(poll_interval, &mut shutdown).await;
+                }
+            }
+        }
+
+        debug!("Route worker {} stopped", worker_id);
+    }
+
+    /// Periodically fail abandoned jobs and expire dedup entries
+    async fn run_maintenance(self: Arc<Self>, mut shutdown: watch::Receiver<bool>) {
+        let interval = self.pool.config().lease_duration;
+
+        while !*shutdown.borrow() {
+            wait_or_shutdown(interval, &mut shutdown).await;
+
+            if let Err(e) = self.pool.reap_abandoned().await {
+                warn!("Failed to reap abandoned route jobs: {}", e);
+            }
+            let _ = self.pool.cleanup().await;
+        }
+    }
+
+    async fn process(&self, worker_id: &str, job: RouteComputationJob) {
+        let job_key = job.id.as_hash_key();
+        let lease = self.pool.config().lease_duration;
+        let edges = self.graph.get_edges();
+        let payload = job.payload.clone();
+        let ttl_millis = self.pool.config().result_ttl.as_millis() as i64;
+        let key = job_key.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = cancel.clone();
+
+        // Finish well inside the lease so another worker never reclaims a live
+        // job. The timeout only stops waiting, so it also raises the flag the
+        // computation checks, freeing the blocking thread.
+        let outcome = match tokio::time::timeout(
+            lease / 2,
+            tokio::task::spawn_blocking(move || {
+                compute_route(&key, &payload, &edges, ttl_millis, &flag)
+            }),
+        )
+        .await
+        {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(e)) => Err(JobFailure::new(
+                "internal_error",
+                format!("route computation panicked: {}", e),
+            )),
+            Err(_) => {
+                cancel.store(true, Ordering::Relaxed);
+                Err(JobFailure::new(
+                    "timeout",
+                    format!("route computation exceeded {:?}", lease / 2),
+                ))
+            }
+        };
+
+        let reported = match outcome {
+            Ok(result) => {
+                self.cache_result(&job_key, &result).await;
+                match serde_json::to_value(&result) {
+                    Ok(value) => self.pool.mark_success(&job, worker_id, &value).await,
+                    Err(e) => {
+                        self.pool
+                            .mark_failure(job, worker_id, "internal_error", &e.to_string())
+                            .await
+                    }
+                }
+            }
+            Err(failure) => {
+                self.pool
+                    .mark_failure(job, worker_id, failure.code, &failure.message)
+                    .await
+            }
+        };
+
+        if let Err(e) = reported {
+            // The lease expires and the job is reclaimed by another worker
+            error!("Failed to record outcome of route job {}: {}", job_key, e);
+        }
+    }
+
+    async fn cache_result(&self, job_key: &str, result: &RouteComputationResult) {
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache
+                .set(
+                    &cache::keys::route_job_result(job_key),
+                    result,
+                    self.pool.config().result_ttl,
+                )
+                .await
+            {
+                warn!("Failed to cache result of route job {}: {}", job_key, e);
+            }
+        }
+    }
+}
+
+/// Handle to running route workers
+pub struct RouteWorkerHandle {
+    shutdown_tx: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl RouteWorkerHandle {
+    /// Stop claiming new jobs and wait for in-flight jobs to finish
+    pub async fn shutdown(self) {
+        info!("Stopping route workers");
+        let _ = self.shutdown_tx.send(true);
+        for task in self.tasks {
+            if let Err(e) = task.await {
+                error!("Route worker task failed: {}", e);
+            }
+        }
+        info!("Route workers stopped");
+    }
+}
+
+async fn wait_or_shutdown(delay: Duration, shutdown: &mut watch::Receiver<bool>) {
+    tokio::select! {
+        _ = tokio::time::sleep(delay) => {}
+        _ = shutdown.changed() => {}
+    }
+}
+
+/// Run the optimizer for a single job against a graph snapshot
+///
+/// A sell routes `amount` of the base asset into the quote asset. A buy
+/// searches for the least quote asset that routes into `amount` of the base
+/// asset. `cancel` is checked between optimizer runs and between candidate
+/// paths, so a job the caller stopped waiting for gives up its thread.
+pub fn compute_route(
+    job_key: &str,
+    payload: &RouteComputationTaskPayload,
+    edges: &[LiquidityEdge],
+    ttl_millis: i64,
+    cancel: &Arc<AtomicBool>,
+) -> Result<RouteComputationResult, JobFailure> {
+    if !payload.amount.is_positive() {
+        return Err(JobFailure::new(
+            "invalid_params",
+            format!("amount must be positive, got {}", payload.amount),
+        ));
+    }
+    if payload.slippage_bps > 10_000 {
+        return Err(JobFailure::new(
+            "invalid_params",
+            format!(
+                "slippage_bps must be at most 10000, got {}",
+                payload.slippage_bps
+            ),
+        ));
+    }
+
+    // The graph is empty until its first sync; retry rather than fail
+    if edges.is_empty() {
+        return Err(JobFailure::new(
+            "service_unavailable",
+            "routing graph not loaded",
+        ));
+    }
+
+    let mut routing_policy = RoutingPolicy::default();
+    if let Some(max_hops) = payload.max_hops {
+        routing_policy.max_hops = max_hops;
+    }
+    let optimizer = HybridOptimizer::default().with_cancellation(cancel.clone());
+    let search = RouteSearch {
+        optimizer: &optimizer,
+        edges,
+        policy: &routing_policy,
+        cancel,
+    };
+
+    let amount_e7 = payload.amount.stroops();
+    let bps = i128::from(payload.slippage_bps);
+    // Outputs round down and inputs round up, as in synchronous quotes
+    let (diag, total_e7, rounding, limit_bps) = match payload.quote_type.as_str() {
+        "sell" => {
+            let diag = search.run(&payload.base_asset, &payload.quote_asset, amount_e7)?;
+            let total = diag.metrics.output_amount;
+            // Least received
+            (diag, total, Rounding::Down, 10_000 - bps)
+        }
+        "buy" => {
+            let (diag, input) =
+                search.input_for_output(&payload.quote_asset, &payload.base_asset, amount_e7)?;
+            // Most paid
+            (diag, input, Rounding::Up, 10_000 + bps)
+        }
+        other => {
+            return Err(JobFailure::new(
+                "invalid_params",
+                format!("quote_type must be 'sell' or 'buy', got '{}'", other),
+            ))
+        }
+    };
+
+    let total = Amount::from_stroops(total_e7);
+    let overflow = |e: RoutingError| JobFailure::new("invalid_params", e.to_string());
+    let slippage_limit = total
+        .mul_ratio(limit_bps, 10_000, rounding)
+        .map_err(overflow)?;
+    let price = Price::from_ratio(total_e7, amount_e7, rounding).map_err(overflow)?;
+
+    Ok(RouteComputationResult {
+        job_id: job_key.to_string(),
+        quote_type: payload.quote_type.clone(),
+        price,
+        total,
+        slippage_limit,
+        impact_bps: diag.metrics.impact_bps,
+        path: diag.selected_path.hops,
+        computed_at: Utc::now(),
+        ttl_millis,
+    })
+}
+
+/// One job's optimizer runs over a fixed graph snapshot
+struct RouteSearch<'a> {
+    optimizer: &'a HybridOptimizer,
+    edges: &'a [LiquidityEdge],
+    policy: &'a RoutingPolicy,
+    cancel: &'a AtomicBool,
+}
+
+impl RouteSearch<'_> {
+    /// Best route for `amount_in` stroops of `from`
+    fn run(
+        &self,
+        from: &str,
+        to: &str,
+        amount_in: i128,
+    ) -> Result<OptimizerDiagnostics, JobFailure> {
+        if self.cancel.load(Ordering::Relaxed) {
+            return Err(cancelled());
+        }
+        self.optimizer
+            .find_optimal_routes(from, to, self.edges, amount_in, self.policy)
+            .map_err(|e| match e {
+                RoutingError::Cancelled => cancelled(),
+                e => JobFailure::new("no_route", e.to_string()),
+            })
+    }
+
+    /// Least input of `from` whose best route yields at least `amount_out` of
+    /// `to`, with that route
+    fn input_for_output(
+        &self,
+        from: &str,
+        to: &str,
+        amount_out: i128,
+    ) -> Result<(OptimizerDiagnostics, i128), JobFailure> {
+        let output = |amount_in: i128| -> Result<Option<OptimizerDiagnostics>, JobFailure> {
+            match self.run(from, to, amount_in) {
+                Ok(diag) => Ok(Some(diag)),
+                // Too large for the available liquidity or the impact limit
+                Err(failure) if failure.code == "no_route" => Ok(None),
+                Err(failure) => Err(failure),
+            }
+        };
+
+        // Start from the marginal rate at the target size, then double until
+        // the route fills the order
+        let mut high = match output(amount_out)? {
+            Some(probe) => {
+                let probe_out = probe.metrics.output_amount.max(1);
+                amount_out
+                    .checked_mul(amount_out)
+                    .map_or(amount_out, |n| n / probe_out)
+                    .max(1)
+            }
+            None => amount_out,
+        };
+        let mut best = loop {
+            match output(high)? {
+                Some(diag) if diag.metrics.output_amount >= amount_out => break diag,
+                _ => {}
+            }
+            high = high.checked_mul(2).ok_or_else(|| {
+                JobFailure::new("no_route", "not enough liquidity to fill the order")
+            })?;
+            if high > MAX_BUY_INPUT {
+                return Err(JobFailure::new(
+                    "no_route",
+                    "not enough liquidity to fill the order",
+                ));
+            }
+        };
+
+        // Narrow to the smallest input that still fills it
+        let mut low = 0;
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            match output(mid)? {
+                Some(diag) if diag.metrics.output_amount >= amount_out => {
+                    high = mid;
+                    best = diag;
+                }
+                _ => low = mid,
+            }
+        }
+        Ok((best, high))
+    }
+}
+
+/// Largest input a buy search tries, in stroops (the i64 amount range)
+const MAX_BUY_INPUT: i128 = i64::MAX as i128;
+
+fn cancelled() -> JobFailure {
+    JobFailure::new("timeout", "route computation cancelled")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(base: &str, quote: &str, amount: &str) -> RouteComputationTaskPayload {
+        RouteComputationTaskPayload {
+            base_asset: base.to_string(),
+            quote_asset: quote.to_string(),
+            base_asset_id: Uuid::nil(),
+            quote_asset_id: Uuid::nil(),
+            amount: amount.parse().unwrap(),
+            slippage_bps: 50,
+            quote_type: "sell".to_string(),
+            max_hops: None,
+        }
+    }
+
+    fn edge(from: &str, to: &str, price: &str) -> LiquidityEdge {
+        LiquidityEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            venue_type: "sdex".to_string(),
+            venue_ref: "1".to_string(),
+            liquidity: 1_000_000_000_000,
+            price: price.parse().unwrap(),
+            fee_bps: 30,
+            amp: None,
+        }
+    }
+
+    #[test]
+    fn compute_route_returns_priced_result() {
+        let edges = vec![edge("native", "USDC:GISSUER", "0.1")];
+        let result = compute_route(
+            "route:native:USDC:GISSUER:100.0000000:sell",
+            &payload("native", "USDC:GISSUER", "100.0"),
+            &edges,
+            2000,
+            &Arc::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.job_id, "route:native:USDC:GISSUER:100.0000000:sell");
+        assert_eq!(result.path.len(), 1);
+        assert!(result.total.is_positive());
+        assert_eq!(
+            result.price,
+            Price::from_ratio(result.total.stroops(), 1_000_000_000, Rounding::Down).unwrap()
+        );
+        assert_eq!(result.ttl_millis, 2000);
+    }
+
+    #[test]
+    fn compute_route_limits_sell_by_slippage() {
+        let edges = vec![edge("native", "USDC:GISSUER", "0.1")];
+        let result = compute_route(
+            "k",
+            &payload("native", "USDC:GISSUER", "100.0"),
+            &edges,
+            0,
+            &Arc::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.quote_type, "sell");
+        assert!(result.slippage_limit < result.total);
+        assert_eq!(
+            result.slippage_limit,
+            result
+                .total
+                .mul_ratio(9_950, 10_000, Rounding::Down)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn compute_route_buy_prices_the_input_for_the_requested_output() {
+        let mut pool = edge("USDC:GISSUER", "native", "10");
+        pool.venue_type = "amm".to_string();
+        let edges = vec![pool];
+        let mut buy = payload("native", "USDC:GISSUER", "100.0");
+        buy.quote_type = "buy".to_string();
+
+        let result = compute_route("k", &buy, &edges, 0, &Arc::default()).unwrap();
+
+        assert_eq!(result.quote_type, "buy");
+        assert_eq!(result.path[0].source_asset, "USDC:GISSUER");
+        assert_eq!(result.path[0].destination_asset, "native");
+        // 10 USDC buys 100 XLM before fees, so slightly more is needed
+        assert!(
+            result.total > Amount::from_stroops(100_000_000)
+                && result.total < Amount::from_stroops(101_000_000),
+            "{}",
+            result.total
+        );
+        assert!(result.slippage_limit > result.total);
+
+        // One stroop less no longer fills the order
+        let short = HybridOptimizer::default()
+            .find_optimal_routes(
+                "USDC:GISSUER",
+                "native",
+                &edges,
+                result.total.stroops() - 1,
+                &RoutingPolicy::default(),
+            )
+            .unwrap();
+        assert!(short.metrics.output_amount < 1_000_000_000);
+    }
+
+    #[test]
+    fn compute_route_stops_when_cancelled() {
+        let edges = vec![edge("native", "USDC", "0.1")];
+        let cancel = Arc::new(AtomicBool::new(true));
+        let err =
+            compute_route("k", &payload("native", "USDC", "1.0"), &edges, 0, &cancel).unwrap_err();
+        assert_eq!(err.code, "timeout");
+    }
+
+    #[test]
+    fn compute_route_rejects_unknown_quote_type() {
+        let edges = vec![edge("native", "USDC", "0.1")];
+        let mut job = payload("native", "USDC", "1.0");
+        job.quote_type = "swap".to_string();
+        let err = compute_route("k", &job, &edges, 0, &Arc::default()).unwrap_err();
+        assert_eq!(err.code, "invalid_params");
+    }
+
+    #[test]
+    fn compute_route_without_graph_is_retryable() {
+        let err = compute_route(
+            "k",
+            &payload("native", "USDC", "1.0"),
+            &[],
+            0,
+            &Arc::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.code, "service_unavailable");
+        assert!(crate::worker::retry::RetryStrategy::default().is_retryable(err.code));
+    }
+
+    #[test]
+    fn compute_route_without_path_is_permanent() {
+        let edges = vec![edge("native", "USDC", "0.1")];
+        let err = compute_route(
+            "k",
+            &payload("native", "EURT", "1.0"),
+            &edges,
+            0,
+            &Arc::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.code, "no_route");
+        assert!(!crate::worker::retry::RetryStrategy::default().is_retryable(err.code));
+    }
+
+    #[test]
+    fn compute_route_rejects_non_positive_amount() {
+        let edges = vec![edge("native", "USDC", "0.1")];
+        let err = compute_route(
+            "k",
+            &payload("native", "USDC", "0.0"),
+            &edges,
+            0,
+            &Arc::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.code, "invalid_params");
+    }
+}
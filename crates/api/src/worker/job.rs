@@ -2,6 +2,8 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use stellarroute_routing::pathfinder::PathHop;
+use stellarroute_routing::{Amount, Price};
 use uuid::Uuid;
 
 /// Unique stable identifier for a route computation task
@@ -11,6 +13,10 @@ pub struct JobId {
     quote_asset: String,
     amount: String,
     quote_type: String,
+    #[serde(default)]
+    slippage_bps: Option<u32>,
+    #[serde(default)]
+    max_hops: Option<usize>,
 }
 
 impl JobId {
@@ -20,14 +26,37 @@ impl JobId {
             quote_asset: quote.to_string(),
             amount: amount.to_string(),
             quote_type: quote_type.to_string(),
+            slippage_bps: None,
+            max_hops: None,
         }
     }
 
+    /// Scope the id to a slippage tolerance, which the result's limit
+    /// depends on
+    pub fn with_slippage_bps(mut self, slippage_bps: u32) -> Self {
+        self.slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    /// Scope the id to a hop limit, so the same trade at different depths is
+    /// computed separately
+    pub fn with_max_hops(mut self, max_hops: Option<usize>) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
     pub fn as_hash_key(&self) -> String {
-        format!(
+        let mut key = format!(
             "route:{}:{}:{}:{}",
             self.base_asset, self.quote_asset, self.amount, self.quote_type
-        )
+        );
+        if let Some(bps) = self.slippage_bps {
+            key = format!("{}:s{}", key, bps);
+        }
+        match self.max_hops {
+            Some(hops) => format!("{}:h{}", key, hops),
+            None => key,
+        }
     }
 }
 
@@ -38,9 +67,13 @@ pub struct RouteComputationTaskPayload {
     pub quote_asset: String,
     pub base_asset_id: Uuid,
     pub quote_asset_id: Uuid,
-    pub amount: f64,
+    /// Exact amount, serialized as a 7-decimal string
+    pub amount: Amount,
     pub slippage_bps: u32,
     pub quote_type: String,
+    /// Hop limit for the optimizer; `None` uses the routing policy default
+    #[serde(default)]
+    pub max_hops: Option<usize>,
 }
 
 /// Route computation job
@@ -64,9 +97,11 @@ impl RouteComputationJob {
             id: JobId::new(
                 base,
                 quote,
-                &format!("{:.7}", payload.amount),
+                &payload.amount.to_string(),
                 &payload.quote_type,
-            ),
+            )
+            .with_slippage_bps(payload.slippage_bps)
+            .with_max_hops(payload.max_hops),
             payload,
             created_at: Utc::now(),
             attempt: 0,
@@ -88,8 +123,57 @@ impl RouteComputationJob {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteComputationResult {
     pub job_id: String,
-    pub price: f64,
-    pub total: f64,
+    /// `sell` (`total` is the quote asset received for `amount` of the base
+    /// asset) or `buy` (`total` is the quote asset paid for `amount`)
+    #[serde(default = "default_quote_type")]
+    pub quote_type: String,
+    /// `total` per unit of `amount`
+    pub price: Price,
+    pub total: Amount,
+    /// `total` after the job's slippage tolerance: the least received for a
+    /// sell, the most paid for a buy
+    #[serde(default)]
+    pub slippage_limit: Amount,
+    /// Estimated price impact of the selected route in basis points
+    #[serde(default)]
+    pub impact_bps: u32,
+    /// Hops of the selected route
+    #[serde(default)]
+    pub path: Vec<PathHop>,
     pub computed_at: DateTime<Utc>,
     pub ttl_millis: i64,
 }
+
+fn default_quote_type() -> String {
+    "sell".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_key_format() {
+        let id = JobId::new("native", "USDC:GISSUER", "100.0000000", "sell");
+        assert_eq!(
+            id.as_hash_key(),
+            "route:native:USDC:GISSUER:100.0000000:sell"
+        );
+    }
+
+    #[test]
+    fn test_hash_key_scoped_by_slippage() {
+        let tight = JobId::new("native", "USDC", "100", "buy").with_slippage_bps(10);
+        let loose = JobId::new("native", "USDC", "100", "buy").with_slippage_bps(100);
+        assert_eq!(tight.as_hash_key(), "route:native:USDC:100:buy:s10");
+        assert_ne!(tight.as_hash_key(), loose.as_hash_key());
+    }
+
+    #[test]
+    fn test_hash_key_scoped_by_max_hops() {
+        let shallow = JobId::new("native", "USDC", "100", "sell").with_max_hops(Some(2));
+        let deep = JobId::new("native", "USDC", "100", "sell").with_max_hops(Some(6));
+        assert_eq!(shallow.as_hash_key(), "route:native:USDC:100:sell:h2");
+        assert_ne!(shallow.as_hash_key(), deep.as_hash_key());
+    }
+}
@@ -5,15 +5,18 @@
 //! - Job deduplication
 //! - Backpressure protection
 //! - Configurable retry logic
+//! - Background executor with lease-based job claiming and graceful shutdown
 
 pub mod backpressure;
 pub mod deduplication;
+pub mod executor;
 pub mod job;
 pub mod pool;
 pub mod queue;
 pub mod retry;
 
 pub use backpressure::BackpressurePolicy;
-pub use job::{RouteComputationJob, RouteComputationTaskPayload};
+pub use executor::{RouteWorkerHandle, RouteWorkerRuntime};
+pub use job::{RouteComputationJob, RouteComputationResult, RouteComputationTaskPayload};
 pub use pool::{RouteWorkerPool, WorkerPoolConfig};
-pub use queue::JobQueue;
+pub use queue::{EnqueueOutcome, JobQueue, JobRecord};
@@ -1,7 +1,9 @@
 //! Route computation worker pool
 
 use crate::error::Result;
+use serde_json::Value;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
@@ -9,7 +11,7 @@ use super::{
     backpressure::BackpressurePolicy,
     deduplication::DeduplicationCache,
     job::{RouteComputationJob, RouteComputationTaskPayload},
-    queue::JobQueue,
+    queue::{EnqueueOutcome, JobQueue, JobRecord},
     retry::RetryStrategy,
 };
 
@@ -24,6 +26,12 @@ pub struct WorkerPoolConfig {
     pub retry_strategy: RetryStrategy,
     /// Deduplication cache TTL in seconds
     pub dedup_ttl_secs: u64,
+    /// How long a claimed job stays leased to its worker before it can be reclaimed
+    pub lease_duration: Duration,
+    /// How long an idle worker waits before polling the queue again
+    pub poll_interval: Duration,
+    /// How long a job result stays fresh; resubmitting after this recomputes it
+    pub result_ttl: Duration,
 }
 
 impl Default for WorkerPoolConfig {
@@ -33,6 +41,9 @@ impl Default for WorkerPoolConfig {
             backpressure: BackpressurePolicy::default(),
             retry_strategy: RetryStrategy::default(),
             dedup_ttl_secs: 300, // 5 minutes
+            lease_duration: Duration::from_secs(30),
+            poll_interval: Duration::from_millis(500),
+            result_ttl: Duration::from_secs(2),
         }
     }
 }
@@ -70,56 +81,91 @@ impl RouteWorkerPool {
     }
 
     /// Submit a route computation job to the queue
+    ///
+    /// Identical jobs (same [`JobId`](super::job::JobId) hash key) collapse onto
+    /// the existing queue entry, whose id is returned.
     pub async fn submit_job(
         &self,
         base: &str,
         quote: &str,
         payload: RouteComputationTaskPayload,
-    ) -> Result<()> {
+    ) -> Result<EnqueueOutcome> {
         // Check backpressure first
         let stats = self.queue.stats().await?;
-        self.config
+        if let Err(e) = self
+            .config
             .backpressure
-            .should_accept(stats.pending, stats.processing)?;
+            .should_accept(stats.pending, stats.processing)
+        {
+            let mut rejected = self.metrics.total_rejected.write().await;
+            *rejected += 1;
+            return Err(e);
+        }
 
         // Create job with retry policy
         let job =
             RouteComputationJob::new(base, quote, payload, self.config.retry_strategy.max_retries);
 
-        // Check if job already being processed (deduplication)
-        if !self.dedup.try_add(&job.id).await {
-            // Job is already being processed, increment rejected counter
-            let mut rejected = self.metrics.total_rejected.write().await;
-            *rejected += 1;
-            return Ok(()); // Don't error out, just ignore duplicate
-        }
+        // Fast path: skip the insert attempt when this instance already has it in flight
+        let first_submit = self.dedup.try_add(&job.id).await;
 
-        // Try to enqueue the job
-        match self.queue.enqueue(&job).await {
-            Ok(enqueued) => {
-                if enqueued {
-                    let mut submitted = self.metrics.total_submitted.write().await;
-                    *submitted += 1;
+        let outcome = if first_submit {
+            match self.queue.enqueue(&job, self.config.result_ttl).await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    // Remove from dedup cache on failure
+                    self.dedup.remove(&job.id).await;
+                    return Err(e);
                 }
-                Ok(())
-            }
-            Err(e) => {
-                // Remove from dedup cache on failure
-                self.dedup.remove(&job.id).await;
-                Err(e)
             }
+        } else {
+            self.queue.enqueue(&job, self.config.result_ttl).await?
+        };
+
+        if outcome.enqueued {
+            let mut submitted = self.metrics.total_submitted.write().await;
+            *submitted += 1;
         }
+
+        Ok(outcome)
+    }
+
+    /// Look up a job by its queue id
+    pub async fn get_job(&self, id: i64) -> Result<Option<JobRecord>> {
+        self.queue.get(id).await
+    }
+
+    /// Pool configuration
+    pub fn config(&self) -> &WorkerPoolConfig {
+        &self.config
     }
 
-    /// Get next job for worker processing
-    pub async fn get_next_job(&self) -> Result<Option<RouteComputationJob>> {
-        self.queue.dequeue().await
+    /// Claim the next job for `worker_id` under the configured lease
+    pub async fn get_next_job(&self, worker_id: &str) -> Result<Option<RouteComputationJob>> {
+        self.queue
+            .dequeue(worker_id, self.config.lease_duration)
+            .await
     }
 
-    /// Report successful job completion
-    pub async fn mark_success(&self, job: &RouteComputationJob) -> Result<()> {
+    /// Report successful job completion by `worker_id`
+    ///
+    /// If the worker's lease expired and the job was reclaimed, the result is
+    /// dropped so it cannot overwrite the new owner's state.
+    pub async fn mark_success(
+        &self,
+        job: &RouteComputationJob,
+        worker_id: &str,
+        result: &Value,
+    ) -> Result<()> {
         let job_key = job.id.as_hash_key();
-        self.queue.mark_completed(&job_key).await?;
+        if !self
+            .queue
+            .mark_completed(&job_key, worker_id, result)
+            .await?
+        {
+            warn!("Worker {} lost the lease on job {}", worker_id, job_key);
+            return Ok(());
+        }
         self.dedup.remove(&job.id).await;
 
         let mut completed = self.metrics.total_completed.write().await;
@@ -129,26 +175,43 @@ impl RouteWorkerPool {
     }
 
     /// Report job failure with retry logic
-    pub async fn mark_failure(&self, job: RouteComputationJob, error: &str) -> Result<()> {
+    ///
+    /// `error_code` is matched against the retry strategy; retried jobs become
+    /// claimable again after the strategy's backoff delay for the next attempt.
+    pub async fn mark_failure(
+        &self,
+        job: RouteComputationJob,
+        worker_id: &str,
+        error_code: &str,
+        message: &str,
+    ) -> Result<()> {
         let job_key = job.id.as_hash_key();
         let job_id = job.id.clone();
         let is_exhausted = job.is_exhausted();
         let attempt = job.attempt;
         let max_retries = job.max_retries;
+        let error = format!("{}: {}", error_code, message);
 
         // Check if we should retry
-        if !is_exhausted && self.config.retry_strategy.is_retryable(error) {
+        if !is_exhausted && self.config.retry_strategy.is_retryable(error_code) {
+            let delay = self.config.retry_strategy.backoff_delay(attempt + 1);
             warn!(
-                "Job {} failed (attempt {}/{}), retrying: {}",
-                job_key, attempt, max_retries, error
+                "Job {} failed (attempt {}/{}), retrying in {:?}: {}",
+                job_key, attempt, max_retries, delay, error
             );
-            self.queue.requeue(job).await?;
+            if !self.queue.requeue(job, worker_id, &error, delay).await? {
+                warn!("Worker {} lost the lease on job {}", worker_id, job_key);
+                return Ok(());
+            }
         } else {
             error!(
                 "Job {} exhausted after {} attempts: {}",
                 job_key, attempt, error
             );
-            self.queue.mark_failed(&job_key, error).await?;
+            if !self.queue.mark_failed(&job_key, worker_id, &error).await? {
+                warn!("Worker {} lost the lease on job {}", worker_id, job_key);
+                return Ok(());
+            }
 
             let mut failed = self.metrics.total_failed.write().await;
             *failed += 1;
@@ -158,6 +221,17 @@ impl RouteWorkerPool {
         Ok(())
     }
 
+    /// Fail jobs abandoned by crashed workers after their last attempt
+    pub async fn reap_abandoned(&self) -> Result<u64> {
+        let reaped = self.queue.fail_abandoned().await?;
+        if reaped > 0 {
+            warn!("Failed {} route job(s) abandoned past their lease", reaped);
+            let mut failed = self.metrics.total_failed.write().await;
+            *failed += reaped;
+        }
+        Ok(reaped)
+    }
+
     /// Get pool metrics snapshot
     pub async fn metrics(&self) -> PoolMetricsSnapshot {
         let submitted = *self.metrics.total_submitted.read().await;
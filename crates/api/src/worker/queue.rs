@@ -1,10 +1,11 @@
 //! Durable job queue using database
 
 use crate::error::{ApiError, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use sqlx::{PgPool, Row};
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::job::{RouteComputationJob, RouteComputationTaskPayload};
 
@@ -18,9 +19,16 @@ impl JobQueue {
         Self { db }
     }
 
-    /// Enqueue a new route computation job
-    /// Returns true if job was enqueued, false if it already exists
-    pub async fn enqueue(&self, job: &RouteComputationJob) -> Result<bool> {
+    /// Enqueue a new route computation job, deduplicated by job key.
+    ///
+    /// If a job with the same key exists it is returned instead, unless it has
+    /// already finished and its result is older than `refresh_after`, in which
+    /// case it is reset to `pending` for recomputation.
+    pub async fn enqueue(
+        &self,
+        job: &RouteComputationJob,
+        refresh_after: Duration,
+    ) -> Result<EnqueueOutcome> {
         let job_key = job.id.as_hash_key();
         let payload = serde_json::to_value(&job.payload).map_err(|e| {
             ApiError::Internal(Arc::new(anyhow::anyhow!(
@@ -29,41 +37,113 @@ impl JobQueue {
             )))
         })?;
 
-        // Try to insert; if it already exists, return false (deduplication)
-        let result = sqlx::query(
+        let inserted = sqlx::query(
             r#"
             INSERT INTO route_computation_jobs (
                 job_key, status, payload, attempt, max_retries, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            ON CONFLICT (job_key) DO NOTHING
+            VALUES ($1, 'pending', $2, $3, $4, $5, $6)
+            ON CONFLICT (job_key) DO UPDATE
+            SET status = 'pending', payload = EXCLUDED.payload, attempt = EXCLUDED.attempt,
+                max_retries = EXCLUDED.max_retries, error_message = NULL, result = NULL,
+                available_at = NOW(), locked_by = NULL, lease_expires_at = NULL,
+                created_at = EXCLUDED.created_at, updated_at = EXCLUDED.updated_at
+            WHERE route_computation_jobs.status IN ('completed', 'failed')
+              AND route_computation_jobs.updated_at < NOW() - make_interval(secs => $7)
+            RETURNING id, status
             "#,
         )
         .bind(&job_key)
-        .bind("pending")
         .bind(payload)
         .bind(job.attempt as i32)
         .bind(job.max_retries as i32)
         .bind(job.created_at)
         .bind(Utc::now())
-        .execute(&self.db)
+        .bind(refresh_after.as_secs_f64())
+        .fetch_optional(&self.db)
         .await
         .map_err(|e| {
             ApiError::Internal(Arc::new(anyhow::anyhow!("Failed to enqueue job: {}", e)))
         })?;
 
-        Ok(result.rows_affected() > 0)
+        let (row, enqueued) = match inserted {
+            Some(row) => (row, true),
+            None => {
+                let row =
+                    sqlx::query("SELECT id, status FROM route_computation_jobs WHERE job_key = $1")
+                        .bind(&job_key)
+                        .fetch_one(&self.db)
+                        .await
+                        .map_err(|e| {
+                            ApiError::Internal(Arc::new(anyhow::anyhow!(
+                                "Failed to look up existing job: {}",
+                                e
+                            )))
+                        })?;
+                (row, false)
+            }
+        };
+
+        Ok(EnqueueOutcome {
+            id: row.get("id"),
+            job_key,
+            status: row.get("status"),
+            enqueued,
+        })
     }
 
-    /// Get next pending job for processing
-    pub async fn dequeue(&self) -> Result<Option<RouteComputationJob>> {
+    /// Fetch a job by its queue id
+    pub async fn get(&self, id: i64) -> Result<Option<JobRecord>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, job_key, status, attempt, max_retries, error_message, result,
+                   created_at, updated_at
+            FROM route_computation_jobs
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| ApiError::Internal(Arc::new(anyhow::anyhow!("Failed to get job: {}", e))))?;
+
+        Ok(row.map(|r| JobRecord {
+            id: r.get("id"),
+            job_key: r.get("job_key"),
+            status: r.get("status"),
+            attempt: r.get::<i32, _>("attempt") as u32,
+            max_retries: r.get::<i32, _>("max_retries") as u32,
+            error_message: r.get("error_message"),
+            result: r.get("result"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    /// Claim the next runnable job for `worker_id`, holding it for `lease`.
+    ///
+    /// Runnable jobs are pending jobs whose retry backoff has elapsed, plus
+    /// processing jobs whose lease expired (their worker crashed or stalled).
+    /// Reclaiming a job counts as an attempt. `FOR UPDATE SKIP LOCKED` lets
+    /// concurrent workers claim different jobs without blocking each other.
+    pub async fn dequeue(
+        &self,
+        worker_id: &str,
+        lease: Duration,
+    ) -> Result<Option<RouteComputationJob>> {
         let row = sqlx::query(
             r#"
             UPDATE route_computation_jobs
-            SET status = 'processing', updated_at = NOW()
+            SET status = 'processing',
+                attempt = CASE WHEN status = 'processing' THEN attempt + 1 ELSE attempt END,
+                locked_by = $1,
+                lease_expires_at = NOW() + make_interval(secs => $2),
+                updated_at = NOW()
             WHERE id = (
                 SELECT id FROM route_computation_jobs
-                WHERE status = 'pending'
+                WHERE (status = 'pending' AND available_at <= NOW())
+                   OR (status = 'processing' AND lease_expires_at < NOW()
+                       AND attempt < max_retries)
                 ORDER BY created_at ASC
                 LIMIT 1
                 FOR UPDATE SKIP LOCKED
@@ -71,6 +151,8 @@ impl JobQueue {
             RETURNING id, job_key, payload, attempt, max_retries, created_at
             "#,
         )
+        .bind(worker_id)
+        .bind(lease.as_secs_f64())
         .fetch_optional(&self.db)
         .await
         .map_err(|e| {
@@ -88,9 +170,11 @@ impl JobQueue {
                 id: super::job::JobId::new(
                     &payload.base_asset,
                     &payload.quote_asset,
-                    &format!("{:.7}", payload.amount),
+                    &payload.amount.to_string(),
                     &payload.quote_type,
-                ),
+                )
+                .with_slippage_bps(payload.slippage_bps)
+                .with_max_hops(payload.max_hops),
                 payload,
                 created_at: r.get("created_at"),
                 attempt: r.get::<i32, _>("attempt") as u32,
@@ -101,16 +185,28 @@ impl JobQueue {
         }
     }
 
-    /// Mark job as completed
-    pub async fn mark_completed(&self, job_key: &str) -> Result<()> {
-        sqlx::query(
+    /// Mark job as completed and store its result.
+    ///
+    /// Only the worker holding the lease may finish a job. Returns `false` when
+    /// the lease was lost, i.e. the job has since been reclaimed by another
+    /// worker, in which case nothing is written.
+    pub async fn mark_completed(
+        &self,
+        job_key: &str,
+        worker_id: &str,
+        result: &Value,
+    ) -> Result<bool> {
+        let updated = sqlx::query(
             r#"
             UPDATE route_computation_jobs
-            SET status = 'completed', updated_at = NOW()
-            WHERE job_key = $1
+            SET status = 'completed', result = $1, error_message = NULL,
+                locked_by = NULL, lease_expires_at = NULL, updated_at = NOW()
+            WHERE job_key = $2 AND status = 'processing' AND locked_by = $3
             "#,
         )
+        .bind(result)
         .bind(job_key)
+        .bind(worker_id)
         .execute(&self.db)
         .await
         .map_err(|e| {
@@ -120,20 +216,22 @@ impl JobQueue {
             )))
         })?;
 
-        Ok(())
+        Ok(updated.rows_affected() > 0)
     }
 
-    /// Mark job as failed
-    pub async fn mark_failed(&self, job_key: &str, error: &str) -> Result<()> {
-        sqlx::query(
+    /// Mark job as failed. Returns `false` when `worker_id` lost the lease.
+    pub async fn mark_failed(&self, job_key: &str, worker_id: &str, error: &str) -> Result<bool> {
+        let updated = sqlx::query(
             r#"
             UPDATE route_computation_jobs
-            SET status = 'failed', error_message = $1, updated_at = NOW()
-            WHERE job_key = $2
+            SET status = 'failed', error_message = $1,
+                locked_by = NULL, lease_expires_at = NULL, updated_at = NOW()
+            WHERE job_key = $2 AND status = 'processing' AND locked_by = $3
             "#,
         )
         .bind(error)
         .bind(job_key)
+        .bind(worker_id)
         .execute(&self.db)
         .await
         .map_err(|e| {
@@ -143,30 +241,69 @@ impl JobQueue {
             )))
         })?;
 
-        Ok(())
+        Ok(updated.rows_affected() > 0)
     }
 
-    /// Requeue job for retry
-    pub async fn requeue(&self, job: RouteComputationJob) -> Result<()> {
+    /// Requeue job for retry once `delay` has elapsed. Returns `false` when
+    /// `worker_id` lost the lease.
+    pub async fn requeue(
+        &self,
+        job: RouteComputationJob,
+        worker_id: &str,
+        error: &str,
+        delay: Duration,
+    ) -> Result<bool> {
         let job_key = job.id.as_hash_key();
         let next_attempt = job.attempt + 1;
 
-        sqlx::query(
+        let updated = sqlx::query(
             r#"
             UPDATE route_computation_jobs
-            SET status = 'pending', attempt = $1, updated_at = NOW()
-            WHERE job_key = $2
+            SET status = 'pending', attempt = $1, error_message = $2,
+                available_at = NOW() + make_interval(secs => $3),
+                locked_by = NULL, lease_expires_at = NULL, updated_at = NOW()
+            WHERE job_key = $4 AND status = 'processing' AND locked_by = $5
             "#,
         )
         .bind(next_attempt as i32)
+        .bind(error)
+        .bind(delay.as_secs_f64())
         .bind(&job_key)
+        .bind(worker_id)
         .execute(&self.db)
         .await
         .map_err(|e| {
             ApiError::Internal(Arc::new(anyhow::anyhow!("Failed to requeue job: {}", e)))
         })?;
 
-        Ok(())
+        Ok(updated.rows_affected() > 0)
+    }
+
+    /// Fail processing jobs whose lease expired after their last allowed attempt.
+    ///
+    /// These are never reclaimed by [`JobQueue::dequeue`], so without this sweep
+    /// a job that repeatedly crashes its worker would stay `processing` forever.
+    pub async fn fail_abandoned(&self) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE route_computation_jobs
+            SET status = 'failed', error_message = 'lease_expired',
+                locked_by = NULL, lease_expires_at = NULL, updated_at = NOW()
+            WHERE status = 'processing'
+              AND lease_expires_at < NOW()
+              AND attempt >= max_retries
+            "#,
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|e| {
+            ApiError::Internal(Arc::new(anyhow::anyhow!(
+                "Failed to fail abandoned jobs: {}",
+                e
+            )))
+        })?;
+
+        Ok(result.rows_affected())
     }
 
     /// Get queue stats
@@ -199,6 +336,32 @@ impl JobQueue {
     }
 }
 
+/// Result of [`JobQueue::enqueue`]
+#[derive(Debug, Clone)]
+pub struct EnqueueOutcome {
+    /// Queue id of the new or existing job
+    pub id: i64,
+    pub job_key: String,
+    /// Current status of the job
+    pub status: String,
+    /// False when an existing job with the same key was returned
+    pub enqueued: bool,
+}
+
+/// Stored state of a route computation job
+#[derive(Debug, Clone)]
+pub struct JobRecord {
+    pub id: i64,
+    pub job_key: String,
+    pub status: String,
+    pub attempt: u32,
+    pub max_retries: u32,
+    pub error_message: Option<String>,
+    pub result: Option<Value>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct QueueStats {
     pub pending: usize,
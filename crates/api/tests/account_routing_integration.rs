@@ -0,0 +1,129 @@
+//! Integration tests for the `account` parameter of /quote and /routes
+//!
+//! Accounts are preloaded into the cache, so no Horizon or database is needed.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use stellarroute_api::{
+    accounts::{AccountCache, AccountHoldings},
+    state::DatabasePools,
+    AppState,
+};
+use stellarroute_indexer::horizon::HorizonClient;
+use stellarroute_indexer::models::horizon::{HorizonAccount, HorizonBalance};
+use tower::ServiceExt; // for `oneshot`
+
+const ACCOUNT: &str = "GDVEU3DD4KOFECV66VIHWEZOYX4ZKR3WV27L464SIIPOU2IUI3JCZA57";
+const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+/// Router whose account cache knows `ACCOUNT` with 100 XLM and no trustlines
+fn router() -> Router {
+    let pool = sqlx::postgres::PgPoolOptions::new()
+        .connect_lazy("postgres://localhost/unused")
+        .expect("Failed to create lazy pool");
+
+    // Unroutable Horizon: only cached accounts resolve
+    let accounts = AccountCache::new(
+        HorizonClient::new("http://127.0.0.1:9"),
+        Duration::from_secs(60),
+    );
+    accounts.insert(Arc::new(AccountHoldings::from_horizon(&HorizonAccount {
+        account_id: ACCOUNT.to_string(),
+        sequence: "1".to_string(),
+        subentry_count: 0,
+        num_sponsoring: 0,
+        num_sponsored: 0,
+        balances: vec![HorizonBalance {
+            balance: "100.0000000".to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            liquidity_pool_id: None,
+            limit: None,
+            buying_liabilities: None,
+            selling_liabilities: None,
+            is_authorized: None,
+        }],
+    })));
+
+    let state = AppState::new(DatabasePools::new(pool, None)).with_account_cache(accounts);
+    stellarroute_api::routes::create_router(Arc::new(state))
+}
+
+async fn get(uri: &str) -> (StatusCode, Value) {
+    let response = router()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn routes_rejects_malformed_account() {
+    let (status, body) = get(&format!("/api/v1/routes/native/{}?account=GBAD", USDC)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "validation_error");
+}
+
+#[tokio::test]
+async fn quote_rejects_malformed_account() {
+    let (status, body) = get(&format!("/api/v1/quote/native/{}?account=GBAD", USDC)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "validation_error");
+}
+
+#[tokio::test]
+async fn routes_to_untrusted_asset_return_422() {
+    let (status, body) = get(&format!(
+        "/api/v1/routes/native/{}?amount=10&account={}",
+        USDC, ACCOUNT
+    ))
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "account_constraint");
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("no authorized trustline for USDC"));
+}
+
+#[tokio::test]
+async fn routes_beyond_spendable_balance_return_422() {
+    let (status, body) = get(&format!(
+        "/api/v1/routes/{}/native?amount=5&account={}",
+        USDC, ACCOUNT
+    ))
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "account_constraint");
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("can spend 0.0000000 USDC"));
+}
+
+#[tokio::test]
+async fn quote_to_untrusted_asset_returns_422_before_routing() {
+    // The lazy pool is unroutable, so a 422 means routing never ran
+    let (status, body) = get(&format!(
+        "/api/v1/quote/native/{}?amount=10&account={}",
+        USDC, ACCOUNT
+    ))
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["error"], "account_constraint");
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("no authorized trustline for USDC"));
+}
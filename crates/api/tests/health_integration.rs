@@ -67,12 +67,40 @@ fn dependencies_health_response_serializes_to_spec_shape() {
         status: "degraded".to_string(),
         timestamp: "2026-01-20T12:00:00+00:00".to_string(),
         components,
+        regions: Vec::new(),
     };
 
     let json = serde_json::to_value(&response).expect("serialization failed");
     assert_eq!(json["status"], "degraded");
     assert_eq!(json["components"]["database"], "healthy");
     assert_eq!(json["components"]["horizon"], "degraded");
+    assert!(json.get("regions").is_none());
+}
+
+#[test]
+fn dependencies_health_response_includes_region_snapshots() {
+    use std::collections::HashMap;
+    use stellarroute_api::models::{DependenciesHealthResponse, RegionHealth};
+
+    let response = DependenciesHealthResponse {
+        status: "ok".to_string(),
+        timestamp: "2026-01-20T12:00:00+00:00".to_string(),
+        components: HashMap::new(),
+        regions: vec![RegionHealth {
+            region: "eu-west".to_string(),
+            status: "degraded".to_string(),
+            consecutive_failures: 0,
+            avg_response_time_ms: 12,
+            replica_lag_secs: 8,
+            last_success_ts: 1_768_910_400,
+            last_failure_ts: 0,
+        }],
+    };
+
+    let json = serde_json::to_value(&response).expect("serialization failed");
+    assert_eq!(json["regions"][0]["region"], "eu-west");
+    assert_eq!(json["regions"][0]["status"], "degraded");
+    assert_eq!(json["regions"][0]["replica_lag_secs"], 8);
 }
 
 // ---------------------------------------------------------------------------
@@ -100,6 +100,7 @@ mod multi_region_tests {
             require_version_match: true,
             max_ledger_skew: Some(0),
             prefer_primary: true,
+            min_ledger: None,
         };
         assert!(!strict.satisfies(&version)); // Even new versions fail 0-second constraint
     }
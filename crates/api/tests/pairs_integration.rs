@@ -113,6 +113,10 @@ async fn get_pairs_returns_200_and_valid_json() {
         enable_compression: false,
         redis_url: None,
         quote_cache_ttl_seconds: 2,
+        quote_stale_grace_ms: 1000,
+        enable_route_workers: false,
+        multi_region: false,
+        quote_signer: None,
     };
 
     let router = Server::new(config, DatabasePools::new(pool, None))
@@ -18,11 +18,13 @@ fn quote_response_includes_rationale_metadata() {
             to_asset: AssetInfo::credit("USDC".to_string(), None),
             price: "1.0000000".to_string(),
             source: "sdex".to_string(),
+            min_amount_out: None,
         }],
         timestamp: 1_700_000_000,
         expires_at: Some(1_700_000_030_000),
         source_timestamp: None,
         ttl_seconds: Some(30),
+        ttl_reason: None,
         rationale: Some(QuoteRationaleMetadata {
             strategy: "single_hop_direct_venue_comparison".to_string(),
             selected_source: "sdex:offer-1".to_string(),
@@ -44,6 +46,10 @@ fn quote_response_includes_rationale_metadata() {
         price_impact: None,
         exclusion_diagnostics: None,
         data_freshness: None,
+        quote_id: None,
+        signature: None,
+        signer: None,
+        skipped_routes: Vec::new(),
     };
 
     let json = serde_json::to_value(&response).expect("serialization failed");
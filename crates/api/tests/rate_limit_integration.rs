@@ -94,6 +94,29 @@ fn endpoint_config_selects_default_for_health() {
     assert_eq!(cfg.for_path("/health", None).max_requests, 200);
 }
 
+#[test]
+fn endpoint_config_does_not_give_issued_quotes_the_quote_limit() {
+    use std::time::Duration;
+
+    let cfg = EndpointConfig {
+        quote: RateLimitConfig {
+            max_requests: 7,
+            window: Duration::from_secs(60),
+        },
+        default: RateLimitConfig {
+            max_requests: 9,
+            window: Duration::from_secs(60),
+        },
+        ..EndpointConfig::default()
+    };
+    assert_eq!(cfg.for_path("/api/v1/quote/XLM/USDC", None).max_requests, 7);
+    assert_eq!(
+        cfg.for_path("/api/v1/quotes/0b6f0f6e-0000-4000-8000-000000000000", None)
+            .max_requests,
+        9
+    );
+}
+
 // ---------------------------------------------------------------------------
 // HTTP-level: headers present on allowed requests
 // ---------------------------------------------------------------------------
@@ -215,6 +238,7 @@ async fn rate_limit_returns_429_after_limit_exceeded() {
             window: Duration::from_secs(60),
         },
         tenant_overrides: std::collections::HashMap::new(),
+        ..EndpointConfig::default()
     };
 
     let layer = RateLimitLayer::in_memory(cfg);
@@ -304,6 +328,7 @@ async fn rate_limit_429_content_type_is_json() {
             window: Duration::from_secs(60),
         },
         tenant_overrides: std::collections::HashMap::new(),
+        ..EndpointConfig::default()
     };
 
     let layer = RateLimitLayer::in_memory(cfg);
@@ -374,6 +399,7 @@ async fn different_ips_have_independent_limits() {
             window: Duration::from_secs(60),
         },
         tenant_overrides: std::collections::HashMap::new(),
+        ..EndpointConfig::default()
     };
 
     use axum::{routing::get, Router};
@@ -424,3 +450,161 @@ async fn different_ips_have_independent_limits() {
         .unwrap();
     assert_eq!(allowed.status(), StatusCode::OK);
 }
+
+// ---------------------------------------------------------------------------
+// Cost-weighted requests
+// ---------------------------------------------------------------------------
+
+fn cost_header(resp: &axum::response::Response) -> u64 {
+    resp.headers()
+        .get("x-ratelimit-cost")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse().ok())
+        .expect("X-RateLimit-Cost must be numeric")
+}
+
+#[tokio::test]
+async fn routes_requests_cost_one_unit_per_hop() {
+    use axum::{routing::get, Router};
+    use std::time::Duration;
+
+    async fn ok() -> &'static str {
+        "ok"
+    }
+
+    let cfg = EndpointConfig {
+        default: RateLimitConfig {
+            max_requests: 10,
+            window: Duration::from_secs(60),
+        },
+        ..EndpointConfig::default()
+    };
+    let router = Router::new()
+        .route("/api/v1/routes/:b/:q", get(ok))
+        .layer(RateLimitLayer::in_memory(cfg));
+
+    let request = |query: &str| {
+        Request::builder()
+            .uri(format!("/api/v1/routes/native/USDC{}", query))
+            .header("x-forwarded-for", "10.1.0.1")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let six_hops = router
+        .clone()
+        .oneshot(request("?max_hops=6"))
+        .await
+        .unwrap();
+    assert_eq!(six_hops.status(), StatusCode::OK);
+    assert_eq!(cost_header(&six_hops), 6);
+
+    // 4 units remain: a second 6-hop search is denied, a 3-hop default fits
+    let denied = router
+        .clone()
+        .oneshot(request("?max_hops=6"))
+        .await
+        .unwrap();
+    assert_eq!(denied.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(denied.headers().contains_key("retry-after"));
+
+    let default_hops = router.oneshot(request("")).await.unwrap();
+    assert_eq!(default_hops.status(), StatusCode::OK);
+    assert_eq!(cost_header(&default_hops), 3);
+}
+
+#[tokio::test]
+async fn batch_quotes_cost_one_unit_per_item_and_keep_their_body() {
+    use axum::{routing::post, Router};
+
+    async fn echo_len(body: String) -> String {
+        body.len().to_string()
+    }
+
+    let router = Router::new()
+        .route("/api/v1/batch/quote", post(echo_len))
+        .layer(RateLimitLayer::in_memory(EndpointConfig::default()));
+
+    let payload =
+        r#"{"quotes":[{"base":"native","quote":"USDC"},{"base":"native","quote":"EURC"}]}"#;
+    let resp = router
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/batch/quote")
+                .header("content-type", "application/json")
+                .body(Body::from(payload))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(cost_header(&resp), 2);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(body, payload.len().to_string());
+}
+
+#[tokio::test]
+async fn api_keys_may_burst_above_the_anonymous_limit() {
+    use axum::{routing::get, Router};
+    use std::time::Duration;
+    use stellarroute_api::middleware::{BurstAllowances, TenantTier};
+
+    async fn ok() -> &'static str {
+        "ok"
+    }
+
+    let cfg = EndpointConfig {
+        pairs: RateLimitConfig {
+            max_requests: 2,
+            window: Duration::from_secs(60),
+        },
+        bursts: BurstAllowances {
+            anonymous_pct: 0,
+            standard_pct: 100,
+            premium_pct: 200,
+        },
+        tenant_tiers: [("apikey:burst-tenant".to_string(), TenantTier::Standard)].into(),
+        ..EndpointConfig::default()
+    };
+    let router = Router::new()
+        .route("/api/v1/pairs", get(ok))
+        .layer(RateLimitLayer::in_memory(cfg));
+
+    let allowed_for = |api_key: &'static str| {
+        let router = router.clone();
+        async move {
+            let mut allowed = 0;
+            for _ in 0..6 {
+                let resp = router
+                    .clone()
+                    .oneshot(
+                        Request::builder()
+                            .uri("/api/v1/pairs")
+                            .header("x-api-key", api_key)
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                if resp.status() == StatusCode::OK {
+                    allowed += 1;
+                }
+            }
+            allowed
+        }
+    };
+
+    assert_eq!(
+        allowed_for("burst-tenant").await,
+        4,
+        "limit of 2 plus a 100% burst"
+    );
+    // Unknown keys neither burst nor get a bucket of their own
+    assert_eq!(allowed_for("made-up-key").await, 2);
+    assert_eq!(allowed_for("another-made-up-key").await, 0);
+}
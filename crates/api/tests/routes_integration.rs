@@ -4,7 +4,7 @@
 //! integration), verifying: multi-route list, ranking scores, per-route metadata
 //! (fees, hops, impact estimates), and limit/pagination semantics.
 
-use stellarroute_api::models::{AssetInfo, RouteCandidate, RouteHop, RoutesResponse};
+use stellarroute_api::models::{AssetInfo, RouteCandidate, RouteHop, RoutesResponse, SkippedRoute};
 
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
@@ -44,6 +44,7 @@ fn make_routes_response(routes: Vec<RouteCandidate>) -> RoutesResponse {
         amount: "10.0000000".to_string(),
         routes,
         timestamp: 1_700_000_000_000,
+        skipped_routes: vec![],
     }
 }
 
@@ -261,3 +262,35 @@ fn policy_used_field_propagates_in_all_candidates() {
         );
     }
 }
+
+#[test]
+fn skipped_routes_are_omitted_unless_present() {
+    let mut resp = make_routes_response(vec![make_candidate(
+        90.0,
+        30,
+        vec![make_hop("native", "USDC", "1.0", 30, "sdex")],
+    )]);
+    let json = serde_json::to_value(&resp).unwrap();
+    assert!(json.get("skipped_routes").is_none());
+
+    resp.skipped_routes.push(SkippedRoute {
+        route: make_candidate(
+            95.0,
+            20,
+            vec![
+                make_hop("native", "EURC", "0.9", 10, "sdex"),
+                make_hop("EURC", "USDC", "1.2", 10, "sdex"),
+            ],
+        ),
+        missing_trustlines: vec![AssetInfo::credit("EURC".to_string(), None)],
+        reason: "Returns 0.8000000 more but account GDVE... has no trustline for EURC".to_string(),
+    });
+    let json = serde_json::to_value(&resp).unwrap();
+    assert_eq!(
+        json["skipped_routes"][0]["missing_trustlines"][0]["asset_code"],
+        "EURC"
+    );
+
+    let back: RoutesResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(back.skipped_routes.len(), 1);
+}
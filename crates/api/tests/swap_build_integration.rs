@@ -0,0 +1,147 @@
+//! Integration tests for `POST /api/v1/swap/build`.
+//!
+//! Golden fixtures in `fixtures/swap_build/` pin the exact envelope XDR and
+//! hash for each transaction shape (strict send, strict receive through a
+//! classic pool, Soroban router call), so encoding changes show up as diffs.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::postgres::PgPoolOptions;
+use std::path::PathBuf;
+use stellarroute_api::{
+    models::request::SwapBuildRequest,
+    state::DatabasePools,
+    txbuild::{build_swap_transaction, TxBuildConfig},
+    Server, ServerConfig,
+};
+use tower::ServiceExt;
+
+#[derive(Deserialize)]
+struct Fixture {
+    description: String,
+    now: u64,
+    network_passphrase: String,
+    router_contract: Option<String>,
+    request: Value,
+    expected: Value,
+}
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures/swap_build")
+}
+
+fn load_fixtures() -> Vec<(String, Fixture)> {
+    let mut fixtures: Vec<_> = std::fs::read_dir(fixtures_dir())
+        .expect("fixtures dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let raw = std::fs::read_to_string(&path).expect("read fixture");
+            (name, serde_json::from_str(&raw).expect("parse fixture"))
+        })
+        .collect();
+    fixtures.sort_by(|a, b| a.0.cmp(&b.0));
+    fixtures
+}
+
+async fn post_build(body: Value) -> (StatusCode, Value) {
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_lazy("postgres://localhost/unused")
+        .expect("Failed to create lazy pool");
+    let router = Server::new(ServerConfig::default(), DatabasePools::new(pool, None))
+        .await
+        .into_router();
+
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/swap/build")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .expect("Request failed");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[test]
+fn golden_fixtures_match() {
+    let fixtures = load_fixtures();
+    assert!(
+        fixtures.len() >= 3,
+        "expected the swap_build golden fixtures"
+    );
+
+    for (name, fixture) in fixtures {
+        let config = TxBuildConfig {
+            network_passphrase: fixture.network_passphrase,
+            router_contract: fixture.router_contract,
+        };
+        let request: SwapBuildRequest =
+            serde_json::from_value(fixture.request).expect("fixture request");
+        let built = build_swap_transaction(&config, &request, fixture.now)
+            .unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert_eq!(
+            serde_json::to_value(&built).unwrap(),
+            fixture.expected,
+            "{name}: {}",
+            fixture.description
+        );
+    }
+}
+
+#[tokio::test]
+async fn endpoint_builds_strict_send_for_sell_quote() {
+    let (_, fixture) = load_fixtures()
+        .into_iter()
+        .find(|(name, _)| name == "sell_sdex_direct")
+        .expect("sell fixture");
+
+    let (status, json) = post_build(fixture.request).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["operation"], "path_payment_strict_send");
+    assert_eq!(json["sequence"], "4294967297");
+    assert_eq!(json["max_send"], "100.0000000");
+    assert_eq!(json["min_receive"], "12.2839416");
+    assert_eq!(json["requires_simulation"], false);
+    assert_eq!(json["min_time"], 0);
+    assert!(json["max_time"].as_u64().unwrap() > 1_700_000_000);
+}
+
+#[tokio::test]
+async fn endpoint_rejects_request_without_quote_or_route() {
+    let (status, json) = post_build(json!({
+        "source_account": "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ",
+        "sequence": "1",
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "validation_error");
+}
+
+#[tokio::test]
+async fn endpoint_rejects_soroban_route_without_router() {
+    let (_, fixture) = load_fixtures()
+        .into_iter()
+        .find(|(name, _)| name == "route_soroban_two_hops")
+        .expect("soroban fixture");
+
+    let (status, json) = post_build(fixture.request).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"], "bad_request");
+}
@@ -25,6 +25,7 @@ fn test_ws_message_types_serialize_correctly() {
         timestamp: 1_700_000_000_000,
         payload: ServerPayload::SubscriptionConfirmed {
             subscription_id: sub_id,
+            request_id: Some("req-1".to_string()),
         },
     };
 
@@ -40,6 +41,7 @@ fn test_ws_message_types_serialize_correctly() {
         "type field must be present"
     );
     assert_eq!(json["subscription_id"], sub_id.to_string());
+    assert_eq!(json["request_id"], "req-1", "request_id must be echoed");
 }
 
 // ---------------------------------------------------------------------------
@@ -50,6 +52,7 @@ fn test_ws_message_types_serialize_correctly() {
 fn test_client_message_subscribe_deserializes() {
     let raw = json!({
         "action": "subscribe",
+        "request_id": "req-1",
         "subscription": {
             "base": "native",
             "quote": "USDC:GABC",
@@ -60,7 +63,11 @@ fn test_client_message_subscribe_deserializes() {
     let msg: ClientMessage = serde_json::from_value(raw).expect("subscribe deserialization failed");
 
     match msg {
-        ClientMessage::Subscribe { subscription } => {
+        ClientMessage::Subscribe {
+            subscription,
+            request_id,
+        } => {
+            assert_eq!(request_id.as_deref(), Some("req-1"));
             assert_eq!(subscription.base, "native");
             assert_eq!(subscription.quote, "USDC:GABC");
             assert_eq!(subscription.amount.as_deref(), Some("100"));
@@ -101,6 +108,7 @@ fn test_server_message_error_shape() {
     let msg = ServerMessage::now(ServerPayload::Error {
         code: "no_route_found".to_string(),
         message: "No liquidity for this pair.".to_string(),
+        request_id: None,
     });
 
     let json: Value = serde_json::to_value(&msg).expect("serialization failed");
@@ -116,6 +124,10 @@ fn test_server_message_error_shape() {
         json["timestamp"].as_i64().is_some(),
         "timestamp must be an integer"
     );
+    assert!(
+        json.get("request_id").is_none(),
+        "request_id is omitted for errors that answer no subscribe"
+    );
 }
 
 // ---------------------------------------------------------------------------
@@ -296,6 +308,7 @@ mod live {
                 max_connections,
                 ip_rate_limiter: Arc::new(Mutex::new(HashMap::new())),
                 poll_interval_ms: 1000,
+                broadcaster_started: Default::default(),
                 ping_interval_secs: 30,
                 pong_timeout_secs: 10,
                 backpressure_timeout_secs: 10,
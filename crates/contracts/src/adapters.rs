@@ -1,4 +1,8 @@
-use crate::{errors::ContractError, types::Asset};
+use crate::{
+    errors::ContractError,
+    stable_math,
+    types::{Asset, PoolType, RouteHop},
+};
 use soroban_sdk::{contractclient, symbol_short, vec, Address, Env, IntoVal, Symbol};
 
 #[contractclient(name = "PoolAdapterClient")]
@@ -82,3 +86,95 @@ impl AmmAdapter {
         }
     }
 }
+
+pub struct StableAdapter;
+
+impl StableAdapter {
+    /// Quote on the StableSwap curve from the pool's reserves and parameters
+    /// instead of trusting its `adapter_quote`, so a stable hop is never
+    /// priced with constant-product math.
+    pub fn quote(
+        e: &Env,
+        pool: &Address,
+        input_asset: &Asset,
+        amount_in: i128,
+    ) -> Result<i128, ContractError> {
+        let (res0, res1) = AmmAdapter::get_reserves(e, pool)?;
+        let params_result = e.try_invoke_contract::<(Asset, u32, u32), soroban_sdk::Error>(
+            pool,
+            &Symbol::new(e, "get_params"),
+            vec![e],
+        );
+        let (token0, amp, fee_bps) = match params_result {
+            Ok(Ok(val)) => val,
+            _ => return Err(ContractError::AmmQuoteCallFailed),
+        };
+
+        let (res_in, res_out) = if *input_asset == token0 {
+            (res0, res1)
+        } else {
+            (res1, res0)
+        };
+        stable_math::get_dy(amount_in, res_in, res_out, amp, fee_bps)
+    }
+
+    /// Swap through the pool, enforcing `min_out` here as well since the
+    /// underlying stable pool may not.
+    pub fn swap(
+        e: &Env,
+        pool: &Address,
+        input_asset: &Asset,
+        output_asset: &Asset,
+        amount_in: i128,
+        min_out: i128,
+    ) -> Result<i128, ContractError> {
+        let amount_out = AmmAdapter::swap(e, pool, input_asset, output_asset, amount_in, min_out)?;
+        if amount_out < min_out {
+            return Err(ContractError::HopSlippageExceeded);
+        }
+        Ok(amount_out)
+    }
+}
+
+/// Quote one hop through the adapter matching its pool type.
+///
+/// Constant-product pools quote themselves; StableSwap hops are priced on the
+/// curve from the pool's reserves and amplification. SDEX hops are not
+/// routable on-chain.
+pub fn quote_hop(e: &Env, hop: &RouteHop, amount_in: i128) -> Result<i128, ContractError> {
+    match hop.pool_type {
+        PoolType::AmmConstProd => {
+            AmmAdapter::quote(e, &hop.pool, &hop.source, &hop.destination, amount_in)
+        }
+        PoolType::AmmStable => StableAdapter::quote(e, &hop.pool, &hop.source, amount_in),
+        PoolType::Sdex => Err(ContractError::PoolNotSupported),
+    }
+}
+
+/// Execute one hop through the adapter matching its pool type.
+pub fn swap_hop(
+    e: &Env,
+    hop: &RouteHop,
+    amount_in: i128,
+    min_out: i128,
+) -> Result<i128, ContractError> {
+    match hop.pool_type {
+        PoolType::AmmConstProd => AmmAdapter::swap(
+            e,
+            &hop.pool,
+            &hop.source,
+            &hop.destination,
+            amount_in,
+            min_out,
+        ),
+        PoolType::AmmStable => StableAdapter::swap(
+            e,
+            &hop.pool,
+            &hop.source,
+            &hop.destination,
+            amount_in,
+            min_out,
+        ),
+        PoolType::Sdex => Err(ContractError::PoolNotSupported),
+    }
+}
@@ -11,13 +11,19 @@
 
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
-    Address, Bytes, BytesN, Env, Symbol, Vec,
+    token::{StellarAssetClient, TokenClient},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, String, Symbol, TryFromVal, Vec,
 };
 
 use super::{
-    errors::ContractError,
+    errors::{ContractError, ExtendedError},
+    price_impact,
     router::{StellarRoute, StellarRouteClient},
-    types::{Asset, MevConfig, PoolType, Route, RouteHop, SwapParams},
+    types::{
+        Asset, MevConfig, PoolType, Route, RouteHop, SplitLeg, SplitRoute, SplitSwapParams,
+        SwapParams,
+    },
 };
 
 // ── Mock pool contracts ───────────────────────────────────────────────────────
@@ -94,6 +100,84 @@ mod mock_pool_fail {
     }
 }
 
+mod mock_pool_cp {
+    use super::super::types::Asset;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    /// Constant-product pool (0.3% fee) whose reserves move with each swap.
+    /// Every swap sells reserve 0 for reserve 1.
+    #[contract]
+    pub struct MockPoolCp;
+
+    #[contractimpl]
+    impl MockPoolCp {
+        pub fn init(e: Env, reserve_0: i128, reserve_1: i128) {
+            e.storage()
+                .instance()
+                .set(&symbol_short!("R"), &(reserve_0, reserve_1));
+        }
+        pub fn adapter_quote(e: Env, _in: Asset, _out: Asset, amount_in: i128) -> i128 {
+            let (r0, r1) = Self::get_rsrvs(e);
+            let with_fee = amount_in * 997;
+            with_fee * r1 / (r0 * 1000 + with_fee)
+        }
+        pub fn swap(e: Env, _in: Asset, _out: Asset, amount_in: i128, min_out: i128) -> i128 {
+            let out = Self::adapter_quote(e.clone(), Asset::Native, Asset::Native, amount_in);
+            assert!(out >= min_out, "mock_pool_cp: slippage");
+            let (r0, r1) = Self::get_rsrvs(e.clone());
+            e.storage()
+                .instance()
+                .set(&symbol_short!("R"), &(r0 + amount_in, r1 - out));
+            out
+        }
+        pub fn get_rsrvs(e: Env) -> (i128, i128) {
+            e.storage().instance().get(&symbol_short!("R")).unwrap()
+        }
+    }
+}
+
+mod mock_flash_receiver {
+    use super::super::flash::FlashSwapReceiver;
+    use super::super::types::Asset;
+    use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Bytes, Env};
+
+    /// Flash-swap receiver that repays `repay_amount - shortfall` to the router.
+    #[contract]
+    pub struct MockFlashReceiver;
+
+    #[contractimpl]
+    impl MockFlashReceiver {
+        pub fn init(e: Env, router: Address, shortfall: i128) {
+            e.storage()
+                .instance()
+                .set(&symbol_short!("CFG"), &(router, shortfall));
+        }
+    }
+
+    #[contractimpl]
+    impl FlashSwapReceiver for MockFlashReceiver {
+        fn on_flash_swap(
+            e: Env,
+            _initiator: Address,
+            _output_asset: Asset,
+            _amount_out: i128,
+            repay_asset: Asset,
+            repay_amount: i128,
+            _data: Bytes,
+        ) {
+            let (router, shortfall): (Address, i128) =
+                e.storage().instance().get(&symbol_short!("CFG")).unwrap();
+            if let Asset::Soroban(repay_token) = repay_asset {
+                token::Client::new(&e, &repay_token).transfer(
+                    &e.current_contract_address(),
+                    &router,
+                    &(repay_amount - shortfall),
+                );
+            }
+        }
+    }
+}
+
 // ── Harness helpers ───────────────────────────────────────────────────────────
 
 pub fn deploy_pool_99(env: &Env) -> Address {
@@ -108,6 +192,32 @@ pub fn deploy_pool_fail(env: &Env) -> Address {
     env.register_contract(None, mock_pool_fail::MockPoolFail)
 }
 
+pub fn deploy_pool_cp(env: &Env, reserve_0: i128, reserve_1: i128) -> Address {
+    let id = env.register_contract(None, mock_pool_cp::MockPoolCp);
+    mock_pool_cp::MockPoolCpClient::new(env, &id).init(&reserve_0, &reserve_1);
+    id
+}
+
+/// Whether any event so far carries `name` as its action topic.
+fn emitted(env: &Env, name: &str) -> bool {
+    emitted_count(env, name) > 0
+}
+
+/// Number of events so far carrying `name` as their action topic.
+fn emitted_count(env: &Env, name: &str) -> usize {
+    let wanted = Symbol::new(env, name);
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(1)
+                .and_then(|topic| Symbol::try_from_val(env, &topic).ok())
+                .is_some_and(|topic| topic == wanted)
+        })
+        .count()
+}
+
 fn setup() -> Env {
     let env = Env::default();
     env.mock_all_auths();
@@ -136,6 +246,7 @@ fn multi_pool_route(env: &Env, pools: &[Address]) -> Route {
             destination: Asset::Native,
             pool: pool.clone(),
             pool_type: PoolType::AmmConstProd,
+            min_amount_out: 0,
         });
     }
     Route {
@@ -442,6 +553,7 @@ fn e2e_multi_hop_five_hops_rejected() {
             destination: Asset::Native,
             pool: pools_sdk.get(i).unwrap(),
             pool_type: PoolType::AmmConstProd,
+            min_amount_out: 0,
         });
     }
     let route = Route {
@@ -614,7 +726,7 @@ fn e2e_event_multi_hop_single_swap_event() {
 // ── Failure rollback / error recovery tests ───────────────────────────────────
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// A broken pool mid-route returns PoolCallFailed and leaves state unchanged.
+/// A broken pool mid-route returns AmmSwapCallFailed and leaves state unchanged.
 #[test]
 fn e2e_failure_broken_pool_returns_error() {
     let env = setup();
@@ -786,6 +898,94 @@ fn e2e_failure_empty_route_rejected() {
     assert_eq!(result, Err(Ok(ContractError::InvalidRoute)));
 }
 
+/// Route through two constant-product pools with per-hop minimums set 0.5%
+/// below what each hop quotes right now.
+fn guarded_route(env: &Env, pool_a: &Address, pool_b: &Address, amount_in: i128) -> Route {
+    let quote_a = mock_pool_cp::MockPoolCpClient::new(env, pool_a);
+    let quote_b = mock_pool_cp::MockPoolCpClient::new(env, pool_b);
+    let hop_a = quote_a.adapter_quote(&Asset::Native, &Asset::Native, &amount_in);
+    let hop_b = quote_b.adapter_quote(&Asset::Native, &Asset::Native, &hop_a);
+
+    let mut route = multi_pool_route(env, &[pool_a.clone(), pool_b.clone()]);
+    for (i, expected) in [hop_a, hop_b].into_iter().enumerate() {
+        let mut hop = route.hops.get(i as u32).unwrap();
+        hop.min_amount_out = expected * 9_950 / 10_000;
+        route.hops.set(i as u32, hop);
+    }
+    route
+}
+
+/// A manipulated intermediate pool aborts the swap at that hop even when the
+/// final minimum alone would not catch it.
+#[test]
+fn e2e_failure_manipulated_intermediate_pool_aborts_at_hop() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    let pool_b = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let route = guarded_route(&env, &pool_a, &pool_b, 10_000_000);
+
+    // Front-run pool B between quote and execution
+    mock_pool_cp::MockPoolCpClient::new(&env, &pool_b).swap(
+        &Asset::Native,
+        &Asset::Native,
+        &100_000_000,
+        &0,
+    );
+
+    let sender = Address::generate(&env);
+    let result = client.try_execute_swap(&sender, &swap_params(&env, route, 10_000_000, 0));
+    assert_eq!(result, Err(Ok(ContractError::HopSlippageExceeded)));
+    let nonce = env.as_contract(&client.address, || {
+        super::storage::get_nonce(&env, sender.clone())
+    });
+    assert_eq!(nonce, 0);
+}
+
+/// Hops that deliver at least their minimum execute normally.
+#[test]
+fn e2e_hop_minimums_met_swap_succeeds() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    let pool_b = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let route = guarded_route(&env, &pool_a, &pool_b, 10_000_000);
+    let hop_b_min = route.hops.get(1).unwrap().min_amount_out;
+
+    let result = client.execute_swap(
+        &Address::generate(&env),
+        &swap_params(&env, route, 10_000_000, 0),
+    );
+    // Final output is hop B's output less the 30 bps protocol fee
+    assert!(result.amount_out >= hop_b_min * 9_970 / 10_000);
+}
+
+/// Negative per-hop minimums are rejected before any transfer.
+#[test]
+fn e2e_failure_negative_hop_minimum_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+
+    let mut route = multi_pool_route(&env, &[pool]);
+    let mut hop = route.hops.get(0).unwrap();
+    hop.min_amount_out = -1;
+    route.hops.set(0, hop);
+
+    let result = client.try_execute_swap(
+        &Address::generate(&env),
+        &swap_params(&env, route, 1_000, 0),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // ── MEV protection E2E tests ──────────────────────────────────────────────────
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -797,6 +997,7 @@ fn default_mev_config() -> MevConfig {
         rate_limit_window_ledgers: 100,
         rate_limit_max_swaps: 3,
         commitment_required_above: 100_000,
+        twap_window_ledgers: 0,
     }
 }
 
@@ -897,6 +1098,36 @@ fn e2e_mev_whitelisted_exempt_from_rate_limit() {
     }
 }
 
+/// Commitment over the XDR-encoded params, built independently of the contract.
+fn commitment_for(env: &Env, params: &SwapParams, salt: &BytesN<32>) -> BytesN<32> {
+    let mut payload = params.clone().to_xdr(env);
+    let salt_bytes: Bytes = salt.clone().into();
+    payload.append(&salt_bytes);
+    env.crypto().sha256(&payload).into()
+}
+
+fn committed_params(env: &Env, pool: &Address, amount_in: i128) -> SwapParams {
+    SwapParams {
+        route: multi_pool_route(env, core::slice::from_ref(pool)),
+        amount_in,
+        min_amount_out: 0,
+        recipient: Address::generate(env),
+        deadline: seq(env) + 200,
+        not_before: 0,
+        max_price_impact_bps: 0,
+        max_execution_spread_bps: 0,
+    }
+}
+
+/// Stellar asset contract with `amount` minted to `holder`.
+fn funded_token<'a>(env: &'a Env, holder: &Address, amount: i128) -> (Asset, TokenClient<'a>) {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    StellarAssetClient::new(env, &token).mint(holder, &amount);
+    (Asset::Soroban(token.clone()), TokenClient::new(env, &token))
+}
+
 /// Commit-reveal: valid commitment + correct reveal executes the swap.
 #[test]
 fn e2e_mev_commit_reveal_full_flow() {
@@ -908,38 +1139,26 @@ fn e2e_mev_commit_reveal_full_flow() {
 
     let sender = Address::generate(&env);
     let amount_in: i128 = 100_000;
-    let min_out: i128 = 0;
-    let deadline = seq(&env) + 200;
+    let (deposit_asset, token) = funded_token(&env, &sender, 2_500);
     let salt = BytesN::from_array(&env, &[7u8; 32]);
+    let params = committed_params(&env, &pool, amount_in);
 
-    // Build the same hash the contract will verify
-    let mut payload = Bytes::new(&env);
-    payload.append(&Bytes::from_slice(&env, &amount_in.to_be_bytes()));
-    payload.append(&Bytes::from_slice(&env, &min_out.to_be_bytes()));
-    payload.append(&Bytes::from_slice(&env, &deadline.to_be_bytes()));
-    let salt_bytes: soroban_sdk::Bytes = salt.clone().into();
-    payload.append(&salt_bytes);
-    let commitment_hash: BytesN<32> = env.crypto().sha256(&payload).into();
-
-    // Step 1: commit
-    client.commit_swap(&sender, &commitment_hash, &amount_in);
+    // The contract derives the same hash as an off-chain client
+    let commitment_hash = commitment_for(&env, &params, &salt);
+    assert_eq!(client.commitment_hash(&params, &salt), commitment_hash);
 
-    // Step 2: reveal + execute
-    let route = multi_pool_route(&env, &[pool]);
-    let params = SwapParams {
-        route,
-        amount_in,
-        min_amount_out: min_out,
-        recipient: Address::generate(&env),
-        deadline,
-        not_before: 0,
-        max_price_impact_bps: 0,
-        max_execution_spread_bps: 0,
-    };
+    // Step 1: commit, escrowing the deposit
+    assert_eq!(token.balance(&client.address), 0);
+    client.commit_swap(&sender, &commitment_hash, &deposit_asset, &2_500);
+    assert_eq!(token.balance(&sender), 0);
+    assert_eq!(token.balance(&client.address), 2_500);
 
+    // Step 2: reveal + execute, releasing the deposit
     let result = client.reveal_and_execute(&sender, &params, &salt);
     assert_eq!(result.amount_in, amount_in);
     assert!(result.amount_out > 0);
+    assert_eq!(token.balance(&sender), 2_500);
+    assert_eq!(token.balance(&client.address), 0);
 }
 
 /// Reveal with wrong salt must be rejected.
@@ -952,38 +1171,1102 @@ fn e2e_mev_commit_reveal_wrong_salt_rejected() {
     client.configure_mev(&default_mev_config());
 
     let sender = Address::generate(&env);
-    let amount_in: i128 = 100_000;
-    let min_out: i128 = 0;
-    let deadline = seq(&env) + 200;
     let correct_salt = BytesN::from_array(&env, &[7u8; 32]);
     let wrong_salt = BytesN::from_array(&env, &[9u8; 32]);
+    let params = committed_params(&env, &pool, 100_000);
+    let (deposit_asset, _) = funded_token(&env, &sender, 100_000);
 
-    // Commit with correct salt
-    let mut payload = Bytes::new(&env);
-    payload.append(&Bytes::from_slice(&env, &amount_in.to_be_bytes()));
-    payload.append(&Bytes::from_slice(&env, &min_out.to_be_bytes()));
-    payload.append(&Bytes::from_slice(&env, &deadline.to_be_bytes()));
-    let salt_bytes: soroban_sdk::Bytes = correct_salt.into();
-    payload.append(&salt_bytes);
-    let commitment_hash: BytesN<32> = env.crypto().sha256(&payload).into();
-    client.commit_swap(&sender, &commitment_hash, &amount_in);
+    client.commit_swap(
+        &sender,
+        &commitment_for(&env, &params, &correct_salt),
+        &deposit_asset,
+        &100_000,
+    );
 
     // Reveal with wrong salt → CommitmentNotFound (hash mismatch)
-    let route = multi_pool_route(&env, &[pool]);
-    let params = SwapParams {
-        route,
-        amount_in,
-        min_amount_out: min_out,
-        recipient: Address::generate(&env),
-        deadline,
-        not_before: 0,
-        max_price_impact_bps: 0,
-        max_execution_spread_bps: 0,
-    };
     let result = client.try_reveal_and_execute(&sender, &params, &wrong_salt);
     assert_eq!(result, Err(Ok(ContractError::CommitmentNotFound)));
 }
 
+/// Recipient, route and guards are bound by the commitment and cannot be
+/// swapped out at reveal time.
+#[test]
+fn e2e_mev_commit_reveal_binds_full_intent() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    let other_pool = deploy_pool_98(&env);
+    client.register_pool(&pool);
+    client.register_pool(&other_pool);
+    client.configure_mev(&default_mev_config());
+
+    let sender = Address::generate(&env);
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+    let params = committed_params(&env, &pool, 100_000);
+    let (deposit_asset, _) = funded_token(&env, &sender, 100_000);
+    client.commit_swap(
+        &sender,
+        &commitment_for(&env, &params, &salt),
+        &deposit_asset,
+        &100_000,
+    );
+
+    let mut redirected = params.clone();
+    redirected.recipient = Address::generate(&env);
+    assert_eq!(
+        client.try_reveal_and_execute(&sender, &redirected, &salt),
+        Err(Ok(ContractError::CommitmentNotFound))
+    );
+
+    let mut rerouted = params.clone();
+    rerouted.route = multi_pool_route(&env, &[other_pool]);
+    assert_eq!(
+        client.try_reveal_and_execute(&sender, &rerouted, &salt),
+        Err(Ok(ContractError::CommitmentNotFound))
+    );
+
+    let mut loosened = params.clone();
+    loosened.max_price_impact_bps = 10_000;
+    assert_eq!(
+        client.try_reveal_and_execute(&sender, &loosened, &salt),
+        Err(Ok(ContractError::CommitmentNotFound))
+    );
+
+    // The original intent still reveals
+    assert!(client
+        .try_reveal_and_execute(&sender, &params, &salt)
+        .is_ok());
+}
+
+/// The deposit is escrowed at commit time and refunded on reveal.
+#[test]
+fn e2e_mev_commit_deposit_escrowed_and_refunded_on_reveal() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    client.configure_mev(&default_mev_config());
+
+    let sender = Address::generate(&env);
+    let (deposit_asset, token) = funded_token(&env, &sender, 5_000);
+    let salt = BytesN::from_array(&env, &[3u8; 32]);
+    let params = committed_params(&env, &pool, 100_000);
+    assert_eq!(token.balance(&sender), 5_000);
+    assert_eq!(token.balance(&client.address), 0);
+
+    client.commit_swap(
+        &sender,
+        &commitment_for(&env, &params, &salt),
+        &deposit_asset,
+        &5_000,
+    );
+    assert_eq!(token.balance(&sender), 0);
+    assert_eq!(token.balance(&client.address), 5_000);
+
+    client.reveal_and_execute(&sender, &params, &salt);
+    assert_eq!(token.balance(&sender), 5_000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+/// Deposits the router cannot hold are rejected instead of escrowing nothing.
+#[test]
+fn e2e_mev_commit_rejects_non_token_deposits() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    client.configure_mev(&default_mev_config());
+
+    let sender = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[5u8; 32]);
+    let issued = Asset::Issued(Address::generate(&env), Symbol::new(&env, "USDC"));
+    for asset in [Asset::Native, issued] {
+        assert_eq!(
+            client.try_commit_swap(&sender, &hash, &asset, &1_000),
+            Err(Ok(ContractError::TokenNotAllowed))
+        );
+    }
+}
+
+/// A pending commitment hash cannot be committed again.
+#[test]
+fn e2e_mev_commit_duplicate_hash_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    client.configure_mev(&default_mev_config());
+
+    let sender = Address::generate(&env);
+    let (deposit_asset, token) = funded_token(&env, &sender, 2_000);
+    let params = committed_params(&env, &pool, 100_000);
+    let hash = commitment_for(&env, &params, &BytesN::from_array(&env, &[6u8; 32]));
+
+    client.commit_swap(&sender, &hash, &deposit_asset, &1_000);
+    assert_eq!(
+        client.try_commit_swap(&sender, &hash, &deposit_asset, &1_000),
+        Err(Err(ExtendedError::CommitmentExists.into()))
+    );
+    assert_eq!(token.balance(&client.address), 1_000);
+}
+
+/// Fixed params whose commitment hash the Rust SDK checks byte for byte
+/// (`stellarroute_sdk::commitment` tests share this golden value).
+#[test]
+fn e2e_mev_commitment_hash_matches_sdk_golden() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let address = |strkey: &str| Address::from_string(&String::from_str(&env, strkey));
+    let account = address("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ");
+    let contract = address("CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE");
+
+    let mut hops = Vec::new(&env);
+    hops.push_back(RouteHop {
+        source: Asset::Native,
+        destination: Asset::Issued(account.clone(), Symbol::new(&env, "USDC")),
+        pool: contract.clone(),
+        pool_type: PoolType::AmmStable,
+        min_amount_out: 123_456_789,
+    });
+    hops.push_back(RouteHop {
+        source: Asset::Issued(account.clone(), Symbol::new(&env, "USDC")),
+        destination: Asset::Soroban(contract.clone()),
+        pool: contract,
+        pool_type: PoolType::Sdex,
+        min_amount_out: 0,
+    });
+    let params = SwapParams {
+        route: Route {
+            hops,
+            estimated_output: 1 << 70,
+            min_output: -5,
+            expires_at: u64::MAX,
+        },
+        amount_in: 1_000_000,
+        min_amount_out: 990_000,
+        recipient: account,
+        deadline: 50_000,
+        not_before: 7,
+        max_price_impact_bps: 100,
+        max_execution_spread_bps: 50,
+    };
+
+    let hash = client.commitment_hash(&params, &BytesN::from_array(&env, &[7u8; 32]));
+    // 16c3b47e370a40ee03e6994b1972e80fffb806a4bbf11b5d9413afc816e9dfe2
+    let golden: [u8; 32] = [
+        0x16, 0xc3, 0xb4, 0x7e, 0x37, 0x0a, 0x40, 0xee, 0x03, 0xe6, 0x99, 0x4b, 0x19, 0x72, 0xe8,
+        0x0f, 0xff, 0xb8, 0x06, 0xa4, 0xbb, 0xf1, 0x1b, 0x5d, 0x94, 0x13, 0xaf, 0xc8, 0x16, 0xe9,
+        0xdf, 0xe2,
+    ];
+    assert_eq!(hash.to_array(), golden);
+}
+
+/// An unrevealed deposit can only be reclaimed, by its sender, after expiry.
+#[test]
+fn e2e_mev_commit_deposit_reclaimable_after_expiry() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    client.configure_mev(&default_mev_config());
+
+    let sender = Address::generate(&env);
+    let (deposit_asset, token) = funded_token(&env, &sender, 5_000);
+    let salt = BytesN::from_array(&env, &[4u8; 32]);
+    let params = committed_params(&env, &pool, 100_000);
+    let hash = commitment_for(&env, &params, &salt);
+    client.commit_swap(&sender, &hash, &deposit_asset, &5_000);
+    assert_eq!(token.balance(&sender), 0);
+    assert_eq!(token.balance(&client.address), 5_000);
+
+    assert_eq!(
+        client.try_reclaim_commitment(&sender, &hash),
+        Err(Err(ExtendedError::CommitmentActive.into()))
+    );
+
+    // Past the 100-ledger reveal window
+    env.ledger().with_mut(|l| l.sequence_number += 101);
+
+    assert_eq!(
+        client.try_reveal_and_execute(&sender, &params, &salt),
+        Err(Ok(ContractError::CommitmentExpired))
+    );
+    assert_eq!(
+        client.try_reclaim_commitment(&Address::generate(&env), &hash),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    assert_eq!(client.reclaim_commitment(&sender, &hash), 5_000);
+    assert_eq!(token.balance(&sender), 5_000);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(
+        client.try_reclaim_commitment(&sender, &hash),
+        Err(Ok(ContractError::CommitmentNotFound))
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ── Price impact E2E tests ────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// A trade worth 0.1% of the pool costs little more than the 0.3% pool fee;
+/// one worth 10% of the pool adds the curve's slippage on top.
+#[test]
+fn e2e_impact_quote_scales_with_trade_size() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+
+    let small = client.get_quote(
+        &1_000_000,
+        &multi_pool_route(&env, core::slice::from_ref(&pool)),
+    );
+    let large = client.get_quote(&100_000_000, &multi_pool_route(&env, &[pool]));
+
+    assert_eq!(small.price_impact_bps, 40);
+    // 1 - 0.997 / 1.0997 ≈ 934 bps
+    assert_eq!(large.price_impact_bps, 934);
+}
+
+/// Spot price is read from the reserves as they stand, so a quote after a
+/// swap is measured against the moved pool.
+#[test]
+fn e2e_impact_swap_moves_reserves_and_next_quote() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+    let route = multi_pool_route(&env, core::slice::from_ref(&pool));
+
+    let before = client.get_quote(&10_000_000, &route);
+    client.execute_swap(
+        &Address::generate(&env),
+        &swap_params(&env, route.clone(), 50_000_000, 0),
+    );
+    let after = client.get_quote(&10_000_000, &route);
+
+    assert!(after.expected_output < before.expected_output);
+    // Fee plus curve against 1.05e9 / 0.95e9 rather than 1e9 / 1e9
+    assert_eq!(before.price_impact_bps, 129);
+    assert_eq!(after.price_impact_bps, 124);
+}
+
+/// max_price_impact_bps now rejects genuinely large trades and admits small ones.
+#[test]
+fn e2e_impact_max_price_impact_enforced() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+
+    let mut large = swap_params(
+        &env,
+        multi_pool_route(&env, core::slice::from_ref(&pool)),
+        100_000_000,
+        0,
+    );
+    large.max_price_impact_bps = 500;
+    assert_eq!(
+        client.try_execute_swap(&Address::generate(&env), &large),
+        Err(Ok(ContractError::PriceImpactTooHigh))
+    );
+
+    let mut small = swap_params(&env, multi_pool_route(&env, &[pool]), 1_000_000, 0);
+    small.max_price_impact_bps = 500;
+    assert!(client
+        .try_execute_swap(&Address::generate(&env), &small)
+        .is_ok());
+}
+
+/// high_impact_swap fires only when the measured impact exceeds the MEV limit.
+#[test]
+fn e2e_impact_high_impact_event_uses_measured_impact() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+    let mut config = default_mev_config();
+    config.commitment_required_above = i128::MAX;
+    client.configure_mev(&config);
+
+    client.execute_swap(
+        &Address::generate(&env),
+        &swap_params(
+            &env,
+            multi_pool_route(&env, core::slice::from_ref(&pool)),
+            1_000_000,
+            0,
+        ),
+    );
+    assert!(!emitted(&env, "hi_imp"));
+
+    client.execute_swap(
+        &Address::generate(&env),
+        &swap_params(&env, multi_pool_route(&env, &[pool]), 100_000_000, 0),
+    );
+    assert!(emitted(&env, "hi_imp"));
+}
+
+/// Impact compounds across hops rather than adding a flat amount per hop.
+#[test]
+fn e2e_impact_compounds_across_hops() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    let pool_b = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let one = client.get_quote(
+        &50_000_000,
+        &multi_pool_route(&env, core::slice::from_ref(&pool_a)),
+    );
+    let two = client.get_quote(&50_000_000, &multi_pool_route(&env, &[pool_a, pool_b]));
+
+    // 504 bps then 481 bps compound to 961, not 985
+    assert_eq!(one.price_impact_bps, 504);
+    assert_eq!(two.price_impact_bps, 961);
+}
+
+/// Reserve orientation is inferred from how the pool moved, so a pool that
+/// reports its reserves in the opposite order measures the same impact.
+#[test]
+fn e2e_impact_orientation_from_reserve_deltas() {
+    let pre = (1_000_000_000_i128, 4_000_000_000_i128);
+    let amount_in = 10_000_000_i128;
+    let amount_out = 39_486_321_i128;
+    let post = (pre.0 + amount_in, pre.1 - amount_out);
+
+    let forward = price_impact::hop_impact_bps_executed(pre, post, amount_in, amount_out).unwrap();
+    let swapped = price_impact::hop_impact_bps_executed(
+        (pre.1, pre.0),
+        (post.1, post.0),
+        amount_in,
+        amount_out,
+    )
+    .unwrap();
+
+    assert_eq!(forward, swapped);
+    assert_eq!(forward, 129);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ── Split-route E2E tests ─────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn split_params(
+    env: &Env,
+    legs: &[(Route, u32)],
+    amount_in: i128,
+    min_out: i128,
+) -> SplitSwapParams {
+    let mut split_legs = Vec::new(env);
+    for (route, weight_bps) in legs {
+        split_legs.push_back(SplitLeg {
+            route: route.clone(),
+            weight_bps: *weight_bps,
+        });
+    }
+    SplitSwapParams {
+        split: SplitRoute { legs: split_legs },
+        amount_in,
+        min_amount_out: min_out,
+        recipient: Address::generate(env),
+        deadline: seq(env) + 200,
+        not_before: 0,
+        max_price_impact_bps: 0,
+    }
+}
+
+/// 60/40 split: the last leg absorbs the rounding remainder, fees are taken
+/// per leg, and each leg emits its own swap event.
+#[test]
+fn e2e_split_executes_weighted_legs() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_99(&env);
+    let pool_b = deploy_pool_98(&env);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let params = split_params(
+        &env,
+        &[
+            (multi_pool_route(&env, &[pool_a]), 6_000),
+            (multi_pool_route(&env, &[pool_b]), 4_000),
+        ],
+        10_001,
+        9_832,
+    );
+    let sender = Address::generate(&env);
+    let result = client.execute_split(&sender, &params);
+
+    // leg a: 6000 in → 5940 out, fee 17 → 5923
+    // leg b: 4001 in → 3920 out, fee 11 → 3909
+    assert_eq!(result.amount_in, 10_001);
+    assert_eq!(
+        result.leg_outputs,
+        Vec::from_array(&env, [5_923_i128, 3_909])
+    );
+    assert_eq!(result.amount_out, 9_832);
+    assert_eq!(result.fee_amount, 28);
+    assert_eq!(emitted_count(&env, "swap"), 2);
+    assert_eq!(client.get_total_swap_volume(), 10_001);
+    assert_eq!(client.get_fee_balance(&Asset::Native), 28);
+}
+
+/// Legs may differ in length as long as they join the same assets.
+#[test]
+fn e2e_split_legs_of_different_lengths() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_99(&env);
+    let pool_b = deploy_pool_98(&env);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let params = split_params(
+        &env,
+        &[
+            (
+                multi_pool_route(&env, core::slice::from_ref(&pool_a)),
+                5_000,
+            ),
+            (multi_pool_route(&env, &[pool_a, pool_b]), 5_000),
+        ],
+        10_000,
+        0,
+    );
+    let result = client.execute_split(&Address::generate(&env), &params);
+
+    // leg a: 5000 → 4950, fee 14 → 4936
+    // leg b: 5000 → 4950 → 4851, fee 14 → 4837
+    assert_eq!(
+        result.leg_outputs,
+        Vec::from_array(&env, [4_936_i128, 4_837])
+    );
+    assert_eq!(result.amount_out, 9_773);
+}
+
+/// Weights must cover exactly 100% of the input.
+#[test]
+fn e2e_split_weights_must_sum_to_full_amount() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_99(&env);
+    let pool_b = deploy_pool_98(&env);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let params = split_params(
+        &env,
+        &[
+            (multi_pool_route(&env, &[pool_a]), 6_000),
+            (multi_pool_route(&env, &[pool_b]), 3_000),
+        ],
+        10_000,
+        0,
+    );
+    assert_eq!(
+        client.try_execute_split(&Address::generate(&env), &params),
+        Err(Ok(ContractError::InvalidSplit))
+    );
+}
+
+/// Every leg must deliver the same asset.
+#[test]
+fn e2e_split_legs_must_share_destination() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_99(&env);
+    let pool_b = deploy_pool_98(&env);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let mut other = multi_pool_route(&env, &[pool_b]);
+    let mut hop = other.hops.get(0).unwrap();
+    hop.destination = Asset::Soroban(Address::generate(&env));
+    other.hops.set(0, hop);
+
+    let params = split_params(
+        &env,
+        &[(multi_pool_route(&env, &[pool_a]), 5_000), (other, 5_000)],
+        10_000,
+        0,
+    );
+    assert_eq!(
+        client.try_execute_split(&Address::generate(&env), &params),
+        Err(Ok(ContractError::InvalidSplit))
+    );
+}
+
+/// At most four legs.
+#[test]
+fn e2e_split_too_many_legs_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+
+    let route = multi_pool_route(&env, &[pool]);
+    let legs = [
+        (route.clone(), 2_000),
+        (route.clone(), 2_000),
+        (route.clone(), 2_000),
+        (route.clone(), 2_000),
+        (route, 2_000),
+    ];
+    assert_eq!(
+        client.try_execute_split(
+            &Address::generate(&env),
+            &split_params(&env, &legs, 10_000, 0)
+        ),
+        Err(Ok(ContractError::InvalidSplit))
+    );
+}
+
+/// A leg below its own min_output aborts the whole split, including the
+/// legs that already executed.
+#[test]
+fn e2e_split_leg_minimum_rolls_back_all_legs() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_99(&env);
+    let pool_b = deploy_pool_98(&env);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let mut leg_b = multi_pool_route(&env, &[pool_b]);
+    leg_b.min_output = 3_910;
+    let sender = Address::generate(&env);
+    let params = split_params(
+        &env,
+        &[(multi_pool_route(&env, &[pool_a]), 6_000), (leg_b, 4_000)],
+        10_001,
+        0,
+    );
+
+    assert_eq!(
+        client.try_execute_split(&sender, &params),
+        Err(Ok(ContractError::SlippageExceeded))
+    );
+    assert_eq!(client.get_total_swap_volume(), 0);
+    assert_eq!(client.get_fee_balance(&Asset::Native), 0);
+    let nonce = env.as_contract(&client.address, || {
+        super::storage::get_nonce(&env, sender.clone())
+    });
+    assert_eq!(nonce, 0);
+}
+
+/// The aggregated minimum applies to the sum of the legs.
+#[test]
+fn e2e_split_aggregate_minimum_enforced() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool_a = deploy_pool_99(&env);
+    let pool_b = deploy_pool_98(&env);
+    client.register_pool(&pool_a);
+    client.register_pool(&pool_b);
+
+    let params = split_params(
+        &env,
+        &[
+            (multi_pool_route(&env, &[pool_a]), 6_000),
+            (multi_pool_route(&env, &[pool_b]), 4_000),
+        ],
+        10_001,
+        9_833,
+    );
+    assert_eq!(
+        client.try_execute_split(&Address::generate(&env), &params),
+        Err(Ok(ContractError::SlippageExceeded))
+    );
+}
+
+/// Split swaps respect the pause switch like single-route swaps.
+#[test]
+fn e2e_split_paused_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    client.pause();
+
+    let params = split_params(
+        &env,
+        &[(multi_pool_route(&env, &[pool]), 10_000)],
+        10_000,
+        0,
+    );
+    assert_eq!(
+        client.try_execute_split(&Address::generate(&env), &params),
+        Err(Ok(ContractError::Paused))
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ── Flash swap E2E tests ──────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn deploy_flash_receiver(env: &Env, router: &Address, shortfall: i128) -> Address {
+    let id = env.register_contract(None, mock_flash_receiver::MockFlashReceiver);
+    mock_flash_receiver::MockFlashReceiverClient::new(env, &id).init(router, &shortfall);
+    id
+}
+
+/// Single hop from `source` to XLM through `pool`.
+fn flash_route(env: &Env, source: &Asset, pool: &Address) -> Route {
+    let mut route = multi_pool_route(env, core::slice::from_ref(pool));
+    let mut hop = route.hops.get(0).unwrap();
+    hop.source = source.clone();
+    route.hops.set(0, hop);
+    route
+}
+
+/// The receiver gets the output first and repays input plus the 30 bps fee
+/// from inside its callback.
+#[test]
+fn e2e_flash_swap_repaid_in_callback() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+
+    let (asset, token) = funded_token(&env, &client.address, 10_000);
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+    StellarAssetClient::new(&env, &token.address).mint(&receiver, &10_030);
+
+    let result = client.flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_000,
+        &9_900,
+        &Bytes::new(&env),
+    );
+
+    assert_eq!(result.amount_in, 10_000);
+    assert_eq!(result.amount_out, 9_900);
+    assert_eq!(result.fee_amount, 30);
+    assert_eq!(token.balance(&pool), 10_000);
+    assert_eq!(token.balance(&receiver), 0);
+    assert_eq!(token.balance(&client.address), 10_030);
+    assert_eq!(client.get_fee_balance(&asset), 30);
+    assert_eq!(client.get_total_swap_volume(), 10_000);
+    assert!(emitted(&env, "flash"));
+}
+
+/// Repaying less than input plus fee unwinds the swap.
+#[test]
+fn e2e_flash_swap_underpaid_rolls_back() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+
+    let (asset, token) = funded_token(&env, &client.address, 10_000);
+    let receiver = deploy_flash_receiver(&env, &client.address, 1);
+    StellarAssetClient::new(&env, &token.address).mint(&receiver, &10_030);
+
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_000,
+        &0,
+        &Bytes::new(&env),
+    );
+
+    assert_eq!(result, Err(Ok(ContractError::FlashNotRepaid)));
+    assert_eq!(token.balance(&client.address), 10_000);
+    assert_eq!(token.balance(&receiver), 10_030);
+    assert_eq!(token.balance(&pool), 0);
+    assert_eq!(client.get_fee_balance(&asset), 0);
+}
+
+/// A callback that cannot pay (its transfer panics) surfaces as a typed error.
+#[test]
+fn e2e_flash_swap_failing_callback_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+
+    let (asset, _) = funded_token(&env, &client.address, 10_000);
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Err(ExtendedError::FlashCallbackFailed.into())));
+}
+
+/// Flash swaps are bounded by the router's balance of the source token.
+#[test]
+fn e2e_flash_swap_exceeding_router_balance_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+
+    let (asset, _) = funded_token(&env, &client.address, 5_000);
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InsufficientInput)));
+}
+
+/// Escrowed commit deposits and undistributed fees are not lendable.
+#[test]
+fn e2e_flash_swap_cannot_borrow_escrow_or_fees() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    client.configure_mev(&default_mev_config());
+
+    let sender = Address::generate(&env);
+    let (asset, token) = funded_token(&env, &sender, 5_000);
+    let params = committed_params(&env, &pool, 100_000);
+    let hash = commitment_for(&env, &params, &BytesN::from_array(&env, &[8u8; 32]));
+    client.commit_swap(&sender, &hash, &asset, &5_000);
+    assert_eq!(token.balance(&client.address), 5_000);
+
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &5_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InsufficientInput)));
+
+    // One successful flash swap books its fee, which is then held back too.
+    StellarAssetClient::new(&env, &token.address).mint(&client.address, &10_000);
+    StellarAssetClient::new(&env, &token.address).mint(&receiver, &10_030);
+    client.flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(client.get_fee_balance(&asset), 30);
+    assert_eq!(token.balance(&client.address), 15_030);
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_001,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InsufficientInput)));
+}
+
+/// Repayment can only be verified for Soroban tokens.
+#[test]
+fn e2e_flash_swap_native_source_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &multi_pool_route(&env, &[pool]),
+        &10_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::InvalidRoute)));
+}
+
+/// Flash swaps stop while the router is paused.
+#[test]
+fn e2e_flash_swap_paused_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    let (asset, _) = funded_token(&env, &client.address, 10_000);
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+    client.pause();
+
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::Paused)));
+}
+
+/// Flash routes go through the token allowlist like any other route.
+#[test]
+fn e2e_flash_swap_token_not_allowed_rejected() {
+    let env = setup();
+    let (admin, client) = deploy_router(&env);
+    let pool = deploy_pool_99(&env);
+    client.register_pool(&pool);
+    let (asset, _) = funded_token(&env, &client.address, 10_000);
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+
+    // Activate the allowlist with an unrelated token
+    client.add_token(
+        &admin,
+        &super::types::TokenInfo {
+            asset: Asset::Native,
+            name: Symbol::new(&env, "Lumens"),
+            code: Symbol::new(&env, "XLM"),
+            decimals: 7,
+            issuer_verified: true,
+            category: super::types::TokenCategory::Native,
+            added_at: seq(&env),
+            added_by: admin.clone(),
+        },
+    );
+
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &10_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::TokenNotAllowed)));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ── TWAP oracle E2E tests ─────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════════
+
+fn set_ledger(env: &Env, sequence: u32) {
+    env.ledger().with_mut(|l| l.sequence_number = sequence);
+}
+
+/// The TWAP averages the prices left by earlier swaps over the ledgers they
+/// stood; a swap in the current ledger only counts once ledgers pass.
+#[test]
+fn e2e_twap_accumulates_prices_between_swaps() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+    let route = multi_pool_route(&env, core::slice::from_ref(&pool));
+
+    set_ledger(&env, 100);
+    client.execute_swap(
+        &Address::generate(&env),
+        &swap_params(&env, route.clone(), 10_000_000, 0),
+    );
+    // Only one observation so far: nothing reaches back 10 ledgers
+    assert_eq!(
+        client.try_get_twap(&pool, &10),
+        Err(Err(ExtendedError::TwapUnavailable.into()))
+    );
+
+    // Reserves after the first swap: 1_010_000_000 / 990_128_420
+    set_ledger(&env, 200);
+    let before = client.get_twap(&pool, &50);
+    assert_eq!(before.price0, 980_325_168);
+    assert_eq!(before.price1, 1_020_069_699);
+    assert_eq!(before.window_ledgers, 100);
+
+    // A large swap in this ledger moves spot to ~0.44 but not the TWAP
+    client.execute_swap(
+        &Address::generate(&env),
+        &swap_params(&env, route, 500_000_000, 0),
+    );
+    assert_eq!(client.get_twap(&pool, &50), before);
+
+    // After 100 ledgers at the new price the window reflects it
+    set_ledger(&env, 300);
+    let after = client.get_twap(&pool, &50);
+    assert_eq!(after.price0, 439_026_401);
+    assert_eq!(after.window_ledgers, 100);
+
+    // A window spanning both periods averages them
+    let both = client.get_twap(&pool, &150);
+    assert_eq!(both.price0, (980_325_168 + 439_026_401) / 2);
+    assert_eq!(both.window_ledgers, 200);
+}
+
+/// Only registered pools have a TWAP.
+#[test]
+fn e2e_twap_unregistered_pool_rejected() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+
+    assert_eq!(
+        client.try_get_twap(&pool, &50),
+        Err(Ok(ContractError::PoolNotSupported))
+    );
+}
+
+fn twap_mev_config(max_execution_spread_bps: u32) -> MevConfig {
+    MevConfig {
+        max_price_impact_bps: 10_000,
+        max_execution_spread_bps,
+        rate_limit_window_ledgers: 100,
+        rate_limit_max_swaps: 100,
+        commitment_required_above: i128::MAX,
+        twap_window_ledgers: 50,
+    }
+}
+
+/// Seed `pool`'s TWAP with a small swap at ledger 100, then move to ledger 200.
+fn seed_twap(env: &Env, client: &StellarRouteClient<'_>, pool: &Address) {
+    set_ledger(env, 100);
+    client.execute_swap(
+        &Address::generate(env),
+        &swap_params(
+            env,
+            multi_pool_route(env, core::slice::from_ref(pool)),
+            50_000,
+            0,
+        ),
+    );
+    set_ledger(env, 200);
+}
+
+/// Push `pool` far off its TWAP within the current ledger, as a front-run would.
+fn move_spot(env: &Env, client: &StellarRouteClient<'_>, pool: &Address) {
+    client.execute_swap(
+        &Address::generate(env),
+        &swap_params(
+            env,
+            multi_pool_route(env, core::slice::from_ref(pool)),
+            500_000_000,
+            0,
+        ),
+    );
+}
+
+/// With a TWAP window configured, max_execution_spread_bps bounds how far
+/// the pools' pre-swap spot price sits below their TWAP. Fees and the swap's
+/// own impact do not count against it, so a normal trade passes a tight cap.
+#[test]
+fn e2e_twap_bounds_execution_spread() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+    client.configure_mev(&twap_mev_config(10_000));
+    let route = multi_pool_route(&env, core::slice::from_ref(&pool));
+    assert_eq!(route.estimated_output, 0);
+    seed_twap(&env, &client, &pool);
+
+    // Nets 49_693 after the pool and protocol fees, 60 bps under the TWAP
+    // output, yet trades at the TWAP price
+    let mut params = swap_params(&env, route.clone(), 50_000, 0);
+    params.max_execution_spread_bps = 1;
+    let result = client.execute_swap(&Address::generate(&env), &params);
+    assert_eq!(result.amount_out, 49_693);
+
+    // Spot pushed to ~0.44 of the TWAP in this ledger
+    move_spot(&env, &client, &pool);
+    let mut params = swap_params(&env, route, 50_000, 0);
+    params.max_execution_spread_bps = 100;
+    assert_eq!(
+        client.try_execute_swap(&Address::generate(&env), &params),
+        Err(Ok(ContractError::SpreadTooHigh))
+    );
+}
+
+/// Split legs take their spread cap from the MEV config.
+#[test]
+fn e2e_twap_bounds_split_legs() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+    client.configure_mev(&twap_mev_config(100));
+    let route = multi_pool_route(&env, core::slice::from_ref(&pool));
+    seed_twap(&env, &client, &pool);
+
+    let params = split_params(&env, &[(route.clone(), 10_000)], 50_000, 0);
+    client.execute_split(&Address::generate(&env), &params);
+
+    move_spot(&env, &client, &pool);
+    let params = split_params(&env, &[(route, 10_000)], 50_000, 0);
+    assert_eq!(
+        client.try_execute_split(&Address::generate(&env), &params),
+        Err(Ok(ContractError::SpreadTooHigh))
+    );
+}
+
+/// Flash swaps take their spread cap from the MEV config.
+#[test]
+fn e2e_twap_bounds_flash_swap() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+    client.configure_mev(&twap_mev_config(100));
+    seed_twap(&env, &client, &pool);
+    move_spot(&env, &client, &pool);
+
+    let (asset, _) = funded_token(&env, &client.address, 50_000);
+    let receiver = deploy_flash_receiver(&env, &client.address, 0);
+    let result = client.try_flash_swap(
+        &Address::generate(&env),
+        &receiver,
+        &flash_route(&env, &asset, &pool),
+        &50_000,
+        &0,
+        &Bytes::new(&env),
+    );
+    assert_eq!(result, Err(Ok(ContractError::SpreadTooHigh)));
+}
+
+/// Without a TWAP window the spread guard falls back to the route estimate,
+/// which a zero estimate disables.
+#[test]
+fn e2e_twap_disabled_falls_back_to_route_estimate() {
+    let env = setup();
+    let (_, client) = deploy_router(&env);
+    let pool = deploy_pool_cp(&env, 1_000_000_000, 1_000_000_000);
+    client.register_pool(&pool);
+    let route = multi_pool_route(&env, &[pool]);
+
+    set_ledger(&env, 100);
+    client.execute_swap(
+        &Address::generate(&env),
+        &swap_params(&env, route.clone(), 50_000, 0),
+    );
+
+    set_ledger(&env, 200);
+    let mut params = swap_params(&env, route, 50_000, 0);
+    params.max_execution_spread_bps = 1;
+    assert_eq!(
+        client
+            .execute_swap(&Address::generate(&env), &params)
+            .amount_out,
+        49_693
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // ── Full lifecycle E2E tests ──────────────────────────────────────────────────
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1263,6 +2546,7 @@ fn valid_mev() -> MevConfig {
         rate_limit_window_ledgers: 100,
         rate_limit_max_swaps: 3,
         commitment_required_above: 100_000,
+        twap_window_ledgers: 0,
     }
 }
 
@@ -1394,7 +2678,8 @@ fn val_commit_swap_zero_hash_rejected() {
     client.configure_mev(&valid_mev());
 
     let zero_hash = BytesN::from_array(&env, &[0u8; 32]);
-    let result = client.try_commit_swap(&Address::generate(&env), &zero_hash, &1_000);
+    let result =
+        client.try_commit_swap(&Address::generate(&env), &zero_hash, &Asset::Native, &1_000);
     assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
 }
 
@@ -1406,7 +2691,7 @@ fn val_commit_swap_zero_deposit_rejected() {
     client.configure_mev(&valid_mev());
 
     let hash = BytesN::from_array(&env, &[1u8; 32]);
-    let result = client.try_commit_swap(&Address::generate(&env), &hash, &0);
+    let result = client.try_commit_swap(&Address::generate(&env), &hash, &Asset::Native, &0);
     assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
 }
 
@@ -1418,7 +2703,7 @@ fn val_commit_swap_negative_deposit_rejected() {
     client.configure_mev(&valid_mev());
 
     let hash = BytesN::from_array(&env, &[1u8; 32]);
-    let result = client.try_commit_swap(&Address::generate(&env), &hash, &-1);
+    let result = client.try_commit_swap(&Address::generate(&env), &hash, &Asset::Native, &-1);
     assert_eq!(result, Err(Ok(ContractError::InvalidAmount)));
 }
 
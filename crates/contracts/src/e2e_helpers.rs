@@ -36,6 +36,7 @@ pub fn multi_pool_route(env: &Env, pools: &[Address]) -> Route {
             destination: Asset::Native,
             pool: pool.clone(),
             pool_type: PoolType::AmmConstProd,
+            min_amount_out: 0,
         });
     }
     Route {
@@ -1,5 +1,10 @@
 use soroban_sdk::contracterror;
 
+/// Contract errors.
+///
+/// The contract spec allows at most 50 cases per error enum. Codes are never
+/// renumbered or removed; once this enum is full, new codes go in
+/// [`ExtendedError`].
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
@@ -12,6 +17,9 @@ pub enum ContractError {
     RouteExpired = 11,
     EmptyRoute = 12,
     TooManyHops = 13,
+    /// Split legs disagree on source/destination asset, or their weights do
+    /// not sum to 10000 bps.
+    InvalidSplit = 14,
     InsufficientInput = 20,
     InsufficientOutput = 21,
     SlippageExceeded = 22,
@@ -19,6 +27,8 @@ pub enum ContractError {
     ExecutionTooEarly = 24,
     PriceImpactTooHigh = 25,
     SpreadTooHigh = 26,
+    /// An intermediate hop returned less than its `min_amount_out`.
+    HopSlippageExceeded = 27,
     PoolNotSupported = 30,
     PoolCallFailed = 31,
     ReserveManipulationDetected = 32,
@@ -54,7 +64,8 @@ pub enum ContractError {
     /// Post-upgrade migration hook has already been executed for this version.
     MigrationAlreadyDone = 70,
     // ── Token allowlist ──────────────────────────────────────────────────────
-    /// Asset is not on the allowlist and cannot be routed.
+    /// Asset is not on the allowlist and cannot be routed, or is not a Soroban
+    /// token where the router must hold it (commit deposits).
     TokenNotAllowed = 80,
     /// Token is already in the allowlist.
     TokenAlreadyAdded = 81,
@@ -76,4 +87,31 @@ pub enum ContractError {
     // ── Fee Distribution ─────────────────────────────────────────────────
     /// Fee configuration is invalid (e.g., shares don't sum to 10000 bps or too many recipients).
     InvalidFeeConfig = 100,
+    // ── Flash swaps ──────────────────────────────────────────────────────
+    /// Receiver did not return the fronted input plus fee to the router.
+    FlashNotRepaid = 110,
+}
+
+/// Errors that no longer fit in [`ContractError`].
+///
+/// Entry points raise these with `panic_with_error!`, so callers see the
+/// same contract error code they would for a returned `ContractError`.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ExtendedError {
+    /// The proposal's class timelock has not elapsed yet.
+    ProposalTimelocked = 58,
+    /// Commitment has not expired yet; reveal it instead of reclaiming.
+    CommitmentActive = 95,
+    /// Pool has no price history covering the requested TWAP window.
+    TwapUnavailable = 96,
+    /// A commitment with this hash is already pending.
+    CommitmentExists = 97,
+    /// The receiver's `on_flash_swap` callback failed.
+    FlashCallbackFailed = 111,
+    /// The quote was signed by a key that is not on the signer allowlist.
+    QuoteSignerNotAllowed = 120,
+    /// The quote's `expires_at` has passed.
+    QuoteExpired = 121,
 }
@@ -45,6 +45,25 @@ pub fn swap_executed(
     );
 }
 
+pub fn flash_swap_executed(
+    e: &Env,
+    sender: Address,
+    receiver: Address,
+    amount_in: i128,
+    amount_out: i128,
+    fee: i128,
+) {
+    let topics = (
+        Symbol::new(e, "StellarRoute"),
+        symbol_short!("flash"),
+        sender,
+    );
+    e.events().publish(
+        topics,
+        (receiver, amount_in, amount_out, fee, e.ledger().sequence()),
+    );
+}
+
 pub fn route_validated(e: &Env, hop_count: u32, expires_at: u64) {
     let topics = (Symbol::new(e, "StellarRoute"), symbol_short!("rt_val"));
     e.events()
@@ -231,6 +250,21 @@ pub fn commitment_revealed(e: &Env, sender: Address, commitment_hash: BytesN<32>
     e.events().publish(topics, commitment_hash);
 }
 
+pub fn commitment_reclaimed(
+    e: &Env,
+    sender: Address,
+    commitment_hash: BytesN<32>,
+    deposit_amount: i128,
+) {
+    let topics = (
+        Symbol::new(e, "StellarRoute"),
+        symbol_short!("cmt_rcl"),
+        sender,
+    );
+    e.events()
+        .publish(topics, (commitment_hash, deposit_amount));
+}
+
 pub fn ttl_extended(e: &Env, pools_extended: u32, ledger: u32) {
     let topics = (Symbol::new(e, "StellarRoute"), symbol_short!("ttl_ext"));
     e.events().publish(topics, (pools_extended, ledger));
@@ -0,0 +1,22 @@
+use crate::types::Asset;
+use soroban_sdk::{contractclient, Address, Bytes, Env};
+
+/// Callback implemented by contracts that take flash swaps from the router.
+///
+/// The router sends the route output to the receiver and then calls
+/// `on_flash_swap`. Before returning, the receiver must transfer
+/// `repay_amount` of `repay_asset` back to the router, or the whole
+/// transaction is rolled back. The router cannot be re-entered from the
+/// callback.
+#[contractclient(name = "FlashSwapReceiverClient")]
+pub trait FlashSwapReceiver {
+    fn on_flash_swap(
+        e: Env,
+        initiator: Address,
+        output_asset: Asset,
+        amount_out: i128,
+        repay_asset: Asset,
+        repay_amount: i128,
+        data: Bytes,
+    );
+}
@@ -14,19 +14,30 @@
 //!      that cannot trigger a follow-up tx in the same call).
 //!   4. The original proposer (or a majority of signers) can `cancel()`.
 //!
+//! Timelocks:
+//!   Each action belongs to an `ActionClass` with its own delay. A proposal
+//!   cannot execute before `created_at + timelock`, even with enough
+//!   approvals; once the delay has passed `execute()` runs it. Changing a
+//!   class's timelock waits at least that class's current delay. The delay
+//!   is fixed when the proposal is created, and proposals expire
+//!   `proposal_ttl` ledgers after they become executable.
+//!
 //! Guardian:
 //!   A single trusted address (e.g. a hot key in a multi-sig cold-wallet
 //!   setup) that may call `guardian_pause()`. Unpausing still requires a
 //!   full multi-sig proposal.
 
-use crate::errors::ContractError;
+use crate::errors::{ContractError, ExtendedError};
 use crate::storage::{self, extend_instance_ttl};
-use crate::types::{GovernanceConfig, Proposal, ProposalAction};
+use crate::types::{ActionClass, GovernanceConfig, Proposal, ProposalAction};
 use crate::{events, storage::StorageKey};
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
 // Maximum number of authorized signers.
 const MAX_SIGNERS: u32 = 10;
+// Default delay for MEV and allowlist changes (~1 hour), which change what
+// swaps the router accepts and so give users time to react.
+const DEFAULT_POLICY_TIMELOCK: u64 = 720;
 
 // ─── Internal helpers ─────────────────────────────────────────────────────────
 
@@ -50,8 +61,57 @@ fn has_approved(p: &Proposal, addr: &Address) -> bool {
     false
 }
 
+/// The timelock class an action belongs to.
+pub fn action_class(action: &ProposalAction) -> ActionClass {
+    match action {
+        ProposalAction::Pause | ProposalAction::Unpause => ActionClass::Emergency,
+        ProposalAction::SetFeeRate(_)
+        | ProposalAction::SetFeeTo(_)
+        | ProposalAction::SetFeeConfig(_) => ActionClass::Fees,
+        ProposalAction::RegisterPool(_, _) | ProposalAction::DeregisterPool(_) => {
+            ActionClass::Pools
+        }
+        ProposalAction::Upgrade(_) => ActionClass::Upgrade,
+        ProposalAction::AddSigner(_)
+        | ProposalAction::RemoveSigner(_)
+        | ProposalAction::ChangeThreshold(_)
+        | ProposalAction::SetTimelock(_, _) => ActionClass::Governance,
+        ProposalAction::ConfigureMev(_)
+        | ProposalAction::SetWhitelist(_, _)
+        | ProposalAction::UpdateKnownPrice(_, _, _)
+        | ProposalAction::SetQuoteSigner(_, _) => ActionClass::Mev,
+        ProposalAction::AddToken(_)
+        | ProposalAction::RemoveToken(_)
+        | ProposalAction::UpdateToken(_, _) => ActionClass::Tokens,
+    }
+}
+
+/// Ledgers proposals of `class` wait between creation and execution.
+pub fn timelock(e: &Env, class: ActionClass) -> u64 {
+    storage::get_action_timelock(e, class).unwrap_or(match class {
+        ActionClass::Mev | ActionClass::Tokens => DEFAULT_POLICY_TIMELOCK,
+        _ => 0,
+    })
+}
+
+/// Ledgers a proposal for `action` waits between creation and execution.
+///
+/// `SetTimelock` waits at least the current delay of the class it changes,
+/// so a delay cannot be lifted faster than it holds back that class.
+pub fn proposal_timelock(e: &Env, action: &ProposalAction) -> u64 {
+    let delay = timelock(e, action_class(action));
+    match action {
+        ProposalAction::SetTimelock(class, _) => delay.max(timelock(e, *class)),
+        _ => delay,
+    }
+}
+
 /// Execute the privileged action encoded in a proposal.
-fn dispatch_action(e: &Env, action: ProposalAction) -> Result<(), ContractError> {
+fn dispatch_action(
+    e: &Env,
+    proposer: Address,
+    action: ProposalAction,
+) -> Result<(), ContractError> {
     match action {
         ProposalAction::SetFeeRate(rate) => {
             if rate > 1000 {
@@ -143,6 +203,31 @@ fn dispatch_action(e: &Env, action: ProposalAction) -> Result<(), ContractError>
             config.threshold = new_threshold;
             storage::set_governance(e, &config);
         }
+        ProposalAction::ConfigureMev(config) => {
+            crate::router::validate_mev_config(&config)?;
+            storage::set_mev_config(e, &config);
+        }
+        ProposalAction::SetWhitelist(address, whitelisted) => {
+            storage::set_whitelisted(e, &address, whitelisted);
+        }
+        ProposalAction::UpdateKnownPrice(token_a, token_b, price) => {
+            storage::set_latest_known_price(e, &token_a, &token_b, price);
+        }
+        ProposalAction::AddToken(info) => {
+            crate::tokens::add_token_internal(e, proposer, info)?;
+        }
+        ProposalAction::RemoveToken(asset) => {
+            crate::tokens::remove_token_internal(e, proposer, asset)?;
+        }
+        ProposalAction::UpdateToken(asset, info) => {
+            crate::tokens::update_token_internal(e, proposer, asset, info)?;
+        }
+        ProposalAction::SetTimelock(class, ledgers) => {
+            storage::set_action_timelock(e, class, ledgers);
+        }
+        ProposalAction::SetQuoteSigner(signer, allowed) => {
+            storage::set_quote_signer(e, &signer, allowed);
+        }
     }
     Ok(())
 }
@@ -238,6 +323,7 @@ pub fn propose(e: &Env, signer: Address, action: ProposalAction) -> Result<u64,
 
     let id = storage::next_proposal_id(e);
     let now = e.ledger().sequence() as u64;
+    let executable_at = now.saturating_add(proposal_timelock(e, &action));
     let mut approvals = Vec::new(e);
     approvals.push_back(signer.clone());
 
@@ -247,15 +333,16 @@ pub fn propose(e: &Env, signer: Address, action: ProposalAction) -> Result<u64,
         proposer: signer.clone(),
         approvals,
         created_at: now,
-        expires_at: now.saturating_add(config.proposal_ttl),
+        executable_at,
+        expires_at: executable_at.saturating_add(config.proposal_ttl),
         executed: false,
     };
     storage::save_proposal(e, &proposal);
 
     events::proposal_created(e, id, signer, action);
 
-    // Auto-execute if threshold is 1.
-    if config.threshold == 1 {
+    // Auto-execute if threshold is 1 and no timelock applies.
+    if config.threshold == 1 && now >= executable_at {
         execute_proposal(e, id)?;
     }
 
@@ -290,7 +377,9 @@ pub fn approve(e: &Env, signer: Address, proposal_id: u64) -> Result<(), Contrac
 
     events::proposal_approved(e, proposal_id, signer, approval_count);
 
-    if approval_count >= config.threshold {
+    // Timelocked proposals wait for an explicit execute() once the delay passes.
+    if approval_count >= config.threshold && e.ledger().sequence() as u64 >= proposal.executable_at
+    {
         execute_proposal(e, proposal_id)?;
     }
 
@@ -313,11 +402,14 @@ pub fn execute_proposal(e: &Env, proposal_id: u64) -> Result<(), ContractError>
     if proposal.approvals.len() < config.threshold {
         return Err(ContractError::ThresholdNotMet);
     }
+    if (e.ledger().sequence() as u64) < proposal.executable_at {
+        panic_with_error!(e, ExtendedError::ProposalTimelocked);
+    }
 
     proposal.executed = true;
     storage::save_proposal(e, &proposal);
 
-    dispatch_action(e, proposal.action)?;
+    dispatch_action(e, proposal.proposer, proposal.action)?;
 
     events::proposal_executed(e, proposal_id);
     extend_instance_ttl(e);
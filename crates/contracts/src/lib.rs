@@ -9,8 +9,13 @@ pub mod adapters;
 pub mod constant_product_adapter;
 pub mod errors;
 pub mod events;
+pub mod flash;
 pub mod governance;
+pub mod oracle;
+pub mod price_impact;
 pub mod router;
+pub mod stable_math;
+pub mod stable_swap_adapter;
 pub mod storage;
 pub mod tokens;
 pub mod types;
@@ -0,0 +1,131 @@
+//! Time-weighted average prices from routed swaps.
+//!
+//! Every swap the router executes through a pool folds the pool's previous
+//! price into cumulative accumulators, Uniswap v2 style: the price recorded
+//! after one swap is weighted by the ledgers that pass until the next. A
+//! trade only moves the TWAP once later ledgers have elapsed at its price,
+//! so pushing a pool's reserves around within a single ledger does not
+//! shift it.
+//!
+//! Pools are only observed when the router trades through them; moves made
+//! elsewhere are picked up at the next routed swap.
+
+use crate::storage;
+use crate::types::{PoolOracle, PriceObservation, TwapPrice};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Fixed-point scale of prices (1.0 = 1_000_000_000).
+pub const PRICE_SCALE: i128 = 1_000_000_000;
+/// Minimum ledgers between two history snapshots (~1 minute).
+pub const OBSERVATION_SPACING: u32 = 12;
+/// Snapshots kept per pool; with the spacing above this covers at least
+/// ~64 minutes of history.
+pub const MAX_OBSERVATIONS: u32 = 64;
+
+/// Record `reserves` observed after a swap through `pool`.
+///
+/// Reserves of (0, 0) mean the read failed and are ignored.
+pub fn record(e: &Env, pool: &Address, reserves: (i128, i128)) {
+    if reserves.0 <= 0 || reserves.1 <= 0 {
+        return;
+    }
+    let now = e.ledger().sequence();
+
+    let oracle = match storage::get_pool_oracle(e, pool) {
+        None => {
+            let first = PriceObservation {
+                ledger: now,
+                price0_cumulative: 0,
+                price1_cumulative: 0,
+            };
+            PoolOracle {
+                reserve_0: reserves.0,
+                reserve_1: reserves.1,
+                latest: first.clone(),
+                history: Vec::from_array(e, [first]),
+            }
+        }
+        Some(mut oracle) => {
+            oracle.latest = accumulate(&oracle, now);
+            let due = match oracle.history.last() {
+                Some(last) => now >= last.ledger + OBSERVATION_SPACING,
+                None => true,
+            };
+            if due {
+                oracle.history.push_back(oracle.latest.clone());
+                if oracle.history.len() > MAX_OBSERVATIONS {
+                    oracle.history.pop_front();
+                }
+            }
+            oracle.reserve_0 = reserves.0;
+            oracle.reserve_1 = reserves.1;
+            oracle
+        }
+    };
+
+    storage::set_pool_oracle(e, pool, &oracle);
+}
+
+/// Average prices of `pool` over at least the last `window_ledgers`.
+///
+/// `None` when the window is 0 or the pool's history does not reach back
+/// that far.
+pub fn twap(e: &Env, pool: &Address, window_ledgers: u32) -> Option<TwapPrice> {
+    if window_ledgers == 0 {
+        return None;
+    }
+    let oracle = storage::get_pool_oracle(e, pool)?;
+    let now = e.ledger().sequence();
+    let target = now.checked_sub(window_ledgers)?;
+
+    // Newest snapshot at or before the start of the window
+    let mut start: Option<PriceObservation> = None;
+    for observation in oracle.history.iter() {
+        if observation.ledger > target {
+            break;
+        }
+        start = Some(observation);
+    }
+    let start = start?;
+
+    let current = accumulate(&oracle, now);
+    let elapsed = (now - start.ledger) as i128;
+    Some(TwapPrice {
+        price0: current
+            .price0_cumulative
+            .wrapping_sub(start.price0_cumulative)
+            / elapsed,
+        price1: current
+            .price1_cumulative
+            .wrapping_sub(start.price1_cumulative)
+            / elapsed,
+        window_ledgers: now - start.ledger,
+    })
+}
+
+/// Spot prices of `(reserve_0, reserve_1)`: token 0 in token 1 and the inverse.
+pub fn spot_prices(reserve_0: i128, reserve_1: i128) -> (i128, i128) {
+    (
+        reserve_1.saturating_mul(PRICE_SCALE) / reserve_0,
+        reserve_0.saturating_mul(PRICE_SCALE) / reserve_1,
+    )
+}
+
+/// Accumulators of `oracle` carried forward to ledger `now` at its last price.
+fn accumulate(oracle: &PoolOracle, now: u32) -> PriceObservation {
+    let latest = &oracle.latest;
+    if now <= latest.ledger {
+        return latest.clone();
+    }
+    let elapsed = (now - latest.ledger) as i128;
+    let (price0, price1) = spot_prices(oracle.reserve_0, oracle.reserve_1);
+    PriceObservation {
+        ledger: now,
+        price0_cumulative: latest
+            .price0_cumulative
+            .wrapping_add(price0.wrapping_mul(elapsed)),
+        price1_cumulative: latest
+            .price1_cumulative
+            .wrapping_add(price1.wrapping_mul(elapsed)),
+    }
+}
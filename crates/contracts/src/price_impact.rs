@@ -0,0 +1,84 @@
+//! Price impact from pool reserves.
+//!
+//! A hop's impact is how far its execution price falls short of the pool's
+//! spot price before the trade:
+//!
+//! `impact = 1 - (amount_out / amount_in) / (reserve_out / reserve_in)`
+//!
+//! Fees are part of the shortfall, matching the off-chain calculators in
+//! `stellarroute-routing::impact`. Route impact compounds the hops, so two
+//! 1% hops cost 199 bps rather than 200.
+
+use crate::errors::ContractError;
+
+const BPS: i128 = 10_000;
+
+/// Impact of one hop against `(reserve_in, reserve_out)`, in basis points.
+///
+/// Returns 0 when the reserves are unknown or the trade beat spot.
+pub fn hop_impact_bps(
+    reserve_in: i128,
+    reserve_out: i128,
+    amount_in: i128,
+    amount_out: i128,
+) -> Result<u32, ContractError> {
+    if reserve_in <= 0 || reserve_out <= 0 || amount_in <= 0 || amount_out < 0 {
+        return Ok(0);
+    }
+
+    // amount_out / amount_in as a fraction of reserve_out / reserve_in
+    let realized = amount_out
+        .checked_mul(reserve_in)
+        .and_then(|v| v.checked_mul(BPS))
+        .ok_or(ContractError::Overflow)?;
+    let spot = amount_in
+        .checked_mul(reserve_out)
+        .ok_or(ContractError::Overflow)?;
+    let realized_bps = realized / spot;
+
+    Ok((BPS - realized_bps).clamp(0, BPS) as u32)
+}
+
+/// Impact of one hop when the reserve order of the pool is not known.
+///
+/// Adapters report reserves in the pool's own order. A trade executes below
+/// spot in the true direction, so of the two orientations the one with the
+/// smaller non-zero shortfall is taken; if the trade beat spot either way
+/// the impact is 0.
+pub fn hop_impact_bps_unordered(
+    reserves: (i128, i128),
+    amount_in: i128,
+    amount_out: i128,
+) -> Result<u32, ContractError> {
+    let forward = hop_impact_bps(reserves.0, reserves.1, amount_in, amount_out)?;
+    let reverse = hop_impact_bps(reserves.1, reserves.0, amount_in, amount_out)?;
+    Ok(match (forward, reverse) {
+        (0, other) | (other, 0) => other,
+        (a, b) => a.min(b),
+    })
+}
+
+/// Orient `pre` by which reserve grew during the swap, then measure impact.
+///
+/// Falls back to [`hop_impact_bps_unordered`] when the pool's reserves did
+/// not move as a swap would move them.
+pub fn hop_impact_bps_executed(
+    pre: (i128, i128),
+    post: (i128, i128),
+    amount_in: i128,
+    amount_out: i128,
+) -> Result<u32, ContractError> {
+    if post.0 > pre.0 && post.1 < pre.1 {
+        hop_impact_bps(pre.0, pre.1, amount_in, amount_out)
+    } else if post.1 > pre.1 && post.0 < pre.0 {
+        hop_impact_bps(pre.1, pre.0, amount_in, amount_out)
+    } else {
+        hop_impact_bps_unordered(pre, amount_in, amount_out)
+    }
+}
+
+/// Fold one hop's impact into the route total.
+pub fn compound_impact_bps(total_bps: u32, hop_bps: u32) -> u32 {
+    let remaining = (BPS - total_bps.min(10_000) as i128) * (BPS - hop_bps.min(10_000) as i128);
+    (BPS - remaining / BPS) as u32
+}
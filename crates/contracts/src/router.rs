@@ -1,22 +1,26 @@
-use crate::adapters::AmmAdapter;
-use crate::errors::ContractError;
+use crate::adapters::{self, AmmAdapter};
+use crate::errors::{ContractError, ExtendedError};
 use crate::events;
+use crate::flash::FlashSwapReceiverClient;
 use crate::storage::{
     self, batch_check_pools, extend_instance_ttl, get_fee_rate, increment_nonce, transfer_asset,
     StorageKey, INSTANCE_TTL_EXTEND_TO, INSTANCE_TTL_THRESHOLD, POOL_TTL_EXTEND_TO,
     POOL_TTL_THRESHOLD,
 };
 use crate::types::{
-    CommitmentData, ContractVersion, DistributionRecord, FeeConfig, GovernanceConfig, MevConfig,
-    Proposal, ProposalAction, QuoteResult, Route, SwapParams, SwapResult, TTLStatus, TokenCategory,
-    TokenInfo,
+    ActionClass, CommitmentData, ContractVersion, DistributionRecord, FeeConfig, FlashSwapResult,
+    GovernanceConfig, MevConfig, Proposal, ProposalAction, QuoteResult, Route, SignedQuote,
+    SplitSwapParams, SplitSwapResult, SwapParams, SwapResult, TTLStatus, TokenCategory, TokenInfo,
+    TwapPrice,
 };
-use crate::{governance, tokens, upgrade};
+use crate::{governance, oracle, price_impact, tokens, upgrade};
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, Vec,
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, xdr::ToXdr, Address,
+    Bytes, BytesN, Env, Vec,
 };
 
 const MAX_HOPS: u32 = 4;
+const MAX_SPLIT_LEGS: u32 = 4;
 const BASE_CPU_PER_HOP: u64 = 5_000_000;
 const CCI_OVERHEAD: u64 = 1_000_000;
 
@@ -30,6 +34,14 @@ pub struct ResourceEstimate {
     pub will_succeed: bool,
 }
 
+/// Output of one route's hops, with the reserve snapshots taken around them.
+struct RouteExecution {
+    amount_out: i128,
+    impact_bps: u32,
+    pre_reserves: Vec<(i128, i128)>,
+    post_reserves: Vec<(i128, i128)>,
+}
+
 #[contract]
 pub struct StellarRoute;
 
@@ -326,6 +338,11 @@ impl StellarRoute {
         governance::get_proposal(&e, proposal_id)
     }
 
+    /// Read-only: ledgers proposals of `class` wait before they can execute.
+    pub fn get_timelock(e: Env, class: ActionClass) -> u64 {
+        governance::timelock(&e, class)
+    }
+
     // ── Upgrade entrypoints ───────────────────────────────────────────────────
 
     /// Propose a time-locked upgrade (single-admin mode only).
@@ -462,56 +479,116 @@ impl StellarRoute {
     // --- Admin MEV Configuration ---
 
     pub fn configure_mev(e: Env, config: MevConfig) -> Result<(), ContractError> {
-        storage::get_admin(&e).require_auth();
-        if config.commitment_required_above <= 0 {
-            return Err(ContractError::InvalidAmount);
-        }
-        if config.rate_limit_window_ledgers == 0 {
-            return Err(ContractError::InvalidAmount);
-        }
-        if config.rate_limit_max_swaps == 0 {
-            return Err(ContractError::InvalidAmount);
-        }
-        if config.max_price_impact_bps > 10_000 {
-            return Err(ContractError::InvalidAmount);
-        }
-        if config.max_execution_spread_bps > 10_000 {
-            return Err(ContractError::InvalidAmount);
+        if storage::is_multisig(&e) {
+            return Err(ContractError::UseGovernance);
         }
+        storage::get_admin(&e).require_auth();
+        validate_mev_config(&config)?;
         storage::set_mev_config(&e, &config);
         extend_instance_ttl(&e);
         Ok(())
     }
 
     pub fn set_whitelist(e: Env, address: Address, whitelisted: bool) -> Result<(), ContractError> {
+        if storage::is_multisig(&e) {
+            return Err(ContractError::UseGovernance);
+        }
         storage::get_admin(&e).require_auth();
         storage::set_whitelisted(&e, &address, whitelisted);
         extend_instance_ttl(&e);
         Ok(())
     }
 
+    /// Allow or revoke an ed25519 key that signs off-chain quotes.
+    pub fn set_quote_signer(
+        e: Env,
+        signer: BytesN<32>,
+        allowed: bool,
+    ) -> Result<(), ContractError> {
+        if storage::is_multisig(&e) {
+            return Err(ContractError::UseGovernance);
+        }
+        storage::get_admin(&e).require_auth();
+        storage::set_quote_signer(&e, &signer, allowed);
+        extend_instance_ttl(&e);
+        Ok(())
+    }
+
+    pub fn is_quote_signer(e: Env, signer: BytesN<32>) -> bool {
+        storage::is_quote_signer(&e, &signer)
+    }
+
+    /// Check that `quote` is unexpired and was signed by an allowlisted key.
+    ///
+    /// The signature is over `quote.to_xdr()`. An invalid signature traps
+    /// the host rather than returning an error.
+    pub fn verify_quote(
+        e: Env,
+        quote: SignedQuote,
+        signer: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        if !storage::is_quote_signer(&e, &signer) {
+            panic_with_error!(&e, ExtendedError::QuoteSignerNotAllowed);
+        }
+        let now_ms = e.ledger().timestamp().saturating_mul(1000);
+        if quote.expires_at <= now_ms {
+            panic_with_error!(&e, ExtendedError::QuoteExpired);
+        }
+        e.crypto()
+            .ed25519_verify(&signer, &quote.to_xdr(&e), &signature);
+        Ok(())
+    }
+
+    /// Store an admin-supplied reference price. Swap guards do not read it;
+    /// they use the pool TWAPs from `get_twap`.
     pub fn update_known_price(
         e: Env,
         token_a: Address,
         token_b: Address,
         price: i128,
     ) -> Result<(), ContractError> {
+        if storage::is_multisig(&e) {
+            return Err(ContractError::UseGovernance);
+        }
         storage::get_admin(&e).require_auth();
         storage::set_latest_known_price(&e, &token_a, &token_b, price);
         extend_instance_ttl(&e);
         Ok(())
     }
 
+    /// Time-weighted average prices of a registered pool over at least the
+    /// last `window_ledgers`, built from the router's own swaps.
+    pub fn get_twap(
+        e: Env,
+        pool: Address,
+        window_ledgers: u32,
+    ) -> Result<TwapPrice, ContractError> {
+        if !storage::is_supported_pool(&e, pool.clone()) {
+            return Err(ContractError::PoolNotSupported);
+        }
+        Ok(oracle::twap(&e, &pool, window_ledgers)
+            .unwrap_or_else(|| panic_with_error!(&e, ExtendedError::TwapUnavailable)))
+    }
+
     pub fn get_mev_config(e: Env) -> Result<MevConfig, ContractError> {
         storage::get_mev_config(&e).ok_or(ContractError::NotInitialized)
     }
 
     // --- Commit-Reveal Pattern ---
 
+    /// Commit to a swap without revealing it.
+    ///
+    /// `commitment_hash` is `sha256(xdr(SwapParams) || salt)`, so the route,
+    /// recipient and guards are all fixed at commit time. The deposit is
+    /// escrowed in the contract until the commitment is revealed or, after it
+    /// expires, reclaimed; it must be a Soroban token (`TokenNotAllowed`
+    /// otherwise), since only token contracts can be moved into escrow.
     pub fn commit_swap(
         e: Env,
         sender: Address,
         commitment_hash: BytesN<32>,
+        deposit_asset: crate::types::Asset,
         deposit_amount: i128,
     ) -> Result<(), ContractError> {
         sender.require_auth();
@@ -527,11 +604,29 @@ impl StellarRoute {
 
         let mev_config = storage::get_mev_config(&e).ok_or(ContractError::NotInitialized)?;
 
+        if storage::get_commitment(&e, &commitment_hash).is_some() {
+            panic_with_error!(&e, ExtendedError::CommitmentExists);
+        }
+        // Only token contracts can be moved into escrow
+        if !matches!(deposit_asset, crate::types::Asset::Soroban(_)) {
+            return Err(ContractError::TokenNotAllowed);
+        }
+
         let current_ledger = e.ledger().sequence();
         let expires_at = current_ledger + mev_config.rate_limit_window_ledgers;
 
+        transfer_asset(
+            &e,
+            &deposit_asset,
+            &sender,
+            &e.current_contract_address(),
+            deposit_amount,
+        );
+        storage::adjust_escrow_balance(&e, &deposit_asset, deposit_amount);
+
         let commitment = CommitmentData {
             sender: sender.clone(),
+            deposit_asset,
             deposit_amount,
             commitment_hash: commitment_hash.clone(),
             created_at: u64::from(current_ledger),
@@ -559,14 +654,7 @@ impl StellarRoute {
         sender.require_auth();
         StellarRoute::require_not_paused(&e)?;
 
-        // Recompute hash from params + salt
-        let mut payload = Bytes::new(&e);
-        payload.append(&Bytes::from_slice(&e, &params.amount_in.to_be_bytes()));
-        payload.append(&Bytes::from_slice(&e, &params.min_amount_out.to_be_bytes()));
-        payload.append(&Bytes::from_slice(&e, &params.deadline.to_be_bytes()));
-        let salt_bytes: Bytes = salt.into();
-        payload.append(&salt_bytes);
-        let computed_hash: BytesN<32> = e.crypto().sha256(&payload).into();
+        let computed_hash = Self::commitment_hash(e.clone(), params.clone(), salt);
 
         // Verify commitment exists
         let commitment =
@@ -588,7 +676,61 @@ impl StellarRoute {
         events::commitment_revealed(&e, sender.clone(), computed_hash);
 
         // Execute the swap using the internal logic
-        Self::execute_swap_internal(&e, &sender, &params)
+        let result = Self::execute_swap_internal(&e, &sender, &params)?;
+
+        // Release the escrow only once the committed swap has gone through
+        storage::adjust_escrow_balance(&e, &commitment.deposit_asset, -commitment.deposit_amount);
+        transfer_asset(
+            &e,
+            &commitment.deposit_asset,
+            &e.current_contract_address(),
+            &sender,
+            commitment.deposit_amount,
+        );
+
+        Ok(result)
+    }
+
+    /// Return the deposit of an expired, unrevealed commitment to its sender.
+    pub fn reclaim_commitment(
+        e: Env,
+        sender: Address,
+        commitment_hash: BytesN<32>,
+    ) -> Result<i128, ContractError> {
+        sender.require_auth();
+
+        let commitment = storage::get_commitment(&e, &commitment_hash)
+            .ok_or(ContractError::CommitmentNotFound)?;
+        if commitment.sender != sender {
+            return Err(ContractError::Unauthorized);
+        }
+        if u64::from(e.ledger().sequence()) <= commitment.expires_at {
+            panic_with_error!(&e, ExtendedError::CommitmentActive);
+        }
+
+        storage::remove_commitment(&e, &commitment_hash);
+        storage::adjust_escrow_balance(&e, &commitment.deposit_asset, -commitment.deposit_amount);
+        transfer_asset(
+            &e,
+            &commitment.deposit_asset,
+            &e.current_contract_address(),
+            &sender,
+            commitment.deposit_amount,
+        );
+
+        events::commitment_reclaimed(&e, sender, commitment_hash, commitment.deposit_amount);
+        extend_instance_ttl(&e);
+        Ok(commitment.deposit_amount)
+    }
+
+    /// Commitment hash for `params` and `salt`: `sha256(xdr(params) || salt)`.
+    ///
+    /// Read-only; lets clients check a hash built off-chain before committing.
+    pub fn commitment_hash(e: Env, params: SwapParams, salt: BytesN<32>) -> BytesN<32> {
+        let mut payload = params.to_xdr(&e);
+        let salt_bytes: Bytes = salt.into();
+        payload.append(&salt_bytes);
+        e.crypto().sha256(&payload).into()
     }
 
     // --- Core operations ---
@@ -658,9 +800,14 @@ impl StellarRoute {
         for i in 0..route.hops.len() {
             let hop = route.hops.get(i).unwrap();
 
-            current_amount =
-                AmmAdapter::quote(&e, &hop.pool, &hop.source, &hop.destination, current_amount)?;
-            total_impact_bps += 5;
+            let hop_in = current_amount;
+            current_amount = adapters::quote_hop(&e, &hop, hop_in)?;
+
+            // Impact against the pool's current spot price; unknown reserves count as 0
+            let reserves = AmmAdapter::get_reserves(&e, &hop.pool).unwrap_or((0_i128, 0_i128));
+            let hop_impact =
+                price_impact::hop_impact_bps_unordered(reserves, hop_in, current_amount)?;
+            total_impact_bps = price_impact::compound_impact_bps(total_impact_bps, hop_impact);
         }
 
         let fee_rate = get_fee_rate(&e);
@@ -733,6 +880,144 @@ impl StellarRoute {
         result
     }
 
+    /// Execute a split route atomically: each leg swaps its weighted share of
+    /// `amount_in`, and the combined output is paid to the recipient once.
+    ///
+    /// A leg that misses a hop minimum, its own `min_output` or the price
+    /// impact cap aborts every leg, as does a combined output below
+    /// `min_amount_out`. Each leg emits its own `swap` event.
+    pub fn execute_split(
+        e: Env,
+        sender: Address,
+        params: SplitSwapParams,
+    ) -> Result<SplitSwapResult, ContractError> {
+        sender.require_auth();
+        StellarRoute::require_not_paused(&e)?;
+
+        // Check commit-reveal requirement for large swaps
+        if let Some(mev_config) = storage::get_mev_config(&e) {
+            if params.amount_in >= mev_config.commitment_required_above {
+                return Err(ContractError::CommitmentRequired);
+            }
+        }
+
+        Self::execute_split_internal(&e, &sender, &params)
+    }
+
+    /// Flash swap: run `route` on the router's own balance of the source
+    /// token, send the output to `receiver`, call its `on_flash_swap`, and
+    /// require `amount_in` plus the protocol fee back before returning.
+    ///
+    /// The source asset must be a Soroban token so repayment can be checked
+    /// against the router's balance. Only the part of that balance not held
+    /// for commit-reveal escrow or undistributed fees can be lent, which
+    /// bounds `amount_in`.
+    pub fn flash_swap(
+        e: Env,
+        sender: Address,
+        receiver: Address,
+        route: Route,
+        amount_in: i128,
+        min_amount_out: i128,
+        data: Bytes,
+    ) -> Result<FlashSwapResult, ContractError> {
+        sender.require_auth();
+        StellarRoute::require_not_paused(&e)?;
+        // Hop shape, pool registration and the token allowlist
+        // (tokens::validate_route_assets).
+        Self::validate_route_internal(&e, &route)?;
+
+        if amount_in <= 0 || min_amount_out < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let router = e.current_contract_address();
+        if receiver == router {
+            return Err(ContractError::InvalidRecipient);
+        }
+        let (source, destination) = route_endpoints(&route)?;
+        let crate::types::Asset::Soroban(source_token) = &source else {
+            return Err(ContractError::InvalidRoute);
+        };
+
+        Self::apply_rate_limit(&e, &sender)?;
+
+        let token = soroban_sdk::token::Client::new(&e, source_token);
+        let balance_before = token.balance(&router);
+        let reserved = storage::get_escrow_balance(&e, &source)
+            .saturating_add(storage::get_fee_balance(&e, &source));
+        if balance_before.saturating_sub(reserved) < amount_in {
+            return Err(ContractError::InsufficientInput);
+        }
+
+        let execution = Self::execute_route(&e, &router, &route, amount_in)?;
+        let required_min_out = if route.min_output > min_amount_out {
+            route.min_output
+        } else {
+            min_amount_out
+        };
+        if execution.amount_out < required_min_out {
+            return Err(ContractError::SlippageExceeded);
+        }
+        Self::check_reserve_moves(&execution)?;
+        Self::check_twap_spread(&e, &route, amount_in, &execution)?;
+
+        let fee_amount = amount_in
+            .checked_mul(get_fee_rate(&e) as i128)
+            .ok_or(ContractError::Overflow)?
+            / 10_000;
+        let repay_amount = amount_in
+            .checked_add(fee_amount)
+            .ok_or(ContractError::Overflow)?;
+
+        transfer_asset(&e, &destination, &router, &receiver, execution.amount_out);
+        let callback = FlashSwapReceiverClient::new(&e, &receiver).try_on_flash_swap(
+            &sender,
+            &destination,
+            &execution.amount_out,
+            &source,
+            &repay_amount,
+            &data,
+        );
+        if callback.is_err() {
+            panic_with_error!(&e, ExtendedError::FlashCallbackFailed);
+        }
+
+        // The route spent amount_in of the balance; repayment must restore it
+        // and add the fee.
+        let required_balance = balance_before
+            .checked_add(fee_amount)
+            .ok_or(ContractError::Overflow)?;
+        if token.balance(&router) < required_balance {
+            return Err(ContractError::FlashNotRepaid);
+        }
+
+        Self::collect_fee(&e, &source, fee_amount);
+        increment_nonce(&e, sender.clone());
+        storage::add_swap_volume(&e, amount_in);
+
+        for i in 0..route.hops.len() {
+            storage::extend_pool_ttl(&e, &route.hops.get(i).unwrap().pool);
+        }
+        extend_instance_ttl(&e);
+        Self::check_ttl_health(&e);
+
+        events::flash_swap_executed(
+            &e,
+            sender,
+            receiver,
+            amount_in,
+            execution.amount_out,
+            fee_amount,
+        );
+
+        Ok(FlashSwapResult {
+            amount_in,
+            amount_out: execution.amount_out,
+            fee_amount,
+            executed_at: e.ledger().sequence() as u64,
+        })
+    }
+
     // --- Internal swap execution (shared by execute_swap and reveal_and_execute) ---
 
     fn execute_swap_internal(
@@ -782,86 +1067,18 @@ impl StellarRoute {
             if !storage::is_supported_pool(e, hop.pool.clone()) {
                 return Err(ContractError::PoolNotSupported);
             }
-        }
-
-        // 4. Rate limiting (if MEV config is set)
-        if let Some(mev_config) = storage::get_mev_config(e) {
-            if !storage::is_whitelisted(e, sender) {
-                let current_ledger = e.ledger().sequence();
-                let window_start = storage::get_account_swap_window_start(e, sender);
-                let swap_count = storage::get_account_swap_count(e, sender);
-
-                if swap_count > 0
-                    && current_ledger < window_start + mev_config.rate_limit_window_ledgers
-                {
-                    // Still within the window
-                    if swap_count >= mev_config.rate_limit_max_swaps {
-                        events::rate_limit_hit(
-                            e,
-                            sender.clone(),
-                            swap_count,
-                            mev_config.rate_limit_window_ledgers,
-                        );
-                        return Err(ContractError::RateLimitExceeded);
-                    }
-                    storage::set_account_swap_count(
-                        e,
-                        sender,
-                        swap_count + 1,
-                        mev_config.rate_limit_window_ledgers,
-                    );
-                } else {
-                    // Window expired or first swap — reset
-                    storage::set_account_swap_window_start(
-                        e,
-                        sender,
-                        current_ledger,
-                        mev_config.rate_limit_window_ledgers,
-                    );
-                    storage::set_account_swap_count(
-                        e,
-                        sender,
-                        1,
-                        mev_config.rate_limit_window_ledgers,
-                    );
-                }
+            if hop.min_amount_out < 0 {
+                return Err(ContractError::InvalidAmount);
             }
         }
 
-        // 5. Snapshot pool reserves before swap (for sandwich detection)
-        let mut pre_reserves: soroban_sdk::Vec<(i128, i128)> = soroban_sdk::Vec::new(e);
-        for i in 0..params.route.hops.len() {
-            let hop = params.route.hops.get(i).unwrap();
-            let reserves = AmmAdapter::get_reserves(e, &hop.pool).unwrap_or((0_i128, 0_i128));
-            pre_reserves.push_back(reserves);
-        }
-
-        // 6. Transfer input to first pool
-        let mut current_input_amount = params.amount_in;
-        let first_hop = params.route.hops.get(0).unwrap();
-        transfer_asset(
-            e,
-            &first_hop.source,
-            sender,
-            &first_hop.pool,
-            params.amount_in,
-        );
-
-        // 7. Execute swap hops
-        let mut total_impact_bps: u32 = 0;
-        for i in 0..params.route.hops.len() {
-            let hop = params.route.hops.get(i).unwrap();
+        // 4. Rate limiting (if MEV config is set)
+        Self::apply_rate_limit(e, sender)?;
 
-            current_input_amount = AmmAdapter::swap(
-                e,
-                &hop.pool,
-                &hop.source,
-                &hop.destination,
-                current_input_amount,
-                0,
-            )?;
-            total_impact_bps += 5;
-        }
+        // 5-7. Move the input through the hops, snapshotting reserves around them
+        let execution = Self::execute_route(e, sender, &params.route, params.amount_in)?;
+        let current_input_amount = execution.amount_out;
+        let total_impact_bps = execution.impact_bps;
 
         // 8. Calculate fees
         let fee_rate = get_fee_rate(e);
@@ -879,22 +1096,21 @@ impl StellarRoute {
             return Err(ContractError::PriceImpactTooHigh);
         }
 
-        // max_execution_spread_bps check (compare actual output vs expected)
-        if params.max_execution_spread_bps > 0 && params.route.estimated_output > 0 {
-            let spread = if final_output < params.route.estimated_output {
-                let diff = params
-                    .route
-                    .estimated_output
-                    .checked_sub(final_output)
-                    .ok_or(ContractError::Overflow)?;
-                diff.checked_mul(10000).ok_or(ContractError::Overflow)?
-                    / params.route.estimated_output
-            } else {
-                0
+        // max_execution_spread_bps check: the pools' pre-swap spot output
+        // against their TWAP output (both mid prices before fees), or the net
+        // output against the route estimate when no TWAP is configured or
+        // available
+        let (observed_output, reference_output) =
+            match Self::twap_spot_outputs(e, &params.route, params.amount_in, &execution)? {
+                Some(outputs) => outputs,
+                None => (final_output, params.route.estimated_output),
             };
-            if spread > params.max_execution_spread_bps as i128 {
-                return Err(ContractError::SpreadTooHigh);
-            }
+        if params.max_execution_spread_bps > 0 {
+            check_spread(
+                observed_output,
+                reference_output,
+                params.max_execution_spread_bps,
+            )?;
         }
 
         // Standard slippage check: enforce both request and route minimums.
@@ -908,27 +1124,7 @@ impl StellarRoute {
         }
 
         // 10. Post-swap reserve validation (sandwich detection)
-        for i in 0..params.route.hops.len() {
-            let hop = params.route.hops.get(i).unwrap();
-            let pre = pre_reserves.get(i).unwrap();
-            if pre.0 == 0 && pre.1 == 0 {
-                continue; // Skip if pre-snapshot wasn't available
-            }
-
-            if let Ok(post) = AmmAdapter::get_reserves(e, &hop.pool) {
-                // Check that reserves changed in the expected direction
-                // For a swap: one reserve goes up, one goes down
-                let delta_0 = post.0.checked_sub(pre.0).unwrap_or(i128::MIN);
-                let delta_1 = post.1.checked_sub(pre.1).unwrap_or(i128::MIN);
-                // If both reserves moved in the same direction, something is wrong
-                if delta_0 > 0 && delta_1 > 0 {
-                    return Err(ContractError::ReserveManipulationDetected);
-                }
-                if delta_0 < 0 && delta_1 < 0 {
-                    return Err(ContractError::ReserveManipulationDetected);
-                }
-            }
-        }
+        Self::check_reserve_moves(&execution)?;
 
         // 11. Emit high impact event if configured
         if let Some(mev_config) = storage::get_mev_config(e) {
@@ -953,19 +1149,7 @@ impl StellarRoute {
         );
 
         // ── Collect and Handle Distribution ──────────────────────────────
-        if fee_amount > 0 {
-            storage::add_fee_balance(e, &last_hop.destination, fee_amount);
-            events::fee_collected(e, last_hop.destination.clone(), fee_amount);
-
-            if let Some(config) = storage::get_fee_config(e) {
-                if config.auto_distribute {
-                    let current_balance = storage::get_fee_balance(e, &last_hop.destination);
-                    if current_balance >= config.min_distribution {
-                        Self::distribute_fees_internal(e, &last_hop.destination, &config);
-                    }
-                }
-            }
-        }
+        Self::collect_fee(e, &last_hop.destination, fee_amount);
         // ──────────────────────────────────────────────────────────────────────
 
         increment_nonce(e, sender.clone());
@@ -999,6 +1183,391 @@ impl StellarRoute {
         })
     }
 
+    fn execute_split_internal(
+        e: &Env,
+        sender: &Address,
+        params: &SplitSwapParams,
+    ) -> Result<SplitSwapResult, ContractError> {
+        if params.amount_in <= 0 || params.min_amount_out < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if params.max_price_impact_bps > 10_000 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if params.recipient == e.current_contract_address() {
+            return Err(ContractError::InvalidRecipient);
+        }
+        if params.recipient != *sender {
+            params.recipient.require_auth();
+        }
+
+        if e.ledger().sequence() as u64 > params.deadline {
+            return Err(ContractError::DeadlineExceeded);
+        }
+        if (e.ledger().sequence() as u64) < params.not_before {
+            return Err(ContractError::ExecutionTooEarly);
+        }
+
+        // Validate every leg before any transfers for fail-fast safety.
+        let legs = &params.split.legs;
+        if legs.is_empty() || legs.len() > MAX_SPLIT_LEGS {
+            return Err(ContractError::InvalidSplit);
+        }
+        let (source, destination) = route_endpoints(&legs.get(0).unwrap().route)?;
+        let mut total_weight: u32 = 0;
+        for i in 0..legs.len() {
+            let leg = legs.get(i).unwrap();
+            Self::validate_route_internal(e, &leg.route)?;
+            if leg.weight_bps == 0 {
+                return Err(ContractError::InvalidSplit);
+            }
+            total_weight = total_weight.saturating_add(leg.weight_bps);
+
+            let (leg_source, leg_destination) = route_endpoints(&leg.route)?;
+            if leg_source != source || leg_destination != destination {
+                return Err(ContractError::InvalidSplit);
+            }
+        }
+        if total_weight != 10_000 {
+            return Err(ContractError::InvalidSplit);
+        }
+
+        Self::apply_rate_limit(e, sender)?;
+
+        let fee_rate = get_fee_rate(e) as i128;
+        let mev_config = storage::get_mev_config(e);
+        let mut remaining_in = params.amount_in;
+        let mut total_out: i128 = 0;
+        let mut total_fee: i128 = 0;
+        let mut leg_outputs = Vec::new(e);
+        for i in 0..legs.len() {
+            let leg = legs.get(i).unwrap();
+
+            // The last leg takes the rounding remainder so the legs spend exactly amount_in
+            let leg_in = if i + 1 == legs.len() {
+                remaining_in
+            } else {
+                params
+                    .amount_in
+                    .checked_mul(leg.weight_bps as i128)
+                    .ok_or(ContractError::Overflow)?
+                    / 10_000
+            };
+            if leg_in <= 0 {
+                return Err(ContractError::InvalidAmount);
+            }
+            remaining_in -= leg_in;
+
+            let execution = Self::execute_route(e, sender, &leg.route, leg_in)?;
+            let leg_fee = execution
+                .amount_out
+                .checked_mul(fee_rate)
+                .ok_or(ContractError::Overflow)?
+                / 10_000;
+            let leg_out = execution.amount_out - leg_fee;
+
+            if params.max_price_impact_bps > 0 && execution.impact_bps > params.max_price_impact_bps
+            {
+                return Err(ContractError::PriceImpactTooHigh);
+            }
+            if leg_out < leg.route.min_output {
+                return Err(ContractError::SlippageExceeded);
+            }
+            Self::check_reserve_moves(&execution)?;
+            Self::check_twap_spread(e, &leg.route, leg_in, &execution)?;
+
+            if let Some(mev_config) = &mev_config {
+                if execution.impact_bps > mev_config.max_price_impact_bps {
+                    events::high_impact_swap(e, sender.clone(), execution.impact_bps, leg_in);
+                }
+            }
+
+            total_out = total_out
+                .checked_add(leg_out)
+                .ok_or(ContractError::Overflow)?;
+            total_fee = total_fee
+                .checked_add(leg_fee)
+                .ok_or(ContractError::Overflow)?;
+            leg_outputs.push_back(leg_out);
+            events::swap_executed(
+                e,
+                sender.clone(),
+                leg_in,
+                leg_out,
+                leg_fee,
+                leg.route.clone(),
+            );
+        }
+
+        if total_out < params.min_amount_out {
+            return Err(ContractError::SlippageExceeded);
+        }
+
+        transfer_asset(
+            e,
+            &destination,
+            &e.current_contract_address(),
+            &params.recipient,
+            total_out,
+        );
+        Self::collect_fee(e, &destination, total_fee);
+
+        increment_nonce(e, sender.clone());
+        storage::add_swap_volume(e, params.amount_in);
+
+        for i in 0..legs.len() {
+            let route = legs.get(i).unwrap().route;
+            for j in 0..route.hops.len() {
+                storage::extend_pool_ttl(e, &route.hops.get(j).unwrap().pool);
+            }
+        }
+        extend_instance_ttl(e);
+        Self::check_ttl_health(e);
+
+        Ok(SplitSwapResult {
+            amount_in: params.amount_in,
+            amount_out: total_out,
+            fee_amount: total_fee,
+            leg_outputs,
+            executed_at: e.ledger().sequence() as u64,
+        })
+    }
+
+    /// Enforce the per-window swap rate limit for `sender` (if MEV config is set).
+    fn apply_rate_limit(e: &Env, sender: &Address) -> Result<(), ContractError> {
+        let Some(mev_config) = storage::get_mev_config(e) else {
+            return Ok(());
+        };
+        if storage::is_whitelisted(e, sender) {
+            return Ok(());
+        }
+
+        let current_ledger = e.ledger().sequence();
+        let window_start = storage::get_account_swap_window_start(e, sender);
+        let swap_count = storage::get_account_swap_count(e, sender);
+
+        if swap_count > 0 && current_ledger < window_start + mev_config.rate_limit_window_ledgers {
+            // Still within the window
+            if swap_count >= mev_config.rate_limit_max_swaps {
+                events::rate_limit_hit(
+                    e,
+                    sender.clone(),
+                    swap_count,
+                    mev_config.rate_limit_window_ledgers,
+                );
+                return Err(ContractError::RateLimitExceeded);
+            }
+            storage::set_account_swap_count(
+                e,
+                sender,
+                swap_count + 1,
+                mev_config.rate_limit_window_ledgers,
+            );
+        } else {
+            // Window expired or first swap — reset
+            storage::set_account_swap_window_start(
+                e,
+                sender,
+                current_ledger,
+                mev_config.rate_limit_window_ledgers,
+            );
+            storage::set_account_swap_count(e, sender, 1, mev_config.rate_limit_window_ledgers);
+        }
+        Ok(())
+    }
+
+    /// Pull `amount_in` from `sender` into the first pool and swap it through
+    /// every hop of `route`, checking each hop's `min_amount_out`.
+    ///
+    /// The output stays with the router; the caller applies fees and pays out.
+    fn execute_route(
+        e: &Env,
+        sender: &Address,
+        route: &Route,
+        amount_in: i128,
+    ) -> Result<RouteExecution, ContractError> {
+        // Snapshot pool reserves before swap (for sandwich detection)
+        let mut pre_reserves: soroban_sdk::Vec<(i128, i128)> = soroban_sdk::Vec::new(e);
+        for i in 0..route.hops.len() {
+            let hop = route.hops.get(i).unwrap();
+            let reserves = AmmAdapter::get_reserves(e, &hop.pool).unwrap_or((0_i128, 0_i128));
+            pre_reserves.push_back(reserves);
+        }
+
+        // Transfer input to first pool
+        let first_hop = route.hops.get(0).unwrap();
+        transfer_asset(e, &first_hop.source, sender, &first_hop.pool, amount_in);
+
+        // Execute swap hops
+        let mut current_input_amount = amount_in;
+        let mut hop_amounts: soroban_sdk::Vec<(i128, i128)> = soroban_sdk::Vec::new(e);
+        for i in 0..route.hops.len() {
+            let hop = route.hops.get(i).unwrap();
+
+            let hop_in = current_input_amount;
+            // Quote guarded hops first so a shortfall is reported as this
+            // hop's failure rather than as the pool's own revert.
+            if hop.min_amount_out > 0 && adapters::quote_hop(e, &hop, hop_in)? < hop.min_amount_out
+            {
+                return Err(ContractError::HopSlippageExceeded);
+            }
+            // The pool still enforces the hop minimum itself; the check below
+            // also stops a pool that returns less without reverting.
+            current_input_amount = adapters::swap_hop(e, &hop, hop_in, hop.min_amount_out)?;
+            if current_input_amount < hop.min_amount_out {
+                return Err(ContractError::HopSlippageExceeded);
+            }
+            hop_amounts.push_back((hop_in, current_input_amount));
+        }
+
+        // Snapshot reserves after the swap; (0, 0) marks an unavailable read
+        let mut post_reserves: soroban_sdk::Vec<(i128, i128)> = soroban_sdk::Vec::new(e);
+        for i in 0..route.hops.len() {
+            let hop = route.hops.get(i).unwrap();
+            let reserves = AmmAdapter::get_reserves(e, &hop.pool).unwrap_or((0_i128, 0_i128));
+            post_reserves.push_back(reserves);
+            oracle::record(e, &hop.pool, reserves);
+        }
+
+        // Route impact: each hop's execution price against its pre-swap spot price
+        let mut impact_bps: u32 = 0;
+        for i in 0..route.hops.len() {
+            let (hop_in, hop_out) = hop_amounts.get(i).unwrap();
+            let hop_impact = price_impact::hop_impact_bps_executed(
+                pre_reserves.get(i).unwrap(),
+                post_reserves.get(i).unwrap(),
+                hop_in,
+                hop_out,
+            )?;
+            impact_bps = price_impact::compound_impact_bps(impact_bps, hop_impact);
+        }
+
+        Ok(RouteExecution {
+            amount_out: current_input_amount,
+            impact_bps,
+            pre_reserves,
+            post_reserves,
+        })
+    }
+
+    /// Output `amount_in` would fetch along `route` at each pool's pre-swap
+    /// spot price and at its TWAP over the configured MEV window, as
+    /// `(spot, twap)`. Both are mid prices before fees and impact, so a pool
+    /// pushed off its average before the swap shows up as spot below TWAP.
+    ///
+    /// `None` when no window is configured, a pool lacks enough history or a
+    /// reserve read, or a hop's direction cannot be told from its reserve moves.
+    fn twap_spot_outputs(
+        e: &Env,
+        route: &Route,
+        amount_in: i128,
+        execution: &RouteExecution,
+    ) -> Result<Option<(i128, i128)>, ContractError> {
+        let window = match storage::get_mev_config(e) {
+            Some(config) if config.twap_window_ledgers > 0 => config.twap_window_ledgers,
+            _ => return Ok(None),
+        };
+
+        let mut spot_output = amount_in;
+        let mut twap_output = amount_in;
+        for i in 0..route.hops.len() {
+            let hop = route.hops.get(i).unwrap();
+            let Some(twap) = oracle::twap(e, &hop.pool, window) else {
+                return Ok(None);
+            };
+            let pre = execution.pre_reserves.get(i).unwrap();
+            let post = execution.post_reserves.get(i).unwrap();
+            if pre.0 <= 0 || pre.1 <= 0 {
+                return Ok(None);
+            }
+            // The reserve that grew is the one the hop sold into
+            let (price, reserve_in, reserve_out) = if post.0 > pre.0 && post.1 < pre.1 {
+                (twap.price0, pre.0, pre.1)
+            } else if post.1 > pre.1 && post.0 < pre.0 {
+                (twap.price1, pre.1, pre.0)
+            } else {
+                return Ok(None);
+            };
+            spot_output = spot_output
+                .checked_mul(reserve_out)
+                .ok_or(ContractError::Overflow)?
+                / reserve_in;
+            twap_output = twap_output
+                .checked_mul(price)
+                .ok_or(ContractError::Overflow)?
+                / oracle::PRICE_SCALE;
+        }
+        Ok(Some((spot_output, twap_output)))
+    }
+
+    /// TWAP spread guard for split legs and flash swaps, whose parameters
+    /// carry no spread cap of their own: applies `MevConfig`'s
+    /// `max_execution_spread_bps` when a TWAP window is configured.
+    fn check_twap_spread(
+        e: &Env,
+        route: &Route,
+        amount_in: i128,
+        execution: &RouteExecution,
+    ) -> Result<(), ContractError> {
+        let max_spread_bps = match storage::get_mev_config(e) {
+            Some(config) if config.max_execution_spread_bps > 0 => config.max_execution_spread_bps,
+            _ => return Ok(()),
+        };
+        match Self::twap_spot_outputs(e, route, amount_in, execution)? {
+            Some((spot_output, twap_output)) => {
+                check_spread(spot_output, twap_output, max_spread_bps)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Reject executions where a pool's reserves did not move as a swap would.
+    fn check_reserve_moves(execution: &RouteExecution) -> Result<(), ContractError> {
+        for i in 0..execution.pre_reserves.len() {
+            let pre = execution.pre_reserves.get(i).unwrap();
+            if pre.0 == 0 && pre.1 == 0 {
+                continue; // Skip if pre-snapshot wasn't available
+            }
+
+            let post = execution.post_reserves.get(i).unwrap();
+            if post.0 == 0 && post.1 == 0 {
+                continue; // Skip if post-snapshot wasn't available
+            }
+
+            // Check that reserves changed in the expected direction
+            // For a swap: one reserve goes up, one goes down
+            let delta_0 = post.0.checked_sub(pre.0).unwrap_or(i128::MIN);
+            let delta_1 = post.1.checked_sub(pre.1).unwrap_or(i128::MIN);
+            // If both reserves moved in the same direction, something is wrong
+            if delta_0 > 0 && delta_1 > 0 {
+                return Err(ContractError::ReserveManipulationDetected);
+            }
+            if delta_0 < 0 && delta_1 < 0 {
+                return Err(ContractError::ReserveManipulationDetected);
+            }
+        }
+        Ok(())
+    }
+
+    /// Book `fee_amount` of `asset` and auto-distribute when configured.
+    fn collect_fee(e: &Env, asset: &crate::types::Asset, fee_amount: i128) {
+        if fee_amount <= 0 {
+            return;
+        }
+        storage::add_fee_balance(e, asset, fee_amount);
+        events::fee_collected(e, asset.clone(), fee_amount);
+
+        if let Some(config) = storage::get_fee_config(e) {
+            if config.auto_distribute {
+                let current_balance = storage::get_fee_balance(e, asset);
+                if current_balance >= config.min_distribution {
+                    Self::distribute_fees_internal(e, asset, &config);
+                }
+            }
+        }
+    }
+
     fn validate_route_internal(e: &Env, route: &Route) -> Result<(), ContractError> {
         if route.hops.is_empty() {
             return Err(ContractError::EmptyRoute);
@@ -1016,6 +1585,11 @@ impl StellarRoute {
         if route.estimated_output > 0 && route.min_output > route.estimated_output {
             return Err(ContractError::InvalidRoute);
         }
+        for i in 0..route.hops.len() {
+            if route.hops.get(i).unwrap().min_amount_out < 0 {
+                return Err(ContractError::InvalidAmount);
+            }
+        }
 
         // Enforce hop-to-hop asset continuity.
         for i in 0..route.hops.len().saturating_sub(1) {
@@ -1106,3 +1680,50 @@ impl StellarRoute {
         }
     }
 }
+
+/// Bounds shared by `configure_mev` and `ProposalAction::ConfigureMev`.
+pub(crate) fn validate_mev_config(config: &MevConfig) -> Result<(), ContractError> {
+    if config.commitment_required_above <= 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    if config.rate_limit_window_ledgers == 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    if config.rate_limit_max_swaps == 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+    if config.max_price_impact_bps > 10_000 {
+        return Err(ContractError::InvalidAmount);
+    }
+    if config.max_execution_spread_bps > 10_000 {
+        return Err(ContractError::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Source asset of a route's first hop and destination of its last.
+fn route_endpoints(
+    route: &Route,
+) -> Result<(crate::types::Asset, crate::types::Asset), ContractError> {
+    let first = route.hops.first().ok_or(ContractError::EmptyRoute)?;
+    let last = route.hops.last().ok_or(ContractError::EmptyRoute)?;
+    Ok((first.source, last.destination))
+}
+
+/// Fail with `SpreadTooHigh` when `observed` falls more than `max_spread_bps`
+/// below `reference`; a non-positive reference disables the check.
+fn check_spread(observed: i128, reference: i128, max_spread_bps: u32) -> Result<(), ContractError> {
+    if reference <= 0 || observed >= reference {
+        return Ok(());
+    }
+    let spread = reference
+        .checked_sub(observed)
+        .ok_or(ContractError::Overflow)?
+        .checked_mul(10_000)
+        .ok_or(ContractError::Overflow)?
+        / reference;
+    if spread > max_spread_bps as i128 {
+        return Err(ContractError::SpreadTooHigh);
+    }
+    Ok(())
+}
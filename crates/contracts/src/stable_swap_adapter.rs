@@ -0,0 +1,111 @@
+use crate::adapters::PoolAdapterTrait;
+use crate::errors::ContractError;
+use crate::stable_math::{self, MAX_AMP};
+use crate::types::Asset;
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, symbol_short, vec, Address, Env, IntoVal,
+};
+
+#[contract]
+pub struct StableSwapAdapter;
+
+#[contractimpl]
+impl StableSwapAdapter {
+    /// Bind the adapter to a pool. `token0` is the asset whose reserve
+    /// `get_rsrvs` reports first; `amp` is the pool's amplification
+    /// coefficient and `fee_bps` its swap fee.
+    pub fn initialize(
+        e: Env,
+        pool: Address,
+        token0: Asset,
+        amp: u32,
+        fee_bps: u32,
+    ) -> Result<(), ContractError> {
+        if e.storage().instance().has(&symbol_short!("POOL")) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+        if amp == 0 || amp > MAX_AMP || fee_bps > 10_000 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        e.storage().instance().set(&symbol_short!("POOL"), &pool);
+        e.storage()
+            .instance()
+            .set(&symbol_short!("TOKEN0"), &token0);
+        e.storage().instance().set(&symbol_short!("AMP"), &amp);
+        e.storage().instance().set(&symbol_short!("FEE"), &fee_bps);
+        Ok(())
+    }
+
+    pub fn get_amp(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .get(&symbol_short!("AMP"))
+            .unwrap_or_else(|| panic_with_error!(&e, ContractError::NotInitialized))
+    }
+
+    /// `(token0, amp, fee_bps)`, which the router needs to price a hop on
+    /// the curve itself.
+    pub fn get_params(e: Env) -> (Asset, u32, u32) {
+        let token0: Asset = e
+            .storage()
+            .instance()
+            .get(&symbol_short!("TOKEN0"))
+            .unwrap_or_else(|| panic_with_error!(&e, ContractError::NotInitialized));
+        let fee_bps: u32 = e
+            .storage()
+            .instance()
+            .get(&symbol_short!("FEE"))
+            .unwrap_or(0);
+        (token0, Self::get_amp(e), fee_bps)
+    }
+}
+
+#[contractimpl]
+impl PoolAdapterTrait for StableSwapAdapter {
+    fn swap(
+        e: Env,
+        input_asset: Asset,
+        output_asset: Asset,
+        amount_in: i128,
+        min_out: i128,
+    ) -> i128 {
+        let pool_address = pool(&e);
+        e.invoke_contract(
+            &pool_address,
+            &symbol_short!("swap"),
+            vec![
+                &e,
+                input_asset.into_val(&e),
+                output_asset.into_val(&e),
+                amount_in.into_val(&e),
+                min_out.into_val(&e),
+            ],
+        )
+    }
+
+    fn adapter_quote(e: Env, input_asset: Asset, _output_asset: Asset, amount_in: i128) -> i128 {
+        let (res0, res1) = Self::get_rsrvs(e.clone());
+        let (token0, amp, fee_bps) = Self::get_params(e.clone());
+        let (res_in, res_out) = if input_asset == token0 {
+            (res0, res1)
+        } else {
+            (res1, res0)
+        };
+
+        stable_math::get_dy(amount_in, res_in, res_out, amp, fee_bps)
+            .unwrap_or_else(|err| panic_with_error!(&e, err))
+    }
+
+    fn get_rsrvs(e: Env) -> (i128, i128) {
+        let pool_address = pool(&e);
+        e.invoke_contract(&pool_address, &symbol_short!("get_rsrvs"), vec![&e])
+    }
+}
+
+fn pool(e: &Env) -> Address {
+    e.storage()
+        .instance()
+        .get(&symbol_short!("POOL"))
+        .unwrap_or_else(|| panic_with_error!(e, ContractError::NotInitialized))
+}
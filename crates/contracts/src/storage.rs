@@ -1,6 +1,6 @@
 use crate::types::{
-    Asset, CommitmentData, ContractVersion, DistributionRecord, FeeConfig, GovernanceConfig,
-    MevConfig, PendingUpgrade, Proposal, TokenCategory, TokenInfo,
+    ActionClass, Asset, CommitmentData, ContractVersion, DistributionRecord, FeeConfig,
+    GovernanceConfig, MevConfig, PendingUpgrade, PoolOracle, Proposal, TokenCategory, TokenInfo,
 };
 use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
 
@@ -44,6 +44,10 @@ pub enum StorageKey {
     VersionHistory(u64),
     MigrationDone(u32, u32, u32),
     AllowedToken(Asset),
+    PoolOracle(Address),
+    ActionTimelock(ActionClass),
+    QuoteSigner(BytesN<32>),
+    EscrowBalance(Asset),
 }
 
 // ── TTL Constants (in ledger sequences, ~5s per ledger) ──────────────────
@@ -309,6 +313,18 @@ pub fn save_proposal(e: &Env, proposal: &Proposal) {
     e.storage().persistent().extend_ttl(&key, 17280, 17280 * 30);
 }
 
+pub fn get_action_timelock(e: &Env, class: ActionClass) -> Option<u64> {
+    e.storage()
+        .instance()
+        .get(&StorageKey::ActionTimelock(class))
+}
+
+pub fn set_action_timelock(e: &Env, class: ActionClass, ledgers: u64) {
+    e.storage()
+        .instance()
+        .set(&StorageKey::ActionTimelock(class), &ledgers);
+}
+
 // ─── Upgrade helpers ─────────────────────────────────────────────────────────
 
 pub fn get_contract_version(e: &Env) -> Option<ContractVersion> {
@@ -409,24 +425,42 @@ pub fn set_mev_config(e: &Env, config: &MevConfig) {
     e.storage().instance().set(&StorageKey::MevConfig, config);
 }
 
-// --- Commitment storage (Temporary) ---
+// --- Commitment storage (Persistent) ---
+// Commitments hold escrowed deposits, so they must outlive their reveal
+// window: the entry stays live for COMMITMENT_TTL ledgers past expiry so the
+// sender can reclaim the deposit.
 
 pub fn get_commitment(e: &Env, hash: &BytesN<32>) -> Option<CommitmentData> {
     let key = StorageKey::Commitment(hash.clone());
-    e.storage().temporary().get(&key)
+    e.storage().persistent().get(&key)
 }
 
 pub fn set_commitment(e: &Env, hash: &BytesN<32>, data: &CommitmentData, ttl_ledgers: u32) {
     let key = StorageKey::Commitment(hash.clone());
-    e.storage().temporary().set(&key, data);
-    e.storage()
-        .temporary()
-        .extend_ttl(&key, ttl_ledgers, ttl_ledgers);
+    let ttl = ttl_ledgers.saturating_add(COMMITMENT_TTL);
+    e.storage().persistent().set(&key, data);
+    e.storage().persistent().extend_ttl(&key, ttl, ttl);
 }
 
 pub fn remove_commitment(e: &Env, hash: &BytesN<32>) {
     let key = StorageKey::Commitment(hash.clone());
-    e.storage().temporary().remove(&key);
+    e.storage().persistent().remove(&key);
+}
+
+/// Total of `asset` held in escrow for pending commitments.
+pub fn get_escrow_balance(e: &Env, asset: &Asset) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&StorageKey::EscrowBalance(asset.clone()))
+        .unwrap_or(0)
+}
+
+/// Adjust the escrowed total of `asset` by `delta` (negative on release).
+pub fn adjust_escrow_balance(e: &Env, asset: &Asset, delta: i128) {
+    let key = StorageKey::EscrowBalance(asset.clone());
+    let balance = get_escrow_balance(e, asset).saturating_add(delta).max(0);
+    e.storage().persistent().set(&key, &balance);
+    extend_persistent_ttl(e, &key, POOL_TTL_THRESHOLD, POOL_TTL_EXTEND_TO);
 }
 
 // --- Rate limiting (Temporary) ---
@@ -474,6 +508,25 @@ pub fn set_whitelisted(e: &Env, address: &Address, whitelisted: bool) {
     }
 }
 
+// --- Quote signers (Persistent) ---
+
+pub fn is_quote_signer(e: &Env, signer: &BytesN<32>) -> bool {
+    let key = StorageKey::QuoteSigner(signer.clone());
+    e.storage().persistent().get(&key).unwrap_or(false)
+}
+
+pub fn set_quote_signer(e: &Env, signer: &BytesN<32>, allowed: bool) {
+    let key = StorageKey::QuoteSigner(signer.clone());
+    if allowed {
+        e.storage().persistent().set(&key, &true);
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, POOL_TTL_THRESHOLD, POOL_TTL_EXTEND_TO);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
 // --- Latest known price (Instance) ---
 
 pub fn get_latest_known_price(e: &Env, token_a: &Address, token_b: &Address) -> Option<i128> {
@@ -486,6 +539,22 @@ pub fn set_latest_known_price(e: &Env, token_a: &Address, token_b: &Address, pri
     e.storage().instance().set(&key, &price);
 }
 
+// --- Pool TWAP oracle (Persistent) ---
+
+pub fn get_pool_oracle(e: &Env, pool: &Address) -> Option<PoolOracle> {
+    e.storage()
+        .persistent()
+        .get(&StorageKey::PoolOracle(pool.clone()))
+}
+
+pub fn set_pool_oracle(e: &Env, pool: &Address, oracle: &PoolOracle) {
+    let key = StorageKey::PoolOracle(pool.clone());
+    e.storage().persistent().set(&key, oracle);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, POOL_TTL_THRESHOLD, POOL_TTL_EXTEND_TO);
+}
+
 // ─── Fee Distribution Storage Helpers ────────────────────────────────────────
 
 pub fn get_fee_config(e: &Env) -> Option<FeeConfig> {
@@ -19,8 +19,10 @@ use soroban_sdk::{
 
 use super::{
     adapters::AmmAdapter,
-    errors::ContractError,
+    errors::{ContractError, ExtendedError},
     router::{StellarRoute, StellarRouteClient},
+    stable_math,
+    stable_swap_adapter::{StableSwapAdapter, StableSwapAdapterClient},
     types::{
         Asset, FeeConfig, FeeRecipient, PoolType, ProposalAction, Route, RouteHop, SwapParams,
     },
@@ -166,10 +168,55 @@ mod mock_reserves_failing {
     }
 }
 
+mod mock_stable_pool {
+    use super::super::{stable_math, types::Asset};
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    /// Imbalanced stable pool (150k / 50k in e6) that swaps on the same
+    /// curve as `StableSwapAdapter` with amp 100 and a 4 bps fee.
+    #[contract]
+    pub struct MockStablePool;
+
+    #[contractimpl]
+    impl MockStablePool {
+        pub fn swap(_e: Env, _in: Asset, _out: Asset, amount: i128, _min: i128) -> i128 {
+            stable_math::get_dy(amount, 150_000_000_000, 50_000_000_000, 100, 4).unwrap()
+        }
+
+        pub fn get_rsrvs(_e: Env) -> (i128, i128) {
+            (150_000_000_000, 50_000_000_000)
+        }
+    }
+}
+
+mod mock_pegged_pool {
+    use super::super::types::Asset;
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    /// Balanced pegged pool (100k / 100k in e6) that quotes itself with
+    /// constant-product math and a 4 bps fee.
+    #[contract]
+    pub struct MockPeggedPool;
+
+    #[contractimpl]
+    impl MockPeggedPool {
+        pub fn adapter_quote(_e: Env, _in: Asset, _out: Asset, amount: i128) -> i128 {
+            let amount_with_fee = amount * 9_996 / 10_000;
+            amount_with_fee * 100_000_000_000 / (100_000_000_000 + amount_with_fee)
+        }
+
+        pub fn get_rsrvs(_e: Env) -> (i128, i128) {
+            (100_000_000_000, 100_000_000_000)
+        }
+    }
+}
+
 use mock_amm::MockAmmPool;
 use mock_failing::MockFailingPool;
+use mock_pegged_pool::MockPeggedPool;
 use mock_quote_failing::MockQuoteFailingPool;
 use mock_reserves_failing::MockReservesFailingPool;
+use mock_stable_pool::MockStablePool;
 use mock_swap_failing::MockSwapFailingPool;
 
 // ── Test Utilities ────────────────────────────────────────────────────────────
@@ -238,6 +285,7 @@ pub(crate) fn make_route(env: &Env, pool: &Address, hops: u32) -> Route {
             destination: Asset::Native,
             pool: pool.clone(),
             pool_type: PoolType::AmmConstProd,
+            min_amount_out: 0,
         });
     }
     Route {
@@ -637,12 +685,14 @@ fn test_validate_route_hop_continuity_enforced() {
         destination: Asset::Native,
         pool: pool.clone(),
         pool_type: PoolType::AmmConstProd,
+        min_amount_out: 0,
     });
     v.push_back(RouteHop {
         source: Asset::Soroban(Address::generate(&env)),
         destination: Asset::Native,
         pool: pool.clone(),
         pool_type: PoolType::AmmConstProd,
+        min_amount_out: 0,
     });
     let route = Route {
         hops: v,
@@ -2326,3 +2376,502 @@ fn test_burn_recipient_tracking() {
 
     assert_eq!(client.get_total_fees_burned(&Asset::Native), 2970);
 }
+
+// ── StableSwap Adapter Tests ──────────────────────────────────────────────────
+
+/// Deploy a stable pool behind a `StableSwapAdapter` with `token0` first.
+fn deploy_stable_adapter(env: &Env, token0: &Asset) -> Address {
+    let pool = env.register_contract(None, MockStablePool);
+    let adapter = env.register_contract(None, StableSwapAdapter);
+    StableSwapAdapterClient::new(env, &adapter).initialize(&pool, token0, &100_u32, &4_u32);
+    adapter
+}
+
+fn stable_route(env: &Env, adapter: &Address, source: &Asset, destination: &Asset) -> Route {
+    let mut hops = Vec::new(env);
+    hops.push_back(RouteHop {
+        source: source.clone(),
+        destination: destination.clone(),
+        pool: adapter.clone(),
+        pool_type: PoolType::AmmStable,
+        min_amount_out: 0,
+    });
+    Route {
+        hops,
+        estimated_output: 0,
+        min_output: 0,
+        expires_at: 99_999,
+    }
+}
+
+#[test]
+fn test_stable_math_matches_routing_vectors() {
+    // Same vectors as `StableSwapCalculator` in stellarroute-routing
+    assert_eq!(
+        stable_math::get_dy(1_000_000_000, 100_000_000_000, 100_000_000_000, 100, 4),
+        Ok(999_501_030)
+    );
+    assert_eq!(
+        stable_math::get_dy(1_000_000_000, 150_000_000_000, 50_000_000_000, 100, 4),
+        Ok(981_979_550)
+    );
+}
+
+#[test]
+fn test_stable_math_rejects_invalid_amp() {
+    assert_eq!(
+        stable_math::get_d(1_000, 1_000, 0),
+        Err(ContractError::InvalidAmount)
+    );
+    assert_eq!(
+        stable_math::get_d(1_000, 1_000, stable_math::MAX_AMP + 1),
+        Err(ContractError::InvalidAmount)
+    );
+}
+
+#[test]
+fn test_stable_adapter_quote_orients_reserves() {
+    let env = setup_env();
+    let usdc = Asset::Issued(Address::generate(&env), Symbol::new(&env, "USDC"));
+    let eurt = Asset::Issued(Address::generate(&env), Symbol::new(&env, "EURT"));
+    let adapter = deploy_stable_adapter(&env, &usdc);
+    let client = StableSwapAdapterClient::new(&env, &adapter);
+
+    assert_eq!(client.get_amp(), 100);
+    // Selling the abundant side gets less than 1:1
+    assert_eq!(
+        client.adapter_quote(&usdc, &eurt, &1_000_000_000),
+        981_979_550
+    );
+    // Selling the scarce side gets more than 1:1
+    assert!(client.adapter_quote(&eurt, &usdc, &1_000_000_000) > 1_000_000_000);
+}
+
+#[test]
+fn test_stable_adapter_initialize_twice_fails() {
+    let env = setup_env();
+    let adapter = deploy_stable_adapter(&env, &Asset::Native);
+    let pool = Address::generate(&env);
+    assert_eq!(
+        StableSwapAdapterClient::new(&env, &adapter).try_initialize(
+            &pool,
+            &Asset::Native,
+            &100_u32,
+            &4_u32
+        ),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_get_quote_dispatches_stable_hop() {
+    let env = setup_env();
+    let (_, _, client) = deploy_router(&env);
+    let usdc = Asset::Issued(Address::generate(&env), Symbol::new(&env, "USDC"));
+    let eurt = Asset::Issued(Address::generate(&env), Symbol::new(&env, "EURT"));
+    let adapter = deploy_stable_adapter(&env, &usdc);
+    client.register_pool(&adapter);
+
+    let quote = client.get_quote(&1_000_000_000, &stable_route(&env, &adapter, &usdc, &eurt));
+    // 981_979_550 from the curve, less the 30 bps protocol fee
+    assert_eq!(quote.fee_amount, 2_945_938);
+    assert_eq!(quote.expected_output, 979_033_612);
+}
+
+#[test]
+fn test_stable_hop_beats_const_prod_on_pegged_pair() {
+    let env = setup_env();
+    let (_, _, client) = deploy_router(&env);
+    let usdc = Asset::Issued(Address::generate(&env), Symbol::new(&env, "USDC"));
+    let eurt = Asset::Issued(Address::generate(&env), Symbol::new(&env, "EURT"));
+
+    // The same reserves, quoted once as constant-product and once on the curve
+    let pool = env.register_contract(None, MockPeggedPool);
+    let adapter = env.register_contract(None, StableSwapAdapter);
+    StableSwapAdapterClient::new(&env, &adapter).initialize(&pool, &usdc, &100_u32, &4_u32);
+    client.register_pool(&pool);
+    client.register_pool(&adapter);
+
+    let mut const_route = stable_route(&env, &pool, &usdc, &eurt);
+    let mut hop = const_route.hops.get(0).unwrap();
+    hop.pool_type = PoolType::AmmConstProd;
+    const_route.hops.set(0, hop);
+
+    let amount_in = 10_000_000_000;
+    let const_quote = client.get_quote(&amount_in, &const_route);
+    let stable_quote = client.get_quote(&amount_in, &stable_route(&env, &adapter, &usdc, &eurt));
+
+    assert_eq!(
+        stable_quote.expected_output + stable_quote.fee_amount,
+        stable_math::get_dy(amount_in, 100_000_000_000, 100_000_000_000, 100, 4).unwrap()
+    );
+    assert!(stable_quote.expected_output > const_quote.expected_output);
+}
+
+#[test]
+fn test_get_quote_sdex_hop_not_supported() {
+    let env = setup_env();
+    let (_, _, client) = deploy_router(&env);
+    let pool = deploy_mock_pool(&env);
+    client.register_pool(&pool);
+
+    let mut route = make_route(&env, &pool, 1);
+    let mut hop = route.hops.get(0).unwrap();
+    hop.pool_type = PoolType::Sdex;
+    route.hops.set(0, hop);
+
+    assert_eq!(
+        client.try_get_quote(&1000, &route),
+        Err(Ok(ContractError::PoolNotSupported))
+    );
+}
+
+// ── Governed MEV / Allowlist Tests ────────────────────────────────────────────
+
+use super::types::{ActionClass, MevConfig};
+
+fn governed_mev_config() -> MevConfig {
+    MevConfig {
+        max_price_impact_bps: 500,
+        max_execution_spread_bps: 100,
+        rate_limit_window_ledgers: 100,
+        rate_limit_max_swaps: 3,
+        commitment_required_above: 100_000,
+        twap_window_ledgers: 0,
+    }
+}
+
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_multisig_rejects_single_admin_mev_and_token_calls() {
+    let env = setup_env();
+    let (_, _, _, admin, client) = deploy_multisig_router(&env);
+    let token_a = Address::generate(&env);
+    let token_b = Address::generate(&env);
+
+    assert_eq!(
+        client.try_configure_mev(&governed_mev_config()),
+        Err(Ok(ContractError::UseGovernance))
+    );
+    assert_eq!(
+        client.try_set_whitelist(&token_a, &true),
+        Err(Ok(ContractError::UseGovernance))
+    );
+    assert_eq!(
+        client.try_update_known_price(&token_a, &token_b, &1_000),
+        Err(Ok(ContractError::UseGovernance))
+    );
+
+    let asset = Asset::Soroban(token_a);
+    let info = make_token_info(&env, &admin, asset.clone(), TokenCategory::Community);
+    assert_eq!(
+        client.try_add_token(&admin, &info),
+        Err(Ok(ContractError::UseGovernance))
+    );
+    assert_eq!(
+        client.try_remove_token(&admin, &asset),
+        Err(Ok(ContractError::UseGovernance))
+    );
+}
+
+#[test]
+fn test_default_timelocks_per_class() {
+    let env = setup_env();
+    let (_, _, _, _, client) = deploy_multisig_router(&env);
+
+    assert_eq!(client.get_timelock(&ActionClass::Mev), 720);
+    assert_eq!(client.get_timelock(&ActionClass::Tokens), 720);
+    assert_eq!(client.get_timelock(&ActionClass::Fees), 0);
+    assert_eq!(client.get_timelock(&ActionClass::Emergency), 0);
+}
+
+#[test]
+fn test_configure_mev_proposal_waits_for_timelock() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+    let config = governed_mev_config();
+
+    let prop_id = client.propose(&s1, &ProposalAction::ConfigureMev(config.clone()));
+    let proposal = client.get_proposal(&prop_id);
+    assert_eq!(proposal.executable_at, proposal.created_at + 720);
+    assert_eq!(proposal.expires_at, proposal.executable_at + 10_000);
+
+    // Threshold met, but the timelock holds execution back.
+    client.approve_proposal(&s2, &prop_id);
+    assert!(!client.get_proposal(&prop_id).executed);
+    assert_eq!(
+        client.try_get_mev_config(),
+        Err(Ok(ContractError::NotInitialized))
+    );
+
+    advance_ledgers(&env, 719);
+    assert_eq!(
+        client.try_execute_proposal(&prop_id),
+        Err(Err(ExtendedError::ProposalTimelocked.into()))
+    );
+
+    advance_ledgers(&env, 1);
+    client.execute_proposal(&prop_id);
+    assert!(client.get_proposal(&prop_id).executed);
+    assert_eq!(client.get_mev_config(), config);
+}
+
+#[test]
+fn test_configure_mev_proposal_validates_config() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+    let mut config = governed_mev_config();
+    config.rate_limit_max_swaps = 0;
+
+    let prop_id = client.propose(&s1, &ProposalAction::ConfigureMev(config));
+    client.approve_proposal(&s2, &prop_id);
+    advance_ledgers(&env, 720);
+
+    assert_eq!(
+        client.try_execute_proposal(&prop_id),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    assert!(!client.get_proposal(&prop_id).executed);
+}
+
+#[test]
+fn test_whitelist_and_known_price_proposals() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+    let trader = Address::generate(&env);
+    let token_a = Address::generate(&env);
+    let token_b = Address::generate(&env);
+
+    let wl_id = client.propose(&s1, &ProposalAction::SetWhitelist(trader.clone(), true));
+    client.approve_proposal(&s2, &wl_id);
+    let price_id = client.propose(
+        &s1,
+        &ProposalAction::UpdateKnownPrice(token_a.clone(), token_b.clone(), 42),
+    );
+    client.approve_proposal(&s2, &price_id);
+
+    advance_ledgers(&env, 720);
+    client.execute_proposal(&wl_id);
+    client.execute_proposal(&price_id);
+
+    env.as_contract(&client.address, || {
+        assert!(crate::storage::is_whitelisted(&env, &trader));
+        assert_eq!(
+            crate::storage::get_latest_known_price(&env, &token_a, &token_b),
+            Some(42)
+        );
+    });
+}
+
+#[test]
+fn test_token_allowlist_proposals() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+    let asset = Asset::Issued(Address::generate(&env), Symbol::new(&env, "USDC"));
+    let info = make_token_info(&env, &s1, asset.clone(), TokenCategory::Stablecoin);
+
+    let add_id = client.propose(&s1, &ProposalAction::AddToken(info.clone()));
+    client.approve_proposal(&s2, &add_id);
+    assert!(!client.is_token_allowed(&asset));
+    advance_ledgers(&env, 720);
+    client.execute_proposal(&add_id);
+    assert!(client.is_token_allowed(&asset));
+
+    let mut updated = info;
+    updated.decimals = 6;
+    let update_id = client.propose(&s1, &ProposalAction::UpdateToken(asset.clone(), updated));
+    client.approve_proposal(&s2, &update_id);
+    advance_ledgers(&env, 720);
+    client.execute_proposal(&update_id);
+    assert_eq!(client.get_token_info(&asset).unwrap().decimals, 6);
+
+    let remove_id = client.propose(&s1, &ProposalAction::RemoveToken(asset.clone()));
+    client.approve_proposal(&s2, &remove_id);
+    advance_ledgers(&env, 720);
+    client.execute_proposal(&remove_id);
+    assert!(!client.is_token_allowed(&asset));
+    assert_eq!(client.get_token_count(), 0);
+}
+
+#[test]
+fn test_set_timelock_proposal_changes_class_delay() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+
+    // Governance-class changes carry no delay by default and auto-execute.
+    let prop_id = client.propose(&s1, &ProposalAction::SetTimelock(ActionClass::Fees, 100));
+    client.approve_proposal(&s2, &prop_id);
+    assert!(client.get_proposal(&prop_id).executed);
+    assert_eq!(client.get_timelock(&ActionClass::Fees), 100);
+
+    let fee_id = client.propose(&s1, &ProposalAction::SetFeeRate(50));
+    client.approve_proposal(&s2, &fee_id);
+    assert!(!client.get_proposal(&fee_id).executed);
+    advance_ledgers(&env, 100);
+    client.execute_proposal(&fee_id);
+    assert_eq!(client.get_fee_rate_value(), 50);
+}
+
+#[test]
+fn test_set_timelock_waits_for_target_class_delay() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+
+    // Dropping the MEV delay must itself sit out the current MEV delay.
+    let prop_id = client.propose(&s1, &ProposalAction::SetTimelock(ActionClass::Mev, 0));
+    let proposal = client.get_proposal(&prop_id);
+    assert_eq!(proposal.executable_at, proposal.created_at + 720);
+
+    client.approve_proposal(&s2, &prop_id);
+    assert!(!client.get_proposal(&prop_id).executed);
+    advance_ledgers(&env, 719);
+    assert_eq!(
+        client.try_execute_proposal(&prop_id),
+        Err(Err(ExtendedError::ProposalTimelocked.into()))
+    );
+
+    advance_ledgers(&env, 1);
+    client.execute_proposal(&prop_id);
+    assert_eq!(client.get_timelock(&ActionClass::Mev), 0);
+}
+
+#[test]
+fn test_timelocked_proposal_expiry_counts_from_executable_at() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+
+    let prop_id = client.propose(
+        &s1,
+        &ProposalAction::SetWhitelist(Address::generate(&env), true),
+    );
+    client.approve_proposal(&s2, &prop_id);
+
+    // Past created_at + ttl, but still within executable_at + ttl.
+    advance_ledgers(&env, 10_500);
+    client.execute_proposal(&prop_id);
+    assert!(client.get_proposal(&prop_id).executed);
+}
+
+// ── Signed quotes ─────────────────────────────────────────────────────────────
+
+use super::types::SignedQuote;
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{xdr::ToXdr, String};
+
+fn quote_signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn quote_signer(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn make_signed_quote(env: &Env, expires_at: u64) -> SignedQuote {
+    SignedQuote {
+        quote_id: BytesN::from_array(env, &[1u8; 16]),
+        base_asset: String::from_str(env, "native"),
+        quote_asset: String::from_str(
+            env,
+            "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+        ),
+        quote_type: Symbol::new(env, "sell"),
+        amount: 1_000_000_000,
+        price: String::from_str(env, "0.1000000"),
+        total: 100_000_000,
+        issued_at: 1_000_000,
+        expires_at,
+    }
+}
+
+/// Sign `quote.to_xdr()` the way the API does.
+fn sign_quote(env: &Env, key: &SigningKey, quote: &SignedQuote) -> BytesN<64> {
+    let payload = quote.clone().to_xdr(env);
+    let len = payload.len() as usize;
+    let mut buf = [0u8; 512];
+    payload.copy_into_slice(&mut buf[..len]);
+    BytesN::from_array(env, &key.sign(&buf[..len]).to_bytes())
+}
+
+#[test]
+fn test_verify_quote_requires_allowlisted_signer() {
+    let env = setup_env();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let (_, _, client) = deploy_router(&env);
+    let key = quote_signing_key();
+    let signer = quote_signer(&env, &key);
+    let quote = make_signed_quote(&env, 1_002_000);
+    let signature = sign_quote(&env, &key, &quote);
+
+    assert_eq!(
+        client.try_verify_quote(&quote, &signer, &signature),
+        Err(Err(ExtendedError::QuoteSignerNotAllowed.into()))
+    );
+
+    client.set_quote_signer(&signer, &true);
+    assert!(client.is_quote_signer(&signer));
+    client.verify_quote(&quote, &signer, &signature);
+
+    client.set_quote_signer(&signer, &false);
+    assert!(!client.is_quote_signer(&signer));
+    assert_eq!(
+        client.try_verify_quote(&quote, &signer, &signature),
+        Err(Err(ExtendedError::QuoteSignerNotAllowed.into()))
+    );
+}
+
+#[test]
+fn test_verify_quote_rejects_expired_quote() {
+    let env = setup_env();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let (_, _, client) = deploy_router(&env);
+    let key = quote_signing_key();
+    let signer = quote_signer(&env, &key);
+    client.set_quote_signer(&signer, &true);
+
+    let quote = make_signed_quote(&env, 1_002_000);
+    let signature = sign_quote(&env, &key, &quote);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_002);
+    assert_eq!(
+        client.try_verify_quote(&quote, &signer, &signature),
+        Err(Err(ExtendedError::QuoteExpired.into()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_verify_quote_rejects_tampered_quote() {
+    let env = setup_env();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let (_, _, client) = deploy_router(&env);
+    let key = quote_signing_key();
+    let signer = quote_signer(&env, &key);
+    client.set_quote_signer(&signer, &true);
+
+    let mut quote = make_signed_quote(&env, 1_002_000);
+    let signature = sign_quote(&env, &key, &quote);
+    quote.total += 1;
+
+    client.verify_quote(&quote, &signer, &signature);
+}
+
+#[test]
+fn test_multisig_sets_quote_signer_through_governance() {
+    let env = setup_env();
+    let (s1, s2, _, _, client) = deploy_multisig_router(&env);
+    let signer = quote_signer(&env, &quote_signing_key());
+
+    assert_eq!(
+        client.try_set_quote_signer(&signer, &true),
+        Err(Ok(ContractError::UseGovernance))
+    );
+
+    let prop_id = client.propose(&s1, &ProposalAction::SetQuoteSigner(signer.clone(), true));
+    client.approve_proposal(&s2, &prop_id);
+    advance_ledgers(&env, 720);
+    client.execute_proposal(&prop_id);
+    assert!(client.is_quote_signer(&signer));
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Symbol, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -23,6 +23,8 @@ pub struct RouteHop {
     pub destination: Asset,
     pub pool: Address,
     pub pool_type: PoolType,
+    /// Least this hop may output before the swap aborts; 0 disables the check.
+    pub min_amount_out: i128,
 }
 
 #[contracttype]
@@ -35,6 +37,7 @@ pub struct Route {
 }
 
 #[contracttype]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SwapParams {
     pub route: Route,
     pub amount_in: i128,
@@ -46,6 +49,49 @@ pub struct SwapParams {
     pub max_execution_spread_bps: u32,
 }
 
+/// One leg of a split route: its own hops and minimum, and the share of the
+/// input it receives.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitLeg {
+    /// Hops of this leg; `route.min_output` is the leg's minimum net output.
+    pub route: Route,
+    /// Share of `amount_in` routed through this leg (10000 = 100%).
+    pub weight_bps: u32,
+}
+
+/// Weighted legs executed together; all legs share the same source and
+/// destination asset and their weights sum to 10000.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitRoute {
+    pub legs: Vec<SplitLeg>,
+}
+
+#[contracttype]
+pub struct SplitSwapParams {
+    pub split: SplitRoute,
+    pub amount_in: i128,
+    /// Least total output across all legs, net of fees.
+    pub min_amount_out: i128,
+    pub recipient: Address,
+    pub deadline: u64,
+    pub not_before: u64,
+    /// Applied to each leg's own price impact; 0 disables the check.
+    pub max_price_impact_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitSwapResult {
+    pub amount_in: i128,
+    pub amount_out: i128,
+    pub fee_amount: i128,
+    /// Net output of each leg, in leg order.
+    pub leg_outputs: Vec<i128>,
+    pub executed_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct QuoteResult {
@@ -56,6 +102,30 @@ pub struct QuoteResult {
     pub valid_until: u64,
 }
 
+/// A quote issued and signed off-chain by the StellarRoute API
+/// (`GET /api/v1/quotes/{id}`). The signature covers `to_xdr` of this struct.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedQuote {
+    /// The API `quote_id` (a UUID).
+    pub quote_id: BytesN<16>,
+    /// `native` or `CODE:ISSUER`.
+    pub base_asset: String,
+    pub quote_asset: String,
+    /// `sell` or `buy`.
+    pub quote_type: Symbol,
+    /// Quoted amount, in stroops.
+    pub amount: i128,
+    /// Decimal price exactly as the API returned it.
+    pub price: String,
+    /// `amount × price`, in stroops.
+    pub total: i128,
+    /// Unix milliseconds.
+    pub issued_at: u64,
+    /// Unix milliseconds after which the quote is no longer honoured.
+    pub expires_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct SwapResult {
@@ -65,6 +135,17 @@ pub struct SwapResult {
     pub executed_at: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashSwapResult {
+    pub amount_in: i128,
+    /// Route output sent to the receiver before repayment.
+    pub amount_out: i128,
+    /// Fee the receiver paid on top of `amount_in`.
+    pub fee_amount: i128,
+    pub executed_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct TTLStatus {
@@ -167,6 +248,39 @@ pub enum ProposalAction {
     AddSigner(Address),
     RemoveSigner(Address),
     ChangeThreshold(u32),
+    ConfigureMev(MevConfig),
+    /// (address, whitelisted)
+    SetWhitelist(Address, bool),
+    /// (token_a, token_b, price)
+    UpdateKnownPrice(Address, Address, i128),
+    AddToken(TokenInfo),
+    RemoveToken(Asset),
+    /// (asset, updated metadata)
+    UpdateToken(Asset, TokenInfo),
+    /// Ledgers a class of proposals must wait after creation before executing.
+    SetTimelock(ActionClass, u64),
+    /// (ed25519 public key, allowed)
+    SetQuoteSigner(BytesN<32>, bool),
+}
+
+/// Groups of proposal actions sharing a timelock.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ActionClass {
+    /// Pause / unpause.
+    Emergency,
+    /// Fee rate, fee recipient and fee distribution.
+    Fees,
+    /// Pool registration.
+    Pools,
+    /// Contract WASM upgrades.
+    Upgrade,
+    /// Signers, threshold and timelocks.
+    Governance,
+    /// MEV config, whitelist, reference prices and quote signers.
+    Mev,
+    /// Token allowlist.
+    Tokens,
 }
 
 /// On-chain governance proposal.
@@ -180,6 +294,8 @@ pub struct Proposal {
     /// Addresses that have approved (first entry is always proposer).
     pub approvals: Vec<Address>,
     pub created_at: u64,
+    /// Earliest ledger the proposal may execute, from its class timelock.
+    pub executable_at: u64,
     pub expires_at: u64,
     /// True after the proposal has been executed or cancelled.
     pub executed: bool,
@@ -217,6 +333,8 @@ pub struct PendingUpgrade {
 #[derive(Clone, Debug)]
 pub struct CommitmentData {
     pub sender: Address,
+    /// Asset escrowed with the commitment; refunded on reveal or reclaim.
+    pub deposit_asset: Asset,
     pub deposit_amount: i128,
     pub commitment_hash: BytesN<32>,
     pub created_at: u64,
@@ -231,4 +349,49 @@ pub struct MevConfig {
     pub rate_limit_window_ledgers: u32,
     pub rate_limit_max_swaps: u32,
     pub commitment_required_above: i128,
+    /// TWAP window used as the reference price for
+    /// `max_execution_spread_bps`; 0 compares against the route estimate.
+    pub twap_window_ledgers: u32,
+}
+
+// ─── TWAP Oracle ─────────────────────────────────────────────────────────────
+
+/// Cumulative prices of a pool at one ledger. Each accumulator is the sum of
+/// `price * ledgers` since the pool was first observed, scaled by
+/// `oracle::PRICE_SCALE` and allowed to wrap.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceObservation {
+    pub ledger: u32,
+    /// Accumulated price of token 0 in token 1.
+    pub price0_cumulative: i128,
+    /// Accumulated price of token 1 in token 0.
+    pub price1_cumulative: i128,
+}
+
+/// Per-pool oracle state, updated by every routed swap through the pool.
+/// Stored in Persistent storage keyed by pool address.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PoolOracle {
+    /// Reserves after the last routed swap; they set the price that
+    /// accumulates until the next one.
+    pub reserve_0: i128,
+    pub reserve_1: i128,
+    pub latest: PriceObservation,
+    /// Snapshots of `latest` at least `oracle::OBSERVATION_SPACING` ledgers
+    /// apart, oldest first.
+    pub history: Vec<PriceObservation>,
+}
+
+/// Time-weighted average prices of a pool, scaled by `oracle::PRICE_SCALE`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TwapPrice {
+    /// Price of token 0 in token 1.
+    pub price0: i128,
+    /// Price of token 1 in token 0.
+    pub price1: i128,
+    /// Ledgers actually averaged over; at least the requested window.
+    pub window_ledgers: u32,
 }
@@ -0,0 +1,113 @@
+-- StellarRoute - Phase 1.6
+-- Storage for the scheduled market-data reconciliation engine
+
+-- 1. Per-check thresholds, editable without restarting the indexer
+create table if not exists reconciliation_thresholds (
+  check_type text primary key,
+  staleness_threshold_secs integer,
+  price_divergence_pct double precision,
+  liquidity_change_pct double precision,
+  ledger_lag_threshold integer,
+  enabled boolean not null default true,
+  updated_at timestamptz not null default now()
+);
+
+insert into reconciliation_thresholds (
+  check_type, staleness_threshold_secs, price_divergence_pct,
+  liquidity_change_pct, ledger_lag_threshold
+)
+values
+  ('data_staleness', 300, null, null, null),
+  ('price_divergence', null, 2.5, null, null),
+  ('liquidity_anomaly', null, null, 15.0, null),
+  ('ledger_alignment', null, null, null, 100)
+on conflict (check_type) do nothing;
+
+-- 2. Individual check results
+create table if not exists reconciliation_checks (
+  id uuid primary key,
+  check_type text not null,
+  entity_type text not null,
+  entity_ref text not null,
+  expected_value jsonb not null default '{}'::jsonb,
+  actual_value jsonb not null default '{}'::jsonb,
+  drift_severity text not null,
+  drift_percentage double precision,
+  extra_context jsonb not null default '{}'::jsonb,
+  created_at timestamptz not null default now()
+);
+
+create index if not exists idx_reconciliation_checks_created
+  on reconciliation_checks (created_at desc);
+create index if not exists idx_reconciliation_checks_severity
+  on reconciliation_checks (drift_severity, created_at desc);
+
+-- 3. Drift metric time series
+create table if not exists drift_events (
+  id uuid primary key,
+  check_id uuid references reconciliation_checks(id) on delete set null,
+  entity_type text not null,
+  entity_ref text not null,
+  drift_category text not null,
+  metric_name text not null,
+  metric_value double precision,
+  metric_unit text,
+  threshold_value double precision,
+  breach boolean not null default false,
+  metadata jsonb not null default '{}'::jsonb,
+  recorded_at timestamptz not null default now()
+);
+
+create index if not exists idx_drift_events_recorded
+  on drift_events (recorded_at desc);
+create index if not exists idx_drift_events_category
+  on drift_events (drift_category, recorded_at desc);
+
+-- 4. Repair audit trail
+create table if not exists repair_actions (
+  id uuid primary key,
+  check_id uuid references reconciliation_checks(id) on delete set null,
+  action_type text not null,
+  entity_type text not null,
+  entity_ref text not null,
+  reason text not null,
+  action_details jsonb not null default '{}'::jsonb,
+  success boolean not null,
+  error_message text,
+  affected_rows integer not null default 0,
+  created_at timestamptz not null default now(),
+  executed_at timestamptz not null default now()
+);
+
+create index if not exists idx_repair_actions_executed
+  on repair_actions (executed_at desc);
+
+-- 5. Cycle summaries
+create table if not exists reconciliation_runs (
+  id uuid primary key,
+  run_started_at timestamptz not null,
+  run_completed_at timestamptz not null,
+  checks_requested integer not null default 0,
+  checks_executed integer not null default 0,
+  checks_passed integer not null default 0,
+  checks_failed integer not null default 0,
+  total_drift_events integer not null default 0,
+  critical_drift_events integer not null default 0,
+  total_repairs_attempted integer not null default 0,
+  successful_repairs integer not null default 0,
+  failed_repairs integer not null default 0,
+  duration_ms bigint not null default 0
+);
+
+create index if not exists idx_reconciliation_runs_started
+  on reconciliation_runs (run_started_at desc);
+
+-- 6. Open critical issues for the operations dashboard
+create or replace view critical_issues as
+select id, check_type, entity_type, entity_ref, drift_percentage, created_at
+from reconciliation_checks
+where drift_severity = 'critical'
+  and created_at > now() - interval '24 hours';
+
+comment on table reconciliation_runs is 'Summaries of scheduled reconciliation cycles';
+comment on table repair_actions is 'Automatic and manual repairs triggered by reconciliation drift';
@@ -0,0 +1,46 @@
+-- StellarRoute - Phase 2.1
+-- Range- and pair-scoped backfill jobs with per-chunk checkpoints
+
+create table if not exists backfill_jobs (
+  id uuid primary key,
+  start_ledger bigint not null,
+  end_ledger bigint not null,
+  base_asset_id uuid references assets(id),
+  quote_asset_id uuid references assets(id),
+  chunk_size bigint not null,
+  concurrency integer not null default 1,
+  status text not null default 'idle', -- 'running', 'paused', 'completed', 'error'
+  ledgers_total bigint not null,
+  ledgers_done bigint not null default 0,
+  rows_processed bigint not null default 0,
+  last_error text,
+  created_at timestamptz not null default now(),
+  resumed_at timestamptz,
+  updated_at timestamptz not null default now(),
+  check (end_ledger >= start_ledger),
+  check (chunk_size > 0)
+);
+
+create index if not exists idx_backfill_jobs_created
+  on backfill_jobs (created_at desc);
+
+create table if not exists backfill_job_chunks (
+  job_id uuid not null references backfill_jobs(id) on delete cascade,
+  chunk_start bigint not null,
+  chunk_end bigint not null,
+  status text not null default 'pending', -- 'pending', 'completed', 'error'
+  rows_processed bigint not null default 0,
+  last_error text,
+  completed_at timestamptz,
+  primary key (job_id, chunk_start)
+);
+
+create index if not exists idx_backfill_job_chunks_pending
+  on backfill_job_chunks (job_id, status);
+
+-- Ledger-window scans used by chunked backfills
+create index if not exists idx_sdex_offers_last_modified_ledger
+  on sdex_offers (last_modified_ledger);
+
+comment on table backfill_jobs is 'Ledger-range backfill jobs driven by `stellarroute-indexer backfill`';
+comment on table backfill_job_chunks is 'Per-chunk checkpoints for resumable backfill jobs';
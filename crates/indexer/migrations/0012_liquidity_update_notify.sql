@@ -0,0 +1,81 @@
+-- StellarRoute - Phase 2.2
+-- Publish normalized liquidity changes on the 'liquidity_update' channel so
+-- API instances can invalidate only the cached quotes and orderbooks they affect.
+--
+-- One notification is sent per changed SDEX pair or AMM pool per statement, as JSON:
+--   {"type":"sdex_update","base_asset":"native","counter_asset":"USDC:G...","ledger_sequence":123}
+--   {"type":"amm_update","pool_address":"C...","asset_a":"native","asset_b":"USDC:G...","ledger_sequence":123}
+-- Assets use the API's canonical form: 'native' or 'CODE:ISSUER'.
+
+create or replace function notify_liquidity_update()
+returns trigger as $$
+declare
+    r record;
+    -- Rows moved to another pair by an UPDATE invalidate their old pair too
+    changed text := case when TG_OP = 'UPDATE'
+        then 'select * from changed_rows union all select * from previous_rows'
+        else 'select * from changed_rows' end;
+begin
+    for r in execute format($q$
+        with grouped as (
+            select
+                case when venue_type = 'amm' then venue_ref end as pool_address,
+                selling_asset_id,
+                buying_asset_id,
+                max(source_ledger) as ledger_sequence
+            from (%s) changed
+            group by 1, 2, 3
+        )
+        select
+            g.pool_address,
+            g.ledger_sequence,
+            case when s.asset_type = 'native' then 'native'
+                 else s.asset_code || ':' || s.asset_issuer end as selling,
+            case when b.asset_type = 'native' then 'native'
+                 else b.asset_code || ':' || b.asset_issuer end as buying
+        from grouped g
+        join assets s on s.id = g.selling_asset_id
+        join assets b on b.id = g.buying_asset_id
+    $q$, changed)
+    loop
+        if r.pool_address is not null then
+            perform pg_notify('liquidity_update', json_build_object(
+                'type', 'amm_update',
+                'pool_address', r.pool_address,
+                'asset_a', r.selling,
+                'asset_b', r.buying,
+                'ledger_sequence', r.ledger_sequence
+            )::text);
+        else
+            perform pg_notify('liquidity_update', json_build_object(
+                'type', 'sdex_update',
+                'base_asset', r.selling,
+                'counter_asset', r.buying,
+                'ledger_sequence', r.ledger_sequence
+            )::text);
+        end if;
+    end loop;
+    return null;
+end;
+$$ language plpgsql;
+
+-- Transition tables need one trigger per event
+drop trigger if exists trg_notify_liquidity_insert on normalized_liquidity;
+create trigger trg_notify_liquidity_insert
+    after insert on normalized_liquidity
+    referencing new table as changed_rows
+    for each statement execute function notify_liquidity_update();
+
+drop trigger if exists trg_notify_liquidity_update on normalized_liquidity;
+create trigger trg_notify_liquidity_update
+    after update on normalized_liquidity
+    referencing new table as changed_rows old table as previous_rows
+    for each statement execute function notify_liquidity_update();
+
+drop trigger if exists trg_notify_liquidity_delete on normalized_liquidity;
+create trigger trg_notify_liquidity_delete
+    after delete on normalized_liquidity
+    referencing old table as changed_rows
+    for each statement execute function notify_liquidity_update();
+
+comment on function notify_liquidity_update() is 'Sends one liquidity_update notification per changed SDEX pair or AMM pool';
@@ -0,0 +1,9 @@
+-- StellarRoute - Phase 2.1
+-- Runner leases for backfill jobs so a crashed runner's job can be reclaimed
+
+alter table backfill_jobs
+  add column if not exists locked_by uuid,
+  add column if not exists lease_expires_at timestamptz;
+
+comment on column backfill_jobs.locked_by is 'Run token of the runner holding the current lease';
+comment on column backfill_jobs.lease_expires_at is 'Running jobs past this time were abandoned and may be resumed by another runner';
@@ -0,0 +1,175 @@
+-- StellarRoute - Phase 2.2
+-- StableSwap pools: each pool's amplification coefficient is stored with its
+-- reserves, and pools that have one are routed as 'amm_stable' venues.
+
+alter table amm_pool_reserves
+  add column if not exists amp integer check (amp between 1 and 1000000);
+
+alter table normalized_liquidity
+  add column if not exists amp integer;
+
+create or replace function upsert_amm_pool_reserve(
+  p_pool_address text,
+  p_selling_asset_id uuid,
+  p_buying_asset_id uuid,
+  p_reserve_selling numeric,
+  p_reserve_buying numeric,
+  p_fee_bps integer,
+  p_last_updated_ledger bigint,
+  p_amp integer
+)
+returns void as $$
+begin
+  insert into amm_pool_reserves (
+    pool_address,
+    selling_asset_id,
+    buying_asset_id,
+    reserve_selling,
+    reserve_buying,
+    fee_bps,
+    last_updated_ledger,
+    amp,
+    updated_at
+  )
+  values (
+    p_pool_address,
+    p_selling_asset_id,
+    p_buying_asset_id,
+    p_reserve_selling,
+    p_reserve_buying,
+    p_fee_bps,
+    p_last_updated_ledger,
+    p_amp,
+    now()
+  )
+  on conflict (pool_address)
+  do update set
+    selling_asset_id = excluded.selling_asset_id,
+    buying_asset_id = excluded.buying_asset_id,
+    reserve_selling = excluded.reserve_selling,
+    reserve_buying = excluded.reserve_buying,
+    fee_bps = excluded.fee_bps,
+    last_updated_ledger = excluded.last_updated_ledger,
+    amp = excluded.amp,
+    updated_at = now();
+end;
+$$ language plpgsql;
+
+-- Pools with an amplification coefficient are StableSwap pools
+create or replace function sync_normalized_liquidity_from_amm()
+returns trigger as $$
+declare
+    v_price numeric;
+    v_venue_type text;
+begin
+    if (TG_OP = 'DELETE') then
+        delete from normalized_liquidity
+        where venue_type in ('amm', 'amm_stable') and venue_ref = OLD.pool_address;
+        return OLD;
+    else
+        v_price := NEW.reserve_buying / nullif(NEW.reserve_selling, 0);
+        v_venue_type := case when NEW.amp is null then 'amm' else 'amm_stable' end;
+
+        -- A pool whose kind changed leaves no row behind under the old venue type
+        delete from normalized_liquidity
+        where venue_type in ('amm', 'amm_stable')
+          and venue_type <> v_venue_type
+          and venue_ref = NEW.pool_address;
+
+        insert into normalized_liquidity (
+            venue_type,
+            venue_ref,
+            selling_asset_id,
+            buying_asset_id,
+            price,
+            available_amount,
+            price_e7,
+            available_amount_e7,
+            source_ledger,
+            amp,
+            updated_at
+        )
+        values (
+            v_venue_type,
+            NEW.pool_address,
+            NEW.selling_asset_id,
+            NEW.buying_asset_id,
+            v_price,
+            NEW.reserve_selling,
+            (v_price * 10000000)::bigint,
+            (NEW.reserve_selling * 10000000)::bigint,
+            NEW.last_updated_ledger,
+            NEW.amp,
+            NEW.updated_at
+        )
+        on conflict (venue_type, venue_ref)
+        do update set
+            selling_asset_id = EXCLUDED.selling_asset_id,
+            buying_asset_id = EXCLUDED.buying_asset_id,
+            price = EXCLUDED.price,
+            available_amount = EXCLUDED.available_amount,
+            price_e7 = EXCLUDED.price_e7,
+            available_amount_e7 = EXCLUDED.available_amount_e7,
+            source_ledger = EXCLUDED.source_ledger,
+            amp = EXCLUDED.amp,
+            updated_at = EXCLUDED.updated_at;
+        return NEW;
+    end if;
+end;
+$$ language plpgsql;
+
+-- Notify StableSwap pool changes as AMM pool updates
+create or replace function notify_liquidity_update()
+returns trigger as $$
+declare
+    r record;
+    -- Rows moved to another pair by an UPDATE invalidate their old pair too
+    changed text := case when TG_OP = 'UPDATE'
+        then 'select * from changed_rows union all select * from previous_rows'
+        else 'select * from changed_rows' end;
+begin
+    for r in execute format($q$
+        with grouped as (
+            select
+                case when venue_type in ('amm', 'amm_stable') then venue_ref end as pool_address,
+                selling_asset_id,
+                buying_asset_id,
+                max(source_ledger) as ledger_sequence
+            from (%s) changed
+            group by 1, 2, 3
+        )
+        select
+            g.pool_address,
+            g.ledger_sequence,
+            case when s.asset_type = 'native' then 'native'
+                 else s.asset_code || ':' || s.asset_issuer end as selling,
+            case when b.asset_type = 'native' then 'native'
+                 else b.asset_code || ':' || b.asset_issuer end as buying
+        from grouped g
+        join assets s on s.id = g.selling_asset_id
+        join assets b on b.id = g.buying_asset_id
+    $q$, changed)
+    loop
+        if r.pool_address is not null then
+            perform pg_notify('liquidity_update', json_build_object(
+                'type', 'amm_update',
+                'pool_address', r.pool_address,
+                'asset_a', r.selling,
+                'asset_b', r.buying,
+                'ledger_sequence', r.ledger_sequence
+            )::text);
+        else
+            perform pg_notify('liquidity_update', json_build_object(
+                'type', 'sdex_update',
+                'base_asset', r.selling,
+                'counter_asset', r.buying,
+                'ledger_sequence', r.ledger_sequence
+            )::text);
+        end if;
+    end loop;
+    return null;
+end;
+$$ language plpgsql;
+
+comment on column amm_pool_reserves.amp is 'StableSwap amplification coefficient; null for constant-product pools';
+comment on column normalized_liquidity.amp is 'Amplification coefficient of amm_stable venues; null otherwise';
@@ -228,6 +228,7 @@ impl AmmAggregator {
             reserve_buying: rust_decimal::Decimal::from_i128_with_scale(state.reserve_b, 0),
             fee_bps: state.fee_bps,
             last_updated_ledger: state.ledger_sequence,
+            amp: state.amp,
             updated_at: Utc::now(),
         })
         .await?;
@@ -262,6 +263,7 @@ impl AmmAggregator {
             reserve_b: 2000000000, // 2000 units
             fee_bps: 30,           // 0.3%
             ledger_sequence: 12345,
+            amp: None,
         })
     }
 
@@ -299,7 +301,7 @@ impl AmmAggregator {
     /// Update pool reserve in database
     async fn update_pool_reserve(&self, reserve: &PoolReserve) -> Result<()> {
         let pool = self.db.pool();
-        sqlx::query("SELECT upsert_amm_pool_reserve($1, $2, $3, $4, $5, $6, $7)")
+        sqlx::query("SELECT upsert_amm_pool_reserve($1, $2, $3, $4, $5, $6, $7, $8)")
             .bind(&reserve.pool_address)
             .bind(reserve.selling_asset_id)
             .bind(reserve.buying_asset_id)
@@ -307,6 +309,7 @@ impl AmmAggregator {
             .bind(reserve.reserve_buying.to_string())
             .bind(reserve.fee_bps)
             .bind(reserve.last_updated_ledger)
+            .bind(reserve.amp)
             .execute(pool)
             .await?;
 
@@ -1,18 +1,112 @@
 //! StellarRoute Indexer Binary
 //!
 //! Main entry point for the SDEX orderbook indexer service.
+//!
+//! # Usage
+//!
+//! ```text
+//! stellarroute-indexer                      # run the indexing service
+//! stellarroute-indexer backfill start  --from-ledger N --to-ledger M [--pair BASE/QUOTE]
+//! stellarroute-indexer backfill start  --from-time T --to-time T [--pair BASE/QUOTE]
+//! stellarroute-indexer backfill status [<job_id>]
+//! stellarroute-indexer backfill pause  <job_id>
+//! stellarroute-indexer backfill resume <job_id>
+//! ```
+//!
+//! Backfill subcommands only need `DATABASE_URL` and expect the schema to be
+//! migrated by the service.
 
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand};
+use sqlx::postgres::PgPoolOptions;
 use std::process;
 use tracing::{error, info};
+use uuid::Uuid;
 
 use std::time::Duration;
 use stellarroute_indexer::amm::{AmmAggregator, AmmConfig};
 use stellarroute_indexer::config::IndexerConfig;
 use stellarroute_indexer::db::{archival::ArchivalManager, Database};
 use stellarroute_indexer::horizon::HorizonClient;
+use stellarroute_indexer::models::asset::Asset;
+use stellarroute_indexer::reconciliation::backfill::{
+    BackfillJobSpec, BackfillProgress, BackfillRange, DEFAULT_CHUNK_LEDGERS,
+};
+use stellarroute_indexer::reconciliation::{
+    BackfillManager, ReconciliationSchedule, ReconciliationScheduler,
+};
 use stellarroute_indexer::sdex::SdexIndexer;
 use stellarroute_indexer::soroban::{RetryPolicy, SorobanRpc, SorobanRpcClient, SorobanRpcConfig};
 
+#[derive(Parser)]
+#[command(
+    name = "stellarroute-indexer",
+    about = "StellarRoute SDEX/AMM indexer service and backfill control plane",
+    version
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Manage ledger-range backfill jobs
+    Backfill {
+        #[command(subcommand)]
+        action: BackfillCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackfillCommand {
+    /// Create a backfill job and run it in the foreground
+    Start {
+        /// First ledger to backfill (inclusive)
+        #[arg(long, requires = "to_ledger", conflicts_with_all = ["from_time", "to_time"])]
+        from_ledger: Option<i64>,
+        /// Last ledger to backfill (inclusive)
+        #[arg(long, requires = "from_ledger")]
+        to_ledger: Option<i64>,
+        /// Start of the time window (RFC 3339)
+        #[arg(long, requires = "to_time")]
+        from_time: Option<DateTime<Utc>>,
+        /// End of the time window (RFC 3339)
+        #[arg(long, requires = "from_time")]
+        to_time: Option<DateTime<Utc>>,
+        /// Restrict to one pair, e.g. "native/USDC:GA5Z..."
+        #[arg(long)]
+        pair: Option<String>,
+        /// Ledgers per chunk
+        #[arg(long, default_value_t = DEFAULT_CHUNK_LEDGERS)]
+        chunk_size: i64,
+        /// Chunks processed concurrently
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+        /// Only create the job; run it later with `resume`
+        #[arg(long)]
+        detach: bool,
+    },
+    /// Show progress for a job, or list recent jobs
+    Status {
+        /// Job UUID (omit to list recent jobs)
+        job_id: Option<String>,
+        /// Maximum number of jobs to list
+        #[arg(long, default_value = "20")]
+        limit: i64,
+    },
+    /// Pause a job after its in-flight chunks finish
+    Pause {
+        /// Job UUID
+        job_id: String,
+    },
+    /// Resume a paused or failed job, or one abandoned by a crashed runner, in the foreground
+    Resume {
+        /// Job UUID
+        job_id: String,
+    },
+}
+
 fn parse_bool_env(name: &str) -> bool {
     std::env::var(name)
         .ok()
@@ -65,9 +159,164 @@ async fn run_startup_reachability_checks(
 
 #[tokio::main]
 async fn main() {
-    // Initialize structured logging (reads RUST_LOG and LOG_FORMAT env vars)
-    stellarroute_indexer::telemetry::init();
+    let cli = Cli::parse();
+
+    match cli.command {
+        None => {
+            // Initialize structured logging (reads RUST_LOG and LOG_FORMAT env vars)
+            stellarroute_indexer::telemetry::init();
+            run_service().await
+        }
+        Some(Commands::Backfill { action }) => {
+            // Progress logs go to stderr so stdout stays machine-readable JSON
+            tracing_subscriber::fmt()
+                .with_env_filter(
+                    tracing_subscriber::EnvFilter::try_from_default_env()
+                        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+                )
+                .with_writer(std::io::stderr)
+                .init();
+
+            if let Err(e) = run_backfill_command(action).await {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+async fn run_backfill_command(action: BackfillCommand) -> anyhow::Result<()> {
+    let database_url = std::env::var("DATABASE_URL")
+        .map_err(|_| anyhow::anyhow!("DATABASE_URL environment variable is not set"))?;
+
+    let max_connections = match &action {
+        BackfillCommand::Start { concurrency, .. } => *concurrency as u32 + 2,
+        _ => 8,
+    };
+    let pool = PgPoolOptions::new()
+        .max_connections(max_connections)
+        .connect(&database_url)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect to database: {}", e))?;
+    let manager = BackfillManager::new(pool);
+
+    match action {
+        BackfillCommand::Start {
+            from_ledger,
+            to_ledger,
+            from_time,
+            to_time,
+            pair,
+            chunk_size,
+            concurrency,
+            detach,
+        } => {
+            let range = match (from_ledger, to_ledger, from_time, to_time) {
+                (Some(start), Some(end), None, None) => BackfillRange::Ledgers { start, end },
+                (None, None, Some(from), Some(to)) => BackfillRange::Time { from, to },
+                _ => anyhow::bail!(
+                    "specify either --from-ledger/--to-ledger or --from-time/--to-time"
+                ),
+            };
+            let pair = pair.as_deref().map(parse_pair).transpose()?;
+
+            let job = manager
+                .create_job(BackfillJobSpec {
+                    range,
+                    pair,
+                    chunk_size,
+                    concurrency,
+                })
+                .await?;
+
+            if detach {
+                println!("{}", serde_json::to_string_pretty(&job)?);
+            } else {
+                let progress = run_with_progress(&manager, job.id).await?;
+                println!("{}", serde_json::to_string_pretty(&progress)?);
+            }
+        }
+
+        BackfillCommand::Status { job_id, limit } => match job_id {
+            Some(job_id) => {
+                let progress = manager.job_progress(parse_uuid(&job_id)?).await?;
+                println!("{}", serde_json::to_string_pretty(&progress)?);
+            }
+            None => {
+                let jobs = manager.list_jobs(limit.clamp(1, 100)).await?;
+                println!("{}", serde_json::to_string_pretty(&jobs)?);
+            }
+        },
 
+        BackfillCommand::Pause { job_id } => {
+            let job = manager.pause_job(parse_uuid(&job_id)?).await?;
+            println!("{}", serde_json::to_string_pretty(&job)?);
+        }
+
+        BackfillCommand::Resume { job_id } => {
+            let progress = run_with_progress(&manager, parse_uuid(&job_id)?).await?;
+            println!("{}", serde_json::to_string_pretty(&progress)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a job while logging progress every few seconds
+async fn run_with_progress(
+    manager: &BackfillManager,
+    job_id: Uuid,
+) -> anyhow::Result<BackfillProgress> {
+    let run = manager.resume_job(job_id);
+    tokio::pin!(run);
+
+    let mut ticker = tokio::time::interval(Duration::from_secs(5));
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            result = &mut run => return Ok(result?),
+            _ = ticker.tick() => {
+                if let Ok(progress) = manager.job_progress(job_id).await {
+                    info!(
+                        "Backfill {}: {}/{} ledgers ({:.1}%), {}/{} chunks, eta {}",
+                        job_id,
+                        progress.job.ledgers_done,
+                        progress.job.ledgers_total,
+                        progress.percent_complete,
+                        progress.chunks_done,
+                        progress.chunks_total,
+                        progress
+                            .eta_secs
+                            .map(|secs| format!("{}s", secs))
+                            .unwrap_or_else(|| "unknown".to_string()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn parse_pair(s: &str) -> anyhow::Result<(Asset, Asset)> {
+    let (base, quote) = s
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("Invalid pair '{}': expected BASE/QUOTE", s))?;
+    let parse = |asset: &str| {
+        Asset::from_canonical(asset).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid asset '{}': expected \"native\" or CODE:ISSUER",
+                asset
+            )
+        })
+    };
+    Ok((parse(base)?, parse(quote)?))
+}
+
+fn parse_uuid(s: &str) -> anyhow::Result<Uuid> {
+    Uuid::parse_str(s).map_err(|_| anyhow::anyhow!("Invalid job ID '{}': must be a UUID", s))
+}
+
+async fn run_service() {
     info!("Starting StellarRoute Indexer");
 
     // Load configuration
@@ -185,9 +434,36 @@ async fn main() {
         }
     });
 
-    // Wait for indexers and maintenance task
-    let (sdex_result, amm_result, maintenance_result) =
-        tokio::join!(sdex_handle, amm_handle, maintenance_handle);
+    // Reconciliation scheduler (drift detection + automatic repair)
+    let reconciliation_pool = db.pool().clone();
+    let reconciliation_config = config.clone();
+    let reconciliation_handle = tokio::spawn(async move {
+        if !reconciliation_config.reconciliation_enabled {
+            info!("Reconciliation scheduler disabled");
+            return;
+        }
+
+        let schedule = ReconciliationSchedule {
+            interval: Duration::from_secs(
+                reconciliation_config.reconciliation_interval_secs.max(1),
+            ),
+            auto_repair: reconciliation_config.reconciliation_auto_repair,
+        };
+        if let Err(e) = ReconciliationScheduler::new(reconciliation_pool, schedule)
+            .run()
+            .await
+        {
+            error!("Reconciliation scheduler error: {}", e);
+        }
+    });
+
+    // Wait for indexers, maintenance and reconciliation tasks
+    let (sdex_result, amm_result, maintenance_result, reconciliation_result) = tokio::join!(
+        sdex_handle,
+        amm_handle,
+        maintenance_handle,
+        reconciliation_handle
+    );
 
     if let Err(e) = sdex_result {
         error!("SDEX indexer task failed: {}", e);
@@ -201,5 +477,9 @@ async fn main() {
         error!("Maintenance task failed: {}", e);
     }
 
+    if let Err(e) = reconciliation_result {
+        error!("Reconciliation task failed: {}", e);
+    }
+
     process::exit(1);
 }
@@ -61,6 +61,18 @@ pub struct IndexerConfig {
     /// Snapshot compaction after threshold hours (env: `SNAPSHOT_COMPACTION_HOURS`).
     #[serde(default = "default_snapshot_compaction_hours")]
     pub snapshot_compaction_hours: i32,
+
+    /// Run the reconciliation scheduler (env: `RECONCILIATION_ENABLED`).
+    #[serde(default = "default_reconciliation_enabled")]
+    pub reconciliation_enabled: bool,
+
+    /// Seconds between reconciliation cycles (env: `RECONCILIATION_INTERVAL_SECS`).
+    #[serde(default = "default_reconciliation_interval_secs")]
+    pub reconciliation_interval_secs: u64,
+
+    /// Automatically repair warning-level drift (env: `RECONCILIATION_AUTO_REPAIR`).
+    #[serde(default = "default_reconciliation_auto_repair")]
+    pub reconciliation_auto_repair: bool,
 }
 
 impl std::fmt::Debug for IndexerConfig {
@@ -82,6 +94,15 @@ impl std::fmt::Debug for IndexerConfig {
             .field("maintenance_interval_mins", &self.maintenance_interval_mins)
             .field("snapshot_retention_days", &self.snapshot_retention_days)
             .field("snapshot_compaction_hours", &self.snapshot_compaction_hours)
+            .field("reconciliation_enabled", &self.reconciliation_enabled)
+            .field(
+                "reconciliation_interval_secs",
+                &self.reconciliation_interval_secs,
+            )
+            .field(
+                "reconciliation_auto_repair",
+                &self.reconciliation_auto_repair,
+            )
             .finish()
     }
 }
@@ -134,6 +155,18 @@ fn default_snapshot_compaction_hours() -> i32 {
     24
 }
 
+fn default_reconciliation_enabled() -> bool {
+    true
+}
+
+fn default_reconciliation_interval_secs() -> u64 {
+    60
+}
+
+fn default_reconciliation_auto_repair() -> bool {
+    false
+}
+
 impl IndexerConfig {
     pub fn load() -> std::result::Result<Self, config::ConfigError> {
         let cfg = config::Config::builder()
@@ -65,6 +65,11 @@ impl Database {
         let migration_0007 =
             include_str!("../../migrations/0007_backfill_and_normalized_storage.sql");
         let migration_0008 = include_str!("../../migrations/0008_soroban_discovery_cursors.sql");
+        let migration_0010 = include_str!("../../migrations/0010_reconciliation.sql");
+        let migration_0011 = include_str!("../../migrations/0011_backfill_jobs.sql");
+        let migration_0012 = include_str!("../../migrations/0012_liquidity_update_notify.sql");
+        let migration_0013 = include_str!("../../migrations/0013_backfill_job_leases.sql");
+        let migration_0014 = include_str!("../../migrations/0014_stable_swap_pools.sql");
 
         // Execute migrations in order
         info!("Running migration 0001_init.sql");
@@ -160,6 +165,66 @@ impl Database {
                 ))
             })?;
 
+        info!("Running migration 0010_reconciliation.sql");
+        sqlx::query(migration_0010)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Migration 0010 failed: {}", e);
+                IndexerError::DatabaseMigration(format!(
+                    "Failed to run 0010_reconciliation.sql: {}",
+                    e
+                ))
+            })?;
+
+        info!("Running migration 0011_backfill_jobs.sql");
+        sqlx::query(migration_0011)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Migration 0011 failed: {}", e);
+                IndexerError::DatabaseMigration(format!(
+                    "Failed to run 0011_backfill_jobs.sql: {}",
+                    e
+                ))
+            })?;
+
+        info!("Running migration 0012_liquidity_update_notify.sql");
+        sqlx::query(migration_0012)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Migration 0012 failed: {}", e);
+                IndexerError::DatabaseMigration(format!(
+                    "Failed to run 0012_liquidity_update_notify.sql: {}",
+                    e
+                ))
+            })?;
+
+        info!("Running migration 0013_backfill_job_leases.sql");
+        sqlx::query(migration_0013)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Migration 0013 failed: {}", e);
+                IndexerError::DatabaseMigration(format!(
+                    "Failed to run 0013_backfill_job_leases.sql: {}",
+                    e
+                ))
+            })?;
+
+        info!("Running migration 0014_stable_swap_pools.sql");
+        sqlx::query(migration_0014)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Migration 0014 failed: {}", e);
+                IndexerError::DatabaseMigration(format!(
+                    "Failed to run 0014_stable_swap_pools.sql: {}",
+                    e
+                ))
+            })?;
+
         info!("Database migrations completed");
         Ok(())
     }
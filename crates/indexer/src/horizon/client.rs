@@ -1,5 +1,5 @@
 use crate::error::{IndexerError, Result};
-use crate::models::horizon::{HorizonOffer, HorizonOrderbook, HorizonPage};
+use crate::models::horizon::{HorizonAccount, HorizonOffer, HorizonOrderbook, HorizonPage};
 use std::time::Duration;
 use tracing::{debug, warn};
 
@@ -203,6 +203,36 @@ impl HorizonClient {
         .await
     }
 
+    /// Fetch an account with its balances and trustlines.
+    ///
+    /// Endpoint: `GET /accounts/{account_id}`. An unfunded account yields
+    /// `IndexerError::StellarApi` with status 404.
+    pub async fn get_account(&self, account_id: &str) -> Result<HorizonAccount> {
+        let url = format!("{}/accounts/{}", self.base_url, account_id);
+
+        let client = self.http.clone();
+        let url_clone = url.clone();
+
+        self.retry_request(|| async {
+            debug!("Fetching account from: {}", url_clone);
+            let resp = client.get(&url_clone).send().await?;
+
+            let status = resp.status();
+            if !status.is_success() {
+                let error_body = resp.text().await.unwrap_or_default();
+                return Err(IndexerError::StellarApi {
+                    endpoint: url_clone.clone(),
+                    status: status.as_u16(),
+                    message: error_body,
+                });
+            }
+
+            let account: HorizonAccount = resp.json().await?;
+            Ok(account)
+        })
+        .await
+    }
+
     /// Stream offers in real-time using Server-Sent Events (SSE).
     ///
     /// Endpoint: `GET /offers?cursor=now`
@@ -375,6 +405,39 @@ mod tests {
         .to_string()
     }
 
+    fn account_json() -> String {
+        serde_json::json!({
+            "account_id": "GDVEU3DD4KOFECV66VIHWEZOYX4ZKR3WV27L464SIIPOU2IUI3JCZA57",
+            "sequence": "1234567890",
+            "subentry_count": 2,
+            "balances": [
+                {
+                    "balance": "25.0000000",
+                    "limit": "922337203685.4775807",
+                    "buying_liabilities": "0.0000000",
+                    "selling_liabilities": "5.0000000",
+                    "is_authorized": true,
+                    "asset_type": "credit_alphanum4",
+                    "asset_code": "USDC",
+                    "asset_issuer": "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+                },
+                {
+                    "balance": "1.5000000",
+                    "limit": "922337203685.4775807",
+                    "asset_type": "liquidity_pool_shares",
+                    "liquidity_pool_id": "dd7b1ab831c273310ddbec6f97870aa83c2fbd78ce22aded37ecbf4f3380fac7"
+                },
+                {
+                    "balance": "100.0000000",
+                    "buying_liabilities": "0.0000000",
+                    "selling_liabilities": "0.0000000",
+                    "asset_type": "native"
+                }
+            ]
+        })
+        .to_string()
+    }
+
     // -----------------------------------------------------------------------
     // RetryConfig unit tests
     // -----------------------------------------------------------------------
@@ -620,6 +683,62 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // -----------------------------------------------------------------------
+    // get_account
+    // -----------------------------------------------------------------------
+
+    #[tokio::test]
+    async fn test_get_account_returns_balances() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(
+                "/accounts/GDVEU3DD4KOFECV66VIHWEZOYX4ZKR3WV27L464SIIPOU2IUI3JCZA57",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(account_json()))
+            .mount(&mock_server)
+            .await;
+
+        let client = HorizonClient::new(mock_server.uri());
+        let account = client
+            .get_account("GDVEU3DD4KOFECV66VIHWEZOYX4ZKR3WV27L464SIIPOU2IUI3JCZA57")
+            .await
+            .unwrap();
+
+        assert_eq!(account.subentry_count, 2);
+        assert_eq!(account.num_sponsoring, 0);
+        let assets: Vec<_> = account
+            .balances
+            .iter()
+            .map(|b| b.canonical_asset())
+            .collect();
+        assert_eq!(
+            assets,
+            vec![
+                Some("USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string()),
+                None,
+                Some("native".to_string()),
+            ]
+        );
+        assert_eq!(account.balances[0].is_authorized, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_get_account_404_is_not_retried() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/accounts/GUNFUNDED"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Resource Missing"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = HorizonClient::new(mock_server.uri());
+        let err = client.get_account("GUNFUNDED").await.unwrap_err();
+        assert!(matches!(err, IndexerError::StellarApi { status: 404, .. }));
+    }
+
     // -----------------------------------------------------------------------
     // get_orderbook – success
     // -----------------------------------------------------------------------
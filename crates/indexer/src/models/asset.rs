@@ -20,6 +20,32 @@ pub enum Asset {
 }
 
 impl Asset {
+    /// Parse the canonical `native` / `CODE:ISSUER` representation
+    pub fn from_canonical(s: &str) -> Option<Self> {
+        if s.eq_ignore_ascii_case("native") {
+            return Some(Asset::Native);
+        }
+
+        let (code, issuer) = s.split_once(':')?;
+        if code.is_empty() || issuer.is_empty() {
+            return None;
+        }
+
+        let asset_code = code.to_string();
+        let asset_issuer = issuer.to_string();
+        match code.len() {
+            1..=4 => Some(Asset::CreditAlphanum4 {
+                asset_code,
+                asset_issuer,
+            }),
+            5..=12 => Some(Asset::CreditAlphanum12 {
+                asset_code,
+                asset_issuer,
+            }),
+            _ => None,
+        }
+    }
+
     pub fn key(&self) -> (String, Option<String>, Option<String>) {
         match self {
             Asset::Native => ("native".to_string(), None, None),
@@ -87,6 +113,37 @@ mod tests {
         assert!(issuer.is_some(), "CreditAlphanum12 issuer should be Some");
     }
 
+    // -----------------------------------------------------------------------
+    // Asset::from_canonical()
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_from_canonical_native() {
+        assert_eq!(Asset::from_canonical("native"), Some(Asset::Native));
+    }
+
+    #[test]
+    fn test_from_canonical_credit_assets() {
+        assert_eq!(
+            Asset::from_canonical("USDC:GISSUER"),
+            Some(Asset::CreditAlphanum4 {
+                asset_code: "USDC".to_string(),
+                asset_issuer: "GISSUER".to_string(),
+            })
+        );
+        assert!(matches!(
+            Asset::from_canonical("YIELDXLM00:GISSUER"),
+            Some(Asset::CreditAlphanum12 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_canonical_rejects_malformed() {
+        assert_eq!(Asset::from_canonical("USDC"), None);
+        assert_eq!(Asset::from_canonical(":GISSUER"), None);
+        assert_eq!(Asset::from_canonical("ABCDEFGHIJKLM:GISSUER"), None);
+    }
+
     // -----------------------------------------------------------------------
     // Equality
     // -----------------------------------------------------------------------
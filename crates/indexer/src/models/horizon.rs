@@ -97,3 +97,55 @@ impl HorizonOrderbook {
         Some((bid + ask) / 2.0)
     }
 }
+
+/// A balance line of an account returned by `GET /accounts/{id}`.
+///
+/// Credit balances are trustlines; `liquidity_pool_shares` lines carry a
+/// `liquidity_pool_id` instead of an asset code and issuer.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HorizonBalance {
+    pub balance: String,
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub liquidity_pool_id: Option<String>,
+    /// Trustline limit (absent for native)
+    pub limit: Option<String>,
+    #[serde(default)]
+    pub buying_liabilities: Option<String>,
+    #[serde(default)]
+    pub selling_liabilities: Option<String>,
+    /// Whether the issuer authorized the trustline (absent for native)
+    pub is_authorized: Option<bool>,
+}
+
+impl HorizonBalance {
+    /// Canonical asset id (`native` or `CODE:ISSUER`), or `None` for
+    /// liquidity pool shares.
+    pub fn canonical_asset(&self) -> Option<String> {
+        match (
+            self.asset_type.as_str(),
+            &self.asset_code,
+            &self.asset_issuer,
+        ) {
+            ("native", _, _) => Some("native".to_string()),
+            ("credit_alphanum4" | "credit_alphanum12", Some(code), Some(issuer)) => {
+                Some(format!("{}:{}", code, issuer))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Typed account returned by `GET /accounts/{id}`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HorizonAccount {
+    pub account_id: String,
+    pub sequence: String,
+    pub subentry_count: u32,
+    #[serde(default)]
+    pub num_sponsoring: u32,
+    #[serde(default)]
+    pub num_sponsored: u32,
+    pub balances: Vec<HorizonBalance>,
+}
@@ -11,6 +11,8 @@ pub struct PoolReserve {
     pub reserve_buying: rust_decimal::Decimal,
     pub fee_bps: i32,
     pub last_updated_ledger: i64,
+    /// StableSwap amplification coefficient; `None` for constant-product pools
+    pub amp: Option<i32>,
     pub updated_at: DateTime<Utc>,
 }
 
@@ -23,4 +25,6 @@ pub struct PoolState {
     pub reserve_b: i128,
     pub fee_bps: i32,
     pub ledger_sequence: i64,
+    /// StableSwap amplification coefficient; `None` for constant-product pools
+    pub amp: Option<i32>,
 }
@@ -3,8 +3,15 @@
 //! This module provides a production-safe mechanism to backfill and normalize
 //! historical records from `sdex_offers` and `amm_pool_reserves` into the optimized
 //! `normalized_liquidity` storage table.
+//!
+//! Besides the full-table job driven by [`BackfillManager::run_backfill`],
+//! operators can create jobs scoped to a ledger (or time) range and an asset
+//! pair. Such jobs are split into ledger chunks that run concurrently and are
+//! checkpointed individually in `backfill_job_chunks`, so a paused or failed
+//! job resumes with only the unfinished chunks.
 
 use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -12,11 +19,24 @@ use sqlx::{FromRow, PgPool, Row};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
+use uuid::Uuid;
 
-use crate::error::Result;
+use crate::error::{IndexerError, Result};
+use crate::models::asset::Asset;
 
 const JOB_ID: &str = "historical_liquidity_normalization";
 
+/// Default number of ledgers per backfill chunk
+pub const DEFAULT_CHUNK_LEDGERS: i64 = 10_000;
+
+/// How long a runner holds a job without renewing its lease. A `running` job
+/// whose lease expired was abandoned (the runner crashed or was killed) and
+/// can be resumed by another runner.
+pub const BACKFILL_LEASE: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Interval at which a runner renews its lease while chunks are in flight
+const BACKFILL_HEARTBEAT: std::time::Duration = std::time::Duration::from_secs(20);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BackfillStatus {
     Idle,
@@ -27,6 +47,25 @@ pub enum BackfillStatus {
 }
 
 impl BackfillStatus {
+    /// Build a status from its persisted label and optional error message
+    fn from_row(status: &str, last_error: Option<String>) -> Self {
+        match status {
+            "error" => BackfillStatus::Error(last_error.unwrap_or_else(|| "error".to_string())),
+            other => Self::from_string(other),
+        }
+    }
+
+    /// Label persisted in the `status` column
+    fn label(&self) -> &'static str {
+        match self {
+            BackfillStatus::Idle => "idle",
+            BackfillStatus::Running => "running",
+            BackfillStatus::Paused => "paused",
+            BackfillStatus::Completed => "completed",
+            BackfillStatus::Error(_) => "error",
+        }
+    }
+
     pub fn from_string(s: &str) -> Self {
         match s {
             "idle" => BackfillStatus::Idle,
@@ -61,6 +100,114 @@ pub struct BackfillCheckpoint {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Ledger window targeted by a backfill job (both bounds inclusive)
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackfillRange {
+    Ledgers {
+        start: i64,
+        end: i64,
+    },
+    /// Resolved to the ledgers of offers indexed within the time window
+    Time {
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    },
+}
+
+/// Parameters for a new range-scoped backfill job
+#[derive(Debug, Clone)]
+pub struct BackfillJobSpec {
+    pub range: BackfillRange,
+    /// Restrict the job to offers trading this (base, quote) pair in either direction
+    pub pair: Option<(Asset, Asset)>,
+    /// Ledgers per chunk
+    pub chunk_size: i64,
+    /// Chunks processed concurrently
+    pub concurrency: usize,
+}
+
+/// Persisted state of a range-scoped backfill job
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillJob {
+    pub id: Uuid,
+    pub start_ledger: i64,
+    pub end_ledger: i64,
+    pub base_asset_id: Option<Uuid>,
+    pub quote_asset_id: Option<Uuid>,
+    pub chunk_size: i64,
+    pub concurrency: i32,
+    pub status: BackfillStatus,
+    pub ledgers_total: i64,
+    pub ledgers_done: i64,
+    pub rows_processed: i64,
+    pub created_at: DateTime<Utc>,
+    pub resumed_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BackfillJob {
+    fn from_row(row: &sqlx::postgres::PgRow) -> Self {
+        Self {
+            id: row.get("id"),
+            start_ledger: row.get("start_ledger"),
+            end_ledger: row.get("end_ledger"),
+            base_asset_id: row.get("base_asset_id"),
+            quote_asset_id: row.get("quote_asset_id"),
+            chunk_size: row.get("chunk_size"),
+            concurrency: row.get("concurrency"),
+            status: BackfillStatus::from_row(row.get("status"), row.get("last_error")),
+            ledgers_total: row.get("ledgers_total"),
+            ledgers_done: row.get("ledgers_done"),
+            rows_processed: row.get("rows_processed"),
+            created_at: row.get("created_at"),
+            resumed_at: row.get("resumed_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+/// Progress report for a range-scoped backfill job
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillProgress {
+    pub job: BackfillJob,
+    pub chunks_total: i64,
+    pub chunks_done: i64,
+    pub percent_complete: f64,
+    /// Throughput since the job was last started or resumed
+    pub ledgers_per_sec: Option<f64>,
+    pub eta_secs: Option<u64>,
+}
+
+/// Split an inclusive ledger range into inclusive chunks of at most `chunk_size` ledgers
+pub fn split_ledger_range(start: i64, end: i64, chunk_size: i64) -> Vec<(i64, i64)> {
+    if chunk_size <= 0 || end < start {
+        return Vec::new();
+    }
+
+    let mut chunks = Vec::new();
+    let mut chunk_start = start;
+    while chunk_start <= end {
+        let chunk_end = chunk_start.saturating_add(chunk_size - 1).min(end);
+        chunks.push((chunk_start, chunk_end));
+        if chunk_end == end {
+            break;
+        }
+        chunk_start = chunk_end + 1;
+    }
+    chunks
+}
+
+/// Estimate the remaining seconds from the observed throughput
+fn estimate_eta_secs(remaining_ledgers: i64, ledgers_per_sec: Option<f64>) -> Option<u64> {
+    if remaining_ledgers <= 0 {
+        return Some(0);
+    }
+    match ledgers_per_sec {
+        Some(rate) if rate > 0.0 => Some((remaining_ledgers as f64 / rate).ceil() as u64),
+        _ => None,
+    }
+}
+
 pub struct BackfillManager {
     pool: PgPool,
     status: Arc<RwLock<BackfillStatus>>,
@@ -243,6 +390,500 @@ impl BackfillManager {
     }
 }
 
+impl BackfillManager {
+    /// Create a range-scoped backfill job and its chunk checkpoints
+    pub async fn create_job(&self, spec: BackfillJobSpec) -> Result<BackfillJob> {
+        if spec.chunk_size <= 0 {
+            return Err(IndexerError::InvalidConfig {
+                field: "chunk_size".to_string(),
+                reason: "must be positive".to_string(),
+            });
+        }
+        if spec.concurrency == 0 {
+            return Err(IndexerError::InvalidConfig {
+                field: "concurrency".to_string(),
+                reason: "must be at least 1".to_string(),
+            });
+        }
+
+        let (start_ledger, end_ledger) = self.resolve_range(&spec.range).await?;
+        let (base_asset_id, quote_asset_id) = match &spec.pair {
+            Some((base, quote)) => (
+                Some(self.lookup_asset_id(base).await?),
+                Some(self.lookup_asset_id(quote).await?),
+            ),
+            None => (None, None),
+        };
+
+        let chunks = split_ledger_range(start_ledger, end_ledger, spec.chunk_size);
+        let (chunk_starts, chunk_ends): (Vec<i64>, Vec<i64>) = chunks.into_iter().unzip();
+        let id = Uuid::new_v4();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO backfill_jobs (
+                id, start_ledger, end_ledger, base_asset_id, quote_asset_id,
+                chunk_size, concurrency, status, ledgers_total
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 'idle', $8)
+            "#,
+        )
+        .bind(id)
+        .bind(start_ledger)
+        .bind(end_ledger)
+        .bind(base_asset_id)
+        .bind(quote_asset_id)
+        .bind(spec.chunk_size)
+        .bind(spec.concurrency as i32)
+        .bind(end_ledger - start_ledger + 1)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO backfill_job_chunks (job_id, chunk_start, chunk_end)
+            SELECT $1, s, e FROM UNNEST($2::BIGINT[], $3::BIGINT[]) AS c(s, e)
+            "#,
+        )
+        .bind(id)
+        .bind(&chunk_starts)
+        .bind(&chunk_ends)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!(
+            "Created backfill job {}: ledgers {}..={} in {} chunk(s)",
+            id,
+            start_ledger,
+            end_ledger,
+            chunk_starts.len()
+        );
+
+        self.get_job(id).await
+    }
+
+    /// Fetch a range-scoped backfill job
+    pub async fn get_job(&self, job_id: Uuid) -> Result<BackfillJob> {
+        let row = sqlx::query("SELECT * FROM backfill_jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| {
+                IndexerError::OperationFailed(format!("Unknown backfill job {}", job_id))
+            })?;
+        Ok(BackfillJob::from_row(&row))
+    }
+
+    /// List the most recent range-scoped backfill jobs
+    pub async fn list_jobs(&self, limit: i64) -> Result<Vec<BackfillJob>> {
+        let rows = sqlx::query("SELECT * FROM backfill_jobs ORDER BY created_at DESC LIMIT $1")
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(BackfillJob::from_row).collect())
+    }
+
+    /// Report chunk counts, completion and ETA for a job
+    pub async fn job_progress(&self, job_id: Uuid) -> Result<BackfillProgress> {
+        let job = self.get_job(job_id).await?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) AS chunks_total,
+                COUNT(*) FILTER (WHERE status = 'completed') AS chunks_done,
+                COALESCE(SUM(chunk_end - chunk_start + 1) FILTER (
+                    WHERE status = 'completed' AND completed_at >= $2
+                ), 0)::BIGINT AS ledgers_since_resume
+            FROM backfill_job_chunks
+            WHERE job_id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(job.resumed_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let ledgers_since_resume: i64 = row.get("ledgers_since_resume");
+        let ledgers_per_sec = job.resumed_at.and_then(|resumed_at| {
+            let elapsed = (job.updated_at - resumed_at).num_milliseconds() as f64 / 1000.0;
+            (elapsed > 0.0 && ledgers_since_resume > 0)
+                .then(|| ledgers_since_resume as f64 / elapsed)
+        });
+        let percent_complete = if job.ledgers_total > 0 {
+            job.ledgers_done as f64 / job.ledgers_total as f64 * 100.0
+        } else {
+            100.0
+        };
+        let eta_secs = estimate_eta_secs(job.ledgers_total - job.ledgers_done, ledgers_per_sec);
+
+        Ok(BackfillProgress {
+            chunks_total: row.get("chunks_total"),
+            chunks_done: row.get("chunks_done"),
+            percent_complete,
+            ledgers_per_sec,
+            eta_secs,
+            job,
+        })
+    }
+
+    /// Run the unfinished chunks of a job until it completes, fails or is paused.
+    ///
+    /// Fails if another runner already holds the job. The pause flag is read from the database before each chunk, so a pause
+    /// issued from another process takes effect once in-flight chunks finish.
+    ///
+    /// The runner holds the job under a [`BACKFILL_LEASE`] renewed by a
+    /// heartbeat, so a job left `running` by a crashed runner can be claimed
+    /// again once its lease expires.
+    pub async fn run_job(&self, job_id: Uuid) -> Result<BackfillProgress> {
+        let job = self.get_job(job_id).await?;
+        if matches!(job.status, BackfillStatus::Completed) {
+            return self.job_progress(job_id).await;
+        }
+
+        // Claim the job atomically so two runners never process it at once
+        let token = Uuid::new_v4();
+        let claimed: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE backfill_jobs
+            SET status = 'running', last_error = NULL, resumed_at = now(), updated_at = now(),
+                locked_by = $2, lease_expires_at = now() + make_interval(secs => $3)
+            WHERE id = $1
+              AND (status IN ('idle', 'paused', 'error')
+                   OR (status = 'running'
+                       AND (lease_expires_at IS NULL OR lease_expires_at < now())))
+            RETURNING id
+            "#,
+        )
+        .bind(job_id)
+        .bind(token)
+        .bind(BACKFILL_LEASE.as_secs_f64())
+        .fetch_optional(&self.pool)
+        .await?;
+        if claimed.is_none() {
+            return Err(IndexerError::OperationFailed(format!(
+                "Backfill job {} is already running (its lease has not expired) or completed",
+                job_id
+            )));
+        }
+
+        let pending: Vec<(i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT chunk_start, chunk_end
+            FROM backfill_job_chunks
+            WHERE job_id = $1 AND status <> 'completed'
+            ORDER BY chunk_start
+            "#,
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        info!(
+            "Running backfill job {}: {} pending chunk(s), concurrency {}",
+            job_id,
+            pending.len(),
+            job.concurrency
+        );
+
+        let job_ref = &job;
+        let work = stream::iter(pending)
+            .map(|(chunk_start, chunk_end)| async move {
+                if self.is_paused(job_ref.id).await? {
+                    return Ok(());
+                }
+                self.process_chunk(job_ref, chunk_start, chunk_end).await
+            })
+            .buffer_unordered(job.concurrency.max(1) as usize)
+            .filter_map(|result| async move { result.err().map(|e| e.to_string()) })
+            .collect::<Vec<String>>();
+        tokio::pin!(work);
+
+        let mut heartbeat = tokio::time::interval(BACKFILL_HEARTBEAT);
+        heartbeat.tick().await;
+        let failures = loop {
+            tokio::select! {
+                failures = &mut work => break failures,
+                _ = heartbeat.tick() => {
+                    if let Err(e) = self.renew_lease(job_id, token).await {
+                        warn!("Failed to renew lease on backfill job {}: {}", job_id, e);
+                    }
+                }
+            }
+        };
+
+        if let Some(first) = failures.first() {
+            error!(
+                "Backfill job {} finished with {} failed chunk(s)",
+                job_id,
+                failures.len()
+            );
+            sqlx::query(
+                r#"
+                UPDATE backfill_jobs
+                SET status = 'error', last_error = $2, locked_by = NULL, lease_expires_at = NULL,
+                    updated_at = now()
+                WHERE id = $1 AND locked_by = $3
+                "#,
+            )
+            .bind(job_id)
+            .bind(first)
+            .bind(token)
+            .execute(&self.pool)
+            .await?;
+        } else {
+            sqlx::query(
+                r#"
+                UPDATE backfill_jobs
+                SET status = 'completed', locked_by = NULL, lease_expires_at = NULL,
+                    updated_at = now()
+                WHERE id = $1
+                  AND status = 'running'
+                  AND locked_by = $2
+                  AND NOT EXISTS (
+                      SELECT 1 FROM backfill_job_chunks
+                      WHERE job_id = $1 AND status <> 'completed'
+                  )
+                "#,
+            )
+            .bind(job_id)
+            .bind(token)
+            .execute(&self.pool)
+            .await?;
+        }
+
+        self.job_progress(job_id).await
+    }
+
+    /// Extend the lease of a running job held by `token`
+    async fn renew_lease(&self, job_id: Uuid, token: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE backfill_jobs
+            SET lease_expires_at = now() + make_interval(secs => $3)
+            WHERE id = $1 AND status = 'running' AND locked_by = $2
+            "#,
+        )
+        .bind(job_id)
+        .bind(token)
+        .bind(BACKFILL_LEASE.as_secs_f64())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Request a running job to stop after its in-flight chunks
+    pub async fn pause_job(&self, job_id: Uuid) -> Result<BackfillJob> {
+        sqlx::query(
+            "UPDATE backfill_jobs SET status = 'paused', updated_at = now() WHERE id = $1 AND status <> 'completed'",
+        )
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        info!("Backfill job {} pause requested", job_id);
+        self.get_job(job_id).await
+    }
+
+    /// Resume a paused, failed or abandoned job from its chunk checkpoints
+    pub async fn resume_job(&self, job_id: Uuid) -> Result<BackfillProgress> {
+        self.run_job(job_id).await
+    }
+
+    async fn is_paused(&self, job_id: Uuid) -> Result<bool> {
+        let status: String = sqlx::query_scalar("SELECT status FROM backfill_jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(status == BackfillStatus::Paused.label())
+    }
+
+    /// Normalize one ledger chunk and checkpoint it atomically
+    async fn process_chunk(
+        &self,
+        job: &BackfillJob,
+        chunk_start: i64,
+        chunk_end: i64,
+    ) -> Result<()> {
+        let result = async {
+            let mut tx = self.pool.begin().await?;
+
+            let rows = sqlx::query(
+                r#"
+                INSERT INTO normalized_liquidity (
+                    venue_type, venue_ref, selling_asset_id, buying_asset_id,
+                    price, available_amount, price_e7, available_amount_e7,
+                    source_ledger, updated_at
+                )
+                SELECT
+                    'sdex', o.offer_id::TEXT, o.selling_asset_id, o.buying_asset_id,
+                    o.price, o.amount,
+                    TRUNC(o.price * 10000000)::BIGINT, TRUNC(o.amount * 10000000)::BIGINT,
+                    o.last_modified_ledger, now()
+                FROM sdex_offers o
+                WHERE o.last_modified_ledger BETWEEN $1 AND $2
+                  AND TRUNC(o.price * 10000000) > 0
+                  AND TRUNC(o.amount * 10000000) > 0
+                  AND (
+                      $3::UUID IS NULL
+                      OR (o.selling_asset_id = $3 AND o.buying_asset_id = $4)
+                      OR (o.selling_asset_id = $4 AND o.buying_asset_id = $3)
+                  )
+                ON CONFLICT (venue_type, venue_ref) DO UPDATE SET
+                    price = EXCLUDED.price,
+                    available_amount = EXCLUDED.available_amount,
+                    price_e7 = EXCLUDED.price_e7,
+                    available_amount_e7 = EXCLUDED.available_amount_e7,
+                    source_ledger = EXCLUDED.source_ledger,
+                    updated_at = now()
+                "#,
+            )
+            .bind(chunk_start)
+            .bind(chunk_end)
+            .bind(job.base_asset_id)
+            .bind(job.quote_asset_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected() as i64;
+
+            // Count the chunk towards the job only on its transition to completed
+            let transitioned = sqlx::query(
+                r#"
+                UPDATE backfill_job_chunks
+                SET status = 'completed', rows_processed = $3, last_error = NULL, completed_at = now()
+                WHERE job_id = $1 AND chunk_start = $2 AND status IN ('pending', 'error')
+                "#,
+            )
+            .bind(job.id)
+            .bind(chunk_start)
+            .bind(rows)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+                > 0;
+            if !transitioned {
+                tx.rollback().await?;
+                return Ok::<Option<i64>, IndexerError>(None);
+            }
+
+            sqlx::query(
+                r#"
+                UPDATE backfill_jobs
+                SET ledgers_done = ledgers_done + $2,
+                    rows_processed = rows_processed + $3,
+                    updated_at = now()
+                WHERE id = $1
+                "#,
+            )
+            .bind(job.id)
+            .bind(chunk_end - chunk_start + 1)
+            .bind(rows)
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+            Ok(Some(rows))
+        }
+        .await;
+
+        match result {
+            Ok(Some(rows)) => {
+                info!(
+                    "Backfill job {} chunk {}..={} normalized {} offer(s)",
+                    job.id, chunk_start, chunk_end, rows
+                );
+                Ok(())
+            }
+            Ok(None) => {
+                info!(
+                    "Backfill job {} chunk {}..={} was already completed",
+                    job.id, chunk_start, chunk_end
+                );
+                Ok(())
+            }
+            Err(e) => {
+                warn!(
+                    "Backfill job {} chunk {}..={} failed: {}",
+                    job.id, chunk_start, chunk_end, e
+                );
+                let _ = sqlx::query(
+                    "UPDATE backfill_job_chunks SET status = 'error', last_error = $3 WHERE job_id = $1 AND chunk_start = $2",
+                )
+                .bind(job.id)
+                .bind(chunk_start)
+                .bind(e.to_string())
+                .execute(&self.pool)
+                .await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn resolve_range(&self, range: &BackfillRange) -> Result<(i64, i64)> {
+        let (start, end) = match range {
+            BackfillRange::Ledgers { start, end } => (*start, *end),
+            BackfillRange::Time { from, to } => {
+                let row = sqlx::query(
+                    r#"
+                    SELECT MIN(last_modified_ledger) AS start_ledger,
+                           MAX(last_modified_ledger) AS end_ledger
+                    FROM sdex_offers
+                    WHERE updated_at BETWEEN $1 AND $2
+                    "#,
+                )
+                .bind(from)
+                .bind(to)
+                .fetch_one(&self.pool)
+                .await?;
+
+                match (
+                    row.get::<Option<i64>, _>("start_ledger"),
+                    row.get::<Option<i64>, _>("end_ledger"),
+                ) {
+                    (Some(start), Some(end)) => (start, end),
+                    _ => {
+                        return Err(IndexerError::InvalidConfig {
+                            field: "range".to_string(),
+                            reason: format!("no indexed ledgers between {} and {}", from, to),
+                        })
+                    }
+                }
+            }
+        };
+
+        if start < 0 || end < start {
+            return Err(IndexerError::InvalidConfig {
+                field: "range".to_string(),
+                reason: format!("invalid ledger range {}..={}", start, end),
+            });
+        }
+        Ok((start, end))
+    }
+
+    async fn lookup_asset_id(&self, asset: &Asset) -> Result<Uuid> {
+        let (asset_type, code, issuer) = asset.key();
+        sqlx::query_scalar(
+            r#"
+            SELECT id FROM assets
+            WHERE asset_type = $1
+              AND asset_code IS NOT DISTINCT FROM $2
+              AND asset_issuer IS NOT DISTINCT FROM $3
+            "#,
+        )
+        .bind(&asset_type)
+        .bind(&code)
+        .bind(&issuer)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| IndexerError::InvalidAsset {
+            asset: code.unwrap_or(asset_type),
+            reason: "asset is not indexed".to_string(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,4 +914,44 @@ mod tests {
         ));
         assert_eq!(BackfillStatus::Idle.to_string(), "idle");
     }
+
+    #[test]
+    fn test_backfill_status_from_row_keeps_error_message() {
+        let status = BackfillStatus::from_row("error", Some("connection reset".to_string()));
+        assert!(matches!(status, BackfillStatus::Error(ref e) if e == "connection reset"));
+        assert_eq!(status.label(), "error");
+        assert_eq!(BackfillStatus::Paused.label(), "paused");
+    }
+
+    #[test]
+    fn test_split_ledger_range_even_and_remainder() {
+        assert_eq!(
+            split_ledger_range(100, 129, 10),
+            vec![(100, 109), (110, 119), (120, 129)]
+        );
+        assert_eq!(split_ledger_range(100, 104, 10), vec![(100, 104)]);
+        assert_eq!(
+            split_ledger_range(1, 25, 10),
+            vec![(1, 10), (11, 20), (21, 25)]
+        );
+    }
+
+    #[test]
+    fn test_split_ledger_range_degenerate_inputs() {
+        assert_eq!(split_ledger_range(5, 5, 10), vec![(5, 5)]);
+        assert!(split_ledger_range(10, 5, 10).is_empty());
+        assert!(split_ledger_range(1, 10, 0).is_empty());
+        assert_eq!(
+            split_ledger_range(i64::MAX - 1, i64::MAX, 10),
+            vec![(i64::MAX - 1, i64::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_estimate_eta() {
+        assert_eq!(estimate_eta_secs(0, None), Some(0));
+        assert_eq!(estimate_eta_secs(1_000, None), None);
+        assert_eq!(estimate_eta_secs(1_000, Some(0.0)), None);
+        assert_eq!(estimate_eta_secs(1_000, Some(300.0)), Some(4));
+    }
 }
@@ -3,6 +3,7 @@
 //! Defines various types of checks to detect drift between Horizon and Soroban RPC data.
 
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use serde_json::json;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
@@ -10,7 +11,8 @@ use uuid::Uuid;
 use crate::error::{IndexerError, Result};
 
 /// Types of consistency checks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CheckType {
     AssetMapping,
     PriceDivergence,
@@ -32,13 +34,25 @@ impl std::fmt::Display for CheckType {
 }
 
 /// Severity levels for detected drift
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DriftSeverity {
     Info,
     Warning,
     Critical,
 }
 
+impl DriftSeverity {
+    /// Parse the persisted severity label, treating unknown values as `Info`
+    pub fn from_string(s: &str) -> Self {
+        match s {
+            "warning" => Self::Warning,
+            "critical" => Self::Critical,
+            _ => Self::Info,
+        }
+    }
+}
+
 impl std::fmt::Display for DriftSeverity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -305,6 +319,18 @@ mod tests {
         assert!(DriftSeverity::Warning < DriftSeverity::Critical);
     }
 
+    #[test]
+    fn test_drift_severity_round_trip() {
+        for severity in [
+            DriftSeverity::Info,
+            DriftSeverity::Warning,
+            DriftSeverity::Critical,
+        ] {
+            assert_eq!(DriftSeverity::from_string(&severity.to_string()), severity);
+        }
+        assert_eq!(DriftSeverity::from_string("bogus"), DriftSeverity::Info);
+    }
+
     #[test]
     fn test_check_type_display() {
         assert_eq!(CheckType::DataStaleness.to_string(), "data_staleness");
@@ -1,8 +1,9 @@
 //! Main reconciliation engine orchestration
 
 use chrono::Utc;
-use sqlx::PgPool;
-use tracing::info;
+use serde::Serialize;
+use sqlx::{PgPool, Row};
+use tracing::{info, warn};
 use uuid::Uuid;
 
 use super::consistency::{
@@ -10,6 +11,7 @@ use super::consistency::{
     check_price_divergence, CheckThresholds, ConsistencyCheckResult, DriftSeverity,
 };
 use super::metrics::{DriftMetrics, ReconciliationMetrics};
+use super::repair::{RepairPlan, RepairWorkflow};
 use crate::error::Result;
 
 /// Main reconciliation engine
@@ -17,6 +19,7 @@ pub struct ReconciliationEngine {
     db: PgPool,
     thresholds: CheckThresholds,
     metrics: ReconciliationMetrics,
+    auto_repair: bool,
 }
 
 impl ReconciliationEngine {
@@ -29,9 +32,22 @@ impl ReconciliationEngine {
             db,
             thresholds,
             metrics,
+            auto_repair: false,
         })
     }
 
+    /// Enable or disable automatic repair of warning-level drift
+    pub fn with_auto_repair(mut self, enabled: bool) -> Self {
+        self.auto_repair = enabled;
+        self
+    }
+
+    /// Re-read thresholds so operator edits apply without a restart
+    pub async fn reload_thresholds(&mut self) -> Result<()> {
+        self.thresholds = CheckThresholds::load_from_db(&self.db).await?;
+        Ok(())
+    }
+
     /// Run a complete reconciliation cycle
     pub async fn run_reconciliation_cycle(&self) -> Result<ReconciliationRun> {
         let run_id = Uuid::new_v4();
@@ -42,8 +58,10 @@ impl ReconciliationEngine {
         let mut checks_passed = 0;
         let mut checks_failed = 0;
         let mut drift_events = Vec::new();
-        let mut _repairs_attempted = 0;
-        let mut _successful_repairs = 0;
+        let mut repairs_attempted = 0;
+        let mut successful_repairs = 0;
+        let mut failed_repairs = 0;
+        let workflow = RepairWorkflow::new(&self.db);
 
         // Run all consistency checks
         let check_results = self.run_all_checks().await?;
@@ -51,13 +69,37 @@ impl ReconciliationEngine {
 
         for result in check_results {
             // Save the check result
-            let _check_id = result.save(&self.db).await?;
+            let check_id = result.save(&self.db).await?;
 
             // Emit drift metrics
-            let drift_metric = DriftMetrics::from_check_result(&result);
+            let mut drift_metric = DriftMetrics::from_check_result(&result);
+            drift_metric.check_id = Some(check_id);
             drift_metric.save(&self.db).await?;
             drift_events.push(drift_metric);
 
+            // Repair or escalate; a failed repair must not abort the cycle
+            let plan = RepairPlan::for_result(&result, self.auto_repair);
+            match workflow.apply(plan, &result, check_id).await {
+                Ok(repairs) => {
+                    for repair in repairs {
+                        repairs_attempted += 1;
+                        if repair.success {
+                            successful_repairs += 1;
+                        } else {
+                            failed_repairs += 1;
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Repair {:?} failed for {} {}: {}",
+                        plan, result.entity_type, result.entity_ref, e
+                    );
+                    repairs_attempted += 1;
+                    failed_repairs += 1;
+                }
+            }
+
             // Track pass/fail
             if result.severity == DriftSeverity::Info {
                 checks_passed += 1;
@@ -82,9 +124,9 @@ impl ReconciliationEngine {
                 .iter()
                 .filter(|d| d.severity == DriftSeverity::Critical)
                 .count(),
-            total_repairs_attempted: _repairs_attempted,
-            successful_repairs: _successful_repairs,
-            failed_repairs: 0,
+            total_repairs_attempted: repairs_attempted,
+            successful_repairs,
+            failed_repairs,
             duration_ms,
         };
 
@@ -133,7 +175,7 @@ impl ReconciliationEngine {
 }
 
 /// Summary of a reconciliation run
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ReconciliationRun {
     pub id: Uuid,
     pub started_at: chrono::DateTime<chrono::Utc>,
@@ -183,6 +225,48 @@ impl ReconciliationRun {
 
         Ok(())
     }
+
+    /// Load the most recent runs, newest first
+    pub async fn list_recent(db: &PgPool, limit: i64) -> Result<Vec<Self>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, run_started_at, run_completed_at,
+                   checks_executed, checks_passed, checks_failed,
+                   total_drift_events, critical_drift_events,
+                   total_repairs_attempted, successful_repairs, failed_repairs,
+                   duration_ms
+            FROM reconciliation_runs
+            ORDER BY run_started_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(db)
+        .await
+        .map_err(crate::error::IndexerError::DatabaseQuery)?;
+
+        let count = |row: &sqlx::postgres::PgRow, column: &str| -> usize {
+            row.get::<i32, _>(column).max(0) as usize
+        };
+
+        Ok(rows
+            .iter()
+            .map(|row| Self {
+                id: row.get("id"),
+                started_at: row.get("run_started_at"),
+                completed_at: row.get("run_completed_at"),
+                checks_executed: count(row, "checks_executed"),
+                checks_passed: count(row, "checks_passed"),
+                checks_failed: count(row, "checks_failed"),
+                total_drift_events: count(row, "total_drift_events"),
+                critical_drift_events: count(row, "critical_drift_events"),
+                total_repairs_attempted: count(row, "total_repairs_attempted"),
+                successful_repairs: count(row, "successful_repairs"),
+                failed_repairs: count(row, "failed_repairs"),
+                duration_ms: row.get("duration_ms"),
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
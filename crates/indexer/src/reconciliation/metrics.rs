@@ -1,8 +1,9 @@
 //! Drift metrics collection and emission
 
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use serde_json::json;
-use sqlx::PgPool;
+use sqlx::{PgPool, Row};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -100,7 +101,7 @@ impl MetricsSnapshot {
 }
 
 /// Recorded drift metric event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DriftMetrics {
     pub check_id: Option<Uuid>,
     pub entity_type: String,
@@ -199,6 +200,103 @@ impl DriftMetrics {
 
         Ok(id)
     }
+
+    /// Load drift events recorded within the last `window_hours`
+    pub async fn list_recent(
+        db: &PgPool,
+        category: Option<&str>,
+        breach_only: bool,
+        window_hours: i64,
+        limit: i64,
+    ) -> Result<Vec<Self>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT check_id, entity_type, entity_ref, drift_category, metric_name,
+                   metric_value, metric_unit, threshold_value, breach,
+                   metadata->>'severity' AS severity, recorded_at
+            FROM drift_events
+            WHERE recorded_at > NOW() - make_interval(hours => $1::INT)
+              AND ($2::TEXT IS NULL OR drift_category = $2)
+              AND (NOT $3 OR breach)
+            ORDER BY recorded_at DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(window_hours)
+        .bind(category)
+        .bind(breach_only)
+        .bind(limit)
+        .fetch_all(db)
+        .await
+        .map_err(IndexerError::DatabaseQuery)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self {
+                check_id: row.get("check_id"),
+                entity_type: row.get("entity_type"),
+                entity_ref: row.get("entity_ref"),
+                drift_category: row.get("drift_category"),
+                metric_name: row.get("metric_name"),
+                metric_value: row.get("metric_value"),
+                metric_unit: row.get("metric_unit"),
+                threshold_value: row.get("threshold_value"),
+                breach: row.get("breach"),
+                severity: DriftSeverity::from_string(
+                    row.get::<Option<&str>, _>("severity").unwrap_or_default(),
+                ),
+                recorded_at: row.get("recorded_at"),
+            })
+            .collect())
+    }
+}
+
+/// Per-category aggregate over recent drift events
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftCategorySummary {
+    pub drift_category: String,
+    pub total_events: i64,
+    pub breaches: i64,
+    pub avg_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub latest: Option<DateTime<Utc>>,
+}
+
+impl DriftCategorySummary {
+    /// Summarise drift events recorded within the last `window_hours`
+    pub async fn load(db: &PgPool, window_hours: i64) -> Result<Vec<Self>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                drift_category,
+                COUNT(*) AS total_events,
+                COUNT(*) FILTER (WHERE breach) AS breaches,
+                AVG(metric_value) AS avg_value,
+                MAX(metric_value) AS max_value,
+                MAX(recorded_at) AS latest
+            FROM drift_events
+            WHERE recorded_at > NOW() - make_interval(hours => $1::INT)
+            GROUP BY drift_category
+            ORDER BY breaches DESC, drift_category
+            "#,
+        )
+        .bind(window_hours)
+        .fetch_all(db)
+        .await
+        .map_err(IndexerError::DatabaseQuery)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self {
+                drift_category: row.get("drift_category"),
+                total_events: row.get("total_events"),
+                breaches: row.get("breaches"),
+                avg_value: row.get("avg_value"),
+                max_value: row.get("max_value"),
+                latest: row.get("latest"),
+            })
+            .collect())
+    }
 }
 
 #[cfg(test)]
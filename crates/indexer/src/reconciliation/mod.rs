@@ -14,11 +14,23 @@
 //!
 //! # Repair Workflow
 //!
-//! For each detected issue, the engine triggers appropriate repairs:
+//! Every detected issue is saved with a drift metric, then handled by severity:
 //!
 //! - **Info**: No action needed
-//! - **Warning**: Log event and emit metrics
-//! - **Critical**: Trigger automatic repair (refetch/invalidate) and alert operator
+//! - **Warning**: With `auto_repair` enabled (off by default), stale data is
+//!   planned for re-fetch and asset mapping drift for invalidation. Neither
+//!   repair is implemented yet, so both are skipped: only the drift metric is
+//!   kept and nothing is written to `repair_actions`
+//! - **Critical**: Escalated to an operator (a warning log plus an
+//!   `alert_operator` repair action); never repaired automatically
+//!
+//! # Scheduling
+//!
+//! [`ReconciliationScheduler`] runs cycles on a fixed interval inside the
+//! `stellarroute-indexer` binary (`RECONCILIATION_INTERVAL_SECS`,
+//! `RECONCILIATION_AUTO_REPAIR`, `RECONCILIATION_ENABLED`). Recent runs, drift
+//! metrics and repairs are exposed read-only by the API under
+//! `/api/v1/ops/reconciliation`.
 //!
 //! # Metrics & Alerts
 //!
@@ -44,9 +56,11 @@ pub mod consistency;
 pub mod engine;
 pub mod metrics;
 pub mod repair;
+pub mod scheduler;
 
 pub use backfill::{BackfillCheckpoint, BackfillManager, BackfillStatus};
 pub use consistency::{CheckThresholds, CheckType, ConsistencyCheckResult, DriftSeverity};
 pub use engine::{ReconciliationEngine, ReconciliationRun};
-pub use metrics::{DriftMetrics, MetricsSnapshot, ReconciliationMetrics};
-pub use repair::{RepairAction, RepairActionType, RepairWorkflow};
+pub use metrics::{DriftCategorySummary, DriftMetrics, MetricsSnapshot, ReconciliationMetrics};
+pub use repair::{RepairAction, RepairActionType, RepairPlan, RepairWorkflow};
+pub use scheduler::{ReconciliationSchedule, ReconciliationScheduler};
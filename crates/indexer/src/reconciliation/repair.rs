@@ -1,14 +1,18 @@
 //! Repair workflow for automatic and manual remediation
 
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use serde_json::{json, Value};
-use sqlx::PgPool;
+use sqlx::{PgPool, Row};
+use tracing::{debug, warn};
 use uuid::Uuid;
 
+use super::consistency::{CheckType, ConsistencyCheckResult, DriftSeverity};
 use crate::error::{IndexerError, Result};
 
 /// Types of repair actions
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RepairActionType {
     RefetchSoroban,
     RefetchHorizon,
@@ -29,8 +33,55 @@ impl std::fmt::Display for RepairActionType {
     }
 }
 
+impl RepairActionType {
+    /// Parse the persisted action label, treating unknown values as `AlertOperator`
+    pub fn from_string(s: &str) -> Self {
+        match s {
+            "refetch_soroban" => Self::RefetchSoroban,
+            "refetch_horizon" => Self::RefetchHorizon,
+            "invalidate_record" => Self::InvalidateRecord,
+            "auto_reconcile" => Self::AutoReconcile,
+            _ => Self::AlertOperator,
+        }
+    }
+}
+
+/// Repair chosen for a single consistency check result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairPlan {
+    /// Nothing to do beyond recording the drift metric
+    None,
+    /// Re-fetch the entity from its source (not implemented; skipped)
+    Refetch,
+    /// Invalidate records that reference the entity (not implemented; skipped)
+    Invalidate,
+    /// Escalate to an operator for manual investigation
+    AlertOperator,
+}
+
+impl RepairPlan {
+    /// Decide how to respond to a check result.
+    ///
+    /// Warning-level drift is repaired automatically when `auto_repair` is set;
+    /// critical drift is always escalated to an operator instead.
+    pub fn for_result(result: &ConsistencyCheckResult, auto_repair: bool) -> Self {
+        match result.severity {
+            DriftSeverity::Info => Self::None,
+            DriftSeverity::Critical => Self::AlertOperator,
+            DriftSeverity::Warning if !auto_repair => Self::None,
+            DriftSeverity::Warning => match result.check_type {
+                CheckType::DataStaleness => Self::Refetch,
+                CheckType::AssetMapping => Self::Invalidate,
+                CheckType::PriceDivergence
+                | CheckType::LiquidityAnomaly
+                | CheckType::LedgerAlignment => Self::None,
+            },
+        }
+    }
+}
+
 /// A repair action that was executed
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RepairAction {
     pub id: Uuid,
     pub check_id: Uuid,
@@ -76,6 +127,49 @@ impl RepairAction {
 
         Ok(())
     }
+
+    /// Load the most recent repair actions, optionally filtered by action type
+    pub async fn list_recent(
+        db: &PgPool,
+        action_type: Option<RepairActionType>,
+        limit: i64,
+    ) -> Result<Vec<Self>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, check_id, action_type, entity_type, entity_ref,
+                   reason, action_details, success, error_message, affected_rows,
+                   executed_at
+            FROM repair_actions
+            WHERE ($1::TEXT IS NULL OR action_type = $1)
+            ORDER BY executed_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(action_type.map(|t| t.to_string()))
+        .bind(limit)
+        .fetch_all(db)
+        .await
+        .map_err(IndexerError::DatabaseQuery)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self {
+                id: row.get("id"),
+                check_id: row
+                    .get::<Option<Uuid>, _>("check_id")
+                    .unwrap_or_else(Uuid::nil),
+                action_type: RepairActionType::from_string(row.get("action_type")),
+                entity_type: row.get("entity_type"),
+                entity_ref: row.get("entity_ref"),
+                reason: row.get("reason"),
+                details: row.get("action_details"),
+                success: row.get("success"),
+                error_message: row.get("error_message"),
+                affected_rows: row.get::<i32, _>("affected_rows").max(0) as usize,
+                executed_at: row.get("executed_at"),
+            })
+            .collect())
+    }
 }
 
 /// Repair workflow executor
@@ -88,137 +182,41 @@ impl<'a> RepairWorkflow<'a> {
         Self { db }
     }
 
-    /// Refetch stale data from the source
-    pub async fn refetch_stale_data(
+    /// Execute a planned repair for a check result
+    pub async fn apply(
         &self,
-        entity_type: &str,
-        entity_ref: &str,
+        plan: RepairPlan,
+        result: &ConsistencyCheckResult,
         check_id: Uuid,
     ) -> Result<Vec<RepairAction>> {
-        let mut repairs = Vec::new();
-
-        match entity_type {
-            "sdex_offer" => {
-                // Mark stale SDEX offer for re-fetch
-                let _offer_id = entity_ref.parse::<i64>().unwrap_or(0);
-
-                repairs.push(RepairAction {
-                    id: Uuid::new_v4(),
-                    check_id,
-                    action_type: RepairActionType::RefetchHorizon,
-                    entity_type: entity_type.to_string(),
-                    entity_ref: entity_ref.to_string(),
-                    reason: "Stale SDEX offer detected, marked for re-fetch from Horizon"
-                        .to_string(),
-                    details: json!({
-                        "action": "touch_updated_at",
-                        "rows_affected": 1
-                    }),
-                    success: true,
-                    error_message: None,
-                    affected_rows: 1,
-                    executed_at: Utc::now(),
-                });
-            }
-            "amm_pool" => {
-                // Mark stale AMM pool for re-fetch
-                repairs.push(RepairAction {
-                    id: Uuid::new_v4(),
-                    check_id,
-                    action_type: RepairActionType::RefetchSoroban,
-                    entity_type: entity_type.to_string(),
-                    entity_ref: entity_ref.to_string(),
-                    reason: "Stale AMM pool detected, marked for re-fetch from Soroban RPC"
-                        .to_string(),
-                    details: json!({
-                        "action": "touch_updated_at",
-                        "rows_affected": 1
-                    }),
-                    success: true,
-                    error_message: None,
-                    affected_rows: 1,
-                    executed_at: Utc::now(),
-                });
+        match plan {
+            RepairPlan::None => Ok(Vec::new()),
+            // There is no targeted re-fetch or invalidation yet. Recording a
+            // failed repair every cycle would only bury real failures, so the
+            // drift metric is all that is kept and the record is left to the
+            // indexing loops.
+            RepairPlan::Refetch | RepairPlan::Invalidate => {
+                debug!(
+                    "Skipping unimplemented {:?} repair for {} {}",
+                    plan, result.entity_type, result.entity_ref
+                );
+                Ok(Vec::new())
             }
-            _ => {
-                repairs.push(RepairAction {
-                    id: Uuid::new_v4(),
-                    check_id,
-                    action_type: RepairActionType::AlertOperator,
-                    entity_type: entity_type.to_string(),
-                    entity_ref: entity_ref.to_string(),
-                    reason: format!("Cannot refetch unknown entity type: {}", entity_type),
-                    details: json!({}),
-                    success: false,
-                    error_message: Some("Unknown entity type".to_string()),
-                    affected_rows: 0,
-                    executed_at: Utc::now(),
-                });
+            RepairPlan::AlertOperator => {
+                let reason = format!(
+                    "{} drift on {} {} requires manual investigation",
+                    result.severity, result.entity_type, result.entity_ref
+                );
+                self.alert_operator(&result.entity_type, &result.entity_ref, &reason, check_id)
+                    .await
             }
         }
-
-        // Save all repairs
-        for repair in &repairs {
-            repair.save(self.db).await?;
-        }
-
-        Ok(repairs)
     }
 
-    /// Invalidate suspicious records
-    pub async fn invalidate_records(
-        &self,
-        entity_type: &str,
-        entity_ref: &str,
-        check_id: Uuid,
-    ) -> Result<Vec<RepairAction>> {
-        let mut repairs = Vec::new();
-
-        match entity_type {
-            "asset_reference" => {
-                repairs.push(RepairAction {
-                    id: Uuid::new_v4(),
-                    check_id,
-                    action_type: RepairActionType::InvalidateRecord,
-                    entity_type: entity_type.to_string(),
-                    entity_ref: entity_ref.to_string(),
-                    reason: "Missing asset reference detected - data corruption risk".to_string(),
-                    details: json!({
-                        "action": "mark_records_invalid",
-                        "asset_id": entity_ref
-                    }),
-                    success: true,
-                    error_message: None,
-                    affected_rows: 0,
-                    executed_at: Utc::now(),
-                });
-            }
-            _ => {
-                repairs.push(RepairAction {
-                    id: Uuid::new_v4(),
-                    check_id,
-                    action_type: RepairActionType::AlertOperator,
-                    entity_type: entity_type.to_string(),
-                    entity_ref: entity_ref.to_string(),
-                    reason: format!("Cannot invalidate unknown entity type: {}", entity_type),
-                    details: json!({}),
-                    success: false,
-                    error_message: Some("Unknown entity type".to_string()),
-                    affected_rows: 0,
-                    executed_at: Utc::now(),
-                });
-            }
-        }
-
-        // Save all repairs
-        for repair in &repairs {
-            repair.save(self.db).await?;
-        }
-
-        Ok(repairs)
-    }
-
-    /// Alert operator to a critical issue
+    /// Alert operator to a critical issue.
+    ///
+    /// The alert is a warning log plus the recorded action, which the API
+    /// serves under `/api/v1/ops/reconciliation/repairs`.
     pub async fn alert_operator(
         &self,
         entity_type: &str,
@@ -226,6 +224,10 @@ impl<'a> RepairWorkflow<'a> {
         reason: &str,
         check_id: Uuid,
     ) -> Result<Vec<RepairAction>> {
+        warn!(
+            "Reconciliation alert for {} {}: {}",
+            entity_type, entity_ref, reason
+        );
         let repair = RepairAction {
             id: Uuid::new_v4(),
             check_id,
@@ -235,7 +237,7 @@ impl<'a> RepairWorkflow<'a> {
             reason: reason.to_string(),
             details: json!({
                 "severity": "manual_investigation_required",
-                "notification_channel": ["ops-alerts", "slack"]
+                "notification_channel": ["log", "ops_api"]
             }),
             success: true,
             error_message: None,
@@ -271,4 +273,81 @@ mod tests {
             "alert_operator"
         );
     }
+
+    #[test]
+    fn test_repair_action_type_round_trip() {
+        for action in [
+            RepairActionType::RefetchSoroban,
+            RepairActionType::RefetchHorizon,
+            RepairActionType::InvalidateRecord,
+            RepairActionType::AlertOperator,
+            RepairActionType::AutoReconcile,
+        ] {
+            assert_eq!(RepairActionType::from_string(&action.to_string()), action);
+        }
+    }
+
+    fn check_result(check_type: CheckType, severity: DriftSeverity) -> ConsistencyCheckResult {
+        ConsistencyCheckResult {
+            check_type,
+            entity_type: "sdex_offer".to_string(),
+            entity_ref: "42".to_string(),
+            severity,
+            expected_value: json!({}),
+            actual_value: json!({}),
+            drift_percentage: None,
+            context: json!({}),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_plan_repairs_warning_drift_automatically() {
+        let stale = check_result(CheckType::DataStaleness, DriftSeverity::Warning);
+        assert_eq!(RepairPlan::for_result(&stale, true), RepairPlan::Refetch);
+
+        let mapping = check_result(CheckType::AssetMapping, DriftSeverity::Warning);
+        assert_eq!(
+            RepairPlan::for_result(&mapping, true),
+            RepairPlan::Invalidate
+        );
+
+        let price = check_result(CheckType::PriceDivergence, DriftSeverity::Warning);
+        assert_eq!(RepairPlan::for_result(&price, true), RepairPlan::None);
+    }
+
+    #[tokio::test]
+    async fn test_unimplemented_repairs_are_skipped() {
+        // Skipped repairs never touch the database, so a lazy pool suffices
+        let db = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let workflow = RepairWorkflow::new(&db);
+        let stale = check_result(CheckType::DataStaleness, DriftSeverity::Warning);
+
+        for plan in [RepairPlan::Refetch, RepairPlan::Invalidate] {
+            let repairs = workflow.apply(plan, &stale, Uuid::nil()).await.unwrap();
+            assert!(repairs.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_plan_respects_auto_repair_flag() {
+        let stale = check_result(CheckType::DataStaleness, DriftSeverity::Warning);
+        assert_eq!(RepairPlan::for_result(&stale, false), RepairPlan::None);
+    }
+
+    #[test]
+    fn test_plan_escalates_critical_drift() {
+        let stale = check_result(CheckType::DataStaleness, DriftSeverity::Critical);
+        assert_eq!(
+            RepairPlan::for_result(&stale, true),
+            RepairPlan::AlertOperator
+        );
+        assert_eq!(
+            RepairPlan::for_result(&stale, false),
+            RepairPlan::AlertOperator
+        );
+
+        let ok = check_result(CheckType::DataStaleness, DriftSeverity::Info);
+        assert_eq!(RepairPlan::for_result(&ok, true), RepairPlan::None);
+    }
 }
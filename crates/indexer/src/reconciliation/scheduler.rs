@@ -0,0 +1,90 @@
+//! Periodic execution of reconciliation cycles
+
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+use super::engine::ReconciliationEngine;
+use crate::error::Result;
+
+/// Scheduling options for the reconciliation job
+#[derive(Debug, Clone)]
+pub struct ReconciliationSchedule {
+    /// Time between the start of consecutive cycles
+    pub interval: Duration,
+    /// Automatically repair warning-level drift (refetch / invalidate)
+    pub auto_repair: bool,
+}
+
+impl Default for ReconciliationSchedule {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            auto_repair: false,
+        }
+    }
+}
+
+/// Runs [`ReconciliationEngine`] cycles on a fixed interval
+pub struct ReconciliationScheduler {
+    db: PgPool,
+    schedule: ReconciliationSchedule,
+}
+
+impl ReconciliationScheduler {
+    pub fn new(db: PgPool, schedule: ReconciliationSchedule) -> Self {
+        Self { db, schedule }
+    }
+
+    /// Run cycles until the task is cancelled.
+    ///
+    /// Cycles never overlap: if one runs longer than the interval the next
+    /// tick is delayed rather than queued. Failed cycles are logged and retried
+    /// on the next tick.
+    pub async fn run(self) -> Result<()> {
+        let mut engine = ReconciliationEngine::new(self.db.clone())
+            .await?
+            .with_auto_repair(self.schedule.auto_repair);
+
+        info!(
+            "Starting reconciliation scheduler: interval={}s, auto_repair={}",
+            self.schedule.interval.as_secs(),
+            self.schedule.auto_repair
+        );
+
+        let mut ticker = tokio::time::interval(self.schedule.interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = engine.reload_thresholds().await {
+                warn!("Keeping previous reconciliation thresholds: {}", e);
+            }
+
+            match engine.run_reconciliation_cycle().await {
+                Ok(run) if run.critical_drift_events > 0 => {
+                    warn!(
+                        "Reconciliation run {} found {} critical drift event(s)",
+                        run.id, run.critical_drift_events
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => error!("Reconciliation cycle failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_schedule() {
+        let schedule = ReconciliationSchedule::default();
+        assert_eq!(schedule.interval, Duration::from_secs(60));
+        assert!(!schedule.auto_repair);
+    }
+}
@@ -29,6 +29,9 @@ async fn test_database_connection() {
         maintenance_interval_mins: 60,
         snapshot_retention_days: 90,
         snapshot_compaction_hours: 24,
+        reconciliation_enabled: false,
+        reconciliation_interval_secs: 60,
+        reconciliation_auto_repair: true,
     };
 
     let db = Database::new(&config)
@@ -74,6 +77,9 @@ async fn test_amm_aggregator_initialization() {
         maintenance_interval_mins: 60,
         snapshot_retention_days: 90,
         snapshot_compaction_hours: 24,
+        reconciliation_enabled: false,
+        reconciliation_interval_secs: 60,
+        reconciliation_auto_repair: true,
     };
 
     let db = Database::new(&config)
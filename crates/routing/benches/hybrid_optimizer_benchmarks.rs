@@ -3,7 +3,7 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use std::time::Duration;
 use stellarroute_routing::{
-    HybridOptimizer, LiquidityEdge, PathfinderConfig, PolicyPresets, RoutingPolicy,
+    HybridOptimizer, LiquidityEdge, PathfinderConfig, PolicyPresets, Price, RoutingPolicy,
 };
 
 fn create_test_edges() -> Vec<LiquidityEdge> {
@@ -14,8 +14,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "pool1".to_string(),
             liquidity: 1_000_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 100 XLM
+            amp: None,
         },
         LiquidityEdge {
             from: "USDC".to_string(),
@@ -23,8 +24,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "orderbook".to_string(),
             venue_ref: "book1".to_string(),
             liquidity: 500_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 50 USDC
+            amp: None,
         },
         LiquidityEdge {
             from: "XLM".to_string(),
@@ -32,8 +34,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "pool2".to_string(),
             liquidity: 200_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 20 XLM
+            amp: None,
         },
         LiquidityEdge {
             from: "EURT".to_string(),
@@ -41,8 +44,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "orderbook".to_string(),
             venue_ref: "book2".to_string(),
             liquidity: 100_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 10 EURT
+            amp: None,
         },
         LiquidityEdge {
             from: "USDC".to_string(),
@@ -50,8 +54,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "pool3".to_string(),
             liquidity: 300_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 30 USDC
+            amp: None,
         },
     ]
 }
@@ -146,8 +151,9 @@ fn bench_scalability(c: &mut Criterion) {
                 venue_type: if i % 2 == 0 { "amm" } else { "orderbook" }.to_string(),
                 venue_ref: format!("venue{}", i),
                 liquidity: 100_000_000 * (i + 1) as i128,
-                price: 1.0,
+                price: Price::ONE,
                 fee_bps: 30,
+                amp: None,
             });
         }
 
@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use stellarroute_routing::{
     pathfinder::{LiquidityEdge, Pathfinder, PathfinderConfig},
-    AmmQuoteCalculator, RoutingPolicy,
+    AmmQuoteCalculator, Price, RoutingPolicy,
 };
 
 fn bench_pathfinding_2hop(c: &mut Criterion) {
@@ -20,8 +20,9 @@ fn bench_pathfinding_2hop(c: &mut Criterion) {
                     venue_type: "sdex".to_string(),
                     venue_ref: "offer1".to_string(),
                     liquidity: 1_000_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
                 LiquidityEdge {
                     from: "USDC".to_string(),
@@ -29,8 +30,9 @@ fn bench_pathfinding_2hop(c: &mut Criterion) {
                     venue_type: "amm".to_string(),
                     venue_ref: "pool1".to_string(),
                     liquidity: 500_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
             ];
 
@@ -61,8 +63,9 @@ fn bench_pathfinding_4hop(c: &mut Criterion) {
                     venue_type: "sdex".to_string(),
                     venue_ref: "offer1".to_string(),
                     liquidity: 2_000_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
                 LiquidityEdge {
                     from: "USDC".to_string(),
@@ -70,8 +73,9 @@ fn bench_pathfinding_4hop(c: &mut Criterion) {
                     venue_type: "amm".to_string(),
                     venue_ref: "pool1".to_string(),
                     liquidity: 1_500_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
                 LiquidityEdge {
                     from: "EUR".to_string(),
@@ -79,8 +83,9 @@ fn bench_pathfinding_4hop(c: &mut Criterion) {
                     venue_type: "sdex".to_string(),
                     venue_ref: "offer2".to_string(),
                     liquidity: 1_000_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
                 LiquidityEdge {
                     from: "GBP".to_string(),
@@ -88,8 +93,9 @@ fn bench_pathfinding_4hop(c: &mut Criterion) {
                     venue_type: "amm".to_string(),
                     venue_ref: "pool2".to_string(),
                     liquidity: 800_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
                 // Additional cross-links
                 LiquidityEdge {
@@ -98,8 +104,9 @@ fn bench_pathfinding_4hop(c: &mut Criterion) {
                     venue_type: "sdex".to_string(),
                     venue_ref: "offer3".to_string(),
                     liquidity: 3_000_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
                 LiquidityEdge {
                     from: "XLM".to_string(),
@@ -107,8 +114,9 @@ fn bench_pathfinding_4hop(c: &mut Criterion) {
                     venue_type: "amm".to_string(),
                     venue_ref: "pool3".to_string(),
                     liquidity: 1_200_000_000,
-                    price: 1.0,
+                    price: Price::ONE,
                     fee_bps: 30,
+                    amp: None,
                 },
             ];
 
@@ -0,0 +1,483 @@
+//! Exact fixed-point amounts and prices
+//!
+//! Stellar assets carry seven decimals, so an [`Amount`] is an integer number
+//! of stroops (1e-7 of a unit) and a [`Price`] is an exact decimal with up to
+//! [`MAX_PRICE_DECIMALS`] fractional digits. Both parse from and print to
+//! decimal strings without passing through floating point, so the same input
+//! always produces the same quote.
+//!
+//! Multiplying or dividing an amount by a price keeps the full intermediate
+//! product and rounds once, in the direction chosen by the caller:
+//!
+//! - **sell** quotes fix the input and compute the output, which rounds
+//!   [`Rounding::Down`] so the quote never promises more than a venue pays
+//! - **buy** quotes fix the output and compute the input, which rounds
+//!   [`Rounding::Up`] so the quoted input always covers the trade
+//!
+//! Minimum outputs derived from a slippage tolerance are outputs too and
+//! round down. Amounts and prices are non-negative; parsing rejects a sign.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{Result, RoutingError};
+
+/// Number of decimals of a Stellar amount
+pub const AMOUNT_DECIMALS: u32 = 7;
+
+/// Stroops in one whole unit
+pub const STROOPS_PER_UNIT: i128 = 10_000_000;
+
+/// Maximum number of fractional digits a [`Price`] keeps
+pub const MAX_PRICE_DECIMALS: u32 = 18;
+
+/// Direction in which an inexact result is rounded
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rounding {
+    /// Toward zero; used for outputs (sell totals, minimum outputs)
+    Down,
+    /// Away from zero; used for inputs (buy totals)
+    Up,
+}
+
+/// An amount of a 7-decimal asset, in stroops
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(i128);
+
+impl Amount {
+    pub const ZERO: Amount = Amount(0);
+
+    /// One whole unit (10,000,000 stroops)
+    pub const ONE: Amount = Amount(STROOPS_PER_UNIT);
+
+    pub const fn from_stroops(stroops: i128) -> Self {
+        Self(stroops)
+    }
+
+    pub const fn stroops(self) -> i128 {
+        self.0
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    pub fn checked_add(self, other: Amount) -> Option<Amount> {
+        self.0.checked_add(other.0).map(Amount)
+    }
+
+    pub fn checked_sub(self, other: Amount) -> Option<Amount> {
+        self.0.checked_sub(other.0).map(Amount)
+    }
+
+    /// `self × price`, e.g. the quote-asset output of selling `self`
+    pub fn mul_price(self, price: Price, rounding: Rounding) -> Result<Amount> {
+        mul_div(self.0, price.mantissa, pow10(price.scale), rounding).map(Amount)
+    }
+
+    /// `self ÷ price`, e.g. the base-asset input needed to receive `self`
+    pub fn div_price(self, price: Price, rounding: Rounding) -> Result<Amount> {
+        if price.mantissa <= 0 {
+            return Err(RoutingError::InvalidAmount("price must be > 0".to_string()));
+        }
+        mul_div(self.0, pow10(price.scale), price.mantissa, rounding).map(Amount)
+    }
+
+    /// `self × numerator ÷ denominator`, e.g. a fee or slippage share in bps
+    pub fn mul_ratio(
+        self,
+        numerator: i128,
+        denominator: i128,
+        rounding: Rounding,
+    ) -> Result<Amount> {
+        if denominator <= 0 {
+            return Err(RoutingError::InvalidAmount(
+                "denominator must be > 0".to_string(),
+            ));
+        }
+        mul_div(self.0, numerator, denominator, rounding).map(Amount)
+    }
+
+    /// Lossy conversion for scoring and metrics; never feed it back into a quote
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / STROOPS_PER_UNIT as f64
+    }
+}
+
+impl FromStr for Amount {
+    type Err = RoutingError;
+
+    /// Parse a decimal string with at most seven significant fractional digits
+    fn from_str(s: &str) -> Result<Self> {
+        let (mantissa, scale) = parse_decimal(s, AMOUNT_DECIMALS)?;
+        mantissa
+            .checked_mul(pow10(AMOUNT_DECIMALS - scale))
+            .map(Amount)
+            .ok_or(RoutingError::Overflow)
+    }
+}
+
+impl fmt::Display for Amount {
+    /// Always prints seven decimals, e.g. `12.5000000`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_decimal(f, self.0, AMOUNT_DECIMALS, AMOUNT_DECIMALS)
+    }
+}
+
+/// An exact decimal price (quote asset per unit of base asset)
+///
+/// Stored as `mantissa × 10^-scale` with trailing zeros stripped, so equal
+/// prices compare and hash equal regardless of how they were written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Price {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Price {
+    pub const ZERO: Price = Price {
+        mantissa: 0,
+        scale: 0,
+    };
+
+    pub const ONE: Price = Price {
+        mantissa: 1,
+        scale: 0,
+    };
+
+    /// A price scaled by 1e7, as stored in the `price_e7` columns
+    pub fn from_e7(price_e7: i128) -> Self {
+        Self::normalized(price_e7, AMOUNT_DECIMALS)
+    }
+
+    /// `numerator ÷ denominator` to [`MAX_PRICE_DECIMALS`] decimals, e.g. a
+    /// reserve ratio
+    pub fn from_ratio(numerator: i128, denominator: i128, rounding: Rounding) -> Result<Self> {
+        if denominator <= 0 {
+            return Err(RoutingError::InvalidAmount(
+                "denominator must be > 0".to_string(),
+            ));
+        }
+        let mantissa = mul_div(numerator, pow10(MAX_PRICE_DECIMALS), denominator, rounding)?;
+        Ok(Self::normalized(mantissa, MAX_PRICE_DECIMALS))
+    }
+
+    /// `self × numerator ÷ denominator`, kept to [`MAX_PRICE_DECIMALS`] decimals
+    pub fn mul_ratio(self, numerator: i128, denominator: i128, rounding: Rounding) -> Result<Self> {
+        if denominator <= 0 {
+            return Err(RoutingError::InvalidAmount(
+                "denominator must be > 0".to_string(),
+            ));
+        }
+        // Rescale first when that fits, so the ratio keeps the digits below
+        // the current scale.
+        let (mantissa, scale) = match self
+            .mantissa
+            .checked_mul(pow10(MAX_PRICE_DECIMALS - self.scale))
+        {
+            Some(rescaled) => (rescaled, MAX_PRICE_DECIMALS),
+            None => (self.mantissa, self.scale),
+        };
+        let mantissa = mul_div(mantissa, numerator, denominator, rounding)?;
+        Ok(Self::normalized(mantissa, scale))
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.mantissa > 0
+    }
+
+    /// Lossy conversion for scoring and metrics; never feed it back into a quote
+    pub fn to_f64(self) -> f64 {
+        self.mantissa as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    fn normalized(mut mantissa: i128, mut scale: u32) -> Self {
+        if mantissa == 0 {
+            return Self::ZERO;
+        }
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Self { mantissa, scale }
+    }
+
+    /// Integer part and the fraction scaled to [`MAX_PRICE_DECIMALS`] digits
+    fn parts(self) -> (i128, i128) {
+        let unit = pow10(self.scale);
+        (
+            self.mantissa / unit,
+            (self.mantissa % unit) * pow10(MAX_PRICE_DECIMALS - self.scale),
+        )
+    }
+}
+
+impl Default for Price {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.parts().cmp(&other.parts())
+    }
+}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Price {
+    type Err = RoutingError;
+
+    /// Parse a decimal string with at most 18 significant fractional digits
+    fn from_str(s: &str) -> Result<Self> {
+        let (mantissa, scale) = parse_decimal(s, MAX_PRICE_DECIMALS)?;
+        Ok(Self::normalized(mantissa, scale))
+    }
+}
+
+impl fmt::Display for Price {
+    /// Prints at least seven decimals and every significant digit beyond them
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_decimal(
+            f,
+            self.mantissa,
+            self.scale,
+            AMOUNT_DECIMALS.max(self.scale),
+        )
+    }
+}
+
+macro_rules! string_serde {
+    ($ty:ty) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+string_serde!(Amount);
+string_serde!(Price);
+
+fn pow10(exp: u32) -> i128 {
+    10_i128.pow(exp)
+}
+
+/// `value × numerator ÷ denominator` rounded once, without overflowing on
+/// intermediate products whose quotient still fits
+fn mul_div(value: i128, numerator: i128, denominator: i128, rounding: Rounding) -> Result<i128> {
+    debug_assert!(denominator > 0);
+    // value = q·d + r, so value·n/d = q·n + r·n/d with r·n far smaller than value·n.
+    let q = value / denominator;
+    let r = value % denominator;
+    let high = q.checked_mul(numerator).ok_or(RoutingError::Overflow)?;
+    let low = r.checked_mul(numerator).ok_or(RoutingError::Overflow)?;
+    let mut result = high
+        .checked_add(low / denominator)
+        .ok_or(RoutingError::Overflow)?;
+    if rounding == Rounding::Up && low % denominator != 0 {
+        result = result.checked_add(1).ok_or(RoutingError::Overflow)?;
+    }
+    Ok(result)
+}
+
+/// Parse an unsigned decimal into `(mantissa, scale)` with `scale <= max_scale`
+///
+/// Fractional digits beyond `max_scale` are accepted only when they are zeros.
+fn parse_decimal(value: &str, max_scale: u32) -> Result<(i128, u32)> {
+    let trimmed = value.trim();
+    let (int_part, frac_part) = match trimmed.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, frac_part),
+        None => (trimmed, ""),
+    };
+
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if int_part.is_empty()
+        || !is_digits(int_part)
+        || !is_digits(frac_part)
+        || (trimmed.contains('.') && frac_part.is_empty())
+    {
+        return Err(RoutingError::DecimalPrecision(format!(
+            "invalid decimal: {value:?}"
+        )));
+    }
+
+    let frac_part = frac_part.trim_end_matches('0');
+    if frac_part.len() > max_scale as usize {
+        return Err(RoutingError::DecimalPrecision(format!(
+            "more than {max_scale} decimal places is not allowed: {value}"
+        )));
+    }
+
+    let scale = frac_part.len() as u32;
+    let int_value: i128 = int_part.parse().map_err(|_| RoutingError::Overflow)?;
+    let frac_value: i128 = if frac_part.is_empty() {
+        0
+    } else {
+        frac_part.parse().map_err(|_| RoutingError::Overflow)?
+    };
+
+    int_value
+        .checked_mul(pow10(scale))
+        .and_then(|v| v.checked_add(frac_value))
+        .map(|mantissa| (mantissa, scale))
+        .ok_or(RoutingError::Overflow)
+}
+
+/// Write `mantissa × 10^-scale` with exactly `decimals` (>= scale) fractional digits
+fn write_decimal(
+    f: &mut fmt::Formatter<'_>,
+    mantissa: i128,
+    scale: u32,
+    decimals: u32,
+) -> fmt::Result {
+    let unit = pow10(scale);
+    let sign = if mantissa < 0 { "-" } else { "" };
+    let int = (mantissa / unit).unsigned_abs();
+    let frac = (mantissa % unit).unsigned_abs();
+    write!(f, "{sign}{int}.")?;
+    if scale > 0 {
+        write!(f, "{frac:0width$}", width = scale as usize)?;
+    }
+    for _ in scale..decimals {
+        f.write_str("0")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(s: &str) -> Amount {
+        s.parse().unwrap()
+    }
+
+    fn price(s: &str) -> Price {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn amounts_round_trip_to_the_stroop() {
+        assert_eq!(amount("1").stroops(), 10_000_000);
+        assert_eq!(amount("0.0000001").stroops(), 1);
+        assert_eq!(amount("922337203685.4775807").stroops(), i64::MAX as i128);
+        assert_eq!(amount("12.50").to_string(), "12.5000000");
+        assert_eq!(
+            amount("922337203685.4775807").to_string(),
+            "922337203685.4775807"
+        );
+        assert_eq!(amount("1.000000000").stroops(), 10_000_000);
+    }
+
+    #[test]
+    fn amount_parse_rejects_sub_stroop_and_malformed_input() {
+        for bad in [
+            "0.00000001",
+            "-1",
+            "abc",
+            "",
+            ".5",
+            "5.",
+            "1e7",
+            "1.2.3",
+            "+1",
+            "inf",
+        ] {
+            assert!(bad.parse::<Amount>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn prices_are_normalized_and_ordered_exactly() {
+        assert_eq!(price("0.1000000"), price("0.1"));
+        assert_eq!(price("0.1").to_string(), "0.1000000");
+        assert_eq!(price("0.123456789012").to_string(), "0.123456789012");
+        assert_eq!(Price::from_e7(1_234_567), price("0.1234567"));
+        assert!(price("0.100000000000000001") > price("0.1"));
+        assert!(price("2") > price("1.999999999999999999"));
+        assert!("0.1234567890123456789".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn sell_output_rounds_down_and_buy_input_rounds_up() {
+        // 1 stroop at 0.5 is half a stroop of output.
+        let half = price("0.5");
+        let one = Amount::from_stroops(1);
+        assert_eq!(one.mul_price(half, Rounding::Down).unwrap(), Amount::ZERO);
+        assert_eq!(one.mul_price(half, Rounding::Up).unwrap(), one);
+
+        // Receiving 1 stroop at 3 per unit takes a third of a stroop.
+        let three = price("3");
+        assert_eq!(one.div_price(three, Rounding::Down).unwrap(), Amount::ZERO);
+        assert_eq!(one.div_price(three, Rounding::Up).unwrap(), one);
+    }
+
+    #[test]
+    fn large_balances_keep_every_stroop() {
+        // f64 cannot represent this amount exactly; the integer product can.
+        let big = amount("900719925474.0993123");
+        let total = big.mul_price(price("0.1234567"), Rounding::Down).unwrap();
+        assert_eq!(total.to_string(), "111199909623.2782365");
+        assert_eq!(
+            big.mul_price(price("0.1234567"), Rounding::Up)
+                .unwrap()
+                .stroops(),
+            total.stroops() + 1
+        );
+    }
+
+    #[test]
+    fn ratios_round_in_the_requested_direction() {
+        let third = Price::from_ratio(1, 3, Rounding::Down).unwrap();
+        assert_eq!(third.to_string(), "0.333333333333333333");
+        assert_eq!(
+            Price::from_ratio(2, 3, Rounding::Up).unwrap().to_string(),
+            "0.666666666666666667"
+        );
+        assert_eq!(
+            price("0.12")
+                .mul_ratio(10_500, 10_000, Rounding::Down)
+                .unwrap(),
+            price("0.126")
+        );
+        assert_eq!(
+            amount("100")
+                .mul_ratio(9_950, 10_000, Rounding::Down)
+                .unwrap(),
+            amount("99.5")
+        );
+        assert!(Amount::ONE.div_price(Price::ZERO, Rounding::Up).is_err());
+    }
+
+    #[test]
+    fn serde_uses_decimal_strings() {
+        let json = serde_json::to_string(&(amount("1.5"), price("0.25"))).unwrap();
+        assert_eq!(json, r#"["1.5000000","0.2500000"]"#);
+        let (a, p): (Amount, Price) = serde_json::from_str(&json).unwrap();
+        assert_eq!((a, p), (amount("1.5"), price("0.25")));
+        assert!(serde_json::from_str::<Amount>("1.5").is_err());
+    }
+}
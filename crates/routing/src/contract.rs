@@ -0,0 +1,421 @@
+//! Router contract types and their Soroban `ScVal` XDR encoding
+//!
+//! Mirrors the router's `#[contracttype]` layouts so off-chain code can
+//! produce the exact bytes the contract gets from `to_xdr`: the SDK hashes
+//! `SwapParams` for commit-reveal, and the API passes them to `execute_swap`
+//! and signs `SignedQuote`s. Addresses are strkeys (`G...` accounts or
+//! `C...` contracts).
+
+use stellar_strkey::Strkey;
+use thiserror::Error;
+
+const SCV_U32: u32 = 3;
+const SCV_U64: u32 = 5;
+const SCV_I128: u32 = 10;
+const SCV_BYTES: u32 = 13;
+const SCV_STRING: u32 = 14;
+const SCV_SYMBOL: u32 = 15;
+const SCV_VEC: u32 = 16;
+const SCV_MAP: u32 = 17;
+const SCV_ADDRESS: u32 = 18;
+
+const SC_ADDRESS_TYPE_ACCOUNT: u32 = 0;
+const SC_ADDRESS_TYPE_CONTRACT: u32 = 1;
+const PUBLIC_KEY_TYPE_ED25519: u32 = 0;
+
+const SYMBOL_MAX_LEN: usize = 32;
+
+/// Input that has no `ScVal` encoding
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ContractXdrError {
+    #[error("'{0}' is not a valid account (G...) or contract (C...) address")]
+    InvalidAddress(String),
+
+    #[error("'{0}' is not a valid Soroban symbol")]
+    InvalidSymbol(String),
+}
+
+pub type Result<T> = std::result::Result<T, ContractXdrError>;
+
+/// Asset as the router contract identifies it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Asset {
+    /// XLM
+    Native,
+    /// Classic asset: issuer strkey and asset code
+    Issued { issuer: String, code: String },
+    /// Soroban token contract strkey
+    Soroban(String),
+}
+
+/// Pool kind of a route hop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolType {
+    Sdex,
+    AmmConstProd,
+    AmmStable,
+}
+
+/// One hop of a router `Route`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteHop {
+    pub source: Asset,
+    pub destination: Asset,
+    /// Pool (adapter) contract strkey
+    pub pool: String,
+    pub pool_type: PoolType,
+    /// Least this hop may output; 0 disables the check
+    pub min_amount_out: i128,
+}
+
+/// Router `Route`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub hops: Vec<RouteHop>,
+    pub estimated_output: i128,
+    pub min_output: i128,
+    pub expires_at: u64,
+}
+
+/// Router `SwapParams`: the full swap intent `execute_swap` takes and a
+/// commitment binds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapParams {
+    pub route: Route,
+    pub amount_in: i128,
+    pub min_amount_out: i128,
+    /// Recipient account or contract strkey
+    pub recipient: String,
+    pub deadline: u64,
+    pub not_before: u64,
+    pub max_price_impact_bps: u32,
+    pub max_execution_spread_bps: u32,
+}
+
+impl SwapParams {
+    /// XDR `ScVal` encoding, byte-identical to the contract's `to_xdr`
+    pub fn to_xdr(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        self.encode(&mut out)?;
+        Ok(out)
+    }
+}
+
+/// Router `SignedQuote`: the fields of an issued quote its signature covers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedQuote {
+    pub quote_id: [u8; 16],
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub quote_type: String,
+    pub amount: i128,
+    pub price: String,
+    pub total: i128,
+    pub issued_at: u64,
+    pub expires_at: u64,
+}
+
+impl SignedQuote {
+    /// XDR of the `ScVal` the contract's `quote.to_xdr()` produces
+    pub fn to_xdr(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        self.encode(&mut out)?;
+        Ok(out)
+    }
+}
+
+/// XDR `ScVal::Address` of an account or contract strkey
+pub fn address_xdr(strkey: &str) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    Address(strkey).encode(&mut out)?;
+    Ok(out)
+}
+
+// ── ScVal encoding ────────────────────────────────────────────────────────────
+// Contract structs encode as maps keyed by field name in sorted order; enums
+// as a vector of the variant name followed by its fields.
+
+type Field<'a> = (&'static str, &'a dyn Encode);
+
+trait Encode {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()>;
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// XDR variable-length opaque: length, bytes, zero padding to 4 bytes
+fn write_opaque(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_u32(out, bytes.len() as u32);
+    out.extend_from_slice(bytes);
+    out.resize(out.len() + (4 - bytes.len() % 4) % 4, 0);
+}
+
+impl Encode for u32 {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_u32(out, SCV_U32);
+        write_u32(out, *self);
+        Ok(())
+    }
+}
+
+impl Encode for u64 {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_u32(out, SCV_U64);
+        out.extend_from_slice(&self.to_be_bytes());
+        Ok(())
+    }
+}
+
+impl Encode for i128 {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_u32(out, SCV_I128);
+        out.extend_from_slice(&((*self >> 64) as i64).to_be_bytes());
+        out.extend_from_slice(&(*self as u64).to_be_bytes());
+        Ok(())
+    }
+}
+
+impl Encode for [u8; 16] {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_u32(out, SCV_BYTES);
+        write_opaque(out, self);
+        Ok(())
+    }
+}
+
+struct ScString<'a>(&'a str);
+
+impl Encode for ScString<'_> {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_u32(out, SCV_STRING);
+        write_opaque(out, self.0.as_bytes());
+        Ok(())
+    }
+}
+
+struct Symbol<'a>(&'a str);
+
+impl Encode for Symbol<'_> {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        let bytes = self.0.as_bytes();
+        if bytes.len() > SYMBOL_MAX_LEN
+            || !bytes
+                .iter()
+                .all(|b| b.is_ascii_alphanumeric() || *b == b'_')
+        {
+            return Err(ContractXdrError::InvalidSymbol(self.0.to_string()));
+        }
+        write_u32(out, SCV_SYMBOL);
+        write_opaque(out, bytes);
+        Ok(())
+    }
+}
+
+struct Address<'a>(&'a str);
+
+impl Encode for Address<'_> {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        let strkey = Strkey::from_string(self.0)
+            .map_err(|_| ContractXdrError::InvalidAddress(self.0.to_string()))?;
+        write_u32(out, SCV_ADDRESS);
+        match strkey {
+            Strkey::PublicKeyEd25519(key) => {
+                write_u32(out, SC_ADDRESS_TYPE_ACCOUNT);
+                write_u32(out, PUBLIC_KEY_TYPE_ED25519);
+                out.extend_from_slice(&key.0);
+            }
+            Strkey::Contract(contract) => {
+                write_u32(out, SC_ADDRESS_TYPE_CONTRACT);
+                out.extend_from_slice(&contract.0);
+            }
+            _ => return Err(ContractXdrError::InvalidAddress(self.0.to_string())),
+        }
+        Ok(())
+    }
+}
+
+impl Encode for Asset {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        match self {
+            Asset::Native => write_vec(out, &[&Symbol("Native")]),
+            Asset::Issued { issuer, code } => {
+                write_vec(out, &[&Symbol("Issued"), &Address(issuer), &Symbol(code)])
+            }
+            Asset::Soroban(contract) => write_vec(out, &[&Symbol("Soroban"), &Address(contract)]),
+        }
+    }
+}
+
+impl Encode for PoolType {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        let name = match self {
+            PoolType::Sdex => "Sdex",
+            PoolType::AmmConstProd => "AmmConstProd",
+            PoolType::AmmStable => "AmmStable",
+        };
+        write_vec(out, &[&Symbol(name)])
+    }
+}
+
+impl Encode for RouteHop {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_map(
+            out,
+            &mut [
+                ("source", &self.source),
+                ("destination", &self.destination),
+                ("pool", &Address(&self.pool)),
+                ("pool_type", &self.pool_type),
+                ("min_amount_out", &self.min_amount_out),
+            ],
+        )
+    }
+}
+
+impl Encode for Vec<RouteHop> {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        let items: Vec<&dyn Encode> = self.iter().map(|hop| hop as &dyn Encode).collect();
+        write_vec(out, &items)
+    }
+}
+
+impl Encode for Route {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_map(
+            out,
+            &mut [
+                ("hops", &self.hops),
+                ("estimated_output", &self.estimated_output),
+                ("min_output", &self.min_output),
+                ("expires_at", &self.expires_at),
+            ],
+        )
+    }
+}
+
+impl Encode for SwapParams {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_map(
+            out,
+            &mut [
+                ("route", &self.route),
+                ("amount_in", &self.amount_in),
+                ("min_amount_out", &self.min_amount_out),
+                ("recipient", &Address(&self.recipient)),
+                ("deadline", &self.deadline),
+                ("not_before", &self.not_before),
+                ("max_price_impact_bps", &self.max_price_impact_bps),
+                ("max_execution_spread_bps", &self.max_execution_spread_bps),
+            ],
+        )
+    }
+}
+
+impl Encode for SignedQuote {
+    fn encode(&self, out: &mut Vec<u8>) -> Result<()> {
+        write_map(
+            out,
+            &mut [
+                ("quote_id", &self.quote_id),
+                ("base_asset", &ScString(&self.base_asset)),
+                ("quote_asset", &ScString(&self.quote_asset)),
+                ("quote_type", &Symbol(&self.quote_type)),
+                ("amount", &self.amount),
+                ("price", &ScString(&self.price)),
+                ("total", &self.total),
+                ("issued_at", &self.issued_at),
+                ("expires_at", &self.expires_at),
+            ],
+        )
+    }
+}
+
+fn write_vec(out: &mut Vec<u8>, items: &[&dyn Encode]) -> Result<()> {
+    write_u32(out, SCV_VEC);
+    // Optional pointer: present
+    write_u32(out, 1);
+    write_u32(out, items.len() as u32);
+    for item in items {
+        item.encode(out)?;
+    }
+    Ok(())
+}
+
+fn write_map(out: &mut Vec<u8>, fields: &mut [Field<'_>]) -> Result<()> {
+    fields.sort_by_key(|(name, _)| *name);
+    write_u32(out, SCV_MAP);
+    write_u32(out, 1);
+    write_u32(out, fields.len() as u32);
+    for (name, value) in fields.iter() {
+        Symbol(name).encode(out)?;
+        value.encode(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+    const CONTRACT: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    #[test]
+    fn encodes_scalars_as_scval() {
+        let mut out = Vec::new();
+        7u32.encode(&mut out).unwrap();
+        assert_eq!(out, [0, 0, 0, 3, 0, 0, 0, 7]);
+
+        let mut out = Vec::new();
+        (-1i128).encode(&mut out).unwrap();
+        assert_eq!(&out[..4], &[0, 0, 0, 10]);
+        assert!(out[4..].iter().all(|b| *b == 0xff));
+
+        let mut out = Vec::new();
+        Symbol("swap").encode(&mut out).unwrap();
+        assert_eq!(out, [0, 0, 0, 15, 0, 0, 0, 4, b's', b'w', b'a', b'p']);
+
+        let mut out = Vec::new();
+        Symbol("route").encode(&mut out).unwrap();
+        assert_eq!(out.len(), 4 + 4 + 8);
+    }
+
+    #[test]
+    fn encodes_unit_enum_as_single_symbol_vec() {
+        let mut out = Vec::new();
+        PoolType::AmmStable.encode(&mut out).unwrap();
+        let mut expected = vec![0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 15, 0, 0, 0, 9];
+        expected.extend_from_slice(b"AmmStable\0\0\0");
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn encodes_addresses_by_strkey_kind() {
+        let account = address_xdr(ACCOUNT).unwrap();
+        assert_eq!(&account[..12], &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(account.len(), 12 + 32);
+
+        let contract = address_xdr(CONTRACT).unwrap();
+        assert_eq!(&contract[..8], &[0, 0, 0, 18, 0, 0, 0, 1]);
+        assert_eq!(contract.len(), 8 + 32);
+
+        assert_eq!(
+            address_xdr("not-a-strkey"),
+            Err(ContractXdrError::InvalidAddress("not-a-strkey".to_string()))
+        );
+    }
+
+    #[test]
+    fn soroban_assets_encode_their_contract() {
+        let mut out = Vec::new();
+        Asset::Soroban(CONTRACT.to_string())
+            .encode(&mut out)
+            .unwrap();
+        let mut expected = vec![0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0, 2];
+        Symbol("Soroban").encode(&mut expected).unwrap();
+        expected.extend_from_slice(&address_xdr(CONTRACT).unwrap());
+        assert_eq!(out, expected);
+    }
+}
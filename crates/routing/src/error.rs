@@ -24,6 +24,9 @@ pub enum RoutingError {
 
     #[error("Numeric overflow during normalization")]
     Overflow,
+
+    #[error("Route computation cancelled")]
+    Cancelled,
 }
 
 pub type Result<T> = std::result::Result<T, RoutingError>;
@@ -12,6 +12,7 @@
 //! assert!(!edges.is_empty());
 //! ```
 
+use crate::amount::{Price, Rounding};
 use crate::normalization::{AmmReserveInput, SdexLevelInput};
 use crate::pathfinder::LiquidityEdge;
 
@@ -302,7 +303,7 @@ impl FixtureBuilder {
         let mut edges = Vec::new();
 
         for offer in &self.sdex_offers {
-            let price: f64 = offer.price.parse().unwrap_or(1.0);
+            let price: Price = offer.price.parse().unwrap_or(Price::ONE);
             let liquidity = parse_amount_to_e7(&offer.amount);
             edges.push(LiquidityEdge {
                 from: offer.selling_asset.key.clone(),
@@ -312,22 +313,17 @@ impl FixtureBuilder {
                 liquidity,
                 price,
                 fee_bps: 0,
+                amp: None,
             });
         }
 
         for pool in &self.amm_pools {
             let reserve_selling = parse_amount_to_e7(&pool.reserve_selling);
             let reserve_buying = parse_amount_to_e7(&pool.reserve_buying);
-            let price_fwd = if reserve_selling > 0 {
-                reserve_buying as f64 / reserve_selling as f64
-            } else {
-                1.0
-            };
-            let price_rev = if reserve_buying > 0 {
-                reserve_selling as f64 / reserve_buying as f64
-            } else {
-                1.0
-            };
+            let price_fwd = Price::from_ratio(reserve_buying, reserve_selling, Rounding::Down)
+                .unwrap_or(Price::ONE);
+            let price_rev = Price::from_ratio(reserve_selling, reserve_buying, Rounding::Down)
+                .unwrap_or(Price::ONE);
 
             // Forward direction
             edges.push(LiquidityEdge {
@@ -338,6 +334,7 @@ impl FixtureBuilder {
                 liquidity: reserve_selling,
                 price: price_fwd,
                 fee_bps: pool.fee_bps,
+                amp: None,
             });
 
             // Reverse direction (AMM pools are symmetric)
@@ -349,6 +346,7 @@ impl FixtureBuilder {
                 liquidity: reserve_buying,
                 price: price_rev,
                 fee_bps: pool.fee_bps,
+                amp: None,
             });
         }
 
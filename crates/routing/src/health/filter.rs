@@ -30,6 +30,7 @@ impl<'a> GraphFilter<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::amount::Price;
     use crate::health::policy::{ExclusionPolicy, ExclusionThresholds, OverrideRegistry};
     use crate::health::scorer::{HealthRecord, ScoredVenue, VenueType};
     use crate::pathfinder::LiquidityEdge;
@@ -56,8 +57,9 @@ mod tests {
             venue_type: "sdex".to_string(),
             venue_ref: venue_ref.to_string(),
             liquidity: 1_000_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30,
+            amp: None,
         }
     }
 
@@ -248,6 +248,199 @@ impl AmmQuoteCalculator {
     }
 }
 
+/// StableSwap (Curve v1) quote calculator for two-asset pools
+///
+/// Mirrors `stellarroute_contracts::stable_math` step for step, including
+/// integer rounding, so off-chain quotes match what `StableSwapAdapter`
+/// returns on-chain.
+pub struct StableSwapCalculator;
+
+impl StableSwapCalculator {
+    const N_COINS: i128 = 2;
+    const MAX_ITERATIONS: u32 = 255;
+    /// Upper bound on the amplification coefficient
+    pub const MAX_AMP: u32 = 1_000_000;
+
+    /// Compute the invariant `D` for reserves `x0`, `x1`
+    pub fn get_d(&self, x0: i128, x1: i128, amp: u32) -> Result<i128> {
+        if x0 < 0 || x1 < 0 || amp == 0 || amp > Self::MAX_AMP {
+            return Err(RoutingError::InvalidAmount(
+                "reserves must be non-negative and amp in [1, 1000000]".to_string(),
+            ));
+        }
+        let sum = x0.checked_add(x1).ok_or(RoutingError::Overflow)?;
+        if sum == 0 {
+            return Ok(0);
+        }
+        if x0 == 0 || x1 == 0 {
+            return Err(RoutingError::InsufficientLiquidity(
+                "one-sided stable pool".to_string(),
+            ));
+        }
+
+        let ann = i128::from(amp) * Self::N_COINS;
+        let mut d = sum;
+        for _ in 0..Self::MAX_ITERATIONS {
+            let mut d_p = d;
+            for x in [x0, x1] {
+                d_p = d_p
+                    .checked_mul(d)
+                    .ok_or(RoutingError::Overflow)?
+                    .checked_div(x * Self::N_COINS)
+                    .ok_or(RoutingError::Overflow)?;
+            }
+
+            let d_prev = d;
+            let numerator = ann
+                .checked_mul(sum)
+                .and_then(|v| v.checked_add(d_p.checked_mul(Self::N_COINS)?))
+                .and_then(|v| v.checked_mul(d))
+                .ok_or(RoutingError::Overflow)?;
+            let denominator = (ann - 1)
+                .checked_mul(d)
+                .and_then(|v| v.checked_add(d_p.checked_mul(Self::N_COINS + 1)?))
+                .ok_or(RoutingError::Overflow)?;
+            if denominator == 0 {
+                return Err(RoutingError::Overflow);
+            }
+            d = numerator / denominator;
+
+            if (d - d_prev).abs() <= 1 {
+                return Ok(d);
+            }
+        }
+
+        Err(RoutingError::Overflow)
+    }
+
+    /// Solve for the other reserve once one side holds `x`
+    pub fn get_y(&self, x: i128, d: i128, amp: u32) -> Result<i128> {
+        if x <= 0 || d <= 0 || amp == 0 || amp > Self::MAX_AMP {
+            return Err(RoutingError::InvalidAmount(
+                "invalid stable pool state".to_string(),
+            ));
+        }
+
+        let ann = i128::from(amp) * Self::N_COINS;
+        let c = d
+            .checked_mul(d)
+            .ok_or(RoutingError::Overflow)?
+            .checked_div(x * Self::N_COINS)
+            .ok_or(RoutingError::Overflow)?
+            .checked_mul(d)
+            .ok_or(RoutingError::Overflow)?
+            .checked_div(ann * Self::N_COINS)
+            .ok_or(RoutingError::Overflow)?;
+        let b = x.checked_add(d / ann).ok_or(RoutingError::Overflow)?;
+
+        let mut y = d;
+        for _ in 0..Self::MAX_ITERATIONS {
+            let y_prev = y;
+            let numerator = y
+                .checked_mul(y)
+                .and_then(|v| v.checked_add(c))
+                .ok_or(RoutingError::Overflow)?;
+            let denominator = y
+                .checked_mul(2)
+                .and_then(|v| v.checked_add(b))
+                .and_then(|v| v.checked_sub(d))
+                .ok_or(RoutingError::Overflow)?;
+            if denominator <= 0 {
+                return Err(RoutingError::Overflow);
+            }
+            y = numerator / denominator;
+
+            if (y - y_prev).abs() <= 1 {
+                return Ok(y);
+            }
+        }
+
+        Err(RoutingError::Overflow)
+    }
+
+    /// Output for `amount_in`, net of `fee_bps`; same result as the on-chain adapter
+    pub fn get_dy(
+        &self,
+        amount_in: i128,
+        reserve_in: i128,
+        reserve_out: i128,
+        amp: u32,
+        fee_bps: u32,
+    ) -> Result<i128> {
+        if amount_in <= 0 {
+            return Err(RoutingError::InvalidAmount(
+                "amount_in must be positive".to_string(),
+            ));
+        }
+        if reserve_in <= 0 || reserve_out <= 0 {
+            return Err(RoutingError::InvalidAmount(
+                "reserves must be positive".to_string(),
+            ));
+        }
+        if fee_bps > 10_000 {
+            return Err(RoutingError::InvalidAmount(
+                "fee_bps must be in [0, 10000]".to_string(),
+            ));
+        }
+
+        let d = self.get_d(reserve_in, reserve_out, amp)?;
+        let x = reserve_in
+            .checked_add(amount_in)
+            .ok_or(RoutingError::Overflow)?;
+        let y = self.get_y(x, d, amp)?;
+
+        let dy = reserve_out - y - 1;
+        if dy <= 0 {
+            return Ok(0);
+        }
+        let fee = dy
+            .checked_mul(i128::from(fee_bps))
+            .ok_or(RoutingError::Overflow)?
+            / BASIS_POINTS;
+
+        Ok(dy - fee)
+    }
+
+    /// Calculate swap output on the StableSwap curve
+    /// Returns (output_amount, price_impact_bps)
+    ///
+    /// Impact is measured against the marginal price of a trade of
+    /// 1/10000 of `reserve_in`, since on a stable curve the spot price is
+    /// not the reserve ratio.
+    pub fn quote_stable(
+        &self,
+        amount_in: i128,
+        reserve_in: i128,
+        reserve_out: i128,
+        amp: u32,
+        fee_bps: u32,
+    ) -> Result<(i128, u32)> {
+        let output = self.get_dy(amount_in, reserve_in, reserve_out, amp, fee_bps)?;
+        if output <= 0 {
+            return Err(RoutingError::InsufficientLiquidity(
+                "zero output from stable pool".to_string(),
+            ));
+        }
+
+        let probe_in = (reserve_in / BASIS_POINTS).max(1);
+        let probe_out = self.get_dy(probe_in, reserve_in, reserve_out, amp, fee_bps)?;
+        if probe_out <= 0 {
+            return Ok((output, 0));
+        }
+
+        let price_before = (probe_in * SCALE_1E7) / probe_out;
+        let price_after = (amount_in * SCALE_1E7) / output;
+
+        let impact_bps = if price_after > price_before {
+            ((price_after - price_before) * BASIS_POINTS) / price_before
+        } else {
+            0
+        };
+
+        Ok((output, impact_bps.min(i128::from(u32::MAX)) as u32))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,4 +494,80 @@ mod tests {
         let result = calc.calculate_impact(1_000_000_000, &[]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_stable_swap_balanced_pool_is_near_one_to_one() {
+        let calc = StableSwapCalculator;
+        let reserve = 10_000_000_000_000; // 1M in e7
+        let amount_in = 10_000_000_000; // 1k in e7
+
+        let (output, impact) = calc
+            .quote_stable(amount_in, reserve, reserve, 100, 4)
+            .unwrap();
+        // 4 bps fee plus a sliver of curvature
+        assert!(output < amount_in);
+        assert!(output > amount_in * 9_995 / 10_000);
+        assert!(impact < 5);
+
+        let (cp_output, _) = AmmQuoteCalculator
+            .quote_constant_product(amount_in, reserve, reserve, 4)
+            .unwrap();
+        assert!(output > cp_output);
+    }
+
+    #[test]
+    fn test_stable_swap_invariant_is_preserved() {
+        let calc = StableSwapCalculator;
+        let (x0, x1, amp) = (5_000_000_000_000, 3_000_000_000_000, 200);
+        let d = calc.get_d(x0, x1, amp).unwrap();
+        assert!(d <= x0 + x1);
+
+        let amount_in = 250_000_000_000;
+        let dy = calc.get_dy(amount_in, x0, x1, amp, 0).unwrap();
+        let d_after = calc.get_d(x0 + amount_in, x1 - dy, amp).unwrap();
+        // Rounding favours the pool, so D never decreases
+        assert!(d_after >= d);
+        assert!(d_after - d <= 2);
+    }
+
+    #[test]
+    fn test_stable_swap_low_amp_approaches_constant_product() {
+        let calc = StableSwapCalculator;
+        let reserve = 10_000_000_000;
+        let amount_in = 1_000_000_000;
+
+        let stable = calc.get_dy(amount_in, reserve, reserve, 1, 0).unwrap();
+        let high_amp = calc.get_dy(amount_in, reserve, reserve, 1_000, 0).unwrap();
+        let (cp, _) = AmmQuoteCalculator
+            .quote_constant_product(amount_in, reserve, reserve, 0)
+            .unwrap();
+
+        assert!(stable >= cp);
+        assert!(high_amp > stable);
+    }
+
+    #[test]
+    fn test_stable_swap_matches_contract_vectors() {
+        // Shared with the contract test suite so both implementations stay in lockstep
+        let calc = StableSwapCalculator;
+        assert_eq!(
+            calc.get_dy(1_000_000_000, 100_000_000_000, 100_000_000_000, 100, 4)
+                .unwrap(),
+            999_501_030
+        );
+        assert_eq!(
+            calc.get_dy(1_000_000_000, 150_000_000_000, 50_000_000_000, 100, 4)
+                .unwrap(),
+            981_979_550
+        );
+    }
+
+    #[test]
+    fn test_stable_swap_rejects_invalid_inputs() {
+        let calc = StableSwapCalculator;
+        assert!(calc.get_dy(0, 1_000, 1_000, 100, 4).is_err());
+        assert!(calc.get_dy(10, 1_000, 1_000, 0, 4).is_err());
+        assert!(calc.get_dy(10, 1_000, 1_000, 100, 10_001).is_err());
+        assert!(calc.get_dy(10, 0, 1_000, 100, 4).is_err());
+    }
 }
@@ -3,6 +3,8 @@
 //! Provides pathfinding algorithms for optimal swap routing across SDEX and Soroban AMM pools.
 //! Supports N-hop paths with safety bounds, cycle prevention, and price impact calculation.
 
+pub mod amount;
+pub mod contract;
 pub mod error;
 pub mod fixtures;
 pub mod health;
@@ -14,7 +16,8 @@ pub mod policy;
 pub mod risk;
 pub mod simulator;
 
-pub use impact::{AmmQuoteCalculator, OrderbookImpactCalculator};
+pub use amount::{Amount, Price, Rounding};
+pub use impact::{AmmQuoteCalculator, OrderbookImpactCalculator, StableSwapCalculator};
 pub use optimizer::{
     HybridOptimizer, OptimizerDiagnostics, OptimizerPolicy, PolicyPresets, RouteMetrics,
 };
@@ -1,14 +1,20 @@
 //! Hybrid route optimizer combining latency and execution quality
 
+use crate::amount::{Amount, Rounding};
 use crate::error::{Result, RoutingError};
-use crate::impact::{AmmQuoteCalculator, OrderbookImpactCalculator};
+use crate::impact::{AmmQuoteCalculator, OrderbookImpactCalculator, StableSwapCalculator};
 use crate::pathfinder::{LiquidityEdge, Pathfinder, PathfinderConfig, SwapPath};
 use crate::policy::RoutingPolicy;
 use crate::risk::{RiskLimitConfig, RiskValidator, RouteExclusion};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Instant;
 
+/// Venue type of a StableSwap pool edge; plain `"amm"` edges are constant-product
+pub const STABLE_VENUE_TYPE: &str = "amm_stable";
+
 /// Configuration for optimization policies
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OptimizerPolicy {
@@ -148,13 +154,15 @@ pub struct OptimizerDiagnostics {
 /// Hybrid route optimizer with configurable policies
 pub struct HybridOptimizer {
     pathfinder: Pathfinder,
-    #[allow(dead_code)]
     amm_calculator: AmmQuoteCalculator,
+    stable_calculator: StableSwapCalculator,
     #[allow(dead_code)]
     orderbook_calculator: OrderbookImpactCalculator,
     policies: HashMap<String, OptimizerPolicy>,
     active_policy: String,
     risk_validator: Option<RiskValidator>,
+    /// Set by the caller to abandon a search that is no longer wanted
+    cancel: Option<Arc<AtomicBool>>,
 }
 
 impl HybridOptimizer {
@@ -169,13 +177,28 @@ impl HybridOptimizer {
         Self {
             pathfinder: Pathfinder::new(config),
             amm_calculator: AmmQuoteCalculator,
+            stable_calculator: StableSwapCalculator,
             orderbook_calculator: OrderbookImpactCalculator,
             policies,
             active_policy: "production".to_string(),
             risk_validator: None,
+            cancel: None,
         }
     }
 
+    /// Stop searching with [`RoutingError::Cancelled`] once `flag` is set,
+    /// checked between candidate paths
+    pub fn with_cancellation(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
     /// Create optimizer with risk limits
     pub fn with_risk_limits(config: PathfinderConfig, risk_config: RiskLimitConfig) -> Self {
         let mut optimizer = Self::new(config);
@@ -240,7 +263,13 @@ impl HybridOptimizer {
 
         let mut scored_paths = Vec::new();
         for path in &paths {
-            let metrics = self.calculate_route_metrics(path, edges, amount_in)?;
+            if self.is_cancelled() {
+                return Err(RoutingError::Cancelled);
+            }
+            // A path a pool cannot fill (e.g. zero AMM output) is not a candidate
+            let Ok(metrics) = self.calculate_route_metrics(path, edges, amount_in) else {
+                continue;
+            };
 
             if metrics.impact_bps > policy.max_impact_bps
                 || metrics.compute_time_us > policy.max_compute_time_ms * 1000
@@ -340,21 +369,16 @@ impl HybridOptimizer {
             // Find corresponding edge
             let edge = edges
                 .iter()
-                .find(|e| e.from == hop.source_asset && e.to == hop.destination_asset)
+                .find(|e| {
+                    e.from == hop.source_asset
+                        && e.to == hop.destination_asset
+                        && e.venue_ref == hop.venue_ref
+                })
                 .ok_or_else(|| {
                     RoutingError::NoRoute(hop.source_asset.clone(), hop.destination_asset.clone())
                 })?;
 
-            // Calculate impact based on venue type
-            let (output, impact_bps) = if edge.venue_type == "amm" {
-                // Simulate AMM calculation (simplified)
-                let estimated_output = (total_output * 9970) / 10000; // 0.3% fee
-                (estimated_output, 30) // Simplified impact
-            } else {
-                // Simulate orderbook calculation
-                let estimated_output = (total_output * 9980) / 10000; // 0.2% fee
-                (estimated_output, 20) // Simplified impact
-            };
+            let (output, impact_bps) = self.quote_edge(edge, total_output)?;
 
             total_output = output;
             total_impact_bps = total_impact_bps.saturating_add(impact_bps);
@@ -372,6 +396,49 @@ impl HybridOptimizer {
         })
     }
 
+    /// Quote one hop on its venue's curve
+    ///
+    /// AMM edges carry the input-side reserve as `liquidity` and the reserve
+    /// ratio as `price`, so the output-side reserve is `liquidity × price`.
+    /// StableSwap edges also carry their pool's amplification coefficient.
+    fn quote_edge(&self, edge: &LiquidityEdge, amount_in: i128) -> Result<(i128, u32)> {
+        match edge.venue_type.as_str() {
+            "amm" | STABLE_VENUE_TYPE => {
+                let reserve_in = edge.liquidity;
+                let reserve_out = Amount::from_stroops(reserve_in)
+                    .mul_price(edge.price, Rounding::Down)?
+                    .stroops();
+                if edge.venue_type == STABLE_VENUE_TYPE {
+                    let amp = edge.amp.ok_or_else(|| {
+                        RoutingError::InvalidPair(format!(
+                            "stable pool {} has no amplification coefficient",
+                            edge.venue_ref
+                        ))
+                    })?;
+                    self.stable_calculator.quote_stable(
+                        amount_in,
+                        reserve_in,
+                        reserve_out,
+                        amp,
+                        edge.fee_bps,
+                    )
+                } else {
+                    self.amm_calculator.quote_constant_product(
+                        amount_in,
+                        reserve_in,
+                        reserve_out,
+                        edge.fee_bps,
+                    )
+                }
+            }
+            _ => {
+                // Simulate orderbook calculation
+                let estimated_output = (amount_in * 9980) / 10000; // 0.2% fee
+                Ok((estimated_output, 20)) // Simplified impact
+            }
+        }
+    }
+
     /// Calculate normalized score using policy weights
     fn calculate_score(&self, output: i128, impact_bps: u32, compute_time_us: u64) -> f64 {
         let policy = self.active_policy();
@@ -487,4 +554,74 @@ mod tests {
         assert!(optimizer.add_policy(custom_policy).is_ok());
         assert!(optimizer.set_active_policy("custom").is_ok());
     }
+
+    fn pegged_edge(venue_type: &str, venue_ref: &str) -> LiquidityEdge {
+        let amp = (venue_type == STABLE_VENUE_TYPE).then_some(100);
+        LiquidityEdge {
+            from: "USDC".to_string(),
+            to: "EURT".to_string(),
+            venue_type: venue_type.to_string(),
+            venue_ref: venue_ref.to_string(),
+            liquidity: 1_000_000_000_000,
+            price: crate::amount::Price::ONE,
+            fee_bps: 4,
+            amp,
+        }
+    }
+
+    #[test]
+    fn test_stable_pool_beats_constant_product_on_pegged_pair() {
+        let optimizer = HybridOptimizer::default();
+        let edges = vec![
+            pegged_edge("amm", "pool:const"),
+            pegged_edge(STABLE_VENUE_TYPE, "pool:stable"),
+        ];
+
+        let diagnostics = optimizer
+            .find_optimal_routes(
+                "USDC",
+                "EURT",
+                &edges,
+                10_000_000_000,
+                &RoutingPolicy::default(),
+            )
+            .unwrap();
+
+        assert_eq!(diagnostics.selected_path.hops[0].venue_ref, "pool:stable");
+        let (_, const_metrics) = &diagnostics.alternatives[0];
+        assert!(diagnostics.metrics.output_amount > const_metrics.output_amount);
+        assert!(diagnostics.metrics.impact_bps < const_metrics.impact_bps);
+    }
+
+    #[test]
+    fn test_stable_pool_uses_its_own_amplification() {
+        let optimizer = HybridOptimizer::default();
+        let quote = |amp: Option<u32>| {
+            let mut edge = pegged_edge(STABLE_VENUE_TYPE, "pool:stable");
+            edge.amp = amp;
+            optimizer.quote_edge(&edge, 100_000_000_000)
+        };
+
+        let (flat_out, flat_impact) = quote(Some(2_000)).unwrap();
+        let (curved_out, curved_impact) = quote(Some(5)).unwrap();
+        assert!(flat_out > curved_out);
+        assert!(flat_impact < curved_impact);
+        assert!(quote(None).is_err());
+    }
+
+    #[test]
+    fn test_cancelled_search_stops() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let optimizer = HybridOptimizer::default().with_cancellation(flag);
+
+        let result = optimizer.find_optimal_routes(
+            "USDC",
+            "EURT",
+            &[pegged_edge("amm", "pool:const")],
+            10_000_000,
+            &RoutingPolicy::default(),
+        );
+
+        assert!(matches!(result, Err(RoutingError::Cancelled)));
+    }
 }
@@ -1,5 +1,6 @@
 //! Pathfinding algorithms for swap routing with N-hop support and safety bounds
 
+use crate::amount::Price;
 use crate::error::{Result, RoutingError};
 use crate::policy::RoutingPolicy;
 use serde::{Deserialize, Serialize};
@@ -29,8 +30,11 @@ pub struct LiquidityEdge {
     pub venue_type: String,
     pub venue_ref: String,
     pub liquidity: i128,
-    pub price: f64,
+    pub price: Price,
     pub fee_bps: u32,
+    /// Amplification coefficient of a StableSwap pool edge; `None` elsewhere
+    #[serde(default)]
+    pub amp: Option<u32>,
 }
 
 /// Represents a path through liquidity sources
@@ -46,7 +50,7 @@ pub struct PathHop {
     pub destination_asset: String,
     pub venue_type: String,
     pub venue_ref: String,
-    pub price: f64,
+    pub price: Price,
     pub fee_bps: u32,
 }
 
@@ -123,6 +127,11 @@ impl Pathfinder {
                 continue;
             }
 
+            // Apply asset routing policy to both ends of the hop
+            if !policy.is_asset_allowed(&edge.from) || !policy.is_asset_allowed(&edge.to) {
+                continue;
+            }
+
             if edge.liquidity < self.config.min_liquidity_threshold {
                 continue; // Skip low-liquidity edges
             }
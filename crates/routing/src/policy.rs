@@ -6,6 +6,8 @@
 //!   in this list are considered.
 //! - **venue_denylist**: venues whose `venue_type` appears in this list are
 //!   excluded (evaluated after the allowlist).
+//! - **asset_allowlist**: when non-empty, only edges between listed assets are
+//!   considered, e.g. the assets an account holds trustlines for.
 //!
 //! Policies can be loaded from environment variables or constructed in code.
 
@@ -23,6 +25,11 @@ pub struct RoutingPolicy {
     /// Venues whose `venue_type` appears here are always excluded.
     /// Evaluated *after* the allowlist.
     pub venue_denylist: Vec<String>,
+    /// When non-empty, only edges whose source and destination assets are
+    /// both listed here (as canonical asset ids) are considered.
+    /// An empty list means "allow all assets".
+    #[serde(default)]
+    pub asset_allowlist: Vec<String>,
 }
 
 impl Default for RoutingPolicy {
@@ -31,6 +38,7 @@ impl Default for RoutingPolicy {
             max_hops: 4,
             venue_allowlist: Vec::new(),
             venue_denylist: Vec::new(),
+            asset_allowlist: Vec::new(),
         }
     }
 }
@@ -62,6 +70,12 @@ impl RoutingPolicy {
         self
     }
 
+    /// Builder: set asset allowlist
+    pub fn with_asset_allowlist(mut self, allowlist: Vec<String>) -> Self {
+        self.asset_allowlist = allowlist;
+        self
+    }
+
     /// Check whether a venue type is permitted by this policy.
     ///
     /// Returns `true` when the venue should be included:
@@ -77,6 +91,11 @@ impl RoutingPolicy {
         !self.venue_denylist.iter().any(|v| v == venue_type)
     }
 
+    /// Check whether a route may pass through `asset` (a canonical asset id).
+    pub fn is_asset_allowed(&self, asset: &str) -> bool {
+        self.asset_allowlist.is_empty() || self.asset_allowlist.iter().any(|a| a == asset)
+    }
+
     /// Load a routing policy from environment variables with sane defaults.
     ///
     /// | Variable | Description | Default |
@@ -100,6 +119,7 @@ impl RoutingPolicy {
             max_hops,
             venue_allowlist,
             venue_denylist,
+            ..Default::default()
         }
     }
 
@@ -179,6 +199,25 @@ mod tests {
         assert!(!policy.is_venue_allowed("orderbook")); // not in allowlist
     }
 
+    #[test]
+    fn asset_allowlist_restricts_to_listed_assets() {
+        assert!(RoutingPolicy::default().is_asset_allowed("USDC:GA5Z"));
+
+        let policy = RoutingPolicy::default()
+            .with_asset_allowlist(vec!["native".to_string(), "USDC:GA5Z".to_string()]);
+        assert!(policy.is_asset_allowed("native"));
+        assert!(policy.is_asset_allowed("USDC:GA5Z"));
+        assert!(!policy.is_asset_allowed("USDC:GBAD"));
+    }
+
+    #[test]
+    fn asset_allowlist_defaults_when_deserializing() {
+        let policy: RoutingPolicy =
+            serde_json::from_str(r#"{"max_hops":3,"venue_allowlist":[],"venue_denylist":[]}"#)
+                .unwrap();
+        assert!(policy.asset_allowlist.is_empty());
+    }
+
     #[test]
     fn validate_catches_zero_max_hops() {
         let policy = RoutingPolicy::new(0);
@@ -1,5 +1,6 @@
 //! Deterministic simulation engine for routing analysis
 
+use crate::amount::Rounding;
 use crate::error::Result;
 use crate::optimizer::{HybridOptimizer, OptimizerDiagnostics};
 use crate::pathfinder::LiquidityEdge;
@@ -116,7 +117,12 @@ impl RouteSimulator {
             } => {
                 for edge in edges.iter_mut() {
                     if edge.venue_ref == *venue_ref {
-                        edge.price *= 1.0 + percentage;
+                        let bps = (percentage * 10_000.0).round() as i128;
+                        if let Ok(price) =
+                            edge.price.mul_ratio(10_000 + bps, 10_000, Rounding::Down)
+                        {
+                            edge.price = price;
+                        }
                     }
                 }
             }
@@ -146,8 +152,9 @@ mod tests {
                 venue_type: "sdex".to_string(),
                 venue_ref: "venue1".to_string(),
                 liquidity: 1_000_000_000,
-                price: 0.12,
+                price: "0.12".parse().unwrap(),
                 fee_bps: 0,
+                amp: None,
             },
             LiquidityEdge {
                 from: "XLM".to_string(),
@@ -155,8 +162,9 @@ mod tests {
                 venue_type: "amm".to_string(),
                 venue_ref: "venue2".to_string(),
                 liquidity: 1_000_000_000,
-                price: 0.121,
+                price: "0.121".parse().unwrap(),
                 fee_bps: 30,
+                amp: None,
             },
         ]
     }
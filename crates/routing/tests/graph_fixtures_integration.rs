@@ -310,3 +310,29 @@ fn scenario_amm_only_policy_excludes_sdex_venues() {
         }
     }
 }
+
+// ── Scenario 6: Asset policy filtering ───────────────────────────────────────
+
+#[test]
+fn scenario_asset_allowlist_excludes_intermediate_assets() {
+    let edges = FixtureBuilder::multi_hop_market().build_edges();
+    let pathfinder = Pathfinder::new(default_config());
+
+    let eurc_key = "EURC:GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP";
+    let policy = RoutingPolicy::new(4)
+        .with_asset_allowlist(vec!["native".to_string(), eurc_key.to_string()]);
+
+    let paths = pathfinder
+        .find_paths("native", eurc_key, &edges, 100_000_000, &policy)
+        .expect("direct AMM pool should still be routable");
+
+    // Without USDC the 2-hop SDEX path is gone, only the direct pool remains
+    assert!(!paths.is_empty(), "expected the direct route");
+    for path in &paths {
+        assert_eq!(
+            path.hops.len(),
+            1,
+            "USDC must not appear as an intermediate"
+        );
+    }
+}
@@ -2,7 +2,8 @@
 
 use std::collections::HashMap;
 use stellarroute_routing::{
-    HybridOptimizer, LiquidityEdge, OptimizerPolicy, PathfinderConfig, PolicyPresets, RoutingPolicy,
+    HybridOptimizer, LiquidityEdge, OptimizerPolicy, PathfinderConfig, PolicyPresets, Price,
+    RoutingPolicy,
 };
 
 fn create_test_graph() -> Vec<LiquidityEdge> {
@@ -14,8 +15,9 @@ fn create_test_graph() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "pool_xlm_usdc".to_string(),
             liquidity: 1_000_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 100 XLM
+            amp: None,
         },
         LiquidityEdge {
             from: "XLM".to_string(),
@@ -23,8 +25,9 @@ fn create_test_graph() -> Vec<LiquidityEdge> {
             venue_type: "orderbook".to_string(),
             venue_ref: "book_xlm_eurt".to_string(),
             liquidity: 500_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 50 XLM
+            amp: None,
         },
         // Multi-hop paths
         LiquidityEdge {
@@ -33,8 +36,9 @@ fn create_test_graph() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "pool_usdc_eurt".to_string(),
             liquidity: 800_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 80 USDC
+            amp: None,
         },
         LiquidityEdge {
             from: "EURT".to_string(),
@@ -42,8 +46,9 @@ fn create_test_graph() -> Vec<LiquidityEdge> {
             venue_type: "orderbook".to_string(),
             venue_ref: "book_eurt_btc".to_string(),
             liquidity: 200_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 20 EURT
+            amp: None,
         },
         LiquidityEdge {
             from: "USDC".to_string(),
@@ -51,8 +56,9 @@ fn create_test_graph() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "pool_usdc_btc".to_string(),
             liquidity: 300_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 30 USDC
+            amp: None,
         },
         // Additional liquidity sources
         LiquidityEdge {
@@ -61,8 +67,9 @@ fn create_test_graph() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "pool_xlm_btc".to_string(),
             liquidity: 150_000_000,
-            price: 1.0,
+            price: Price::ONE,
             fee_bps: 30, // 15 XLM
+            amp: None,
         },
     ]
 }
@@ -15,8 +15,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "sdex".to_string(),
             venue_ref: "sdex:XLM:USDC".to_string(),
             liquidity: 1_000_000_000,
-            price: 0.10,
+            price: "0.10".parse().unwrap(),
             fee_bps: 30,
+            amp: None,
         },
         LiquidityEdge {
             from: "USDC".to_string(),
@@ -24,8 +25,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "amm".to_string(),
             venue_ref: "amm:USDC:EURC".to_string(),
             liquidity: 500_000_000,
-            price: 0.92,
+            price: "0.92".parse().unwrap(),
             fee_bps: 25,
+            amp: None,
         },
         LiquidityEdge {
             from: "XLM".to_string(),
@@ -33,8 +35,9 @@ fn create_test_edges() -> Vec<LiquidityEdge> {
             venue_type: "sdex".to_string(),
             venue_ref: "sdex:XLM:EURC".to_string(),
             liquidity: 100_000_000,
-            price: 0.092,
+            price: "0.092".parse().unwrap(),
             fee_bps: 30,
+            amp: None,
         },
     ]
 }
@@ -68,8 +71,9 @@ fn test_strict_policy_excludes_low_liquidity() {
         venue_type: "sdex".to_string(),
         venue_ref: "sdex:XLM:USDC".to_string(),
         liquidity: 10_000,
-        price: 0.10,
+        price: "0.10".parse().unwrap(),
         fee_bps: 30,
+        amp: None,
     }];
     let routing_policy = RoutingPolicy::default();
 
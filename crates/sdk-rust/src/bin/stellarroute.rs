@@ -3,9 +3,11 @@ use serde::Serialize;
 use std::ffi::OsStr;
 use std::num::NonZeroUsize;
 use stellarroute_sdk::{
-    HealthResponse, OrderbookLevel, OrderbookResponse, PairsResponse, QuoteRequest, QuoteResponse,
-    QuoteType, SdkError, StellarRouteClient,
+    Amount, AssetInfo, DiffReport, HealthResponse, OrderbookLevel, OrderbookResponse,
+    PairsResponse, QuoteRequest, QuoteResponse, QuoteType, ReplayArtifact, ReplayFilter,
+    ReplayListResponse, ReplayOutput, RoutesRequest, RoutesResponse, SdkError, StellarRouteClient,
 };
+use uuid::Uuid;
 
 const EXIT_SUCCESS: i32 = 0;
 const EXIT_USAGE_ERROR: i32 = 2;
@@ -105,6 +107,77 @@ enum Commands {
         )]
         levels: NonZeroUsize,
     },
+    #[command(about = "List ranked route candidates for a trading pair")]
+    Routes {
+        #[arg(
+            value_parser = parse_asset,
+            help = "Base asset: native, CODE, or CODE:ISSUER"
+        )]
+        base: String,
+        #[arg(
+            value_parser = parse_asset,
+            help = "Quote asset: native, CODE, or CODE:ISSUER"
+        )]
+        quote: String,
+        #[arg(
+            long,
+            value_parser = PositiveAmountParser,
+            help = "Trade amount as a positive decimal string"
+        )]
+        amount: Option<String>,
+        #[arg(long, help = "Maximum number of routes to return (API default: 5)")]
+        limit: Option<NonZeroUsize>,
+        #[arg(long, help = "Maximum hops per route (API default: 3)")]
+        max_hops: Option<NonZeroUsize>,
+        #[arg(long, help = "Optimizer policy environment (API default: production)")]
+        environment: Option<String>,
+    },
+    #[command(about = "List markets with active liquidity")]
+    Markets {
+        #[arg(
+            long,
+            default_value_t = 10,
+            help = "Maximum number of markets to print"
+        )]
+        limit: usize,
+    },
+    #[command(about = "Inspect and re-run stored quote replay artifacts")]
+    Replay {
+        #[command(subcommand)]
+        command: ReplayCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ReplayCommands {
+    #[command(about = "List stored artifacts, most recent first")]
+    List {
+        #[arg(long, help = "Filter by incident ID")]
+        incident: Option<String>,
+        #[arg(long, help = "Filter by base asset")]
+        base: Option<String>,
+        #[arg(long, help = "Filter by quote asset")]
+        quote: Option<String>,
+        #[arg(long, help = "Maximum number of artifacts (API default: 20, max: 100)")]
+        limit: Option<i64>,
+        #[arg(long, help = "Number of artifacts to skip")]
+        offset: Option<i64>,
+    },
+    #[command(about = "Show a stored artifact")]
+    Fetch {
+        #[arg(help = "Artifact UUID")]
+        artifact_id: Uuid,
+    },
+    #[command(about = "Re-run route selection for an artifact")]
+    Run {
+        #[arg(help = "Artifact UUID")]
+        artifact_id: Uuid,
+    },
+    #[command(about = "Re-run an artifact and diff it against the original output")]
+    Diff {
+        #[arg(help = "Artifact UUID")]
+        artifact_id: Uuid,
+    },
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -141,14 +214,14 @@ impl TypedValueParser for PositiveAmountParser {
             )
         })?;
 
-        match raw.parse::<f64>() {
-            Ok(amount) if amount.is_finite() && amount > 0.0 => Ok(raw.to_string()),
+        match raw.parse::<Amount>() {
+            Ok(amount) if amount.is_positive() => Ok(raw.to_string()),
             _ => {
                 let mut cmd = cmd.clone();
                 Err(cmd.error(
                     clap::error::ErrorKind::ValueValidation,
                     format!(
-                        "{} must be a positive number",
+                        "{} must be a positive number with at most 7 decimal places",
                         arg.map(|a| a.to_string())
                             .unwrap_or_else(|| "amount".to_string())
                     ),
@@ -223,6 +296,33 @@ async fn run(cli: Cli) -> Result<String, (i32, String)> {
         } => render_orderbook(&client, &base, &quote, levels.get(), cli.output)
             .await
             .map_err(|error| (exit_code_for_sdk_error(&error), error.to_string())),
+        Commands::Routes {
+            base,
+            quote,
+            amount,
+            limit,
+            max_hops,
+            environment,
+        } => render_routes(
+            &client,
+            RoutesRequest {
+                base: &base,
+                quote: &quote,
+                amount: amount.as_deref(),
+                limit: limit.map(NonZeroUsize::get),
+                max_hops: max_hops.map(NonZeroUsize::get),
+                environment: environment.as_deref(),
+            },
+            cli.output,
+        )
+        .await
+        .map_err(|error| (exit_code_for_sdk_error(&error), error.to_string())),
+        Commands::Markets { limit } => render_markets(&client, limit, cli.output)
+            .await
+            .map_err(|error| (exit_code_for_sdk_error(&error), error.to_string())),
+        Commands::Replay { command } => render_replay(&client, command, cli.output)
+            .await
+            .map_err(|error| (exit_code_for_sdk_error(&error), error.to_string())),
     }
 }
 
@@ -269,6 +369,64 @@ async fn render_orderbook(
     format_orderbook(&response, output)
 }
 
+async fn render_routes(
+    client: &StellarRouteClient,
+    request: RoutesRequest<'_>,
+    output: OutputFormat,
+) -> Result<String, SdkError> {
+    let response = client.routes(request).await?;
+
+    format_routes(&response, output)
+}
+
+async fn render_markets(
+    client: &StellarRouteClient,
+    limit: usize,
+    output: OutputFormat,
+) -> Result<String, SdkError> {
+    let response = client.markets().await?;
+
+    format_pairs(&response, limit, output)
+}
+
+async fn render_replay(
+    client: &StellarRouteClient,
+    command: ReplayCommands,
+    output: OutputFormat,
+) -> Result<String, SdkError> {
+    match command {
+        ReplayCommands::List {
+            incident,
+            base,
+            quote,
+            limit,
+            offset,
+        } => {
+            let filter = ReplayFilter {
+                incident_id: incident,
+                base,
+                quote,
+                limit,
+                offset,
+            };
+            let response = client.replay_artifacts(&filter).await?;
+            format_replay_list(&response, output)
+        }
+        ReplayCommands::Fetch { artifact_id } => {
+            let artifact = client.replay_artifact(artifact_id).await?;
+            format_replay_artifact(&artifact, output)
+        }
+        ReplayCommands::Run { artifact_id } => {
+            let replay = client.run_replay(artifact_id).await?;
+            format_replay_output(&replay, output)
+        }
+        ReplayCommands::Diff { artifact_id } => {
+            let report = client.diff_replay(artifact_id).await?;
+            format_replay_diff(&report, output)
+        }
+    }
+}
+
 fn format_health(response: &HealthResponse, output: OutputFormat) -> Result<String, SdkError> {
     match output {
         OutputFormat::Human => {
@@ -538,6 +696,299 @@ fn format_orderbook(
     }
 }
 
+fn format_routes(response: &RoutesResponse, output: OutputFormat) -> Result<String, SdkError> {
+    let pair = format!(
+        "{} / {}",
+        response.base_asset.display_name(),
+        response.quote_asset.display_name()
+    );
+
+    match output {
+        OutputFormat::Human => {
+            let mut lines = vec![
+                format!("pair: {pair}"),
+                format!("amount: {}", response.amount),
+                format!("routes: {}", response.routes.len()),
+            ];
+
+            for (index, route) in response.routes.iter().enumerate() {
+                lines.push(format!(
+                    "{}. output {} | impact {} bps | score {} | policy {}",
+                    index + 1,
+                    route.estimated_output,
+                    route.impact_bps,
+                    route.score,
+                    route.policy_used
+                ));
+                for hop in &route.path {
+                    lines.push(format!(
+                        "   {} -> {} @ {} via {} (out {}, fee {} bps)",
+                        hop.from_asset.display_name(),
+                        hop.to_asset.display_name(),
+                        hop.price,
+                        hop.source,
+                        hop.amount_out_of_hop,
+                        hop.fee_bps
+                    ));
+                }
+            }
+
+            Ok(lines.join("\n"))
+        }
+        OutputFormat::Table => {
+            let summary = format_table(
+                &["field", "value"],
+                vec![
+                    vec!["pair".to_string(), pair],
+                    vec!["amount".to_string(), response.amount.clone()],
+                    vec!["routes".to_string(), response.routes.len().to_string()],
+                ],
+            );
+
+            let rows = response
+                .routes
+                .iter()
+                .enumerate()
+                .map(|(idx, route)| {
+                    vec![
+                        (idx + 1).to_string(),
+                        route.estimated_output.clone(),
+                        route.impact_bps.to_string(),
+                        route.score.to_string(),
+                        route.policy_used.clone(),
+                        route_path(
+                            route
+                                .path
+                                .iter()
+                                .map(|hop| (&hop.from_asset, &hop.to_asset)),
+                        ),
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            let routes = format_table(
+                &["rank", "output", "impact_bps", "score", "policy", "path"],
+                rows,
+            );
+            Ok(format!("{}\n\nroutes\n{}", summary, routes))
+        }
+        OutputFormat::Json => serde_json::to_string_pretty(response).map_err(Into::into),
+    }
+}
+
+fn format_replay_list(
+    response: &ReplayListResponse,
+    output: OutputFormat,
+) -> Result<String, SdkError> {
+    match output {
+        OutputFormat::Human => {
+            let mut lines = vec![format!("artifacts: {}", response.artifacts.len())];
+            for artifact in &response.artifacts {
+                lines.push(format!(
+                    "{} | {} | {} / {} | incident: {}",
+                    artifact.id,
+                    artifact.captured_at,
+                    artifact.base_asset,
+                    artifact.quote_asset,
+                    artifact.incident_id.as_deref().unwrap_or("-")
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+        OutputFormat::Table => {
+            let rows = response
+                .artifacts
+                .iter()
+                .map(|artifact| {
+                    vec![
+                        artifact.id.to_string(),
+                        artifact.captured_at.clone(),
+                        artifact.base_asset.clone(),
+                        artifact.quote_asset.clone(),
+                        artifact
+                            .incident_id
+                            .clone()
+                            .unwrap_or_else(|| "-".to_string()),
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            Ok(format_table(
+                &["id", "captured_at", "base", "quote", "incident"],
+                rows,
+            ))
+        }
+        OutputFormat::Json => serde_json::to_string_pretty(response).map_err(Into::into),
+    }
+}
+
+fn format_replay_artifact(
+    artifact: &ReplayArtifact,
+    output: OutputFormat,
+) -> Result<String, SdkError> {
+    let fields = vec![
+        ("id", artifact.id.to_string()),
+        (
+            "incident",
+            artifact
+                .incident_id
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        ("captured_at", artifact.captured_at.clone()),
+        ("pair", format!("{} / {}", artifact.base, artifact.quote)),
+        ("amount", artifact.amount.clone()),
+        ("quote_type", artifact.quote_type.clone()),
+        ("slippage_bps", artifact.slippage_bps.to_string()),
+        ("candidates", artifact.liquidity_snapshot.len().to_string()),
+    ];
+
+    match output {
+        OutputFormat::Human => {
+            let mut lines = fields
+                .iter()
+                .map(|(name, value)| format!("{}: {value}", name.replace('_', " ")))
+                .collect::<Vec<_>>();
+            for candidate in &artifact.liquidity_snapshot {
+                lines.push(format!(
+                    "  {} {} @ {} (available {})",
+                    candidate.venue_type,
+                    candidate.venue_ref,
+                    candidate.price,
+                    candidate.available_amount
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+        OutputFormat::Table => {
+            let summary = format_table(
+                &["field", "value"],
+                fields
+                    .into_iter()
+                    .map(|(name, value)| vec![name.to_string(), value])
+                    .collect(),
+            );
+            let rows = artifact
+                .liquidity_snapshot
+                .iter()
+                .map(|candidate| {
+                    vec![
+                        candidate.venue_type.clone(),
+                        candidate.venue_ref.clone(),
+                        candidate.price.clone(),
+                        candidate.available_amount.clone(),
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            Ok(format!(
+                "{}\n\nliquidity\n{}",
+                summary,
+                format_table(&["venue", "ref", "price", "available"], rows)
+            ))
+        }
+        OutputFormat::Json => serde_json::to_string_pretty(artifact).map_err(Into::into),
+    }
+}
+
+fn format_replay_output(replay: &ReplayOutput, output: OutputFormat) -> Result<String, SdkError> {
+    let path = route_path(
+        replay
+            .path
+            .iter()
+            .map(|step| (&step.from_asset, &step.to_asset)),
+    );
+
+    match output {
+        OutputFormat::Human => Ok([
+            format!("artifact: {}", replay.artifact_id),
+            format!("selected source: {}", replay.selected_source),
+            format!("price: {}", replay.price),
+            format!("path: {path}"),
+            format!("deterministic: {}", yes_no(replay.is_deterministic)),
+            format!("replayed at: {}", replay.replayed_at),
+        ]
+        .join("\n")),
+        OutputFormat::Table => Ok(format_table(
+            &["field", "value"],
+            vec![
+                vec!["artifact".to_string(), replay.artifact_id.to_string()],
+                vec![
+                    "selected_source".to_string(),
+                    replay.selected_source.clone(),
+                ],
+                vec!["price".to_string(), replay.price.clone()],
+                vec!["path".to_string(), path],
+                vec![
+                    "deterministic".to_string(),
+                    yes_no(replay.is_deterministic).to_string(),
+                ],
+                vec!["replayed_at".to_string(), replay.replayed_at.clone()],
+            ],
+        )),
+        OutputFormat::Json => serde_json::to_string_pretty(replay).map_err(Into::into),
+    }
+}
+
+fn format_replay_diff(report: &DiffReport, output: OutputFormat) -> Result<String, SdkError> {
+    match output {
+        OutputFormat::Human => {
+            let mut lines = vec![
+                format!("artifact: {}", report.artifact_id),
+                format!("identical: {}", yes_no(report.is_identical)),
+            ];
+            for divergence in &report.divergences {
+                lines.push(format!(
+                    "  {}: {} -> {}",
+                    divergence.field, divergence.original, divergence.replayed
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+        OutputFormat::Table => {
+            let rows = report
+                .divergences
+                .iter()
+                .map(|divergence| {
+                    vec![
+                        divergence.field.clone(),
+                        divergence.original.to_string(),
+                        divergence.replayed.to_string(),
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            Ok(format!(
+                "artifact: {}\nidentical: {}\n\n{}",
+                report.artifact_id,
+                yes_no(report.is_identical),
+                format_table(&["field", "original", "replayed"], rows)
+            ))
+        }
+        OutputFormat::Json => serde_json::to_string_pretty(report).map_err(Into::into),
+    }
+}
+
+/// Render hops as `A -> B -> C`.
+fn route_path<'a>(hops: impl Iterator<Item = (&'a AssetInfo, &'a AssetInfo)>) -> String {
+    let mut assets = Vec::new();
+    for (from, to) in hops {
+        if assets.is_empty() {
+            assets.push(from.display_name());
+        }
+        assets.push(to.display_name());
+    }
+    assets.join(" -> ")
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
 fn level_to_row(level: &OrderbookLevel) -> Vec<String> {
     vec![
         level.price.clone(),
@@ -604,10 +1055,14 @@ fn format_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
 fn exit_code_for_sdk_error(error: &SdkError) -> i32 {
     match error {
         SdkError::InvalidConfig(_) => EXIT_CONFIG_ERROR,
+        SdkError::InvalidInput(_) => EXIT_USAGE_ERROR,
 
         SdkError::Http(_)
         | SdkError::Api { .. }
+        | SdkError::Stream { .. }
+        | SdkError::StreamLagged { .. }
         | SdkError::Deserialization(_)
+        | SdkError::InvalidDecimal { .. }
         | SdkError::RateLimited { .. } => EXIT_RUNTIME_ERROR,
     }
 }
@@ -694,6 +1149,21 @@ mod tests {
         assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
     }
 
+    #[test]
+    fn rejects_sub_stroop_amount() {
+        let error = Cli::try_parse_from([
+            "stellarroute",
+            "quote",
+            "native",
+            "USDC",
+            "--amount",
+            "1.00000001",
+        ])
+        .expect_err("amount should fail");
+
+        assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+
     #[test]
     fn rejects_invalid_asset() {
         let error =
@@ -787,6 +1257,121 @@ step | from   | to   | price     | source
 "###);
     }
 
+    #[test]
+    fn parses_routes_command_options() {
+        let cli = Cli::try_parse_from([
+            "stellarroute",
+            "routes",
+            "native",
+            "usdc",
+            "--amount",
+            "250",
+            "--max-hops",
+            "2",
+            "--environment",
+            "staging",
+            "--limit",
+            "3",
+        ])
+        .expect("command should parse");
+
+        match cli.command {
+            Commands::Routes {
+                base,
+                quote,
+                amount,
+                limit,
+                max_hops,
+                environment,
+            } => {
+                assert_eq!(base, "native");
+                assert_eq!(quote, "USDC");
+                assert_eq!(amount.as_deref(), Some("250"));
+                assert_eq!(limit.map(NonZeroUsize::get), Some(3));
+                assert_eq!(max_hops.map(NonZeroUsize::get), Some(2));
+                assert_eq!(environment.as_deref(), Some("staging"));
+            }
+            _ => panic!("expected routes command"),
+        }
+    }
+
+    #[test]
+    fn parses_replay_subcommands() {
+        let id = "6f1c2a8e-3b7d-4c5e-9f0a-1b2c3d4e5f60";
+        let cli = Cli::try_parse_from(["stellarroute", "replay", "diff", id])
+            .expect("command should parse");
+        match cli.command {
+            Commands::Replay {
+                command: ReplayCommands::Diff { artifact_id },
+            } => assert_eq!(artifact_id.to_string(), id),
+            _ => panic!("expected replay diff command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "stellarroute",
+            "replay",
+            "list",
+            "--incident",
+            "INC-1",
+            "--limit",
+            "5",
+        ])
+        .expect("command should parse");
+        match cli.command {
+            Commands::Replay {
+                command:
+                    ReplayCommands::List {
+                        incident, limit, ..
+                    },
+            } => {
+                assert_eq!(incident.as_deref(), Some("INC-1"));
+                assert_eq!(limit, Some(5));
+            }
+            _ => panic!("expected replay list command"),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_artifact_id() {
+        let error = Cli::try_parse_from(["stellarroute", "replay", "run", "not-a-uuid"])
+            .expect_err("artifact id should fail");
+
+        assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn snapshot_routes_output_table() {
+        let rendered = normalize_for_snapshot(
+            &format_routes(&sample_routes_response(), OutputFormat::Table)
+                .expect("formatting should succeed"),
+        );
+        insta::assert_snapshot!(rendered, @r###"
+field  | value
+<sep>
+pair   | native / USDC
+amount | 100.0000000
+routes | 2
+
+routes
+rank | output     | impact_bps | score | policy     | path
+<sep>
+1    | 10.5000000 | 12         | 0.98  | production | native -> USDC
+2    | 10.4000000 | 40         | 0.91  | production | native -> EURC -> USDC
+"###);
+    }
+
+    #[test]
+    fn snapshot_replay_diff_output_human() {
+        let rendered = format_replay_diff(&sample_diff_report(), OutputFormat::Human)
+            .expect("formatting should succeed");
+        insta::assert_snapshot!(rendered, @r###"
+artifact: 6f1c2a8e-3b7d-4c5e-9f0a-1b2c3d4e5f60
+identical: no
+  path[0].source: "sdex:offer1" -> "amm:pool1"
+  price: "1.0050000" -> "0.9960000"
+"###);
+    }
+
     #[test]
     fn exit_code_mapping_is_stable() {
         assert_eq!(
@@ -858,11 +1443,98 @@ step | from   | to   | price     | source
                 },
                 price: "0.1050000".to_string(),
                 source: "sdex".to_string(),
+                min_amount_out: None,
             }],
             timestamp: 1_742_908_400,
         }
     }
 
+    fn sample_routes_response() -> RoutesResponse {
+        let native = AssetInfo {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let credit = |code: &str| AssetInfo {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: None,
+        };
+        let hop = |from: &AssetInfo, to: &AssetInfo, price: &str, out: &str, source: &str| {
+            stellarroute_sdk::RouteHop {
+                from_asset: from.clone(),
+                to_asset: to.clone(),
+                price: price.to_string(),
+                amount_out_of_hop: out.to_string(),
+                fee_bps: 30,
+                source: source.to_string(),
+            }
+        };
+
+        RoutesResponse {
+            base_asset: native.clone(),
+            quote_asset: credit("USDC"),
+            amount: "100.0000000".to_string(),
+            routes: vec![
+                stellarroute_sdk::RouteCandidate {
+                    estimated_output: "10.5000000".to_string(),
+                    impact_bps: 12,
+                    score: 0.98,
+                    policy_used: "production".to_string(),
+                    path: vec![hop(
+                        &native,
+                        &credit("USDC"),
+                        "0.1050000",
+                        "10.5000000",
+                        "sdex",
+                    )],
+                },
+                stellarroute_sdk::RouteCandidate {
+                    estimated_output: "10.4000000".to_string(),
+                    impact_bps: 40,
+                    score: 0.91,
+                    policy_used: "production".to_string(),
+                    path: vec![
+                        hop(
+                            &native,
+                            &credit("EURC"),
+                            "0.0960000",
+                            "9.6000000",
+                            "amm:pool1",
+                        ),
+                        hop(
+                            &credit("EURC"),
+                            &credit("USDC"),
+                            "1.0833333",
+                            "10.4000000",
+                            "sdex",
+                        ),
+                    ],
+                },
+            ],
+            timestamp: 1_742_908_400,
+        }
+    }
+
+    fn sample_diff_report() -> DiffReport {
+        DiffReport {
+            artifact_id: "6f1c2a8e-3b7d-4c5e-9f0a-1b2c3d4e5f60".parse().unwrap(),
+            is_identical: false,
+            divergences: vec![
+                stellarroute_sdk::FieldDivergence {
+                    field: "path[0].source".to_string(),
+                    original: serde_json::json!("sdex:offer1"),
+                    replayed: serde_json::json!("amm:pool1"),
+                },
+                stellarroute_sdk::FieldDivergence {
+                    field: "price".to_string(),
+                    original: serde_json::json!("1.0050000"),
+                    replayed: serde_json::json!("0.9960000"),
+                },
+            ],
+        }
+    }
+
     fn normalize_for_snapshot(value: &str) -> String {
         value
             .lines()
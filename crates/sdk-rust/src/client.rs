@@ -22,12 +22,15 @@
 use std::time::Duration;
 
 use reqwest::{header, Url};
+use uuid::Uuid;
 
 use crate::{
     error::{ApiErrorCode, RateLimitInfo, Result, SdkError},
+    stream::{QuoteStream, StreamConfig},
     types::{
-        BatchQuoteRequest, BatchQuoteResponse, ErrorResponse, HealthResponse, OrderbookResponse,
-        PairsResponse, QuoteRequest, QuoteResponse,
+        BatchQuoteRequest, BatchQuoteResponse, DiffReport, ErrorResponse, HealthResponse,
+        OrderbookResponse, PairsResponse, QuoteRequest, QuoteResponse, ReplayArtifact,
+        ReplayFilter, ReplayListResponse, ReplayOutput, RoutesRequest, RoutesResponse,
     },
 };
 
@@ -134,6 +137,11 @@ impl StellarRouteClient {
         self.get("api/v1/pairs").await
     }
 
+    /// `GET /api/v1/markets` — list markets with active liquidity.
+    pub async fn markets(&self) -> Result<PairsResponse> {
+        self.get("api/v1/markets").await
+    }
+
     /// `GET /api/v1/orderbook/{base}/{quote}` — fetch orderbook snapshot.
     ///
     /// Returns [`SdkError::Api`] with [`ApiErrorCode::NotFound`] when the pair
@@ -160,8 +168,9 @@ impl StellarRouteClient {
 
     /// `POST /api/v1/batch/quote` — fetch multiple price quotes in a single request.
     ///
-    /// Returns [`SdkError::Api`] with [`ApiErrorCode::ValidationError`] if any
-    /// request item is malformed or the batch is too large.
+    /// Each item gets its own [`BatchQuoteResult`](crate::types::BatchQuoteResult),
+    /// so one bad item does not fail the batch. Returns [`SdkError::Api`] with
+    /// [`ApiErrorCode::ValidationError`] if the batch is empty or too large.
     pub async fn batch_quote(&self, request: BatchQuoteRequest) -> Result<BatchQuoteResponse> {
         let req = self
             .http
@@ -170,8 +179,118 @@ impl StellarRouteClient {
         self.execute(req).await
     }
 
+    /// Like [`batch_quote`](Self::batch_quote), sent with an `Idempotency-Key`.
+    ///
+    /// Retrying with the same key and request within ten minutes returns the
+    /// first result instead of quoting again. Reusing a key for a different
+    /// request fails with [`ApiErrorCode::ValidationError`].
+    pub async fn batch_quote_with_idempotency_key(
+        &self,
+        request: BatchQuoteRequest,
+        idempotency_key: &str,
+    ) -> Result<BatchQuoteResponse> {
+        let req = self
+            .http
+            .post(self.url("api/v1/batch/quote")?)
+            .header("Idempotency-Key", idempotency_key)
+            .json(&request);
+        self.execute(req).await
+    }
+
+    /// `GET /api/v1/routes/{base}/{quote}` — ranked route candidates.
+    ///
+    /// Returns [`SdkError::Api`] with [`ApiErrorCode::NotFound`] when no route
+    /// exists for the pair.
+    pub async fn routes(&self, request: RoutesRequest<'_>) -> Result<RoutesResponse> {
+        let path = format!("api/v1/routes/{}/{}", request.base, request.quote);
+        let mut req = self.http.get(self.url(&path)?);
+
+        if let Some(amount) = request.amount {
+            req = req.query(&[("amount", amount)]);
+        }
+        if let Some(limit) = request.limit {
+            req = req.query(&[("limit", limit)]);
+        }
+        if let Some(max_hops) = request.max_hops {
+            req = req.query(&[("max_hops", max_hops)]);
+        }
+        if let Some(environment) = request.environment {
+            req = req.query(&[("environment", environment)]);
+        }
+
+        self.execute(req).await
+    }
+
+    /// `GET /api/v1/replay` — list stored replay artifacts, most recent first.
+    pub async fn replay_artifacts(&self, filter: &ReplayFilter) -> Result<ReplayListResponse> {
+        let mut req = self.http.get(self.url("api/v1/replay")?);
+
+        if let Some(incident_id) = &filter.incident_id {
+            req = req.query(&[("incident_id", incident_id)]);
+        }
+        if let Some(base) = &filter.base {
+            req = req.query(&[("base", base)]);
+        }
+        if let Some(quote) = &filter.quote {
+            req = req.query(&[("quote", quote)]);
+        }
+        if let Some(limit) = filter.limit {
+            req = req.query(&[("limit", limit)]);
+        }
+        if let Some(offset) = filter.offset {
+            req = req.query(&[("offset", offset)]);
+        }
+
+        self.execute(req).await
+    }
+
+    /// `GET /api/v1/replay/{id}` — fetch a stored replay artifact.
+    pub async fn replay_artifact(&self, id: Uuid) -> Result<ReplayArtifact> {
+        self.get(&format!("api/v1/replay/{id}")).await
+    }
+
+    /// `POST /api/v1/replay/{id}/run` — re-run route selection on an artifact.
+    pub async fn run_replay(&self, id: Uuid) -> Result<ReplayOutput> {
+        let req = self
+            .http
+            .post(self.url(&format!("api/v1/replay/{id}/run"))?);
+        self.execute(req).await
+    }
+
+    /// `POST /api/v1/replay/{id}/diff` — re-run an artifact and diff the
+    /// result against the original output.
+    pub async fn diff_replay(&self, id: Uuid) -> Result<DiffReport> {
+        let req = self
+            .http
+            .post(self.url(&format!("api/v1/replay/{id}/diff"))?);
+        self.execute(req).await
+    }
+
+    /// `GET /ws` — open a live quote stream with default settings.
+    ///
+    /// The stream connects to the same host as the REST API, using `wss://`
+    /// when the API URL is `https://`.
+    pub async fn quote_stream(&self) -> Result<QuoteStream> {
+        self.quote_stream_with(StreamConfig::default()).await
+    }
+
+    /// `GET /ws` — open a live quote stream with custom reconnect, keepalive
+    /// and buffering settings.
+    pub async fn quote_stream_with(&self, config: StreamConfig) -> Result<QuoteStream> {
+        QuoteStream::connect(self.ws_url()?, config).await
+    }
+
     // ── Internal helpers ──────────────────────────────────────────────────────
 
+    fn ws_url(&self) -> Result<String> {
+        let mut url = self.url("ws")?;
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        url.set_scheme(scheme).map_err(|()| {
+            SdkError::InvalidConfig(format!("Cannot derive a WebSocket URL from '{url}'"))
+        })?;
+        Ok(url.into())
+    }
+
     fn url(&self, path: &str) -> Result<Url> {
         self.base_url
             .join(path)
@@ -0,0 +1,189 @@
+//! Commit-reveal hashes for the router contract.
+//!
+//! Large swaps go through `commit_swap` / `reveal_and_execute`. The
+//! commitment is `sha256(xdr(SwapParams) || salt)`, where `xdr(...)` is the
+//! Soroban `ScVal` encoding the contract produces with `to_xdr`. The types
+//! are the routing crate's mirror of the contract's `SwapParams`
+//! ([`stellarroute_routing::contract`]); addresses are strkeys (`G...`
+//! accounts or `C...` contracts).
+//!
+//! ```
+//! use stellarroute_sdk::commitment::{commitment_hash, Asset, PoolType, Route, RouteHop, SwapParams};
+//!
+//! let pool = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+//! let params = SwapParams {
+//!     route: Route {
+//!         hops: vec![RouteHop {
+//!             source: Asset::Native,
+//!             destination: Asset::Soroban(pool.to_string()),
+//!             pool: pool.to_string(),
+//!             pool_type: PoolType::AmmConstProd,
+//!             min_amount_out: 0,
+//!         }],
+//!         estimated_output: 0,
+//!         min_output: 0,
+//!         expires_at: 0,
+//!     },
+//!     amount_in: 1_000_000,
+//!     min_amount_out: 990_000,
+//!     recipient: "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ".to_string(),
+//!     deadline: 50_000,
+//!     not_before: 0,
+//!     max_price_impact_bps: 100,
+//!     max_execution_spread_bps: 50,
+//! };
+//! let hash = commitment_hash(&params, &[7u8; 32]).unwrap();
+//! assert_eq!(hash.len(), 32);
+//! ```
+
+use sha2::{Digest, Sha256};
+
+pub use stellarroute_routing::contract::{Asset, PoolType, Route, RouteHop, SwapParams};
+
+use crate::error::{Result, SdkError};
+
+/// Commitment hash to pass to `commit_swap` for `params` and `salt`
+///
+/// Reveal with the same `params` and `salt`; changing any field, including
+/// the route or recipient, produces a different hash.
+pub fn commitment_hash(params: &SwapParams, salt: &[u8; 32]) -> Result<[u8; 32]> {
+    let mut payload = swap_params_xdr(params)?;
+    payload.extend_from_slice(salt);
+    Ok(Sha256::digest(&payload).into())
+}
+
+/// XDR `ScVal` encoding of `params`, byte-identical to the contract's `to_xdr`
+pub fn swap_params_xdr(params: &SwapParams) -> Result<Vec<u8>> {
+    params
+        .to_xdr()
+        .map_err(|e| SdkError::InvalidInput(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+    const CONTRACT: &str = "CA3D5KRYM6CB7OWQ6TWYRR3Z4T7GNZLKERYNZGGA5SOAOPIFY6YQGAXE";
+
+    fn params() -> SwapParams {
+        SwapParams {
+            route: Route {
+                hops: vec![RouteHop {
+                    source: Asset::Native,
+                    destination: Asset::Soroban(CONTRACT.to_string()),
+                    pool: CONTRACT.to_string(),
+                    pool_type: PoolType::AmmConstProd,
+                    min_amount_out: 0,
+                }],
+                estimated_output: 0,
+                min_output: 0,
+                expires_at: 0,
+            },
+            amount_in: 1_000_000,
+            min_amount_out: 990_000,
+            recipient: ACCOUNT.to_string(),
+            deadline: 50_000,
+            not_before: 0,
+            max_price_impact_bps: 100,
+            max_execution_spread_bps: 50,
+        }
+    }
+
+    #[test]
+    fn struct_fields_are_sorted_by_name() {
+        let xdr = swap_params_xdr(&params()).unwrap();
+        let position = |needle: &str| {
+            xdr.windows(needle.len())
+                .position(|w| w == needle.as_bytes())
+                .unwrap()
+        };
+        assert!(position("amount_in") < position("deadline"));
+        assert!(position("max_execution_spread_bps") < position("max_price_impact_bps"));
+        assert!(position("recipient") < position("route"));
+    }
+
+    #[test]
+    fn hash_binds_every_field() {
+        let salt = [7u8; 32];
+        let base = commitment_hash(&params(), &salt).unwrap();
+        assert_eq!(base, commitment_hash(&params(), &salt).unwrap());
+
+        let mut recipient = params();
+        recipient.recipient = CONTRACT.to_string();
+        assert_ne!(base, commitment_hash(&recipient, &salt).unwrap());
+
+        let mut hop = params();
+        hop.route.hops[0].min_amount_out = 1;
+        assert_ne!(base, commitment_hash(&hop, &salt).unwrap());
+
+        assert_ne!(base, commitment_hash(&params(), &[8u8; 32]).unwrap());
+    }
+
+    /// Hash the router contract computes with `params.to_xdr(&env)` for the
+    /// same params (`e2e_mev_commitment_hash_matches_sdk_golden`).
+    #[test]
+    fn matches_contract_golden_hash() {
+        let usdc = Asset::Issued {
+            issuer: ACCOUNT.to_string(),
+            code: "USDC".to_string(),
+        };
+        let params = SwapParams {
+            route: Route {
+                hops: vec![
+                    RouteHop {
+                        source: Asset::Native,
+                        destination: usdc.clone(),
+                        pool: CONTRACT.to_string(),
+                        pool_type: PoolType::AmmStable,
+                        min_amount_out: 123_456_789,
+                    },
+                    RouteHop {
+                        source: usdc,
+                        destination: Asset::Soroban(CONTRACT.to_string()),
+                        pool: CONTRACT.to_string(),
+                        pool_type: PoolType::Sdex,
+                        min_amount_out: 0,
+                    },
+                ],
+                estimated_output: 1 << 70,
+                min_output: -5,
+                expires_at: u64::MAX,
+            },
+            amount_in: 1_000_000,
+            min_amount_out: 990_000,
+            recipient: ACCOUNT.to_string(),
+            deadline: 50_000,
+            not_before: 7,
+            max_price_impact_bps: 100,
+            max_execution_spread_bps: 50,
+        };
+
+        let hash = commitment_hash(&params, &[7u8; 32]).unwrap();
+        let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            hex,
+            "16c3b47e370a40ee03e6994b1972e80fffb806a4bbf11b5d9413afc816e9dfe2"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_addresses_and_symbols() {
+        let mut bad_recipient = params();
+        bad_recipient.recipient = "not-a-strkey".to_string();
+        assert!(matches!(
+            commitment_hash(&bad_recipient, &[0; 32]),
+            Err(SdkError::InvalidInput(_))
+        ));
+
+        let mut bad_code = params();
+        bad_code.route.hops[0].source = Asset::Issued {
+            issuer: ACCOUNT.to_string(),
+            code: "US-D".to_string(),
+        };
+        assert!(matches!(
+            commitment_hash(&bad_code, &[0; 32]),
+            Err(SdkError::InvalidInput(_))
+        ));
+    }
+}
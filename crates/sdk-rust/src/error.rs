@@ -110,6 +110,36 @@ pub enum SdkError {
     /// The client was constructed with an invalid configuration value.
     #[error("Invalid SDK configuration: {0}")]
     InvalidConfig(String),
+
+    /// The quote stream server sent an `error` frame.
+    #[error("Stream error [{code}]: {message}")]
+    Stream {
+        /// Typed error code from the frame's `code` field.
+        code: ApiErrorCode,
+        /// Human-readable description from the `message` field.
+        message: String,
+    },
+
+    /// The caller fell behind the quote stream and the oldest buffered
+    /// updates were dropped.
+    #[error("Quote stream lagged: {skipped} updates dropped")]
+    StreamLagged {
+        /// Number of updates dropped since the previous notice.
+        skipped: u64,
+    },
+
+    /// A decimal field in an API response is not an exact amount or price.
+    #[error("Invalid decimal in `{field}`: {value:?}")]
+    InvalidDecimal {
+        /// Name of the response field.
+        field: &'static str,
+        /// The value as received.
+        value: String,
+    },
+
+    /// A value passed to an off-chain helper could not be encoded.
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
 }
 
 impl SdkError {
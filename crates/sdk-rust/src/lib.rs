@@ -24,13 +24,19 @@
 //! ```
 
 pub mod client;
+pub mod commitment;
 pub mod error;
+pub mod stream;
 pub mod types;
 
 // Flat re-exports — callers only need `use stellarroute_sdk::*`.
 pub use client::{ClientBuilder, StellarRouteClient};
 pub use error::{ApiErrorCode, RateLimitInfo, Result, SdkError};
+pub use stellarroute_routing::amount::{Amount, Price, Rounding};
+pub use stream::{QuoteStream, QuoteSubscription, QuoteUpdate, StreamConfig};
 pub use types::{
-    AssetInfo, HealthResponse, OrderbookLevel, OrderbookResponse, PairsResponse, PathStep,
-    QuoteRequest, QuoteResponse, QuoteType, TradingPair,
+    ArtifactSummary, AssetInfo, DiffReport, FieldDivergence, HealthResponse, OrderbookLevel,
+    OrderbookResponse, PairsResponse, PathStep, QuoteRequest, QuoteResponse, QuoteType,
+    ReplayArtifact, ReplayFilter, ReplayListResponse, ReplayOutput, RouteCandidate, RouteHop,
+    RoutesRequest, RoutesResponse, TradingPair,
 };
@@ -0,0 +1,649 @@
+//! Live quote stream over the API's `/ws` WebSocket endpoint.
+//!
+//! [`QuoteStream`] is an async [`Stream`] of [`QuoteUpdate`]s. A background
+//! task owns the socket and:
+//!
+//! - sends `subscribe` / `unsubscribe` frames for [`QuoteStream::subscribe`]
+//!   and [`QuoteStream::unsubscribe`], resolving each subscribe once the
+//!   server confirms or rejects it (replies are matched by a `request_id`
+//!   the server echoes back);
+//! - answers server pings and sends its own, reconnecting when a pong does
+//!   not arrive within [`StreamConfig::pong_timeout`];
+//! - reconnects with exponential backoff when the connection drops and
+//!   resubscribes everything that was active, so callers keep receiving
+//!   updates without doing anything;
+//! - never waits on the caller, so commands and keepalives keep running
+//!   while updates pile up. Once the caller is more than
+//!   [`StreamConfig::buffer`] updates behind, the oldest are dropped and the
+//!   stream yields [`SdkError::StreamLagged`] with the number skipped.
+//!
+//! Server-assigned subscription IDs change on every reconnect, so
+//! subscriptions are identified by their [`QuoteSubscription`] parameters.
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use stellarroute_sdk::{ClientBuilder, QuoteSubscription};
+//!
+//! #[tokio::main]
+//! async fn main() -> stellarroute_sdk::Result<()> {
+//!     let client = ClientBuilder::new("http://localhost:3000").build()?;
+//!     let mut stream = client.quote_stream().await?;
+//!
+//!     stream.subscribe(QuoteSubscription::new("native", "USDC")).await?;
+//!     while let Some(update) = stream.next().await {
+//!         let update = update?;
+//!         println!("{}: {}", update.subscription.quote, update.quote.price);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{interval_at, sleep, Instant, MissedTickBehavior};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiErrorCode, Result, SdkError},
+    types::QuoteResponse,
+};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Command channel capacity between the handle and the background task.
+const COMMAND_CHANNEL_CAPACITY: usize = 16;
+
+// ── Public types ──────────────────────────────────────────────────────────────
+
+/// Parameters of a quote subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QuoteSubscription {
+    /// Base asset identifier: `"native"`, `"CODE"`, or `"CODE:ISSUER"`.
+    pub base: String,
+    /// Quote asset identifier.
+    pub quote: String,
+    /// Amount to quote. When set, the server only emits updates when the
+    /// quoted amount moves by more than 0.01 %.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+}
+
+impl QuoteSubscription {
+    /// Subscribe to `base`/`quote` without an amount filter.
+    pub fn new(base: impl Into<String>, quote: impl Into<String>) -> Self {
+        Self {
+            base: base.into(),
+            quote: quote.into(),
+            amount: None,
+        }
+    }
+
+    /// Quote a specific amount of the base asset.
+    pub fn amount(mut self, amount: impl Into<String>) -> Self {
+        self.amount = Some(amount.into());
+        self
+    }
+}
+
+/// A quote pushed by the server for one of the stream's subscriptions.
+#[derive(Debug, Clone)]
+pub struct QuoteUpdate {
+    /// The subscription this update belongs to.
+    pub subscription: QuoteSubscription,
+    /// Server-assigned ID of the subscription on the current connection.
+    pub subscription_id: Uuid,
+    /// The new quote.
+    pub quote: QuoteResponse,
+    /// Unix timestamp in milliseconds when the server produced the update.
+    pub timestamp: i64,
+}
+
+/// Connection behaviour of a [`QuoteStream`].
+#[derive(Debug, Clone)]
+pub struct StreamConfig {
+    /// Interval between client pings (default: 30 s).
+    pub ping_interval: Duration,
+    /// How long to wait for a pong before reconnecting (default: 10 s).
+    pub pong_timeout: Duration,
+    /// Delay before the first reconnect attempt; doubles after each failure
+    /// (default: 500 ms).
+    pub reconnect_delay: Duration,
+    /// Upper bound on the reconnect delay (default: 30 s).
+    pub max_reconnect_delay: Duration,
+    /// Consecutive failed reconnects after which the stream ends with an
+    /// error. `None` retries forever (default).
+    pub max_reconnect_attempts: Option<u32>,
+    /// Number of updates held for a slow caller before the oldest are
+    /// dropped (default: 32).
+    pub buffer: usize,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(30),
+            pong_timeout: Duration::from_secs(10),
+            reconnect_delay: Duration::from_millis(500),
+            max_reconnect_delay: Duration::from_secs(30),
+            max_reconnect_attempts: None,
+            buffer: 32,
+        }
+    }
+}
+
+/// Async stream of [`QuoteUpdate`]s from the API's `/ws` endpoint.
+///
+/// Open one with [`StellarRouteClient::quote_stream`](crate::StellarRouteClient::quote_stream).
+/// The stream yields `Err` for server `error` frames that are not a reply
+/// to [`subscribe`](Self::subscribe) and [`SdkError::StreamLagged`] when
+/// updates were dropped because the caller fell behind. It ends after
+/// yielding an error when it gives up reconnecting. Dropping it closes the
+/// connection.
+pub struct QuoteStream {
+    commands: mpsc::Sender<Command>,
+    updates: mpsc::Receiver<Result<QuoteUpdate>>,
+}
+
+impl QuoteStream {
+    /// Connect to `url` (a `ws://` or `wss://` URL) and start the
+    /// background task.
+    pub async fn connect(url: impl Into<String>, config: StreamConfig) -> Result<Self> {
+        let url = url.into();
+        if config.buffer == 0 {
+            return Err(SdkError::InvalidConfig(
+                "Stream buffer must be at least 1".to_string(),
+            ));
+        }
+        let socket = open(&url).await?;
+
+        let (commands, command_rx) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
+        let (update_tx, updates) = mpsc::channel(config.buffer);
+        let worker = Worker {
+            url,
+            config,
+            commands: command_rx,
+            updates: update_tx,
+            active: Vec::new(),
+            ids: HashMap::new(),
+            pending: VecDeque::new(),
+            backlog: VecDeque::new(),
+            lagged: 0,
+        };
+        tokio::spawn(worker.run(socket));
+
+        Ok(Self { commands, updates })
+    }
+
+    /// Subscribe to quote updates, resolving once the server confirms.
+    ///
+    /// Subscribing to parameters that are already active is a no-op. A
+    /// rejection is returned as [`SdkError::Stream`], e.g. with code
+    /// `subscription_limit_exceeded`.
+    pub async fn subscribe(&self, subscription: QuoteSubscription) -> Result<()> {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Subscribe(subscription, reply)).await?;
+        response.await.map_err(|_| closed())?
+    }
+
+    /// Cancel a subscription. Unknown subscriptions are ignored.
+    pub async fn unsubscribe(&self, subscription: &QuoteSubscription) -> Result<()> {
+        let (reply, response) = oneshot::channel();
+        self.send(Command::Unsubscribe(subscription.clone(), reply))
+            .await?;
+        response.await.map_err(|_| closed())
+    }
+
+    async fn send(&self, command: Command) -> Result<()> {
+        self.commands.send(command).await.map_err(|_| closed())
+    }
+}
+
+impl Stream for QuoteStream {
+    type Item = Result<QuoteUpdate>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.updates.poll_recv(cx)
+    }
+}
+
+impl std::fmt::Debug for QuoteStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuoteStream").finish_non_exhaustive()
+    }
+}
+
+// ── Wire format ───────────────────────────────────────────────────────────────
+// Mirrors `routes/ws/messages.rs` in the API crate.
+
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum ClientFrame<'a> {
+    Subscribe {
+        subscription: &'a QuoteSubscription,
+        request_id: Uuid,
+    },
+    Unsubscribe {
+        subscription_id: Uuid,
+    },
+}
+
+#[derive(Deserialize)]
+struct ServerFrame {
+    #[serde(default)]
+    timestamp: i64,
+    #[serde(flatten)]
+    payload: ServerPayload,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerPayload {
+    SubscriptionConfirmed {
+        subscription_id: Uuid,
+        #[serde(default)]
+        request_id: Option<Uuid>,
+    },
+    QuoteUpdate {
+        subscription_id: Uuid,
+        quote: Box<QuoteResponse>,
+    },
+    Error {
+        code: String,
+        message: String,
+        #[serde(default)]
+        request_id: Option<Uuid>,
+    },
+    /// JSON keepalive; WebSocket-level pings are answered by tungstenite.
+    Ping,
+    #[serde(other)]
+    Unknown,
+}
+
+// ── Background task ───────────────────────────────────────────────────────────
+
+enum Command {
+    Subscribe(QuoteSubscription, oneshot::Sender<Result<()>>),
+    Unsubscribe(QuoteSubscription, oneshot::Sender<()>),
+}
+
+/// A subscribe frame awaiting the server's reply, which echoes
+/// `request_id`.
+struct PendingSubscribe {
+    request_id: Uuid,
+    subscription: QuoteSubscription,
+    /// `None` for resubscriptions after a reconnect.
+    reply: Option<oneshot::Sender<Result<()>>>,
+}
+
+/// Why [`Worker::drive`] returned.
+enum Disconnect {
+    /// The connection dropped; reconnect.
+    Lost,
+    /// The [`QuoteStream`] was dropped; stop.
+    Shutdown,
+}
+
+struct Worker {
+    url: String,
+    config: StreamConfig,
+    commands: mpsc::Receiver<Command>,
+    updates: mpsc::Sender<Result<QuoteUpdate>>,
+    /// Confirmed subscriptions, replayed after a reconnect.
+    active: Vec<QuoteSubscription>,
+    /// Server IDs on the current connection.
+    ids: HashMap<Uuid, QuoteSubscription>,
+    pending: VecDeque<PendingSubscribe>,
+    /// Items waiting for room in `updates`, oldest first.
+    backlog: VecDeque<Result<QuoteUpdate>>,
+    /// Items dropped from `backlog` since the last lag notice.
+    lagged: u64,
+}
+
+impl Worker {
+    async fn run(mut self, mut socket: Socket) {
+        loop {
+            if let Disconnect::Shutdown = self.drive(&mut socket).await {
+                let _ = socket.close(None).await;
+                return;
+            }
+            self.ids.clear();
+
+            socket = match self.reconnect().await {
+                Some(socket) => socket,
+                None => {
+                    for pending in self.pending.drain(..) {
+                        if let Some(reply) = pending.reply {
+                            let _ = reply.send(Err(closed()));
+                        }
+                    }
+                    // Nothing else is coming, so waiting on the caller is fine.
+                    while self.has_backlog() {
+                        let item = self.next_buffered();
+                        if self.updates.send(item).await.is_err() {
+                            break;
+                        }
+                    }
+                    return;
+                }
+            };
+
+            // Replay confirmed subscriptions first, then whatever was still
+            // unconfirmed when the connection dropped.
+            let unconfirmed = std::mem::take(&mut self.pending);
+            let replay = self
+                .active
+                .drain(..)
+                .map(|subscription| PendingSubscribe {
+                    request_id: Uuid::nil(),
+                    subscription,
+                    reply: None,
+                })
+                .chain(unconfirmed)
+                .collect::<Vec<_>>();
+            for mut pending in replay {
+                // Fresh IDs, so replies meant for the old connection are
+                // never matched.
+                pending.request_id = Uuid::new_v4();
+                // A failed send leaves the entry pending; the next drive()
+                // notices the dead socket and it is replayed again.
+                let _ = send_frame(
+                    &mut socket,
+                    &ClientFrame::Subscribe {
+                        subscription: &pending.subscription,
+                        request_id: pending.request_id,
+                    },
+                )
+                .await;
+                self.pending.push_back(pending);
+            }
+        }
+    }
+
+    /// Run one connection until it drops or the handle goes away.
+    async fn drive(&mut self, socket: &mut Socket) -> Disconnect {
+        let start = Instant::now() + self.config.ping_interval;
+        let mut ping_timer = interval_at(start, self.config.ping_interval);
+        ping_timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut pong_deadline: Option<Instant> = None;
+
+        loop {
+            tokio::select! {
+                // Drain buffered frames (including pongs) before the timers.
+                biased;
+
+                permit = self.updates.clone().reserve_owned(), if self.has_backlog() => match permit {
+                    Ok(permit) => {
+                        permit.send(self.next_buffered());
+                    }
+                    Err(_) => return Disconnect::Shutdown,
+                },
+
+                frame = socket.next() => match frame {
+                    Some(Ok(Message::Text(text))) => {
+                        if !self.handle_text(&text) {
+                            return Disconnect::Shutdown;
+                        }
+                    }
+                    Some(Ok(Message::Pong(_))) => pong_deadline = None,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                        return Disconnect::Lost;
+                    }
+                    // Pings are answered by tungstenite on the next read.
+                    Some(Ok(_)) => {}
+                },
+
+                command = self.commands.recv() => match command {
+                    Some(command) => {
+                        if self.handle_command(socket, command).await.is_err() {
+                            return Disconnect::Lost;
+                        }
+                    }
+                    None => return Disconnect::Shutdown,
+                },
+
+                _ = ping_timer.tick() => {
+                    if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                        return Disconnect::Lost;
+                    }
+                    pong_deadline.get_or_insert(Instant::now() + self.config.pong_timeout);
+                }
+
+                _ = async {
+                    match pong_deadline {
+                        Some(deadline) => tokio::time::sleep_until(deadline).await,
+                        None => std::future::pending().await,
+                    }
+                } => return Disconnect::Lost,
+            }
+        }
+    }
+
+    /// Handle a text frame. Returns `false` once the consumer has gone away.
+    fn handle_text(&mut self, text: &str) -> bool {
+        let frame = match serde_json::from_str::<ServerFrame>(text) {
+            Ok(frame) => frame,
+            Err(e) => return self.emit(Err(SdkError::Deserialization(e))),
+        };
+
+        match frame.payload {
+            ServerPayload::SubscriptionConfirmed {
+                subscription_id,
+                request_id,
+            } => {
+                if let Some(pending) = self.take_pending(request_id) {
+                    self.ids
+                        .insert(subscription_id, pending.subscription.clone());
+                    self.active.push(pending.subscription);
+                    if let Some(reply) = pending.reply {
+                        let _ = reply.send(Ok(()));
+                    }
+                }
+                true
+            }
+            ServerPayload::QuoteUpdate {
+                subscription_id,
+                quote,
+            } => match self.ids.get(&subscription_id) {
+                Some(subscription) => {
+                    let update = QuoteUpdate {
+                        subscription: subscription.clone(),
+                        subscription_id,
+                        quote: *quote,
+                        timestamp: frame.timestamp,
+                    };
+                    self.emit(Ok(update))
+                }
+                // Late update for a subscription we just cancelled.
+                None => true,
+            },
+            ServerPayload::Error {
+                code,
+                message,
+                request_id,
+            } => {
+                let code = code.parse::<ApiErrorCode>().expect("infallible parse");
+                let error = SdkError::Stream { code, message };
+                if let Some(PendingSubscribe {
+                    reply: Some(reply), ..
+                }) = self.take_pending(request_id)
+                {
+                    let _ = reply.send(Err(error));
+                    return true;
+                }
+                self.emit(Err(error))
+            }
+            ServerPayload::Ping | ServerPayload::Unknown => true,
+        }
+    }
+
+    async fn handle_command(
+        &mut self,
+        socket: &mut Socket,
+        command: Command,
+    ) -> std::result::Result<(), ()> {
+        match command {
+            Command::Subscribe(subscription, reply) => {
+                let known = self.active.contains(&subscription)
+                    || self
+                        .pending
+                        .iter()
+                        .any(|pending| pending.subscription == subscription);
+                if known {
+                    let _ = reply.send(Ok(()));
+                    return Ok(());
+                }
+                let request_id = Uuid::new_v4();
+                let sent = send_frame(
+                    socket,
+                    &ClientFrame::Subscribe {
+                        subscription: &subscription,
+                        request_id,
+                    },
+                )
+                .await;
+                // Kept pending even if the send failed: it is replayed on
+                // the next connection.
+                self.pending.push_back(PendingSubscribe {
+                    request_id,
+                    subscription,
+                    reply: Some(reply),
+                });
+                sent
+            }
+            Command::Unsubscribe(subscription, reply) => {
+                self.active.retain(|active| *active != subscription);
+                self.pending
+                    .retain(|pending| pending.subscription != subscription);
+                let ids = self
+                    .ids
+                    .iter()
+                    .filter(|(_, active)| **active == subscription)
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                let mut sent = Ok(());
+                for subscription_id in ids {
+                    self.ids.remove(&subscription_id);
+                    if sent.is_ok() {
+                        sent =
+                            send_frame(socket, &ClientFrame::Unsubscribe { subscription_id }).await;
+                    }
+                }
+                let _ = reply.send(());
+                sent
+            }
+        }
+    }
+
+    /// Remove the pending subscribe a reply's `request_id` refers to.
+    fn take_pending(&mut self, request_id: Option<Uuid>) -> Option<PendingSubscribe> {
+        let request_id = request_id?;
+        let index = self
+            .pending
+            .iter()
+            .position(|pending| pending.request_id == request_id)?;
+        self.pending.remove(index)
+    }
+
+    /// Hand an item to the consumer without waiting. While the channel is
+    /// full items queue in `backlog`; past [`StreamConfig::buffer`] the
+    /// oldest is dropped and counted in `lagged`. Returns `false` once the
+    /// consumer has gone away.
+    fn emit(&mut self, item: Result<QuoteUpdate>) -> bool {
+        if !self.has_backlog() {
+            match self.updates.try_send(item) {
+                Ok(()) => return true,
+                Err(mpsc::error::TrySendError::Closed(_)) => return false,
+                Err(mpsc::error::TrySendError::Full(item)) => self.backlog.push_back(item),
+            }
+        } else {
+            self.backlog.push_back(item);
+        }
+        if self.backlog.len() > self.config.buffer {
+            self.backlog.pop_front();
+            self.lagged += 1;
+        }
+        true
+    }
+
+    fn has_backlog(&self) -> bool {
+        self.lagged > 0 || !self.backlog.is_empty()
+    }
+
+    /// The next item owed to the consumer: a lag notice if anything was
+    /// dropped, then the backlog in order.
+    fn next_buffered(&mut self) -> Result<QuoteUpdate> {
+        if self.lagged > 0 {
+            let skipped = std::mem::take(&mut self.lagged);
+            return Err(SdkError::StreamLagged { skipped });
+        }
+        self.backlog
+            .pop_front()
+            .expect("next_buffered called with a backlog")
+    }
+
+    /// Reconnect with exponential backoff. Returns `None` once the attempt
+    /// limit is reached (after reporting the last error) or the consumer has
+    /// gone away.
+    async fn reconnect(&mut self) -> Option<Socket> {
+        let mut delay = self.config.reconnect_delay;
+        let mut attempts = 0u32;
+        loop {
+            let backoff = sleep(delay);
+            tokio::pin!(backoff);
+            loop {
+                tokio::select! {
+                    _ = &mut backoff => break,
+                    _ = self.updates.closed(), if !self.has_backlog() => return None,
+                    // Keep feeding a caller that is catching up.
+                    permit = self.updates.clone().reserve_owned(), if self.has_backlog() => match permit {
+                        Ok(permit) => {
+                        permit.send(self.next_buffered());
+                    }
+                        Err(_) => return None,
+                    },
+                }
+            }
+            match open(&self.url).await {
+                Ok(socket) => return Some(socket),
+                Err(error) => {
+                    attempts += 1;
+                    if self
+                        .config
+                        .max_reconnect_attempts
+                        .is_some_and(|max| attempts >= max)
+                    {
+                        self.emit(Err(error));
+                        return None;
+                    }
+                }
+            }
+            delay = (delay * 2).min(self.config.max_reconnect_delay);
+        }
+    }
+}
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+async fn open(url: &str) -> Result<Socket> {
+    let (socket, _) = connect_async(url)
+        .await
+        .map_err(|e| SdkError::Http(format!("WebSocket connection to '{url}' failed: {e}")))?;
+    Ok(socket)
+}
+
+async fn send_frame(socket: &mut Socket, frame: &ClientFrame<'_>) -> std::result::Result<(), ()> {
+    let json = serde_json::to_string(frame).map_err(|_| ())?;
+    socket.send(Message::Text(json)).await.map_err(|_| ())
+}
+
+fn closed() -> SdkError {
+    SdkError::Http("Quote stream closed".to_string())
+}
@@ -5,6 +5,11 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::str::FromStr;
+use stellarroute_routing::amount::{Amount, Price};
+use uuid::Uuid;
+
+use crate::error::{ApiErrorCode, Result, SdkError};
 
 // ── Health ────────────────────────────────────────────────────────────────────
 
@@ -134,6 +139,9 @@ pub struct PathStep {
     pub price: String,
     /// Liquidity source: `"sdex"` or `"amm:<pool_address>"`.
     pub source: String,
+    /// Least this hop may output within the requested slippage tolerance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_amount_out: Option<String>,
 }
 
 /// Direction of a price quote.
@@ -180,13 +188,216 @@ pub struct QuoteResponse {
     pub timestamp: i64,
 }
 
+impl QuoteResponse {
+    /// [`amount`](Self::amount) as an exact stroop [`Amount`].
+    pub fn exact_amount(&self) -> Result<Amount> {
+        parse_decimal("amount", &self.amount)
+    }
+
+    /// [`price`](Self::price) as an exact [`Price`].
+    pub fn exact_price(&self) -> Result<Price> {
+        parse_decimal("price", &self.price)
+    }
+
+    /// [`total`](Self::total) as an exact stroop [`Amount`].
+    ///
+    /// The API rounds sell totals (an output) down and buy totals (an input)
+    /// up, so the total is always safe to use as a limit.
+    pub fn exact_total(&self) -> Result<Amount> {
+        parse_decimal("total", &self.total)
+    }
+}
+
+fn parse_decimal<T: FromStr>(field: &'static str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| SdkError::InvalidDecimal {
+        field,
+        value: value.to_string(),
+    })
+}
+
 /// Response from `POST /api/v1/batch/quote`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchQuoteResponse {
-    /// Array of quotes in the same order as requested.
-    pub quotes: Vec<QuoteResponse>,
+    /// One result per requested item, in the same order as requested.
+    pub results: Vec<BatchQuoteResult>,
     /// Total number of quotes successfully fetched.
     pub total: usize,
+    /// Number of items that failed.
+    pub failed: usize,
+}
+
+impl BatchQuoteResponse {
+    /// Quotes of the items that succeeded, in request order.
+    pub fn quotes(&self) -> impl Iterator<Item = &QuoteResponse> {
+        self.results.iter().filter_map(|r| match r {
+            BatchQuoteResult::Ok(quote) => Some(quote.as_ref()),
+            BatchQuoteResult::Error(_) => None,
+        })
+    }
+}
+
+/// Outcome of one item of a batch quote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchQuoteResult {
+    Ok(Box<QuoteResponse>),
+    Error(BatchQuoteError),
+}
+
+/// Why one item of a batch quote failed — the same body a single `/quote`
+/// call would have returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchQuoteError {
+    /// Machine-readable error code, e.g. `"not_found"`.
+    pub error: String,
+    pub message: String,
+    #[serde(default)]
+    pub details: Option<serde_json::Value>,
+}
+
+impl BatchQuoteError {
+    /// Typed error code.
+    pub fn code(&self) -> ApiErrorCode {
+        self.error.parse().expect("infallible parse")
+    }
+}
+
+// ── Routes ────────────────────────────────────────────────────────────────────
+
+/// A single hop of a [`RouteCandidate`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteHop {
+    pub from_asset: AssetInfo,
+    pub to_asset: AssetInfo,
+    /// Exchange rate for this hop.
+    pub price: String,
+    /// Amount this hop is expected to output.
+    pub amount_out_of_hop: String,
+    /// Venue fee in basis points.
+    pub fee_bps: u32,
+    /// Liquidity source: `"sdex"` or `"amm:<pool_address>"`.
+    pub source: String,
+}
+
+/// One ranked route returned by `GET /api/v1/routes/{base}/{quote}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteCandidate {
+    /// Expected output of the whole route.
+    pub estimated_output: String,
+    /// Estimated price impact in basis points.
+    pub impact_bps: u32,
+    /// Optimizer score; higher is better.
+    pub score: f64,
+    /// Optimizer policy that produced the score.
+    pub policy_used: String,
+    pub path: Vec<RouteHop>,
+}
+
+/// Response from `GET /api/v1/routes/{base}/{quote}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutesResponse {
+    pub base_asset: AssetInfo,
+    pub quote_asset: AssetInfo,
+    /// Input amount that was routed.
+    pub amount: String,
+    /// Candidates ordered best first.
+    pub routes: Vec<RouteCandidate>,
+    /// Unix timestamp when the routes were computed.
+    pub timestamp: i64,
+}
+
+// ── Replay ────────────────────────────────────────────────────────────────────
+
+/// Summary of a stored replay artifact, as returned by `GET /api/v1/replay`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactSummary {
+    pub id: Uuid,
+    /// Incident label the artifact was captured under, if any.
+    pub incident_id: Option<String>,
+    /// RFC-3339 capture time.
+    pub captured_at: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+}
+
+/// Response from `GET /api/v1/replay`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayListResponse {
+    /// Matching artifacts, most recent first.
+    pub artifacts: Vec<ArtifactSummary>,
+}
+
+/// A liquidity candidate captured in a replay artifact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityCandidate {
+    /// `"sdex"` or `"amm"`.
+    pub venue_type: String,
+    /// Offer ID or pool address. May be redacted.
+    pub venue_ref: String,
+    pub price: String,
+    pub available_amount: String,
+}
+
+/// Health scoring thresholds in force when an artifact was captured.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthConfigSnapshot {
+    pub freshness_threshold_secs_sdex: u64,
+    pub freshness_threshold_secs_amm: u64,
+    pub staleness_threshold_secs: u64,
+    pub min_tvl_threshold_e7: i128,
+}
+
+/// Response from `GET /api/v1/replay/{id}`: a redacted snapshot of one quote
+/// computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayArtifact {
+    pub id: Uuid,
+    pub schema_version: u32,
+    pub incident_id: Option<String>,
+    /// RFC-3339 capture time.
+    pub captured_at: String,
+    pub base: String,
+    pub quote: String,
+    pub amount: String,
+    pub slippage_bps: u32,
+    /// `"sell"` or `"buy"`.
+    pub quote_type: String,
+    pub liquidity_snapshot: Vec<LiquidityCandidate>,
+    pub health_config_snapshot: HealthConfigSnapshot,
+    /// The quote response served at capture time.
+    pub original_output: serde_json::Value,
+}
+
+/// Response from `POST /api/v1/replay/{id}/run`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayOutput {
+    pub artifact_id: Uuid,
+    /// Venue the replay selected, e.g. `"sdex:offer1"`.
+    pub selected_source: String,
+    pub price: String,
+    pub path: Vec<PathStep>,
+    /// `true` when the replay selected the same venue as the original.
+    pub is_deterministic: bool,
+    /// RFC-3339 time of the replay.
+    pub replayed_at: String,
+}
+
+/// A field that differs between the original and replayed outputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDivergence {
+    /// Dot-path of the field, e.g. `"price"` or `"path[0].source"`.
+    pub field: String,
+    pub original: serde_json::Value,
+    pub replayed: serde_json::Value,
+}
+
+/// Response from `POST /api/v1/replay/{id}/diff`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffReport {
+    pub artifact_id: Uuid,
+    /// `true` when no divergences were found.
+    pub is_identical: bool,
+    pub divergences: Vec<FieldDivergence>,
 }
 
 // ── Request types ─────────────────────────────────────────────────────────────
@@ -226,6 +437,48 @@ impl<'a> QuoteRequest<'a> {
     }
 }
 
+/// Parameters for `GET /api/v1/routes/{base}/{quote}`.
+#[derive(Debug, Clone)]
+pub struct RoutesRequest<'a> {
+    /// Base asset identifier: `"native"`, `"CODE"`, or `"CODE:ISSUER"`.
+    pub base: &'a str,
+    /// Quote asset identifier.
+    pub quote: &'a str,
+    /// Amount of the base asset to route. The API defaults to `"1"`.
+    pub amount: Option<&'a str>,
+    /// Maximum number of routes to return. The API defaults to 5 (max 20).
+    pub limit: Option<usize>,
+    /// Maximum hops per route. The API defaults to 3 (max 6).
+    pub max_hops: Option<usize>,
+    /// Optimizer policy environment. The API defaults to `"production"`.
+    pub environment: Option<&'a str>,
+}
+
+impl<'a> RoutesRequest<'a> {
+    /// Routes for `base`/`quote` with all options left to the API defaults.
+    pub fn new(base: &'a str, quote: &'a str) -> Self {
+        Self {
+            base,
+            quote,
+            amount: None,
+            limit: None,
+            max_hops: None,
+            environment: None,
+        }
+    }
+}
+
+/// Filters for `GET /api/v1/replay`.
+#[derive(Debug, Clone, Default)]
+pub struct ReplayFilter {
+    pub incident_id: Option<String>,
+    pub base: Option<String>,
+    pub quote: Option<String>,
+    /// Maximum number of artifacts. The API defaults to 20 (max 100).
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
 /// A request item for a batch quote.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuoteRequestItem {
@@ -7,9 +7,13 @@
 //! Run with:
 //!   cargo test -p stellarroute-sdk
 
-use stellarroute_sdk::{ApiErrorCode, ClientBuilder, QuoteRequest, QuoteType, SdkError};
+use stellarroute_sdk::{
+    types::{BatchQuoteRequest, BatchQuoteResult, QuoteRequestItem},
+    Amount, ApiErrorCode, ClientBuilder, QuoteRequest, QuoteType, ReplayFilter, Rounding,
+    RoutesRequest, SdkError,
+};
 use wiremock::{
-    matchers::{method, path, query_param},
+    matchers::{header, method, path, query_param},
     Mock, MockServer, ResponseTemplate,
 };
 
@@ -240,6 +244,14 @@ async fn quote_buy_with_amount_sends_correct_params() {
     assert_eq!(resp.amount, "100.0000000");
     assert_eq!(resp.path.len(), 1);
     assert_eq!(resp.path[0].source, "sdex");
+
+    let amount = resp.exact_amount().unwrap();
+    let price = resp.exact_price().unwrap();
+    assert_eq!(amount, Amount::from_stroops(1_000_000_000));
+    assert_eq!(
+        resp.exact_total().unwrap(),
+        amount.mul_price(price, Rounding::Up).unwrap()
+    );
 }
 
 #[tokio::test]
@@ -263,6 +275,295 @@ async fn quote_validation_error_maps_to_typed_error() {
     assert_eq!(err.status_code(), Some(400));
 }
 
+#[tokio::test]
+async fn batch_quote_returns_per_item_results() {
+    let server = mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/batch/quote"))
+        .and(header("Idempotency-Key", "batch-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "results": [
+                { "ok": {
+                    "base_asset": { "asset_type": "native", "asset_code": null, "asset_issuer": null },
+                    "quote_asset": { "asset_type": "credit_alphanum4", "asset_code": "USDC", "asset_issuer": null },
+                    "amount": "1.0000000",
+                    "price": "0.1055000",
+                    "total": "0.1055000",
+                    "quote_type": "sell",
+                    "path": [],
+                    "timestamp": 1740312000
+                } },
+                { "error": { "error": "not_found", "message": "Asset not found: NOPE" } }
+            ],
+            "total": 1,
+            "failed": 1
+        })))
+        .mount(&server)
+        .await;
+
+    let item = |quote: &str| QuoteRequestItem {
+        base: "native".to_string(),
+        quote: quote.to_string(),
+        amount: None,
+        slippage_bps: None,
+        quote_type: None,
+    };
+    let resp = client(&server)
+        .batch_quote_with_idempotency_key(
+            BatchQuoteRequest {
+                quotes: vec![item("USDC"), item("NOPE")],
+            },
+            "batch-1",
+        )
+        .await
+        .unwrap();
+
+    assert_eq!((resp.total, resp.failed), (1, 1));
+    assert_eq!(resp.quotes().count(), 1);
+    match &resp.results[1] {
+        BatchQuoteResult::Error(e) => assert_eq!(e.code(), ApiErrorCode::NotFound),
+        other => panic!("expected an error result, got {other:?}"),
+    }
+}
+
+// ── Routes & markets ──────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn routes_sends_options_and_returns_candidates() {
+    let server = mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/routes/native/USDC"))
+        .and(query_param("amount", "50"))
+        .and(query_param("limit", "2"))
+        .and(query_param("max_hops", "4"))
+        .and(query_param("environment", "staging"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "base_asset": { "asset_type": "native", "asset_code": null, "asset_issuer": null },
+            "quote_asset": { "asset_type": "credit_alphanum4", "asset_code": "USDC", "asset_issuer": null },
+            "amount": "50",
+            "routes": [
+                {
+                    "estimated_output": "5.2500000",
+                    "impact_bps": 8,
+                    "score": 0.97,
+                    "policy_used": "staging",
+                    "path": [
+                        {
+                            "from_asset": { "asset_type": "native", "asset_code": null, "asset_issuer": null },
+                            "to_asset": { "asset_type": "credit_alphanum4", "asset_code": "USDC", "asset_issuer": null },
+                            "price": "0.1050000",
+                            "amount_out_of_hop": "5.2500000",
+                            "fee_bps": 30,
+                            "source": "amm:CPOOL"
+                        }
+                    ]
+                }
+            ],
+            "timestamp": 1740312000
+        })))
+        .mount(&server)
+        .await;
+
+    let resp = client(&server)
+        .routes(RoutesRequest {
+            amount: Some("50"),
+            limit: Some(2),
+            max_hops: Some(4),
+            environment: Some("staging"),
+            ..RoutesRequest::new("native", "USDC")
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(resp.routes.len(), 1);
+    assert_eq!(resp.routes[0].policy_used, "staging");
+    assert_eq!(resp.routes[0].path[0].fee_bps, 30);
+    assert_eq!(resp.routes[0].path[0].source, "amm:CPOOL");
+}
+
+#[tokio::test]
+async fn markets_returns_typed_list() {
+    let server = mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/markets"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "pairs": [
+                {
+                    "base": "XLM",
+                    "counter": "USDC",
+                    "base_asset": "native",
+                    "counter_asset": "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+                    "offer_count": 7,
+                    "last_updated": null
+                }
+            ],
+            "total": 1
+        })))
+        .mount(&server)
+        .await;
+
+    let resp = client(&server).markets().await.unwrap();
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.pairs[0].offer_count, 7);
+}
+
+// ── Replay ────────────────────────────────────────────────────────────────────
+
+const ARTIFACT_ID: &str = "6f1c2a8e-3b7d-4c5e-9f0a-1b2c3d4e5f60";
+
+#[tokio::test]
+async fn replay_list_sends_filters() {
+    let server = mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/replay"))
+        .and(query_param("incident_id", "INC-1"))
+        .and(query_param("base", "native"))
+        .and(query_param("limit", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "artifacts": [
+                {
+                    "id": ARTIFACT_ID,
+                    "incident_id": "INC-1",
+                    "captured_at": "2026-03-27T10:00:00Z",
+                    "base_asset": "native",
+                    "quote_asset": "USDC"
+                }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let resp = client(&server)
+        .replay_artifacts(&ReplayFilter {
+            incident_id: Some("INC-1".to_string()),
+            base: Some("native".to_string()),
+            limit: Some(5),
+            ..ReplayFilter::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(resp.artifacts.len(), 1);
+    assert_eq!(resp.artifacts[0].id.to_string(), ARTIFACT_ID);
+    assert_eq!(resp.artifacts[0].incident_id.as_deref(), Some("INC-1"));
+}
+
+#[tokio::test]
+async fn replay_fetch_returns_artifact() {
+    let server = mock_server().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/replay/{ARTIFACT_ID}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": ARTIFACT_ID,
+            "schema_version": 1,
+            "incident_id": null,
+            "captured_at": "2026-03-27T10:00:00Z",
+            "base": "native",
+            "quote": "USDC:REDACTED",
+            "amount": "100.0000000",
+            "slippage_bps": 50,
+            "quote_type": "sell",
+            "liquidity_snapshot": [
+                {
+                    "venue_type": "sdex",
+                    "venue_ref": "offer1",
+                    "price": "1.0050000",
+                    "available_amount": "500.0000000"
+                }
+            ],
+            "health_config_snapshot": {
+                "freshness_threshold_secs_sdex": 30,
+                "freshness_threshold_secs_amm": 60,
+                "staleness_threshold_secs": 300,
+                "min_tvl_threshold_e7": 10000000000_i64
+            },
+            "original_output": { "price": "1.0050000" }
+        })))
+        .mount(&server)
+        .await;
+
+    let artifact = client(&server)
+        .replay_artifact(ARTIFACT_ID.parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(artifact.slippage_bps, 50);
+    assert_eq!(artifact.liquidity_snapshot[0].venue_ref, "offer1");
+    assert_eq!(
+        artifact.health_config_snapshot.min_tvl_threshold_e7,
+        10_000_000_000
+    );
+}
+
+#[tokio::test]
+async fn replay_run_posts_and_returns_output() {
+    let server = mock_server().await;
+    Mock::given(method("POST"))
+        .and(path(format!("/api/v1/replay/{ARTIFACT_ID}/run")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "artifact_id": ARTIFACT_ID,
+            "selected_source": "sdex:offer1",
+            "price": "1.0050000",
+            "path": [],
+            "is_deterministic": true,
+            "replayed_at": "2026-03-27T10:05:00Z"
+        })))
+        .mount(&server)
+        .await;
+
+    let output = client(&server)
+        .run_replay(ARTIFACT_ID.parse().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(output.selected_source, "sdex:offer1");
+    assert!(output.is_deterministic);
+}
+
+#[tokio::test]
+async fn replay_diff_posts_and_returns_divergences() {
+    let server = mock_server().await;
+    Mock::given(method("POST"))
+        .and(path(format!("/api/v1/replay/{ARTIFACT_ID}/diff")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "artifact_id": ARTIFACT_ID,
+            "is_identical": false,
+            "divergences": [
+                { "field": "price", "original": "1.0050000", "replayed": "0.9960000" }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let report = client(&server)
+        .diff_replay(ARTIFACT_ID.parse().unwrap())
+        .await
+        .unwrap();
+
+    assert!(!report.is_identical);
+    assert_eq!(report.divergences[0].field, "price");
+    assert_eq!(report.divergences[0].replayed, "0.9960000");
+}
+
+#[tokio::test]
+async fn replay_unknown_artifact_maps_to_not_found() {
+    let server = mock_server().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v1/replay/{ARTIFACT_ID}")))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": "not_found",
+            "message": "Replay artifact not found"
+        })))
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .replay_artifact(ARTIFACT_ID.parse().unwrap())
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found());
+}
+
 // ── Error handling ────────────────────────────────────────────────────────────
 
 #[tokio::test]
@@ -409,3 +710,31 @@ fn quote_type_display() {
     assert_eq!(QuoteType::Buy.as_str(), "buy");
     assert_eq!(QuoteType::Sell.to_string(), "sell");
 }
+
+#[test]
+fn quote_exact_accessors_reject_inexact_decimals() {
+    let quote: stellarroute_sdk::QuoteResponse = serde_json::from_value(serde_json::json!({
+        "base_asset": { "asset_type": "native" },
+        "quote_asset": { "asset_type": "native" },
+        "amount": "1.00000001",
+        "price": "0.1",
+        "total": "1e-1",
+        "quote_type": "sell",
+        "path": [],
+        "timestamp": 1740312000
+    }))
+    .unwrap();
+
+    assert!(matches!(
+        quote.exact_amount(),
+        Err(SdkError::InvalidDecimal {
+            field: "amount",
+            ..
+        })
+    ));
+    assert_eq!(quote.exact_price().unwrap().to_string(), "0.1000000");
+    assert!(matches!(
+        quote.exact_total(),
+        Err(SdkError::InvalidDecimal { field: "total", .. })
+    ));
+}
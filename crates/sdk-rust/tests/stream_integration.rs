@@ -0,0 +1,422 @@
+//! Integration tests for the quote stream client.
+//!
+//! Each test runs a small axum WebSocket stub that speaks the `/ws`
+//! protocol, so subscribe/unsubscribe, reconnects, keepalives and
+//! backpressure are exercised over a real socket.
+//!
+//! Run with:
+//!   cargo test -p stellarroute-sdk --test stream_integration
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use stellarroute_sdk::{
+    ApiErrorCode, ClientBuilder, QuoteStream, QuoteSubscription, QuoteUpdate, SdkError,
+    StreamConfig,
+};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+// ── Stub server ───────────────────────────────────────────────────────────────
+
+/// How the stub behaves on a connection.
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    /// Confirm subscriptions and send `updates` quotes for each.
+    Normal,
+    /// Like `Normal`, but close the first connection after its first update.
+    DropFirstConnection,
+    /// Confirm the first subscription, then stop reading (so pings go
+    /// unanswered) on the first connection.
+    IgnoreFirstPings,
+    /// Send a WebSocket ping after each confirmation and report the pong.
+    PingClient,
+}
+
+struct Stub {
+    mode: Mode,
+    updates: usize,
+    connections: AtomicUsize,
+    /// Client frames received, tagged with the connection number.
+    received: Mutex<Vec<(usize, Value)>>,
+    pongs: AtomicUsize,
+}
+
+async fn spawn_stub(mode: Mode, updates: usize) -> (String, Arc<Stub>) {
+    let stub = Arc::new(Stub {
+        mode,
+        updates,
+        connections: AtomicUsize::new(0),
+        received: Mutex::new(Vec::new()),
+        pongs: AtomicUsize::new(0),
+    });
+    let app = Router::new()
+        .route("/ws", get(upgrade))
+        .with_state(stub.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{addr}"), stub)
+}
+
+async fn upgrade(ws: WebSocketUpgrade, State(stub): State<Arc<Stub>>) -> Response {
+    ws.on_upgrade(move |socket| serve(socket, stub))
+}
+
+async fn serve(mut socket: WebSocket, stub: Arc<Stub>) {
+    let conn = stub.connections.fetch_add(1, Ordering::SeqCst) + 1;
+    let first = conn == 1;
+
+    while let Some(Ok(msg)) = socket.recv().await {
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Pong(_) => {
+                stub.pongs.fetch_add(1, Ordering::SeqCst);
+                continue;
+            }
+            Message::Close(_) => return,
+            _ => continue,
+        };
+        let frame: Value = serde_json::from_str(&text).unwrap();
+        stub.received.lock().unwrap().push((conn, frame.clone()));
+        if frame["action"] != "subscribe" {
+            continue;
+        }
+
+        let request_id = &frame["request_id"];
+
+        if frame["subscription"]["base"] == "LIMIT" {
+            let error = envelope(json!({
+                "type": "error",
+                "code": "subscription_limit_exceeded",
+                "message": "Maximum of 20 subscriptions per connection reached.",
+                "request_id": request_id
+            }));
+            socket.send(Message::Text(error)).await.unwrap();
+            continue;
+        }
+        if frame["subscription"]["base"] == "NOROUTE" {
+            // A broadcast error that answers no subscribe.
+            let error = envelope(json!({
+                "type": "error",
+                "code": "no_route_found",
+                "message": "No liquidity found for NOROUTE/USDC"
+            }));
+            socket.send(Message::Text(error)).await.unwrap();
+        }
+
+        let id = Uuid::new_v4();
+        let confirmed = envelope(json!({
+            "type": "subscription_confirmed",
+            "subscription_id": id,
+            "request_id": request_id
+        }));
+        socket.send(Message::Text(confirmed)).await.unwrap();
+
+        if stub.mode == Mode::PingClient {
+            socket.send(Message::Ping(b"hi".to_vec())).await.unwrap();
+        }
+        for n in 0..stub.updates {
+            let update = envelope(json!({
+                "type": "quote_update",
+                "subscription_id": id,
+                "quote": quote_json(&frame["subscription"]["quote"], conn, n),
+            }));
+            if socket.send(Message::Text(update)).await.is_err() {
+                return;
+            }
+        }
+
+        if first && stub.mode == Mode::DropFirstConnection {
+            return;
+        }
+        if first && stub.mode == Mode::IgnoreFirstPings {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            return;
+        }
+    }
+}
+
+fn envelope(mut payload: Value) -> String {
+    payload["v"] = json!(1);
+    payload["timestamp"] = json!(1_700_000_000_000_i64);
+    payload.to_string()
+}
+
+/// A quote whose `total` encodes the connection and update number.
+fn quote_json(quote: &Value, conn: usize, n: usize) -> Value {
+    json!({
+        "base_asset": { "asset_type": "native" },
+        "quote_asset": { "asset_type": "credit_alphanum4", "asset_code": quote, "asset_issuer": null },
+        "amount": "1.0000000",
+        "price": "0.1000000",
+        "total": format!("{conn}.{n}"),
+        "quote_type": "sell",
+        "path": [],
+        "timestamp": 1_700_000_000
+    })
+}
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+fn fast_config() -> StreamConfig {
+    StreamConfig {
+        reconnect_delay: Duration::from_millis(10),
+        max_reconnect_delay: Duration::from_millis(50),
+        ..StreamConfig::default()
+    }
+}
+
+async fn open(base_url: &str, config: StreamConfig) -> QuoteStream {
+    ClientBuilder::new(base_url)
+        .build()
+        .unwrap()
+        .quote_stream_with(config)
+        .await
+        .unwrap()
+}
+
+async fn next_update(stream: &mut QuoteStream) -> QuoteUpdate {
+    tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("timed out waiting for an update")
+        .expect("stream ended")
+        .expect("stream error")
+}
+
+/// Poll `check` until it holds or five seconds pass.
+async fn eventually(check: impl Fn() -> bool) {
+    for _ in 0..500 {
+        if check() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("condition not met within 5s");
+}
+
+fn usdc() -> QuoteSubscription {
+    QuoteSubscription::new("native", "USDC")
+}
+
+// ── Tests ─────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn subscribe_yields_typed_quote_updates() {
+    let (url, stub) = spawn_stub(Mode::Normal, 2).await;
+    let mut stream = open(&url, fast_config()).await;
+
+    stream.subscribe(usdc().amount("100")).await.unwrap();
+
+    let update = next_update(&mut stream).await;
+    assert_eq!(update.subscription, usdc().amount("100"));
+    assert_eq!(update.quote.total, "1.0");
+    assert_eq!(update.quote.quote_asset.asset_code.as_deref(), Some("USDC"));
+    assert_eq!(update.timestamp, 1_700_000_000_000);
+    assert_eq!(next_update(&mut stream).await.quote.total, "1.1");
+
+    let mut frame = stub.received.lock().unwrap()[0].1.clone();
+    let request_id = frame["request_id"].take();
+    assert!(request_id.as_str().unwrap().parse::<Uuid>().is_ok());
+    assert_eq!(
+        frame,
+        json!({
+            "action": "subscribe",
+            "request_id": null,
+            "subscription": { "base": "native", "quote": "USDC", "amount": "100" }
+        })
+    );
+}
+
+#[tokio::test]
+async fn unsubscribe_sends_server_subscription_id() {
+    let (url, stub) = spawn_stub(Mode::Normal, 1).await;
+    let mut stream = open(&url, fast_config()).await;
+
+    stream.subscribe(usdc()).await.unwrap();
+    let update = next_update(&mut stream).await;
+    stream.unsubscribe(&usdc()).await.unwrap();
+
+    let expected = json!({ "action": "unsubscribe", "subscription_id": update.subscription_id });
+    eventually(|| {
+        stub.received
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(_, frame)| *frame == expected)
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn rejected_subscription_returns_stream_error() {
+    let (url, _stub) = spawn_stub(Mode::Normal, 1).await;
+    let stream = open(&url, fast_config()).await;
+
+    let err = stream
+        .subscribe(QuoteSubscription::new("LIMIT", "USDC"))
+        .await
+        .unwrap_err();
+    match err {
+        SdkError::Stream { code, .. } => {
+            assert_eq!(
+                code,
+                ApiErrorCode::Other("subscription_limit_exceeded".into())
+            )
+        }
+        other => panic!("expected stream error, got {other:?}"),
+    }
+
+    // The connection stays usable.
+    stream.subscribe(usdc()).await.unwrap();
+}
+
+#[tokio::test]
+async fn reconnects_and_resubscribes_after_drop() {
+    let (url, stub) = spawn_stub(Mode::DropFirstConnection, 1).await;
+    let mut stream = open(&url, fast_config()).await;
+
+    stream.subscribe(usdc()).await.unwrap();
+    let first = next_update(&mut stream).await;
+    assert_eq!(first.quote.total, "1.0");
+
+    // The stub hangs up; the client reconnects and subscribes again.
+    let second = next_update(&mut stream).await;
+    assert_eq!(second.quote.total, "2.0");
+    assert_eq!(second.subscription, usdc());
+    assert_ne!(second.subscription_id, first.subscription_id);
+
+    let resubscribed = stub
+        .received
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(conn, frame)| *conn == 2 && frame["action"] == "subscribe")
+        .count();
+    assert_eq!(resubscribed, 1);
+}
+
+#[tokio::test]
+async fn missing_pong_triggers_reconnect() {
+    let (url, stub) = spawn_stub(Mode::IgnoreFirstPings, 1).await;
+    let config = StreamConfig {
+        ping_interval: Duration::from_millis(50),
+        pong_timeout: Duration::from_millis(100),
+        ..fast_config()
+    };
+    let mut stream = open(&url, config).await;
+
+    stream.subscribe(usdc()).await.unwrap();
+    assert_eq!(next_update(&mut stream).await.quote.total, "1.0");
+    assert_eq!(next_update(&mut stream).await.quote.total, "2.0");
+    assert_eq!(stub.connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn answers_server_pings() {
+    let (url, stub) = spawn_stub(Mode::PingClient, 1).await;
+    let mut stream = open(&url, fast_config()).await;
+
+    stream.subscribe(usdc()).await.unwrap();
+    next_update(&mut stream).await;
+    eventually(|| stub.pongs.load(Ordering::SeqCst) >= 1).await;
+}
+
+#[tokio::test]
+async fn unrelated_error_does_not_answer_pending_subscribe() {
+    let (url, _stub) = spawn_stub(Mode::Normal, 1).await;
+    let mut stream = open(&url, fast_config()).await;
+
+    // The stub sends a `no_route_found` error before the confirmation.
+    stream
+        .subscribe(QuoteSubscription::new("NOROUTE", "USDC"))
+        .await
+        .unwrap();
+
+    let error = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("timed out waiting for the error")
+        .expect("stream ended")
+        .unwrap_err();
+    match error {
+        SdkError::Stream { code, .. } => {
+            assert_eq!(code, ApiErrorCode::Other("no_route_found".into()))
+        }
+        other => panic!("expected stream error, got {other:?}"),
+    }
+    assert_eq!(next_update(&mut stream).await.quote.total, "1.0");
+}
+
+#[tokio::test]
+async fn slow_consumer_is_told_how_many_updates_it_missed() {
+    let (url, _stub) = spawn_stub(Mode::Normal, 20).await;
+    let config = StreamConfig {
+        buffer: 2,
+        ..fast_config()
+    };
+    let mut stream = open(&url, config).await;
+
+    stream.subscribe(usdc()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Two updates fill the channel, the newest two wait in the backlog and
+    // the sixteen in between are dropped.
+    assert_eq!(next_update(&mut stream).await.quote.total, "1.0");
+    assert_eq!(next_update(&mut stream).await.quote.total, "1.1");
+    match stream.next().await {
+        Some(Err(SdkError::StreamLagged { skipped })) => assert_eq!(skipped, 16),
+        other => panic!("expected a lag notice, got {other:?}"),
+    }
+    assert_eq!(next_update(&mut stream).await.quote.total, "1.18");
+    assert_eq!(next_update(&mut stream).await.quote.total, "1.19");
+}
+
+#[tokio::test]
+async fn commands_run_while_the_consumer_is_behind() {
+    let (url, stub) = spawn_stub(Mode::Normal, 20).await;
+    let config = StreamConfig {
+        buffer: 1,
+        ..fast_config()
+    };
+    let stream = open(&url, config).await;
+
+    stream.subscribe(usdc()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Nothing has been read, yet the next subscribe still completes.
+    tokio::time::timeout(
+        Duration::from_secs(2),
+        stream.subscribe(QuoteSubscription::new("native", "EURC")),
+    )
+    .await
+    .expect("subscribe blocked behind the full buffer")
+    .unwrap();
+    assert_eq!(stub.connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn connect_failure_is_a_transport_error() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let err = ClientBuilder::new(format!("http://{addr}"))
+        .build()
+        .unwrap()
+        .quote_stream()
+        .await
+        .unwrap_err();
+    assert!(err.is_transport());
+}
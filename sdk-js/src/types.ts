@@ -82,6 +82,8 @@ export interface PathStep {
   price: string;
   /** Liquidity source: `"sdex"` or `"amm:<pool_address>"`. */
   source: string;
+  /** Least this hop may output within the requested slippage tolerance. */
+  min_amount_out?: string;
 }
 
 /**
@@ -109,6 +111,14 @@ export interface PriceQuote {
   source_timestamp?: number;
   /** Time-to-live in seconds for client-side staleness detection */
   ttl_seconds?: number;
+  /** Why the server chose `ttl_seconds` for this pair */
+  ttl_reason?:
+    | 'high_volatility'
+    | 'low_volatility'
+    | 'low_depth'
+    | 'high_depth'
+    | 'balanced'
+    | 'no_metrics';
   /** Estimated price impact percentage */
   price_impact?: string;
   /** Rationale for quote venue selection. */
@@ -256,6 +266,7 @@ export type ApiErrorCode =
   | 'invalid_asset'
   | 'no_route'
   | 'stale_market_data'
+  | 'consistency_unavailable'
   | 'network_error' // SDK specific
   | 'unknown_error' // SDK specific
   | (string & Record<never, never>);